@@ -54,7 +54,7 @@ if echo "$MATCH_CMD" | grep -qE '^git[[:space:]]'; then
     -e 's/--(no-pager|no-optional-locks|bare|literal-pathspecs)[[:space:]]*//g' \
     -e 's/^[[:space:]]+//')
   case "$GIT_SUBCMD" in
-    status|status\ *|diff|diff\ *|log|log\ *|add|add\ *|commit|commit\ *|push|push\ *|pull|pull\ *|branch|branch\ *|fetch|fetch\ *|stash|stash\ *|show|show\ *)
+    status|status\ *|diff|diff\ *|log|log\ *|add|add\ *|commit|commit\ *|push|push\ *|pull|pull\ *|branch|branch\ *|fetch|fetch\ *|stash|stash\ *|show|show\ *|blame|blame\ *)
       REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
       ;;
   esac
@@ -63,11 +63,15 @@ if echo "$MATCH_CMD" | grep -qE '^git[[:space:]]'; then
 elif echo "$MATCH_CMD" | grep -qE '^gh[[:space:]]+(pr|issue|run|api|release)([[:space:]]|$)'; then
   REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^gh /rtk gh /')"
 
+# --- GitLab CLI ---
+elif echo "$MATCH_CMD" | grep -qE '^glab[[:space:]]+(mr|issue|ci)([[:space:]]|$)'; then
+  REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^glab /rtk glab /')"
+
 # --- Cargo ---
 elif echo "$MATCH_CMD" | grep -qE '^cargo[[:space:]]'; then
   CARGO_SUBCMD=$(echo "$MATCH_CMD" | sed -E 's/^cargo[[:space:]]+(\+[^[:space:]]+[[:space:]]+)?//')
   case "$CARGO_SUBCMD" in
-    test|test\ *|build|build\ *|clippy|clippy\ *|check|check\ *|install|install\ *|fmt|fmt\ *)
+    test|test\ *|bench|bench\ *|build|build\ *|clippy|clippy\ *|check|check\ *|install|install\ *|fmt|fmt\ *|tree|tree\ *)
       REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
       ;;
   esac
@@ -126,7 +130,7 @@ elif echo "$MATCH_CMD" | grep -qE '^pnpm[[:space:]]+playwright([[:space:]]|$)';
 elif echo "$MATCH_CMD" | grep -qE '^(npx[[:space:]]+)?prisma([[:space:]]|$)'; then
   REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed -E 's/^(npx )?prisma/rtk prisma/')"
 
-# --- Containers (added: docker compose, docker run/build/exec, kubectl describe/apply) ---
+# --- Containers (added: docker compose, docker run/build/exec, kubectl describe/apply/diff) ---
 elif echo "$MATCH_CMD" | grep -qE '^docker[[:space:]]'; then
   if echo "$MATCH_CMD" | grep -qE '^docker[[:space:]]+compose([[:space:]]|$)'; then
     COMPOSE_SUBCMD=$(echo "$MATCH_CMD" | sed -E 's/^docker[[:space:]]+compose[[:space:]]*//')
@@ -142,7 +146,7 @@ elif echo "$MATCH_CMD" | grep -qE '^docker[[:space:]]'; then
       -e 's/--[a-z-]+=[^[:space:]]+[[:space:]]*//g' \
       -e 's/^[[:space:]]+//')
     case "$DOCKER_SUBCMD" in
-      ps|ps\ *|images|images\ *|logs|logs\ *|run|run\ *|build|build\ *|exec|exec\ *)
+      ps|ps\ *|images|images\ *|logs|logs\ *|run|run\ *|build|build\ *|exec|exec\ *|inspect\ *|pull\ *|push\ *)
         REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^docker /rtk docker /')"
         ;;
     esac
@@ -154,7 +158,7 @@ elif echo "$MATCH_CMD" | grep -qE '^kubectl[[:space:]]'; then
     -e 's/--[a-z-]+=[^[:space:]]+[[:space:]]*//g' \
     -e 's/^[[:space:]]+//')
   case "$KUBE_SUBCMD" in
-    get|get\ *|logs|logs\ *|describe|describe\ *|apply|apply\ *)
+    get|get\ *|logs|logs\ *|describe|describe\ *|apply|apply\ *|diff|diff\ *)
       REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^kubectl /rtk kubectl /')"
       ;;
   esac
@@ -169,6 +173,15 @@ elif echo "$MATCH_CMD" | grep -qE '^wget[[:space:]]+'; then
 elif echo "$MATCH_CMD" | grep -qE '^pnpm[[:space:]]+(list|ls|outdated)([[:space:]]|$)'; then
   REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^pnpm /rtk pnpm /')"
 
+# --- bun / yarn ---
+# bun run and watch-mode tests run until interrupted: leave them streaming
+elif echo "$MATCH_CMD" | grep -qE '^bun[[:space:]]+test[[:space:]](.*[[:space:]])?--(watch|hot)([[:space:]]|$)'; then
+  :
+elif echo "$MATCH_CMD" | grep -qE '^bun[[:space:]]+(install|i|add|remove|update|test)([[:space:]]|$)'; then
+  REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
+elif echo "$MATCH_CMD" | grep -qE '^yarn([[:space:]]+(install|add|up|upgrade|remove|dedupe)([[:space:]]|$)|[[:space:]]*$)'; then
+  REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
+
 # --- Python tooling ---
 elif echo "$MATCH_CMD" | grep -qE '^mypy([[:space:]]|$)'; then
   REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^mypy/rtk mypy/')"
@@ -194,6 +207,37 @@ elif echo "$MATCH_CMD" | grep -qE '^go[[:space:]]+vet([[:space:]]|$)'; then
   REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^go vet/rtk go vet/')"
 elif echo "$MATCH_CMD" | grep -qE '^golangci-lint([[:space:]]|$)'; then
   REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed 's/^golangci-lint/rtk golangci-lint/')"
+
+# --- JVM tooling ---
+# Application runs (run, bootRun, spring-boot:run, exec:java) stream their own output
+elif echo "$MATCH_CMD" | grep -qE '^(\./)?(gradlew?|mvnw?)[[:space:]]' && \
+     echo "$MATCH_CMD" | grep -qE '[[:space:]]([^[:space:]]*:)?(run|bootRun)([[:space:]]|$)|[[:space:]]exec:'; then
+  :
+elif echo "$MATCH_CMD" | grep -qE '^(\./)?gradlew?[[:space:]]+'; then
+  REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed -E 's#^(\./)?gradlew #rtk gradle --wrapper #; s#^gradle #rtk gradle #')"
+elif echo "$MATCH_CMD" | grep -qE '^(\./)?mvnw?[[:space:]]+'; then
+  REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed -E 's#^(\./)?mvnw #rtk mvn --wrapper #; s#^mvn #rtk mvn #')"
+
+# --- .NET ---
+elif echo "$MATCH_CMD" | grep -qE '^dotnet[[:space:]]+(build|test|restore|publish|pack|clean)([[:space:]]|$)'; then
+  REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
+
+# --- C/C++ builds ---
+# Builds only (bare, all, flags, VAR=value): serve/watch/run targets stream their own output
+elif echo "$MATCH_CMD" | grep -qE '^(make|ninja)([[:space:]]+(-[CfIo][[:space:]]*[^[:space:]]+|-j[[:space:]]*[0-9]+|-[^[:space:]]+|[A-Za-z_][A-Za-z0-9_]*=[^[:space:]]*|all))*[[:space:]]*$'; then
+  REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
+elif echo "$MATCH_CMD" | grep -qE '^(cmake|gcc|g\+\+|clang|clang\+\+)[[:space:]]+'; then
+  REWRITTEN="${ENV_PREFIX}rtk $CMD_BODY"
+
+# --- Ruby tests ---
+elif echo "$MATCH_CMD" | grep -qE '^(bundle[[:space:]]+exec[[:space:]]+)?(bin/)?rspec([[:space:]]|$)'; then
+  REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed -E 's#^(bundle[[:space:]]+exec[[:space:]]+)?(bin/)?rspec#rtk rspec#')"
+elif echo "$MATCH_CMD" | grep -qE '^(bundle[[:space:]]+exec[[:space:]]+)?(bin/)?rails[[:space:]]+test([[:space:]]|$)'; then
+  REWRITTEN="${ENV_PREFIX}$(echo "$CMD_BODY" | sed -E 's#^(bundle[[:space:]]+exec[[:space:]]+)?(bin/)?rails[[:space:]]+test#rtk minitest#')"
+
+# --- Benchmarks ---
+elif echo "$MATCH_CMD" | grep -qE '^hyperfine[[:space:]]+'; then
+  REWRITTEN="${ENV_PREFIX}rtk bench $CMD_BODY"
 fi
 
 # If no rewrite needed, approve as-is
@@ -77,6 +77,10 @@ test_rewrite "git show abc123" \
   "git show abc123" \
   "rtk git show abc123"
 
+test_rewrite "git blame -L 10,20 src/main.rs" \
+  "git blame -L 10,20 src/main.rs" \
+  "rtk git blame -L 10,20 src/main.rs"
+
 test_rewrite "git add ." \
   "git add ." \
   "rtk git add ."
@@ -113,6 +117,10 @@ test_rewrite "cargo test" \
   "cargo test" \
   "rtk cargo test"
 
+test_rewrite "cargo tree -i serde" \
+  "cargo tree -i serde" \
+  "rtk cargo tree -i serde"
+
 test_rewrite "npx prisma migrate" \
   "npx prisma migrate" \
   "rtk prisma migrate"
@@ -201,6 +209,18 @@ test_rewrite "docker exec -it db psql" \
   "docker exec -it db psql" \
   "rtk docker exec -it db psql"
 
+test_rewrite "docker inspect" \
+  "docker inspect web" \
+  "rtk docker inspect web"
+
+test_rewrite "docker pull" \
+  "docker pull postgres:16" \
+  "rtk docker pull postgres:16"
+
+test_rewrite "docker push" \
+  "docker push registry.example.com/app:1.2" \
+  "rtk docker push registry.example.com/app:1.2"
+
 test_rewrite "find . -name" \
   "find . -name '*.ts'" \
   "rtk find . -name '*.ts'"
@@ -213,6 +233,10 @@ test_rewrite "wget url" \
   "wget https://example.com/file" \
   "rtk wget https://example.com/file"
 
+test_rewrite "glab mr view 12" \
+  "glab mr view 12" \
+  "rtk glab mr view 12"
+
 test_rewrite "gh api repos/owner/repo" \
   "gh api repos/owner/repo" \
   "rtk gh api repos/owner/repo"
@@ -229,6 +253,86 @@ test_rewrite "kubectl apply -f deploy.yaml" \
   "kubectl apply -f deploy.yaml" \
   "rtk kubectl apply -f deploy.yaml"
 
+test_rewrite "kubectl diff -f deploy.yaml" \
+  "kubectl diff -f deploy.yaml" \
+  "rtk kubectl diff -f deploy.yaml"
+
+test_rewrite "./gradlew test" \
+  "./gradlew :app:test" \
+  "rtk gradle --wrapper :app:test"
+
+test_rewrite "gradle build (system gradle)" \
+  "gradle build" \
+  "rtk gradle build"
+
+test_rewrite "./gradlew bootRun (not rewritten)" \
+  "./gradlew :app:bootRun" \
+  ""
+
+test_rewrite "mvn spring-boot:run (not rewritten)" \
+  "mvn -q spring-boot:run" \
+  ""
+
+test_rewrite "mvn verify" \
+  "mvn -q verify" \
+  "rtk mvn -q verify"
+
+test_rewrite "dotnet test" \
+  "dotnet test --no-build" \
+  "rtk dotnet test --no-build"
+
+test_rewrite "make -j8" \
+  "make -j8" \
+  "rtk make -j8"
+
+test_rewrite "make -C dir all" \
+  "make -C lib -j 4 all" \
+  "rtk make -C lib -j 4 all"
+
+test_rewrite "make serve (not rewritten)" \
+  "make serve" \
+  ""
+
+test_rewrite "cmake --build" \
+  "cmake --build build" \
+  "rtk cmake --build build"
+
+test_rewrite "g++ compile" \
+  "g++ -std=c++17 -c main.cpp" \
+  "rtk g++ -std=c++17 -c main.cpp"
+
+test_rewrite "bundle exec rspec" \
+  "bundle exec rspec spec/models" \
+  "rtk rspec spec/models"
+
+test_rewrite "bin/rails test" \
+  "bin/rails test test/models/cart_test.rb:10" \
+  "rtk minitest test/models/cart_test.rb:10"
+
+test_rewrite "bun test" \
+  "bun test src/cart.test.ts" \
+  "rtk bun test src/cart.test.ts"
+
+test_rewrite "bun test --watch (not rewritten)" \
+  "bun test --watch" \
+  ""
+
+test_rewrite "bun run dev (not rewritten)" \
+  "bun run dev" \
+  ""
+
+test_rewrite "yarn install" \
+  "yarn install --immutable" \
+  "rtk yarn install --immutable"
+
+test_rewrite "cargo bench" \
+  "cargo bench --bench parse" \
+  "rtk cargo bench --bench parse"
+
+test_rewrite "hyperfine" \
+  "hyperfine 'rg foo' 'grep -r foo'" \
+  "rtk bench hyperfine 'rg foo' 'grep -r foo'"
+
 echo ""
 
 # ---- SECTION 4: Vitest edge case (fixed double "run" bug) ----
@@ -0,0 +1,1046 @@
+//! Benchmark output compaction.
+//!
+//! `rtk bench <command>` runs criterion (`cargo bench`), hyperfine,
+//! `go test -bench` or pytest-benchmark and prints one row per benchmark
+//! with mean ± stddev and the change against the previous run (criterion)
+//! or the first command (hyperfine). Changes within noise are collapsed
+//! into a single line. Without a command it reads `target/criterion`; a
+//! file argument is parsed as hyperfine `--export-json` or pytest-benchmark
+//! `--benchmark-json` output. `rtk cargo bench` and `rtk go test -bench`
+//! share the same parsers and table.
+
+use crate::parser::emit_passthrough_warning;
+use crate::tracking;
+use crate::utils::{strip_ansi, truncate};
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Rows listed before summarizing the rest
+const MAX_ROWS: usize = 40;
+
+/// Unchanged benchmark names listed on the collapsed line
+const MAX_UNCHANGED_NAMES: usize = 8;
+
+/// Failure lines kept next to a benchmark table
+const MAX_FAILURE_LINES: usize = 20;
+
+/// Assertion lines kept under each `--- FAIL` (as for `rtk go test` failures)
+const MAX_FAILURE_DETAIL_LINES: usize = 5;
+
+/// Relative change criterion treats as noise (its default `noise_threshold`)
+const CRITERION_NOISE: f64 = 0.01;
+
+lazy_static! {
+    // `fib 20    time:   [26.029 µs 26.251 µs 26.505 µs]` (name may sit on the line above)
+    static ref CRITERION_TIME: Regex = Regex::new(
+        r"^(\S.*?)?\s*time:\s+\[[\d.]+ \S+ ([\d.]+) (\S+) [\d.]+ \S+\]"
+    )
+    .unwrap();
+    // `change: [-1.2345% +0.5000% +2.1000%] (p = 0.42 > 0.05)`
+    static ref CRITERION_CHANGE: Regex = Regex::new(
+        r"\[[+-]?[\d.]+% ([+-]?[\d.]+)% [+-]?[\d.]+%\]\s*\(p = ([\d.]+) ([<>]) [\d.]+\)"
+    )
+    .unwrap();
+    // `test bench_parse ... bench:       1,234 ns/iter (+/- 56)`
+    static ref LIBTEST_BENCH: Regex = Regex::new(
+        r"^test (\S+)\s+\.\.\. bench:\s+([\d,.]+) (\w+)/iter \(\+/- ([\d,.]+)\)"
+    )
+    .unwrap();
+    // `BenchmarkParse-8   	  500000	      2345 ns/op	     128 B/op	       2 allocs/op`
+    static ref GO_BENCH: Regex = Regex::new(
+        r"^(Benchmark\S+?)(?:-\d+)?\s+\d+\s+([\d.]+) ns/op(.*)$"
+    )
+    .unwrap();
+    static ref HYPERFINE_NAME: Regex = Regex::new(r"^Benchmark \d+: (.+)$").unwrap();
+    // `Time (mean ± σ):     102.4 ms ±   0.5 ms` or `Time (abs ≡):        1.2 s`
+    static ref HYPERFINE_TIME: Regex = Regex::new(
+        r"Time \((?:mean ± σ|abs ≡)\):\s+([\d.]+) (\S+)(?:\s+±\s+([\d.]+) (\S+))?"
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bench {
+    pub name: String,
+    /// Mean time per iteration, in nanoseconds
+    pub mean_ns: f64,
+    pub stddev_ns: Option<f64>,
+    /// Change of the mean against the comparison point, in percent
+    pub change_pct: Option<f64>,
+    /// Change is outside the tool's noise threshold
+    pub significant: bool,
+    /// Extra per-iteration metrics (`128 B/op, 2 allocs/op`)
+    pub extra: Option<String>,
+}
+
+impl Bench {
+    fn new(name: &str, mean_ns: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            mean_ns,
+            stddev_ns: None,
+            change_pct: None,
+            significant: false,
+            extra: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BenchReport {
+    pub tool: &'static str,
+    /// What changes are measured against ("previous run", the first command)
+    pub compared_to: Option<String>,
+    pub benches: Vec<Bench>,
+}
+
+/// Entry point: `rtk bench [command... | file | dir]`
+pub fn run(args: &[String], verbose: u8) -> Result<()> {
+    match args {
+        [] => {
+            let dir = criterion_dir();
+            if !dir.is_dir() {
+                bail!(
+                    "No benchmark command or results given and {} does not exist \
+                     (usage: rtk bench <command> | <results.json> | <criterion dir>)",
+                    dir.display()
+                );
+            }
+            run_results(&dir, verbose)
+        }
+        [path] if Path::new(path).is_dir() || path.ends_with(".json") => {
+            run_results(Path::new(path), verbose)
+        }
+        _ => run_command(args, verbose),
+    }
+}
+
+/// Summarize results already on disk (criterion directory or a JSON export)
+fn run_results(path: &Path, verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    // A criterion directory has no single raw document to compare against
+    let (raw, report) = if path.is_dir() {
+        (None, parse_criterion_dir(path))
+    } else {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let report = parse_output(&content);
+        (Some(content), report)
+    };
+
+    if report.benches.is_empty() {
+        bail!(
+            "No benchmark results found in {} (expected criterion estimates, \
+             hyperfine --export-json or pytest-benchmark --benchmark-json output)",
+            path.display()
+        );
+    }
+    if verbose > 0 {
+        eprintln!(
+            "Read {} {} benchmarks from {}",
+            report.benches.len(),
+            report.tool,
+            path.display()
+        );
+    }
+
+    let filtered = format_report(&report);
+    println!("{}", filtered);
+
+    let rtk_cmd = format!("rtk bench {}", path.display());
+    match raw {
+        Some(raw) => timer.track(
+            &format!("cat {}", path.display()),
+            &rtk_cmd,
+            &raw,
+            &filtered,
+        ),
+        None => timer.track_passthrough(&format!("ls {}", path.display()), &rtk_cmd),
+    }
+    Ok(())
+}
+
+/// Run a benchmark command, asking hyperfine / pytest-benchmark for a JSON export
+fn run_command(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let temp_export = std::env::temp_dir().join(format!("rtk-bench-{}.json", std::process::id()));
+    let mut cmd_args: Vec<String> = args[1..].to_vec();
+    let export = if is_hyperfine(args) {
+        Some(user_export(args, "--export-json").unwrap_or_else(|| {
+            cmd_args.push("--export-json".to_string());
+            cmd_args.push(temp_export.display().to_string());
+            temp_export.clone()
+        }))
+    } else if is_pytest(args) {
+        Some(user_export(args, "--benchmark-json").unwrap_or_else(|| {
+            cmd_args.push(format!("--benchmark-json={}", temp_export.display()));
+            temp_export.clone()
+        }))
+    } else {
+        None
+    };
+
+    if verbose > 0 {
+        eprintln!("Running: {} {}", args[0], cmd_args.join(" "));
+    }
+
+    let started = SystemTime::now();
+    let output = Command::new(&args[0])
+        .args(&cmd_args)
+        .output()
+        .with_context(|| format!("Failed to run {}", args[0]))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+
+    // A user export left over from an earlier run must not stand in for this one
+    let exported = export
+        .as_ref()
+        .and_then(|path| crate::utils::read_if_modified_since(path, started));
+    if export.as_deref() == Some(temp_export.as_path()) {
+        let _ = std::fs::remove_file(&temp_export);
+    }
+
+    let mut report = match exported {
+        Some(json) => parse_output(&json),
+        None => parse_output(&raw),
+    };
+    if report.tool == "criterion" {
+        fill_criterion_stddev(&mut report, &criterion_dir());
+    }
+
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+    let mut filtered = if report.benches.is_empty() {
+        if verbose > 0 {
+            emit_passthrough_warning("bench", "no benchmark results recognized");
+        }
+        raw.trim().to_string()
+    } else {
+        let mut text = format_report(&report);
+        if exit_code != 0 {
+            text.push_str(&failure_lines(&stdout, &stderr));
+        }
+        text
+    };
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "bench", exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &args.join(" "),
+        &format!("rtk bench {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+fn is_hyperfine(args: &[String]) -> bool {
+    program_name(&args[0]) == "hyperfine"
+}
+
+fn is_pytest(args: &[String]) -> bool {
+    let program = program_name(&args[0]);
+    program == "pytest"
+        || program == "py.test"
+        || (program.starts_with("python") && args.windows(2).any(|w| w == ["-m", "pytest"]))
+}
+
+fn program_name(program: &str) -> &str {
+    program.rsplit('/').next().unwrap_or(program)
+}
+
+/// Export path the user already passed (`--flag path` or `--flag=path`)
+fn user_export(args: &[String], flag: &str) -> Option<PathBuf> {
+    let prefix = format!("{}=", flag);
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if let Some(path) = arg.strip_prefix(&prefix) {
+            Some(PathBuf::from(path))
+        } else if arg == flag {
+            args.get(i + 1).map(PathBuf::from)
+        } else {
+            None
+        }
+    })
+}
+
+/// Failure lines worth keeping next to a benchmark table: each `--- FAIL`
+/// with the indented `x_test.go:NN: ...` lines under it, `FAIL`, errors and
+/// panics. Shared with `rtk go test -bench`.
+pub fn failure_lines(stdout: &str, stderr: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_block = false;
+    let mut details = 0;
+    for line in stdout.lines().chain(stderr.lines()) {
+        let t = line.trim_start();
+        if t.starts_with("--- FAIL") {
+            lines.push(line.to_string());
+            in_block = true;
+            details = 0;
+        } else if in_block && line.starts_with([' ', '\t']) && !t.is_empty() {
+            if details < MAX_FAILURE_DETAIL_LINES {
+                lines.push(truncate(line, 100));
+            }
+            details += 1;
+        } else {
+            in_block = false;
+            if t.starts_with("FAIL")
+                || t.starts_with("error")
+                || t.starts_with("panic:")
+                || t.contains("panicked at")
+            {
+                lines.push(line.to_string());
+            }
+        }
+    }
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.truncate(MAX_FAILURE_LINES);
+        format!("\n\nFailures:\n{}", lines.join("\n"))
+    }
+}
+
+/// `target/criterion`, honoring `CARGO_TARGET_DIR`
+fn criterion_dir() -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("target"))
+        .join("criterion")
+}
+
+/// Detect the format (JSON export or tool text output) and parse it
+pub fn parse_output(content: &str) -> BenchReport {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(content.trim()) {
+        if let Some(report) = parse_hyperfine_json(&json) {
+            return report;
+        }
+        if let Some(report) = parse_pytest_benchmark_json(&json) {
+            return report;
+        }
+    }
+    let text = strip_ansi(content);
+    for parse in [
+        parse_criterion,
+        parse_libtest,
+        parse_go_bench,
+        parse_hyperfine_text,
+    ] {
+        let report = parse(&text);
+        if !report.benches.is_empty() {
+            return report;
+        }
+    }
+    BenchReport {
+        tool: "unknown",
+        compared_to: None,
+        benches: Vec::new(),
+    }
+}
+
+/// `cargo bench` output: criterion reports or libtest `#[bench]` lines
+pub fn parse_cargo_bench(output: &str) -> Option<BenchReport> {
+    let text = strip_ansi(output);
+    let mut report = parse_criterion(&text);
+    if report.benches.is_empty() {
+        report = parse_libtest(&text);
+    } else {
+        fill_criterion_stddev(&mut report, &criterion_dir());
+    }
+    (!report.benches.is_empty()).then_some(report)
+}
+
+fn to_ns(value: f64, unit: &str) -> f64 {
+    match unit {
+        "ps" => value / 1_000.0,
+        "µs" | "us" | "μs" => value * 1_000.0,
+        "ms" => value * 1_000_000.0,
+        "s" => value * 1_000_000_000.0,
+        _ => value,
+    }
+}
+
+/// criterion's terminal report (`time:` / `change:` / verdict lines)
+fn parse_criterion(text: &str) -> BenchReport {
+    let mut benches: Vec<Bench> = Vec::new();
+    let mut pending_name: Option<String> = None;
+    let mut has_change = false;
+
+    for line in text.lines() {
+        // Progress redraws are separated by carriage returns; keep the last frame
+        let line = line.rsplit('\r').next().unwrap_or(line);
+        let trimmed = line.trim();
+
+        if let Some(caps) = CRITERION_TIME.captures(line) {
+            let name = caps
+                .get(1)
+                .map(|m| m.as_str().trim().to_string())
+                .or_else(|| pending_name.take());
+            if let Some(name) = name {
+                let value: f64 = caps[2].parse().unwrap_or(0.0);
+                benches.push(Bench::new(&name, to_ns(value, &caps[3])));
+            }
+            pending_name = None;
+            continue;
+        }
+        if let Some(caps) = CRITERION_CHANGE.captures(line) {
+            if let Some(bench) = benches.last_mut().filter(|b| b.change_pct.is_none()) {
+                bench.change_pct = caps[1].parse().ok();
+                bench.significant = &caps[3] == "<";
+                has_change = true;
+            }
+            continue;
+        }
+        if trimmed.starts_with("Performance has regressed")
+            || trimmed.starts_with("Performance has improved")
+        {
+            if let Some(bench) = benches.last_mut() {
+                bench.significant = true;
+            }
+            continue;
+        }
+        if trimmed.starts_with("No change in performance detected")
+            || trimmed.starts_with("Change within noise threshold")
+        {
+            if let Some(bench) = benches.last_mut() {
+                bench.significant = false;
+            }
+            continue;
+        }
+
+        // Long benchmark names are printed alone, with `time:` on the next line
+        let is_noise = trimmed.is_empty()
+            || line.starts_with(char::is_whitespace)
+            || trimmed.starts_with("Benchmarking ")
+            || trimmed.starts_with("Found ")
+            || trimmed.starts_with("Gnuplot not found")
+            || trimmed.starts_with("Running ")
+            || trimmed.starts_with("Compiling ")
+            || trimmed.starts_with("Finished ")
+            || trimmed.starts_with("WARNING:")
+            || trimmed.starts_with("Warning:");
+        pending_name = (!is_noise).then(|| trimmed.to_string());
+    }
+
+    BenchReport {
+        tool: "criterion",
+        compared_to: has_change.then(|| "previous run".to_string()),
+        benches,
+    }
+}
+
+/// Add stddev from `target/criterion/<name>/new/estimates.json` (not printed by criterion)
+fn fill_criterion_stddev(report: &mut BenchReport, dir: &Path) {
+    for bench in &mut report.benches {
+        if bench.stddev_ns.is_some() {
+            continue;
+        }
+        let estimates = dir.join(&bench.name).join("new").join("estimates.json");
+        bench.stddev_ns = read_json(&estimates)
+            .and_then(|json| json.pointer("/std_dev/point_estimate")?.as_f64());
+    }
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// criterion's saved results: every `<id>/new/estimates.json`, with the
+/// change against the previous run from `<id>/change/estimates.json`
+fn parse_criterion_dir(dir: &Path) -> BenchReport {
+    let mut benches = Vec::new();
+    let mut has_change = false;
+    collect_criterion(dir, dir, &mut benches, &mut has_change);
+    benches.sort_by(|a, b| a.name.cmp(&b.name));
+    BenchReport {
+        tool: "criterion",
+        compared_to: has_change.then(|| "previous run".to_string()),
+        benches,
+    }
+}
+
+fn collect_criterion(root: &Path, dir: &Path, benches: &mut Vec<Bench>, has_change: &mut bool) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut subdirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    subdirs.sort();
+
+    if let Some(estimates) = read_json(&dir.join("new").join("estimates.json")) {
+        let Some(mean) = estimates
+            .pointer("/mean/point_estimate")
+            .and_then(|v| v.as_f64())
+        else {
+            return;
+        };
+        let name = read_json(&dir.join("new").join("benchmark.json"))
+            .and_then(|b| b.get("full_id")?.as_str().map(String::from))
+            .unwrap_or_else(|| dir.strip_prefix(root).unwrap_or(dir).display().to_string());
+        let mut bench = Bench::new(&name, mean);
+        bench.stddev_ns = estimates
+            .pointer("/std_dev/point_estimate")
+            .and_then(|v| v.as_f64());
+        if let Some(change) = read_json(&dir.join("change").join("estimates.json")) {
+            let point = change
+                .pointer("/mean/point_estimate")
+                .and_then(|v| v.as_f64());
+            let lower = change
+                .pointer("/mean/confidence_interval/lower_bound")
+                .and_then(|v| v.as_f64());
+            let upper = change
+                .pointer("/mean/confidence_interval/upper_bound")
+                .and_then(|v| v.as_f64());
+            if let Some(point) = point {
+                bench.change_pct = Some(point * 100.0);
+                // Same rule as criterion's verdict: interval excludes ±noise
+                bench.significant = match (lower, upper) {
+                    (Some(lo), Some(hi)) => lo > CRITERION_NOISE || hi < -CRITERION_NOISE,
+                    _ => point.abs() > CRITERION_NOISE,
+                };
+                *has_change = true;
+            }
+        }
+        benches.push(bench);
+        return;
+    }
+
+    for sub in subdirs {
+        if sub.file_name().is_some_and(|n| n == "report") {
+            continue;
+        }
+        collect_criterion(root, &sub, benches, has_change);
+    }
+}
+
+/// libtest `#[bench]` lines (nightly `cargo bench` without criterion)
+fn parse_libtest(text: &str) -> BenchReport {
+    let benches = text
+        .lines()
+        .filter_map(|line| {
+            let caps = LIBTEST_BENCH.captures(line.trim())?;
+            let number = |s: &str| s.replace(',', "").parse::<f64>().ok();
+            let mut bench = Bench::new(&caps[1], to_ns(number(&caps[2])?, &caps[3]));
+            bench.stddev_ns = number(&caps[4]).map(|d| to_ns(d, &caps[3]));
+            Some(bench)
+        })
+        .collect();
+    BenchReport {
+        tool: "libtest",
+        compared_to: None,
+        benches,
+    }
+}
+
+/// `go test -bench` lines; repeated runs (`-count N`) give mean and stddev
+fn parse_go_bench(text: &str) -> BenchReport {
+    let mut samples: Vec<(String, Vec<f64>, String)> = Vec::new();
+    for line in text.lines() {
+        let Some(caps) = GO_BENCH.captures(line.trim()) else {
+            continue;
+        };
+        let Ok(value) = caps[2].parse::<f64>() else {
+            continue;
+        };
+        let extra = caps[3]
+            .split('\t')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match samples.iter_mut().find(|(name, _, _)| name == &caps[1]) {
+            Some((_, values, _)) => values.push(value),
+            None => samples.push((caps[1].to_string(), vec![value], extra)),
+        }
+    }
+
+    let benches = samples
+        .into_iter()
+        .map(|(name, values, extra)| {
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let mut bench = Bench::new(&name, mean);
+            if values.len() > 1 {
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                bench.stddev_ns = Some(variance.sqrt());
+            }
+            bench.extra = (!extra.is_empty()).then_some(extra);
+            bench
+        })
+        .collect();
+    BenchReport {
+        tool: "go",
+        compared_to: None,
+        benches,
+    }
+}
+
+/// Mark each command's change against the first one; significant when the
+/// difference exceeds the combined stddev
+fn compare_to_first(benches: &mut [Bench]) {
+    let Some((first, rest)) = benches.split_first_mut() else {
+        return;
+    };
+    if first.mean_ns <= 0.0 {
+        return;
+    }
+    for bench in rest {
+        let diff = bench.mean_ns - first.mean_ns;
+        bench.change_pct = Some(diff / first.mean_ns * 100.0);
+        let noise = first.stddev_ns.unwrap_or(0.0) + bench.stddev_ns.unwrap_or(0.0);
+        bench.significant = diff.abs() > noise;
+    }
+}
+
+/// hyperfine `--export-json`: `results[].command/mean/stddev` in seconds
+fn parse_hyperfine_json(json: &serde_json::Value) -> Option<BenchReport> {
+    let results = json.get("results")?.as_array()?;
+    let mut benches: Vec<Bench> = results
+        .iter()
+        .filter_map(|r| {
+            let name = r.get("command")?.as_str()?;
+            let mean = r.get("mean")?.as_f64()?;
+            let mut bench = Bench::new(name, mean * 1e9);
+            bench.stddev_ns = r.get("stddev").and_then(|s| s.as_f64()).map(|s| s * 1e9);
+            Some(bench)
+        })
+        .collect();
+    compare_to_first(&mut benches);
+    Some(BenchReport {
+        tool: "hyperfine",
+        compared_to: (benches.len() > 1).then(|| format!("`{}`", benches[0].name)),
+        benches,
+    })
+}
+
+/// hyperfine terminal output (`Benchmark 1: cmd` / `Time (mean ± σ): ...`)
+fn parse_hyperfine_text(text: &str) -> BenchReport {
+    let mut benches = Vec::new();
+    let mut name: Option<String> = None;
+    for line in text.lines() {
+        let line = line.rsplit('\r').next().unwrap_or(line).trim();
+        if let Some(caps) = HYPERFINE_NAME.captures(line) {
+            name = Some(caps[1].to_string());
+        } else if let Some(caps) = HYPERFINE_TIME.captures(line) {
+            let (Some(command), Ok(mean)) = (name.take(), caps[1].parse::<f64>()) else {
+                continue;
+            };
+            let mut bench = Bench::new(&command, to_ns(mean, &caps[2]));
+            bench.stddev_ns = caps
+                .get(3)
+                .zip(caps.get(4))
+                .and_then(|(v, unit)| Some(to_ns(v.as_str().parse().ok()?, unit.as_str())));
+            benches.push(bench);
+        }
+    }
+    compare_to_first(&mut benches);
+    BenchReport {
+        tool: "hyperfine",
+        compared_to: (benches.len() > 1).then(|| format!("`{}`", benches[0].name)),
+        benches,
+    }
+}
+
+/// pytest-benchmark `--benchmark-json`: `benchmarks[].name/stats.mean/stats.stddev` in seconds
+fn parse_pytest_benchmark_json(json: &serde_json::Value) -> Option<BenchReport> {
+    let entries = json.get("benchmarks")?.as_array()?;
+    let benches = entries
+        .iter()
+        .filter_map(|b| {
+            let name = b.get("name")?.as_str()?;
+            let stats = b.get("stats")?;
+            let mut bench = Bench::new(name, stats.get("mean")?.as_f64()? * 1e9);
+            bench.stddev_ns = stats
+                .get("stddev")
+                .and_then(|s| s.as_f64())
+                .map(|s| s * 1e9);
+            Some(bench)
+        })
+        .collect();
+    Some(BenchReport {
+        tool: "pytest-benchmark",
+        compared_to: None,
+        benches,
+    })
+}
+
+/// 1234567.0 → "1.23 ms"
+fn format_duration(ns: f64) -> String {
+    let (value, unit) = if ns >= 1e9 {
+        (ns / 1e9, "s")
+    } else if ns >= 1e6 {
+        (ns / 1e6, "ms")
+    } else if ns >= 1e3 {
+        (ns / 1e3, "µs")
+    } else {
+        (ns, "ns")
+    };
+    if value >= 100.0 {
+        format!("{:.1} {}", value, unit)
+    } else {
+        format!("{:.2} {}", value, unit)
+    }
+}
+
+/// One row per benchmark; changes within noise collapse into one line
+pub fn format_report(report: &BenchReport) -> String {
+    let unchanged: Vec<&Bench> = report
+        .benches
+        .iter()
+        .filter(|b| b.change_pct.is_some() && !b.significant)
+        .collect();
+    let rows: Vec<&Bench> = report
+        .benches
+        .iter()
+        .filter(|b| b.change_pct.is_none() || b.significant)
+        .collect();
+    let slower = rows
+        .iter()
+        .filter(|b| b.change_pct.is_some_and(|c| c > 0.0))
+        .count();
+    let faster = rows
+        .iter()
+        .filter(|b| b.change_pct.is_some_and(|c| c < 0.0))
+        .count();
+
+    let mut header = format!("Benchmarks ({}, {})", report.tool, report.benches.len());
+    if let Some(compared_to) = &report.compared_to {
+        header.push_str(&format!(
+            " vs {}: {} slower, {} faster, {} unchanged",
+            compared_to,
+            slower,
+            faster,
+            unchanged.len()
+        ));
+    }
+    let mut out = vec![header];
+
+    let name_width = rows
+        .iter()
+        .map(|b| b.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(50);
+    // Means right-aligned, stddevs left-aligned, so `±` lines up
+    let means: Vec<String> = rows.iter().map(|b| format_duration(b.mean_ns)).collect();
+    let stddevs: Vec<Option<String>> = rows
+        .iter()
+        .map(|b| b.stddev_ns.map(format_duration))
+        .collect();
+    let mean_width = means.iter().map(|m| m.chars().count()).max().unwrap_or(0);
+    let stddev_width = stddevs
+        .iter()
+        .flatten()
+        .map(|s| s.chars().count() + 3)
+        .max()
+        .unwrap_or(0);
+
+    for ((bench, mean), stddev) in rows.iter().zip(&means).zip(&stddevs).take(MAX_ROWS) {
+        let stddev = stddev
+            .as_ref()
+            .map(|s| format!(" ± {}", s))
+            .unwrap_or_default();
+        let mut line = format!(
+            "  {:<name_width$}  {:>mean_width$}{:<stddev_width$}",
+            bench.name, mean, stddev
+        );
+        if let Some(change) = bench.change_pct {
+            let verdict = if change > 0.0 { "slower" } else { "faster" };
+            line.push_str(&format!("  {:+.1}% {}", change, verdict));
+        }
+        if let Some(extra) = &bench.extra {
+            line.push_str(&format!("  {}", extra));
+        }
+        out.push(line.trim_end().to_string());
+    }
+    if rows.len() > MAX_ROWS {
+        out.push(format!("  ... +{} more benchmarks", rows.len() - MAX_ROWS));
+    }
+
+    if !unchanged.is_empty() {
+        let mut names: Vec<&str> = unchanged
+            .iter()
+            .take(MAX_UNCHANGED_NAMES)
+            .map(|b| b.name.as_str())
+            .collect();
+        let more = unchanged.len().saturating_sub(MAX_UNCHANGED_NAMES);
+        let more_text = format!("+{} more", more);
+        if more > 0 {
+            names.push(&more_text);
+        }
+        out.push(format!(
+            "  {} unchanged (within noise): {}",
+            unchanged.len(),
+            names.join(", ")
+        ));
+    }
+
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CRITERION_OUTPUT: &str = "\
+   Compiling bench-demo v0.1.0 (/tmp/bench-demo)
+    Finished bench [optimized] target(s) in 4.20s
+     Running benches/parse.rs (target/release/deps/parse-1234)
+Gnuplot not found, using plotters backend
+Benchmarking parse/small
+Benchmarking parse/small: Warming up for 3.0000 s
+Benchmarking parse/small: Collecting 100 samples in estimated 5.0012 s (1.2M iterations)
+Benchmarking parse/small: Analyzing
+parse/small             time:   [4.0512 µs 4.0731 µs 4.0990 µs]
+                        change: [+11.203% +12.310% +13.502%] (p = 0.00 < 0.05)
+                        Performance has regressed.
+Found 7 outliers among 100 measurements (7.00%)
+  4 (4.00%) high mild
+  3 (3.00%) high severe
+parse/large             time:   [812.10 µs 815.33 µs 818.91 µs]
+                        change: [-0.8123% +0.2035% +1.1020%] (p = 0.70 > 0.05)
+                        No change in performance detected.
+a_benchmark_with_a_name_that_is_long_enough_to_wrap
+                        time:   [1.2001 ms 1.2100 ms 1.2200 ms]
+                        change: [-9.1000% -8.0000% -6.9000%] (p = 0.00 < 0.05)
+                        Performance has improved.
+";
+
+    #[test]
+    fn test_parse_criterion_text() {
+        let report = parse_criterion(CRITERION_OUTPUT);
+        assert_eq!(report.benches.len(), 3);
+        assert_eq!(report.compared_to.as_deref(), Some("previous run"));
+
+        let small = &report.benches[0];
+        assert_eq!(small.name, "parse/small");
+        assert!((small.mean_ns - 4073.1).abs() < 0.01);
+        assert_eq!(small.change_pct, Some(12.31));
+        assert!(small.significant);
+
+        assert!(!report.benches[1].significant);
+        let long = &report.benches[2];
+        assert_eq!(
+            long.name,
+            "a_benchmark_with_a_name_that_is_long_enough_to_wrap"
+        );
+        assert!((long.mean_ns - 1_210_000.0).abs() < 0.01);
+        assert_eq!(long.change_pct, Some(-8.0));
+    }
+
+    #[test]
+    fn test_format_collapses_unchanged() {
+        let report = parse_criterion(CRITERION_OUTPUT);
+        let out = format_report(&report);
+        assert!(out.starts_with(
+            "Benchmarks (criterion, 3) vs previous run: 1 slower, 1 faster, 1 unchanged"
+        ));
+        assert!(out.contains("4.07 µs"));
+        assert!(out.contains("+12.3% slower"));
+        assert!(out.contains("-8.0% faster"));
+        assert!(out.contains("1 unchanged (within noise): parse/large"));
+        assert!(!out.contains("815.33"));
+        assert!(!out.contains("outliers"));
+        assert!(!out.contains("Warming up"));
+    }
+
+    #[test]
+    fn test_parse_criterion_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let bench = dir.path().join("parse").join("small");
+        std::fs::create_dir_all(bench.join("new")).unwrap();
+        std::fs::create_dir_all(bench.join("change")).unwrap();
+        std::fs::create_dir_all(dir.path().join("report")).unwrap();
+        std::fs::write(
+            bench.join("new").join("estimates.json"),
+            r#"{"mean":{"point_estimate":4073.1,"standard_error":5.0},
+               "std_dev":{"point_estimate":120.5,"standard_error":2.0}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            bench.join("new").join("benchmark.json"),
+            r#"{"group_id":"parse","function_id":"small","full_id":"parse/small"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            bench.join("change").join("estimates.json"),
+            r#"{"mean":{"point_estimate":0.004,
+               "confidence_interval":{"lower_bound":-0.003,"upper_bound":0.011}}}"#,
+        )
+        .unwrap();
+
+        let report = parse_criterion_dir(dir.path());
+        assert_eq!(report.benches.len(), 1);
+        let small = &report.benches[0];
+        assert_eq!(small.name, "parse/small");
+        assert_eq!(small.stddev_ns, Some(120.5));
+        assert!((small.change_pct.unwrap() - 0.4).abs() < 1e-9);
+        assert!(!small.significant);
+
+        let mut text = parse_criterion(CRITERION_OUTPUT);
+        fill_criterion_stddev(&mut text, dir.path());
+        assert_eq!(text.benches[0].stddev_ns, Some(120.5));
+        assert_eq!(text.benches[1].stddev_ns, None);
+    }
+
+    #[test]
+    fn test_parse_go_bench_with_count() {
+        let output = "\
+goos: linux
+goarch: amd64
+pkg: example.com/demo
+cpu: AMD Ryzen 7 5800X 8-Core Processor
+BenchmarkParse-16     \t  500000\t      2000 ns/op\t     128 B/op\t       2 allocs/op
+BenchmarkParse-16     \t  500000\t      2200 ns/op\t     128 B/op\t       2 allocs/op
+BenchmarkEncode/small-16 \t 1000000\t      1100 ns/op
+PASS
+ok  \texample.com/demo\t4.512s
+";
+        let report = parse_output(output);
+        assert_eq!(report.tool, "go");
+        assert_eq!(report.benches.len(), 2);
+        let parse = &report.benches[0];
+        assert_eq!(parse.name, "BenchmarkParse");
+        assert!((parse.mean_ns - 2100.0).abs() < 1e-9);
+        assert!((parse.stddev_ns.unwrap() - 141.42).abs() < 0.01);
+        assert_eq!(parse.extra.as_deref(), Some("128 B/op, 2 allocs/op"));
+        assert_eq!(report.benches[1].name, "BenchmarkEncode/small");
+
+        let out = format_report(&report);
+        assert!(out.starts_with("Benchmarks (go, 2)\n"));
+        assert!(out.contains("2.10 µs ± 141.4 ns"));
+        assert!(!out.contains("goarch"));
+    }
+
+    #[test]
+    fn test_parse_hyperfine_json_and_text() {
+        let json = r#"{"results":[
+            {"command":"rg foo","mean":0.0102,"stddev":0.0004,"median":0.0101,"times":[0.01]},
+            {"command":"grep -r foo","mean":0.0512,"stddev":0.0021,"median":0.051,"times":[0.05]},
+            {"command":"rg -F foo","mean":0.0104,"stddev":0.0005,"median":0.0103,"times":[0.01]}
+        ]}"#;
+        let report = parse_output(json);
+        assert_eq!(report.tool, "hyperfine");
+        assert_eq!(report.compared_to.as_deref(), Some("`rg foo`"));
+        assert!(report.benches[1].significant);
+        assert!(!report.benches[2].significant);
+        let out = format_report(&report);
+        assert!(out.contains("vs `rg foo`: 1 slower, 0 faster, 1 unchanged"));
+        assert!(out.contains("+402.0% slower"));
+        assert!(out.contains("1 unchanged (within noise): rg -F foo"));
+
+        let text = "\
+Benchmark 1: rg foo
+  Time (mean ± σ):      10.2 ms ±   0.4 ms    [User: 6.1 ms, System: 4.0 ms]
+  Range (min … max):     9.6 ms …  11.9 ms    263 runs
+
+Benchmark 2: grep -r foo
+  Time (mean ± σ):      51.2 ms ±   2.1 ms    [User: 30.0 ms, System: 20.8 ms]
+  Range (min … max):    48.9 ms …  58.3 ms    56 runs
+
+Summary
+  rg foo ran
+    5.02 ± 0.29 times faster than grep -r foo
+";
+        let report = parse_output(text);
+        assert_eq!(report.tool, "hyperfine");
+        assert_eq!(report.benches.len(), 2);
+        assert!((report.benches[0].stddev_ns.unwrap() - 400_000.0).abs() < 1e-6);
+        assert!(report.benches[1].significant);
+    }
+
+    #[test]
+    fn test_parse_pytest_benchmark_json() {
+        let json = r#"{"machine_info":{},"benchmarks":[
+            {"group":null,"name":"test_parse","fullname":"tests/test_bench.py::test_parse",
+             "stats":{"min":0.0001,"max":0.0003,"mean":0.000152,"stddev":0.000011,"rounds":500}}
+        ],"datetime":"2026-10-18T10:00:00","version":"4.0.0"}"#;
+        let report = parse_output(json);
+        assert_eq!(report.tool, "pytest-benchmark");
+        let out = format_report(&report);
+        assert!(out.contains("test_parse  152.0 µs ± 11.00 µs"));
+    }
+
+    #[test]
+    fn test_parse_libtest_bench() {
+        let output = "\
+running 2 tests
+test bench_decode ... bench:       1,234 ns/iter (+/- 56)
+test bench_encode ... bench:         890 ns/iter (+/- 12)
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 2 filtered out
+";
+        let report = parse_cargo_bench(output).unwrap();
+        assert_eq!(report.tool, "libtest");
+        assert_eq!(report.benches[0].mean_ns, 1234.0);
+        assert_eq!(report.benches[0].stddev_ns, Some(56.0));
+        assert!(parse_cargo_bench("error[E0425]: cannot find value").is_none());
+    }
+
+    #[test]
+    fn test_user_export_and_tool_detection() {
+        let args: Vec<String> = ["hyperfine", "--export-json", "out.json", "rg foo"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(is_hyperfine(&args));
+        assert_eq!(
+            user_export(&args, "--export-json"),
+            Some(PathBuf::from("out.json"))
+        );
+        let args: Vec<String> = ["python3", "-m", "pytest", "--benchmark-json=b.json"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(is_pytest(&args));
+        assert_eq!(
+            user_export(&args, "--benchmark-json"),
+            Some(PathBuf::from("b.json"))
+        );
+    }
+
+    #[test]
+    fn test_failure_lines_keep_assertions() {
+        let stdout = "goos: linux\n\
+                      BenchmarkParse-8   \t 1000\t 1200 ns/op\n\
+                      --- FAIL: TestParse (0.00s)\n\
+                      \x20   parse_test.go:14: expected 2, got 3\n\
+                      \x20   parse_test.go:15: trailing input\n\
+                      FAIL\n\
+                      exit status 1\n\
+                      FAIL\texample.com/demo\t0.120s\n";
+        assert_eq!(
+            failure_lines(stdout, "error: could not compile `demo`\n"),
+            "\n\nFailures:\n\
+             --- FAIL: TestParse (0.00s)\n\
+             \x20   parse_test.go:14: expected 2, got 3\n\
+             \x20   parse_test.go:15: trailing input\n\
+             FAIL\n\
+             FAIL\texample.com/demo\t0.120s\n\
+             error: could not compile `demo`"
+        );
+        assert_eq!(failure_lines("BenchmarkParse-8 1000 1200 ns/op\n", ""), "");
+    }
+}
@@ -0,0 +1,527 @@
+//! Bun commands with compact output.
+//!
+//! `rtk bun install` (and `add` / `remove` / `update`) keeps the installed
+//! package summary, warnings and blocked lifecycle scripts. `rtk bun test`
+//! maps Bun's test runner report into a `TestResult`. Every other subcommand
+//! (`bun run`, watch-mode tests) streams through unchanged.
+
+use crate::parser::{
+    emit_degradation_warning, emit_passthrough_warning, FormatMode, ParseResult, TestFailure,
+    TestResult, TokenFormatter,
+};
+use crate::stack_trace::compress_stack_traces;
+use crate::tracking;
+use crate::utils::strip_ansi;
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::process::Command;
+
+/// Subcommands that install or remove dependencies
+const INSTALL_SUBCOMMANDS: &[&str] = &["install", "i", "add", "a", "remove", "rm", "update"];
+
+/// Added packages listed by name before collapsing into "+N more"
+const MAX_PACKAGES: usize = 10;
+
+lazy_static! {
+    // bun install
+    static ref INSTALL_HEADER: Regex = Regex::new(r"^bun (?:install|add|remove|update) v\S+").unwrap();
+    static ref PACKAGE_ADDED: Regex = Regex::new(r"^\s*\+ (\S+)").unwrap();
+    static ref PACKAGE_REMOVED: Regex = Regex::new(r"^\s*- (\S+)$").unwrap();
+    static ref INSTALLED_WITH_BINARIES: Regex =
+        Regex::new(r"^\s*installed (\S+) with binaries:").unwrap();
+    static ref INSTALL_SUMMARY: Regex =
+        Regex::new(r"^\s*\d+ packages? (?:installed|removed) \[").unwrap();
+    static ref NO_CHANGES: Regex =
+        Regex::new(r"^\s*Checked (\d+) installs? across (\d+) packages? \(no changes\) \[([^\]]+)\]")
+            .unwrap();
+    static ref BLOCKED_SCRIPTS: Regex = Regex::new(r"^\s*Blocked \d+ postinstalls?").unwrap();
+
+    // bun test
+    static ref FILE_HEADER: Regex = Regex::new(r"^(\S+\.(?:test|spec|_test|_spec)\.\w+):$").unwrap();
+    static ref TEST_PASS: Regex =
+        Regex::new(r"^(?:\(pass\)|✓) (.+?)(?: \[[\d.]+m?s\])?$").unwrap();
+    static ref TEST_FAIL: Regex =
+        Regex::new(r"^(?:\(fail\)|✗) (.+?)(?: \[[\d.]+m?s\])?$").unwrap();
+    static ref TEST_SKIP: Regex = Regex::new(r"^(?:\(skip\)|\(todo\)|»|✎) ").unwrap();
+    static ref COUNT_LINE: Regex = Regex::new(r"^\s*(\d+) (pass|fail|skip|todo)$").unwrap();
+    static ref RAN_LINE: Regex =
+        Regex::new(r"^Ran (\d+) tests? across \d+ files?\. \[([\d.]+)(ms|s)\]").unwrap();
+    static ref CODE_EXCERPT: Regex = Regex::new(r"^\s*\d+ \|").unwrap();
+    static ref CARET_LINE: Regex = Regex::new(r"^\s*\^+\s*$").unwrap();
+    static ref FRAME: Regex =
+        Regex::new(r"^\s*at (?:.*? \()?([^()\s]+?):(\d+):\d+\)?$").unwrap();
+}
+
+pub fn run(args: &[String], verbose: u8) -> Result<()> {
+    // Bare `bun` installs
+    if args.is_empty() {
+        return run_filtered(&["install".to_string()], verbose, Kind::Install);
+    }
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        return run_passthrough(args, verbose);
+    }
+    match args[0].as_str() {
+        s if INSTALL_SUBCOMMANDS.contains(&s) => run_filtered(args, verbose, Kind::Install),
+        "test" if !is_watch(args) => run_filtered(args, verbose, Kind::Test),
+        // `bun run dev` and `bun test --watch` run until interrupted
+        _ => run_passthrough(args, verbose),
+    }
+}
+
+fn is_watch(args: &[String]) -> bool {
+    args.iter().any(|a| a == "--watch" || a == "--hot")
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Install,
+    Test,
+}
+
+fn run_filtered(args: &[String], verbose: u8, kind: Kind) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = Command::new("bun");
+    cmd.args(args);
+    // Piped output already disables the spinner; NO_COLOR keeps ANSI out of the report
+    cmd.env("NO_COLOR", "1");
+
+    if verbose > 0 {
+        eprintln!("Running: bun {}", args.join(" "));
+    }
+
+    let output = cmd
+        .output()
+        .context("Failed to run bun. Is Bun installed?")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let exit_code = output.status.code().unwrap_or(1);
+
+    let mut filtered = match kind {
+        Kind::Install => filter_install(&raw, exit_code == 0),
+        Kind::Test => {
+            let parsed = parse_test(&raw);
+            let failed_tests = match &parsed {
+                ParseResult::Full(r) | ParseResult::Degraded(r, _) => {
+                    Some(r.failures.iter().map(|f| f.test_name.clone()).collect())
+                }
+                ParseResult::Passthrough(_) => None,
+            };
+            let mode = FormatMode::from_verbosity(verbose);
+            let mut text = match parsed {
+                ParseResult::Full(result) => {
+                    if verbose > 0 {
+                        eprintln!("bun test (Tier 1: Full parse)");
+                    }
+                    result.format(mode)
+                }
+                ParseResult::Degraded(result, warnings) => {
+                    if verbose > 0 {
+                        emit_degradation_warning("bun test", &warnings.join(", "));
+                    }
+                    result.format(mode)
+                }
+                ParseResult::Passthrough(text) => {
+                    emit_passthrough_warning("bun test", "no test results");
+                    compress_stack_traces(&text)
+                }
+            };
+            crate::test_history::record_and_annotate("bun", args, failed_tests, &mut text);
+            text
+        }
+    };
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "bun", exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &format!("bun {}", args.join(" ")),
+        &format!("rtk bun {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+fn run_passthrough(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("bun passthrough: {:?}", args);
+    }
+    let status = Command::new("bun")
+        .args(args)
+        .status()
+        .context("Failed to run bun. Is Bun installed?")?;
+
+    timer.track_passthrough(
+        &format!("bun {}", args.join(" ")),
+        &format!("rtk bun {} (passthrough)", args.join(" ")),
+    );
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+/// Compact `bun install` / `add` / `remove` output
+fn filter_install(output: &str, success: bool) -> String {
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut summary = None;
+    let mut notes = Vec::new();
+    let mut problems = Vec::new();
+    let mut in_binaries = false;
+
+    for line in strip_ansi(output).lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || INSTALL_HEADER.is_match(trimmed) {
+            in_binaries = false;
+            continue;
+        }
+        if let Some(caps) = INSTALLED_WITH_BINARIES.captures(line) {
+            added.push(caps[1].to_string());
+            in_binaries = true;
+            continue;
+        }
+        if let Some(caps) = PACKAGE_ADDED.captures(line) {
+            added.push(caps[1].to_string());
+            continue;
+        }
+        if let Some(caps) = PACKAGE_REMOVED.captures(line) {
+            // " - tsc" under "installed x with binaries:" lists executables
+            if !in_binaries {
+                removed.push(caps[1].to_string());
+            }
+            continue;
+        }
+        in_binaries = false;
+        if INSTALL_SUMMARY.is_match(line) {
+            summary = Some(trimmed.to_string());
+        } else if let Some(caps) = NO_CHANGES.captures(line) {
+            summary = Some(format!("{} packages, no changes [{}]", &caps[2], &caps[3]));
+        } else if BLOCKED_SCRIPTS.is_match(line) {
+            notes.push(trimmed.to_string());
+        } else if (trimmed.starts_with("warn:") || trimmed.starts_with("error:"))
+            && !problems.iter().any(|p: &String| p == trimmed)
+        {
+            problems.push(trimmed.to_string());
+        }
+    }
+
+    let mark = if success { "✓" } else { "✗" };
+    let mut out = vec![match &summary {
+        Some(s) => format!("{} bun install: {}", mark, s),
+        None if success => format!("{} bun install: done", mark),
+        None => format!("{} bun install: failed", mark),
+    }];
+    if !added.is_empty() {
+        out.push(format!("  + {}", join_capped(&added)));
+    }
+    if !removed.is_empty() {
+        out.push(format!("  - {}", join_capped(&removed)));
+    }
+    for note in notes {
+        out.push(format!("  {}", note));
+    }
+    out.extend(problems);
+    out.join("\n")
+}
+
+fn join_capped(packages: &[String]) -> String {
+    let mut text = packages
+        .iter()
+        .take(MAX_PACKAGES)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if packages.len() > MAX_PACKAGES {
+        text.push_str(&format!(" (+{} more)", packages.len() - MAX_PACKAGES));
+    }
+    text
+}
+
+/// Parse the `bun test` report.
+///
+/// Bun prints a failing test's error (code excerpt, message, frames) *before*
+/// its `(fail)` line, so lines are buffered until the next result line.
+fn parse_test(output: &str) -> ParseResult<TestResult> {
+    let clean = strip_ansi(output);
+    let cwd = std::env::current_dir()
+        .map(|d| format!("{}/", d.display()))
+        .unwrap_or_default();
+
+    let mut result = TestResult {
+        total: 0,
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        duration_ms: None,
+        failures: Vec::new(),
+    };
+    let mut current_file = String::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut counted = (0, 0, 0);
+    let mut summary = false;
+
+    for line in clean.lines() {
+        if let Some(caps) = FILE_HEADER.captures(line) {
+            current_file = caps[1].to_string();
+            pending.clear();
+        } else if TEST_PASS.is_match(line) || TEST_SKIP.is_match(line) {
+            if TEST_PASS.is_match(line) {
+                counted.0 += 1;
+            } else {
+                counted.2 += 1;
+            }
+            pending.clear();
+        } else if let Some(caps) = TEST_FAIL.captures(line) {
+            let name = caps[1].to_string();
+            // The end-of-run recap repeats failing tests without their errors
+            if !result.failures.iter().any(|f| f.test_name == name) {
+                counted.1 += 1;
+                result
+                    .failures
+                    .push(failure_from(&name, &pending, &current_file, &cwd));
+            }
+            pending.clear();
+        } else if let Some(caps) = COUNT_LINE.captures(line) {
+            let n: usize = caps[1].parse().unwrap_or(0);
+            match &caps[2] {
+                "pass" => result.passed = n,
+                "fail" => result.failed = n,
+                _ => result.skipped += n,
+            }
+        } else if let Some(caps) = RAN_LINE.captures(line) {
+            result.total = caps[1].parse().unwrap_or(0);
+            let value: f64 = caps[2].parse().unwrap_or(0.0);
+            let ms = if &caps[3] == "s" {
+                value * 1000.0
+            } else {
+                value
+            };
+            result.duration_ms = Some(ms.round() as u64);
+            summary = true;
+        } else {
+            pending.push(line);
+        }
+    }
+
+    if summary {
+        return ParseResult::Full(result);
+    }
+    if counted == (0, 0, 0) {
+        return ParseResult::Passthrough(clean);
+    }
+    result.passed = counted.0;
+    result.failed = counted.1;
+    result.skipped = counted.2;
+    result.total = counted.0 + counted.1 + counted.2;
+    ParseResult::Degraded(
+        result,
+        vec!["no summary line (run interrupted?)".to_string()],
+    )
+}
+
+fn failure_from(name: &str, lines: &[&str], current_file: &str, cwd: &str) -> TestFailure {
+    // Console output can precede the error; the message starts at `error:`
+    let start = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with("error:"))
+        .unwrap_or(0);
+
+    let mut message: Vec<String> = Vec::new();
+    let mut location = None;
+    for line in &lines[start..] {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || CODE_EXCERPT.is_match(line) || CARET_LINE.is_match(line) {
+            continue;
+        }
+        if let Some(caps) = FRAME.captures(line) {
+            let path = caps[1].strip_prefix(cwd).unwrap_or(&caps[1]);
+            if location.is_none() && !path.contains("node_modules") {
+                location = Some(format!("{}:{}", path, &caps[2]));
+            }
+            continue;
+        }
+        let text = trimmed.strip_prefix("error: ").unwrap_or(trimmed);
+        // Keep "Expected: / Received:" together so the compact view shows both
+        match message.last_mut() {
+            Some(prev) if text.starts_with("Received:") && prev.starts_with("Expected:") => {
+                prev.push_str(", ");
+                prev.push_str(text);
+            }
+            _ => message.push(text.to_string()),
+        }
+    }
+
+    TestFailure {
+        test_name: name.to_string(),
+        file_path: location.unwrap_or_else(|| current_file.to_string()),
+        error_message: message.join("\n"),
+        stack_trace: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_tokens(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    const BUN_TEST_OUTPUT: &str = r#"bun test v1.1.38 (bf2f153f)
+
+src/cart.test.ts:
+(pass) cart > sums items [0.08ms]
+ 7 |
+ 8 |   test("applies discount", () => {
+ 9 |     expect(total([100], 0.1)).toBe(90);
+                                   ^
+error: expect(received).toBe(expected)
+
+Expected: 90
+Received: 100
+
+      at <anonymous> (/app/src/cart.test.ts:9:31)
+(fail) cart > applies discount [0.52ms]
+(skip) cart > later
+
+src/user.test.ts:
+(pass) user > parses name [0.11ms]
+(pass) user > rejects empty [0.04ms]
+
+1 tests failed:
+(fail) cart > applies discount [0.52ms]
+
+ 3 pass
+ 1 skip
+ 1 fail
+ 4 expect() calls
+Ran 5 tests across 2 files. [15.00ms]
+"#;
+
+    #[test]
+    fn test_parse_bun_test_failure() {
+        let result = match parse_test(BUN_TEST_OUTPUT) {
+            ParseResult::Full(r) => r,
+            _ => panic!("expected full parse"),
+        };
+        assert_eq!(result.total, 5);
+        assert_eq!(result.passed, 3);
+        assert_eq!(result.failed, 1);
+        assert_eq!(result.skipped, 1);
+        assert_eq!(result.duration_ms, Some(15));
+        assert_eq!(result.failures.len(), 1);
+
+        let failure = &result.failures[0];
+        assert_eq!(failure.test_name, "cart > applies discount");
+        assert_eq!(failure.file_path, "/app/src/cart.test.ts:9");
+        assert_eq!(
+            failure.error_message,
+            "expect(received).toBe(expected)\nExpected: 90, Received: 100"
+        );
+    }
+
+    #[test]
+    fn test_parse_bun_test_without_summary_is_degraded() {
+        let output = "src/a.test.ts:\n(pass) a > one [0.1ms]\n(pass) a > two [0.1ms]\n";
+        match parse_test(output) {
+            ParseResult::Degraded(r, _) => {
+                assert_eq!(r.passed, 2);
+                assert_eq!(r.total, 2);
+            }
+            _ => panic!("expected degraded parse"),
+        }
+        assert!(matches!(
+            parse_test("error: No tests found!"),
+            ParseResult::Passthrough(_)
+        ));
+    }
+
+    #[test]
+    fn test_is_watch() {
+        let args: Vec<String> = vec!["test".into(), "--watch".into()];
+        assert!(is_watch(&args));
+        let args: Vec<String> = vec!["test".into(), "src/cart.test.ts".into()];
+        assert!(!is_watch(&args));
+    }
+
+    #[test]
+    fn test_filter_install_summary() {
+        let output = r#"bun install v1.1.38 (bf2f153f)
+
+ + @types/node@20.11.5
+ + typescript@5.3.3
+ + react@18.2.0
+
+142 packages installed [1.23s]
+
+Blocked 2 postinstalls. Run `bun pm untrusted` for details.
+warn: incorrect peer dependency "react@17.0.2"
+"#;
+        let filtered = filter_install(output, true);
+        assert_eq!(
+            filtered,
+            "✓ bun install: 142 packages installed [1.23s]\n  \
+             + @types/node@20.11.5, typescript@5.3.3, react@18.2.0\n  \
+             Blocked 2 postinstalls. Run `bun pm untrusted` for details.\n\
+             warn: incorrect peer dependency \"react@17.0.2\""
+        );
+    }
+
+    #[test]
+    fn test_filter_install_add_with_binaries() {
+        let output = r#"bun add v1.1.38 (bf2f153f)
+
+installed typescript@5.3.3 with binaries:
+ - tsc
+ - tsserver
+
+1 package installed [412.00ms]
+"#;
+        let filtered = filter_install(output, true);
+        assert!(filtered.starts_with("✓ bun install: 1 package installed [412.00ms]"));
+        assert!(filtered.contains("+ typescript@5.3.3"));
+        assert!(!filtered.contains("- tsc"));
+    }
+
+    #[test]
+    fn test_filter_install_no_changes() {
+        let output = "bun install v1.1.38 (bf2f153f)\n\nChecked 412 installs across 380 packages (no changes) [88.00ms]\n";
+        assert_eq!(
+            filter_install(output, true),
+            "✓ bun install: 380 packages, no changes [88.00ms]"
+        );
+    }
+
+    #[test]
+    fn test_bun_install_savings() {
+        let mut output = String::from("bun install v1.1.38 (bf2f153f)\n\n");
+        for i in 0..60 {
+            output.push_str(&format!(" + package-number-{}@1.{}.0\n", i, i));
+        }
+        output.push_str("\n412 packages installed [2.41s]\n");
+        let filtered = filter_install(&output, true);
+        let savings =
+            100.0 - (count_tokens(&filtered) as f64 / count_tokens(&output) as f64 * 100.0);
+        assert!(
+            savings >= 60.0,
+            "expected ≥60% savings, got {:.1}%",
+            savings
+        );
+        assert!(filtered.contains("(+50 more)"));
+    }
+}
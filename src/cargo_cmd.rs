@@ -1,6 +1,14 @@
+use crate::parser::{
+    emit_degradation_warning, emit_passthrough_warning, truncate_output, LintIssue, LintResult,
+    LintSeverity, OutputParser, ParseResult,
+};
+use crate::sanitizer;
+use crate::stack_trace;
+use crate::test_history;
 use crate::tracking;
-use crate::utils::truncate;
+use crate::utils::{strip_ansi, truncate};
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::ffi::OsString;
 use std::process::Command;
 use std::sync::OnceLock;
@@ -9,25 +17,44 @@ use std::sync::OnceLock;
 pub enum CargoCommand {
     Build,
     Test,
+    Bench,
     Clippy,
     Check,
     Install,
     Nextest,
+    Tree,
 }
 
 pub fn run(cmd: CargoCommand, args: &[String], verbose: u8) -> Result<()> {
     match cmd {
         CargoCommand::Build => run_build(args, verbose),
         CargoCommand::Test => run_test(args, verbose),
+        CargoCommand::Bench => run_bench(args, verbose),
         CargoCommand::Clippy => run_clippy(args, verbose),
         CargoCommand::Check => run_check(args, verbose),
         CargoCommand::Install => run_install(args, verbose),
         CargoCommand::Nextest => run_nextest(args, verbose),
+        CargoCommand::Tree => run_tree(args, verbose),
     }
 }
 
 /// Generic cargo command runner with filtering
 fn run_cargo_filtered<F>(subcommand: &str, args: &[String], verbose: u8, filter_fn: F) -> Result<()>
+where
+    F: Fn(&str) -> String,
+{
+    run_cargo_with_args(subcommand, args, args, verbose, filter_fn)
+}
+
+/// Like `run_cargo_filtered`, but runs `cmd_args` while tracking the user's `args`
+/// (for flags rtk adds to get parseable output)
+fn run_cargo_with_args<F>(
+    subcommand: &str,
+    cmd_args: &[String],
+    args: &[String],
+    verbose: u8,
+    filter_fn: F,
+) -> Result<()>
 where
     F: Fn(&str) -> String,
 {
@@ -35,12 +62,12 @@ where
 
     let mut cmd = Command::new("cargo");
     cmd.arg(subcommand);
-    for arg in args {
+    for arg in cmd_args {
         cmd.arg(arg);
     }
 
     if verbose > 0 {
-        eprintln!("Running: cargo {} {}", subcommand, args.join(" "));
+        eprintln!("Running: cargo {} {}", subcommand, cmd_args.join(" "));
     }
 
     let output = cmd
@@ -78,20 +105,501 @@ where
     Ok(())
 }
 
+/// Format requested from cargo when the user hasn't chosen one
+const JSON_MESSAGE_FORMAT: &str = "--message-format=json-diagnostic-rendered-ansi";
+
+/// Max diagnostic groups shown before summarizing the rest
+const MAX_DIAGNOSTIC_GROUPS: usize = 15;
+
+/// One line of `cargo --message-format=json` output (tool-specific format)
+#[derive(Debug, Deserialize)]
+struct CargoJsonMessage {
+    reason: String,
+    message: Option<RustcDiagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RustcDiagnostic {
+    message: String,
+    code: Option<RustcDiagnosticCode>,
+    level: String,
+    #[serde(default)]
+    spans: Vec<RustcSpan>,
+    #[serde(default)]
+    children: Vec<RustcDiagnostic>,
+    rendered: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RustcDiagnosticCode {
+    code: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RustcSpan {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    label: Option<String>,
+    suggested_replacement: Option<String>,
+}
+
+/// Parser for cargo JSON diagnostics (build, check, clippy)
+pub struct CargoDiagnosticParser;
+
+impl OutputParser for CargoDiagnosticParser {
+    type Output = LintResult;
+
+    fn parse(input: &str) -> ParseResult<LintResult> {
+        let mut issues: Vec<LintIssue> = Vec::new();
+        let mut warnings = Vec::new();
+        let mut json_lines = 0;
+
+        for line in input.lines().filter(|l| l.starts_with('{')) {
+            let msg = match serde_json::from_str::<CargoJsonMessage>(line) {
+                Ok(msg) => msg,
+                Err(e) => {
+                    warnings.push(format!("skipped malformed message: {}", e));
+                    continue;
+                }
+            };
+            json_lines += 1;
+            let Some(diag) = msg.message.filter(|_| msg.reason == "compiler-message") else {
+                continue;
+            };
+            let Some(issue) = diagnostic_to_issue(&diag) else {
+                continue;
+            };
+            // lib and test targets report the same diagnostic twice
+            let duplicate = issues.iter().any(|i| {
+                i.file_path == issue.file_path
+                    && i.line == issue.line
+                    && i.column == issue.column
+                    && i.rule_id == issue.rule_id
+                    && i.message == issue.message
+            });
+            if !duplicate {
+                issues.push(issue);
+            }
+        }
+
+        if json_lines == 0 {
+            return ParseResult::Passthrough(truncate_output(input, 500));
+        }
+
+        let mut files: Vec<&str> = issues.iter().map(|i| i.file_path.as_str()).collect();
+        files.sort_unstable();
+        files.dedup();
+        let result = LintResult {
+            total_files: files.len(),
+            files_with_issues: files.len(),
+            total_issues: issues.len(),
+            errors: issues
+                .iter()
+                .filter(|i| i.severity == LintSeverity::Error)
+                .count(),
+            warnings: issues
+                .iter()
+                .filter(|i| i.severity == LintSeverity::Warning)
+                .count(),
+            issues,
+        };
+
+        if warnings.is_empty() {
+            ParseResult::Full(result)
+        } else {
+            ParseResult::Degraded(result, warnings)
+        }
+    }
+}
+
+/// Convert a rustc diagnostic into a `LintIssue`, skipping rustc's own summaries
+fn diagnostic_to_issue(diag: &RustcDiagnostic) -> Option<LintIssue> {
+    let severity = match diag.level.as_str() {
+        "error" | "error: internal compiler error" => LintSeverity::Error,
+        "warning" => LintSeverity::Warning,
+        _ => return None,
+    };
+    // "aborting due to 2 previous errors", "3 warnings emitted"
+    if diag.spans.is_empty()
+        && (diag.message.starts_with("aborting due to") || diag.message.ends_with("emitted"))
+    {
+        return None;
+    }
+
+    let primary = diag
+        .spans
+        .iter()
+        .find(|s| s.is_primary)
+        .or(diag.spans.first());
+    let mut message = diag.message.clone();
+    if let Some(label) = primary
+        .and_then(|s| s.label.as_deref())
+        .filter(|l| !l.is_empty() && !diag.message.contains(*l))
+    {
+        message.push_str(&format!(" ({})", label));
+    }
+
+    // Prefer machine-applicable fixes; clippy's docs link is never useful inline
+    let helps: Vec<&RustcDiagnostic> = diag
+        .children
+        .iter()
+        .filter(|c| c.level == "help" && !c.message.starts_with("for further information"))
+        .collect();
+    let suggestion = helps
+        .iter()
+        .find(|h| h.spans.iter().any(|s| s.suggested_replacement.is_some()))
+        .or(helps.first())
+        .map(|help| {
+            let replacement = help
+                .spans
+                .iter()
+                .find_map(|s| s.suggested_replacement.as_deref())
+                .filter(|r| !r.is_empty() && !r.contains('\n'));
+            match replacement {
+                Some(r) => format!("{}: `{}`", help.message, r),
+                None => help.message.clone(),
+            }
+        });
+
+    Some(LintIssue {
+        file_path: primary.map(|s| s.file_name.clone()).unwrap_or_default(),
+        line: primary.map(|s| s.line_start).unwrap_or(0),
+        column: primary.map(|s| s.column_start).unwrap_or(0),
+        severity,
+        rule_id: diag
+            .code
+            .as_ref()
+            .map(|c| c.code.clone())
+            .unwrap_or_default(),
+        message,
+        suggestion,
+    })
+}
+
+/// Human-readable equivalent of JSON output (rustc's `rendered` text + cargo stderr)
+fn rendered_cargo_output(stdout: &str, stderr: &str) -> String {
+    let mut out = String::new();
+    for line in stdout.lines().filter(|l| l.starts_with('{')) {
+        if let Some(rendered) = serde_json::from_str::<CargoJsonMessage>(line)
+            .ok()
+            .and_then(|m| m.message)
+            .and_then(|d| d.rendered)
+        {
+            out.push_str(&strip_ansi(&rendered));
+        }
+    }
+    out.push_str(stderr);
+    out
+}
+
+/// Diagnostics grouped by lint/code, then by file and message
+pub(crate) fn format_cargo_diagnostics(
+    subcommand: &str,
+    result: &LintResult,
+    compiled: usize,
+) -> String {
+    if result.issues.is_empty() {
+        return if subcommand == "clippy" {
+            "✓ cargo clippy: No issues found".to_string()
+        } else {
+            format!("✓ cargo {} ({} crates compiled)", subcommand, compiled)
+        };
+    }
+
+    // (is_error, rule) → issues, errors first then by frequency
+    let mut groups: Vec<((bool, &str), Vec<&LintIssue>)> = Vec::new();
+    for issue in &result.issues {
+        let key = (
+            issue.severity == LintSeverity::Error,
+            issue.rule_id.as_str(),
+        );
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, list)) => list.push(issue),
+            None => groups.push((key, vec![issue])),
+        }
+    }
+    groups.sort_by(|a, b| b.0 .0.cmp(&a.0 .0).then(b.1.len().cmp(&a.1.len())));
+
+    let mut out = format!(
+        "cargo {}: {} errors, {} warnings ({} files)\n",
+        subcommand, result.errors, result.warnings, result.files_with_issues
+    );
+    out.push_str("═══════════════════════════════════════\n");
+
+    for ((is_error, rule), issues) in groups.iter().take(MAX_DIAGNOSTIC_GROUPS) {
+        let level = if *is_error { "error" } else { "warning" };
+        if rule.is_empty() {
+            out.push_str(&format!("{} ×{}\n", level, issues.len()));
+        } else {
+            out.push_str(&format!("{}[{}] ×{}\n", level, rule, issues.len()));
+        }
+
+        // Same file + message: positions collapse onto one line
+        let mut rows: Vec<(&str, &str, Vec<String>, Option<&str>)> = Vec::new();
+        for issue in issues {
+            let pos = format!("{}:{}", issue.line, issue.column);
+            match rows
+                .iter_mut()
+                .find(|(f, m, _, _)| *f == issue.file_path && *m == issue.message)
+            {
+                Some((_, _, positions, _)) => positions.push(pos),
+                None => rows.push((
+                    &issue.file_path,
+                    &issue.message,
+                    vec![pos],
+                    issue.suggestion.as_deref(),
+                )),
+            }
+        }
+        for (file, message, positions, suggestion) in rows {
+            if file.is_empty() {
+                out.push_str(&format!("  {}\n", message));
+            } else {
+                out.push_str(&format!(
+                    "  {}:{} {}\n",
+                    file,
+                    positions.join(", "),
+                    truncate(message, 160)
+                ));
+            }
+            if let Some(help) = suggestion {
+                out.push_str(&format!("    help: {}\n", truncate(help, 160)));
+            }
+        }
+    }
+
+    if groups.len() > MAX_DIAGNOSTIC_GROUPS {
+        out.push_str(&format!(
+            "\n... +{} more groups\n",
+            groups.len() - MAX_DIAGNOSTIC_GROUPS
+        ));
+    }
+    out.trim().to_string()
+}
+
+/// Formatted diagnostics, plus cargo's own plain-text errors when the run failed
+/// without any error-level diagnostic (build script panics, resolver errors, ...)
+fn format_diagnostics_run(
+    subcommand: &str,
+    data: &LintResult,
+    compiled: usize,
+    stderr: &str,
+    exit_code: i32,
+) -> String {
+    if exit_code == 0 || data.errors > 0 {
+        return format_cargo_diagnostics(subcommand, data, compiled);
+    }
+    let Some(errors) = stderr_error_blocks(stderr) else {
+        return format_cargo_diagnostics(subcommand, data, compiled);
+    };
+    if data.issues.is_empty() {
+        format!("cargo {} failed\n{}", subcommand, errors)
+    } else {
+        format!(
+            "{}\n\n{}",
+            format_cargo_diagnostics(subcommand, data, compiled),
+            errors
+        )
+    }
+}
+
+/// Max stderr lines kept from cargo's plain-text `error:` blocks
+const MAX_STDERR_ERROR_LINES: usize = 40;
+
+/// `error: ...` blocks from cargo's stderr, including their `Caused by:` chain
+/// and the indented build-script output, without the progress lines
+fn stderr_error_blocks(stderr: &str) -> Option<String> {
+    let mut lines: Vec<&str> = Vec::new();
+    let mut in_error = false;
+    for line in stderr.lines() {
+        let trimmed = line.trim_start();
+        if line.starts_with("error") {
+            in_error = true;
+        } else if line.starts_with("warning") || is_cargo_progress(trimmed) {
+            in_error = false;
+            continue;
+        }
+        if in_error {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    let compressed = stack_trace::compress_stack_traces(&lines.join("\n"));
+    let lines: Vec<&str> = compressed.lines().collect();
+    let mut out = lines
+        .iter()
+        .take(MAX_STDERR_ERROR_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > MAX_STDERR_ERROR_LINES {
+        out.push_str(&format!(
+            "\n... +{} more lines",
+            lines.len() - MAX_STDERR_ERROR_LINES
+        ));
+    }
+    Some(out)
+}
+
+fn is_cargo_progress(trimmed: &str) -> bool {
+    [
+        "Compiling ",
+        "Checking ",
+        "Fresh ",
+        "Finished ",
+        "Building ",
+        "Blocking ",
+        "Downloading ",
+        "Downloaded ",
+        "Updating ",
+        "Locking ",
+    ]
+    .iter()
+    .any(|prefix| trimmed.starts_with(prefix))
+}
+
+/// Insert the JSON message format before any `--` (args after it go to rustc/clippy)
+fn with_json_message_format(args: &[String]) -> Vec<String> {
+    let split = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    let mut out = args[..split].to_vec();
+    out.push(JSON_MESSAGE_FORMAT.to_string());
+    out.extend_from_slice(&args[split..]);
+    out
+}
+
+/// build/check/clippy via JSON diagnostics; text filter when the user picked a format
+/// or when no JSON could be parsed (tier 3)
+fn run_cargo_diagnostics(
+    subcommand: &str,
+    args: &[String],
+    verbose: u8,
+    text_filter: fn(&str) -> String,
+) -> Result<()> {
+    if args.iter().any(|a| a.starts_with("--message-format")) {
+        return run_cargo_filtered(subcommand, args, verbose, text_filter);
+    }
+
+    let timer = tracking::TimedExecution::start();
+    let cmd_args = with_json_message_format(args);
+
+    if verbose > 0 {
+        eprintln!("Running: cargo {} {}", subcommand, cmd_args.join(" "));
+    }
+
+    let output = Command::new("cargo")
+        .arg(subcommand)
+        .args(&cmd_args)
+        .output()
+        .with_context(|| format!("Failed to run cargo {}", subcommand))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = rendered_cargo_output(&stdout, &stderr);
+
+    let compiled = stderr
+        .lines()
+        .filter(|l| {
+            let l = l.trim_start();
+            l.starts_with("Compiling") || l.starts_with("Checking")
+        })
+        .count();
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+    let mut filtered = match CargoDiagnosticParser::parse(&stdout) {
+        ParseResult::Full(data) => {
+            format_diagnostics_run(subcommand, &data, compiled, &stderr, exit_code)
+        }
+        ParseResult::Degraded(data, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning("cargo", &warnings.join(", "));
+            }
+            format_diagnostics_run(subcommand, &data, compiled, &stderr, exit_code)
+        }
+        ParseResult::Passthrough(_) => {
+            if verbose > 0 {
+                emit_passthrough_warning("cargo", "no JSON diagnostics, using text filter");
+            }
+            text_filter(&format!("{}\n{}", stdout, stderr))
+        }
+    };
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, &format!("cargo_{}", subcommand), exit_code)
+    {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &format!("cargo {} {}", subcommand, args.join(" ")),
+        &format!("rtk cargo {} {}", subcommand, args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
 fn run_build(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("build", args, verbose, filter_cargo_build)
+    run_cargo_diagnostics("build", args, verbose, filter_cargo_build)
 }
 
 fn run_test(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("test", args, verbose, filter_cargo_test)
+    run_cargo_filtered("test", args, verbose, |raw| {
+        let mut filtered = with_sanitizer_reports(raw, filter_cargo_test);
+        test_history::record_and_annotate(
+            "cargo",
+            args,
+            test_history::cargo_failed_tests(raw),
+            &mut filtered,
+        );
+        filtered
+    })
+}
+
+/// Filter test output with ASan/TSan/UBSan reports from `-Zsanitizer` test
+/// binaries pulled out first and appended as deduplicated records
+fn with_sanitizer_reports(raw: &str, filter: fn(&str) -> String) -> String {
+    let (reports, rest) = sanitizer::extract_reports(raw);
+    let mut filtered = stack_trace::compress_stack_traces(&filter(&rest));
+    if let Some(reports) = reports {
+        filtered.push_str("\n\n");
+        filtered.push_str(&reports);
+    }
+    filtered
 }
 
 fn run_clippy(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("clippy", args, verbose, filter_cargo_clippy)
+    run_cargo_diagnostics("clippy", args, verbose, filter_cargo_clippy)
+}
+
+/// Benchmarks as one row per bench; compile errors keep the build filter
+fn run_bench(args: &[String], verbose: u8) -> Result<()> {
+    run_cargo_filtered(
+        "bench",
+        args,
+        verbose,
+        |raw| match crate::bench_cmd::parse_cargo_bench(raw) {
+            Some(report) => crate::bench_cmd::format_report(&report),
+            None => filter_cargo_build(raw),
+        },
+    )
 }
 
 fn run_check(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("check", args, verbose, filter_cargo_build)
+    run_cargo_diagnostics("check", args, verbose, filter_cargo_build)
 }
 
 fn run_install(args: &[String], verbose: u8) -> Result<()> {
@@ -99,7 +607,263 @@ fn run_install(args: &[String], verbose: u8) -> Result<()> {
 }
 
 fn run_nextest(args: &[String], verbose: u8) -> Result<()> {
-    run_cargo_filtered("nextest", args, verbose, filter_cargo_nextest)
+    run_cargo_filtered("nextest", args, verbose, |raw| {
+        let mut filtered = with_sanitizer_reports(raw, filter_cargo_nextest);
+        test_history::record_and_annotate(
+            "nextest",
+            args,
+            test_history::nextest_failed_tests(raw),
+            &mut filtered,
+        );
+        filtered
+    })
+}
+
+/// One line of `cargo tree --prefix depth --format "{p}|{f}"`
+#[derive(Debug, Clone, PartialEq)]
+struct TreeNode {
+    depth: usize,
+    name: String,
+    version: String,
+    features: String,
+}
+
+impl TreeNode {
+    fn label(&self) -> String {
+        format!("{} {}", self.name, self.version)
+    }
+}
+
+fn parse_tree_line(line: &str) -> Option<TreeNode> {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let depth = line[..digits].parse().ok()?;
+    let rest = line[digits..].trim_end_matches(" (*)");
+    let (pkg, features) = rest.split_once('|').unwrap_or((rest, ""));
+    let mut parts = pkg.split_whitespace();
+    let name = parts.next()?.to_string();
+    let version = parts.next()?.trim_start_matches('v').to_string();
+    Some(TreeNode {
+        depth,
+        name,
+        version,
+        features: features.trim_end_matches(" (*)").to_string(),
+    })
+}
+
+/// Each node paired with its parent's label (None for roots)
+fn tree_edges(raw: &str) -> Vec<(TreeNode, Option<String>)> {
+    let mut stack: Vec<String> = Vec::new();
+    let mut edges = Vec::new();
+    for node in raw.lines().filter_map(parse_tree_line) {
+        stack.truncate(node.depth);
+        let parent = stack.last().cloned();
+        stack.push(node.label());
+        edges.push((node, parent));
+    }
+    edges
+}
+
+/// `cargo tree`: direct deps per workspace member plus duplicated crates
+pub(crate) fn format_cargo_tree(raw: &str, show_features: bool, duplicates_only: bool) -> String {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let edges = tree_edges(raw);
+    if edges.is_empty() {
+        return raw.trim().to_string();
+    }
+
+    // Workspace members are the depth-0 roots; depth-1 lines are their direct deps
+    let mut members: Vec<(&TreeNode, Vec<String>)> = Vec::new();
+    for (node, _) in &edges {
+        if node.depth == 0 {
+            members.push((node, Vec::new()));
+        } else if node.depth == 1 {
+            if let Some((_, direct)) = members.last_mut() {
+                let label = node.label();
+                if !direct.contains(&label) {
+                    direct.push(label);
+                }
+            }
+        }
+    }
+    let member_names: BTreeSet<&str> = members.iter().map(|(m, _)| m.name.as_str()).collect();
+
+    // name → version → crates pulling it in
+    let mut versions: BTreeMap<&str, BTreeMap<&str, BTreeSet<String>>> = BTreeMap::new();
+    let mut features: BTreeMap<String, &str> = BTreeMap::new();
+    for (node, parent) in &edges {
+        if member_names.contains(node.name.as_str()) {
+            continue;
+        }
+        let pullers = versions
+            .entry(&node.name)
+            .or_default()
+            .entry(&node.version)
+            .or_default();
+        if let Some(parent) = parent {
+            pullers.insert(parent.split(' ').next().unwrap_or(parent).to_string());
+        }
+        if !node.features.is_empty() {
+            features.insert(node.label(), &node.features);
+        }
+    }
+    let duplicated: Vec<_> = versions.iter().filter(|(_, v)| v.len() > 1).collect();
+
+    let mut out = Vec::new();
+    if !duplicates_only {
+        let total: usize = versions.values().map(|v| v.len()).sum();
+        for (member, direct) in &members {
+            out.push(format!(
+                "📦 {} v{}: {} direct deps",
+                member.name,
+                member.version,
+                direct.len()
+            ));
+            if !direct.is_empty() {
+                out.push(format!("  {}", direct.join(", ")));
+            }
+        }
+        out.push(format!(
+            "{} crates total, {} duplicated",
+            total,
+            duplicated.len()
+        ));
+    }
+
+    if duplicated.is_empty() {
+        if duplicates_only {
+            out.push("✓ No duplicated crates".to_string());
+        }
+    } else {
+        out.push(format!("⚠ Duplicated crates ({}):", duplicated.len()));
+        for (name, by_version) in &duplicated {
+            let list: Vec<String> = by_version
+                .iter()
+                .map(|(version, pullers)| {
+                    let pullers: Vec<&str> = pullers.iter().map(|p| p.as_str()).collect();
+                    format!("{} ← {}", version, pullers.join(", "))
+                })
+                .collect();
+            out.push(format!("  {}: {}", name, list.join("; ")));
+        }
+    }
+
+    if show_features && !features.is_empty() {
+        out.push("Features:".to_string());
+        for (label, list) in &features {
+            out.push(format!("  {}: {}", label, list));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// `cargo tree -i <crate>`: shortest chain from each workspace member to the crate
+pub(crate) fn format_cargo_tree_inverted(raw: &str) -> String {
+    use std::collections::{BTreeMap, VecDeque};
+
+    let edges = tree_edges(raw);
+    let Some((target, _)) = edges.first() else {
+        return raw.trim().to_string();
+    };
+
+    // In an inverted tree each child line is a dependent of its parent line
+    let mut dependents: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (node, parent) in &edges {
+        dependents.entry(node.label()).or_default();
+        if let Some(parent) = parent {
+            let list = dependents.entry(parent.clone()).or_default();
+            if !list.contains(&node.label()) {
+                list.push(node.label());
+            }
+        }
+    }
+
+    let mut roots = Vec::new();
+    for start in edges
+        .iter()
+        .filter(|(n, _)| n.depth == 0)
+        .map(|(n, _)| n.label())
+    {
+        let mut came_from: BTreeMap<String, String> = BTreeMap::new();
+        let mut queue = VecDeque::from([start.clone()]);
+        came_from.insert(start.clone(), String::new());
+        while let Some(current) = queue.pop_front() {
+            let next = dependents.get(&current).cloned().unwrap_or_default();
+            if next.is_empty() {
+                let mut chain = vec![current.clone()];
+                let mut cursor = current;
+                while let Some(prev) = came_from.get(&cursor).filter(|p| !p.is_empty()) {
+                    chain.push(prev.clone());
+                    cursor = prev.clone();
+                }
+                roots.push(chain);
+                continue;
+            }
+            for dep in next {
+                if !came_from.contains_key(&dep) {
+                    came_from.insert(dep.clone(), current.clone());
+                    queue.push_back(dep);
+                }
+            }
+        }
+    }
+
+    let direct = dependents
+        .get(&target.label())
+        .map(|d| d.len())
+        .unwrap_or(0);
+    let mut out = vec![format!(
+        "🔎 {} v{}: {} direct dependents, shortest chains:",
+        target.name, target.version, direct
+    )];
+    for chain in &roots {
+        let names: Vec<&str> = chain
+            .iter()
+            .map(|label| label.split(' ').next().unwrap_or(label))
+            .collect();
+        out.push(format!("  {}", names.join(" → ")));
+    }
+    out.join("\n")
+}
+
+fn run_tree(args: &[String], verbose: u8) -> Result<()> {
+    let wants_features_edges = args.iter().enumerate().any(|(i, a)| {
+        (a.starts_with("--edges=") || a.starts_with("-e=")) && a.contains("features")
+            || ((a == "-e" || a == "--edges")
+                && args.get(i + 1).is_some_and(|v| v.contains("features")))
+    });
+    // Custom layouts are what the user asked for; show them untouched
+    if wants_features_edges
+        || args
+            .iter()
+            .any(|a| a == "-f" || a.starts_with("--format") || a.starts_with("--prefix"))
+    {
+        let os_args: Vec<OsString> = std::iter::once(OsString::from("tree"))
+            .chain(args.iter().map(OsString::from))
+            .collect();
+        return run_passthrough(&os_args, verbose);
+    }
+
+    let show_features = args.iter().any(|a| a == "--show-features");
+    let duplicates_only = args.iter().any(|a| a == "-d" || a == "--duplicates");
+    let inverted = args
+        .iter()
+        .any(|a| a == "-i" || a.starts_with("--invert") || (a.starts_with("-i") && a.len() > 2));
+    let mut cmd_args: Vec<String> = args
+        .iter()
+        .filter(|a| !matches!(a.as_str(), "--show-features" | "-d" | "--duplicates"))
+        .cloned()
+        .collect();
+    cmd_args.extend(["--prefix", "depth", "--format", "{p}|{f}"].map(String::from));
+
+    run_cargo_with_args("tree", &cmd_args, args, verbose, |raw| {
+        if inverted {
+            format_cargo_tree_inverted(raw)
+        } else {
+            format_cargo_tree(raw, show_features, duplicates_only)
+        }
+    })
 }
 
 /// Format crate name + version into a display string
@@ -520,7 +1284,7 @@ fn filter_cargo_nextest(output: &str) -> String {
 }
 
 /// Filter cargo build/check output - strip "Compiling"/"Checking" lines, keep errors + summary
-fn filter_cargo_build(output: &str) -> String {
+pub(crate) fn filter_cargo_build(output: &str) -> String {
     let mut errors: Vec<String> = Vec::new();
     let mut warnings = 0;
     let mut error_count = 0;
@@ -714,7 +1478,7 @@ impl AggregatedTestResult {
 }
 
 /// Filter cargo test output - show failures + summary only
-fn filter_cargo_test(output: &str) -> String {
+pub(crate) fn filter_cargo_test(output: &str) -> String {
     let mut failures: Vec<String> = Vec::new();
     let mut summary_lines: Vec<String> = Vec::new();
     let mut in_failure_section = false;
@@ -1707,4 +2471,236 @@ error: could not compile `rtk` due to 1 previous error
             result
         );
     }
+
+    // ── JSON diagnostics ─────────────────────────────────────────
+
+    const CARGO_JSON: &str = r#"{"reason":"compiler-artifact","package_id":"libc 0.2.153","target":{"name":"libc"},"fresh":true}
+{"reason":"compiler-message","package_id":"rtk 0.5.0","target":{"name":"rtk","kind":["bin"]},"message":{"rendered":"warning: unused variable: `x`\n","message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":10,"column_start":9,"is_primary":true,"label":null,"suggested_replacement":null}],"children":[{"message":"if this is intentional, prefix it with an underscore","code":null,"level":"help","spans":[{"file_name":"src/main.rs","line_start":10,"column_start":9,"is_primary":true,"label":null,"suggested_replacement":"_x"}],"children":[],"rendered":null}]}}
+{"reason":"compiler-message","package_id":"rtk 0.5.0","target":{"name":"rtk","kind":["test"]},"message":{"rendered":"warning: unused variable: `x`\n","message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":10,"column_start":9,"is_primary":true,"label":null,"suggested_replacement":null}],"children":[]}}
+{"reason":"compiler-message","package_id":"rtk 0.5.0","target":{"name":"rtk","kind":["bin"]},"message":{"rendered":"warning: unused variable: `y`\n","message":"unused variable: `y`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":14,"column_start":9,"is_primary":true,"label":null,"suggested_replacement":null}],"children":[]}}
+{"reason":"compiler-message","package_id":"rtk 0.5.0","target":{"name":"rtk","kind":["bin"]},"message":{"rendered":"error[E0308]: mismatched types\n","message":"mismatched types","code":{"code":"E0308","explanation":"..."},"level":"error","spans":[{"file_name":"src/lib.rs","line_start":42,"column_start":13,"is_primary":true,"label":"expected `u32`, found `&str`","suggested_replacement":null}],"children":[{"message":"for further information visit https://doc.rust-lang.org/error_codes/E0308.html","code":null,"level":"help","spans":[],"children":[],"rendered":null}]}}
+{"reason":"compiler-message","package_id":"rtk 0.5.0","target":{"name":"rtk","kind":["bin"]},"message":{"rendered":"error: aborting due to 1 previous error\n","message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}}
+{"reason":"build-finished","success":false}
+"#;
+
+    #[test]
+    fn test_cargo_diagnostic_parser_full() {
+        let result = CargoDiagnosticParser::parse(CARGO_JSON);
+        assert_eq!(result.tier(), 1);
+        let data = result.unwrap();
+        assert_eq!(data.errors, 1);
+        assert_eq!(data.warnings, 2, "lib/test duplicate should be merged");
+        assert_eq!(data.files_with_issues, 2);
+        let err = data
+            .issues
+            .iter()
+            .find(|i| i.severity == LintSeverity::Error)
+            .unwrap();
+        assert_eq!(err.rule_id, "E0308");
+        assert_eq!(
+            err.message,
+            "mismatched types (expected `u32`, found `&str`)"
+        );
+        assert_eq!(err.suggestion, None);
+    }
+
+    #[test]
+    fn test_cargo_diagnostic_help_suggestion() {
+        let data = CargoDiagnosticParser::parse(CARGO_JSON).unwrap();
+        let unused = &data.issues[0];
+        assert_eq!(
+            unused.suggestion.as_deref(),
+            Some("if this is intentional, prefix it with an underscore: `_x`")
+        );
+    }
+
+    #[test]
+    fn test_format_cargo_diagnostics_grouped() {
+        let data = CargoDiagnosticParser::parse(CARGO_JSON).unwrap();
+        let out = format_cargo_diagnostics("build", &data, 3);
+        assert_eq!(
+            out,
+            "cargo build: 1 errors, 2 warnings (2 files)\n\
+═══════════════════════════════════════\n\
+error[E0308] ×1\n  \
+src/lib.rs:42:13 mismatched types (expected `u32`, found `&str`)\n\
+warning[unused_variables] ×2\n  \
+src/main.rs:10:9 unused variable: `x`\n    \
+help: if this is intentional, prefix it with an underscore: `_x`\n  \
+src/main.rs:14:9 unused variable: `y`"
+        );
+    }
+
+    #[test]
+    fn test_format_cargo_diagnostics_clean() {
+        let clean = r#"{"reason":"build-finished","success":true}"#;
+        let data = CargoDiagnosticParser::parse(clean).unwrap();
+        assert_eq!(
+            format_cargo_diagnostics("build", &data, 4),
+            "✓ cargo build (4 crates compiled)"
+        );
+        assert_eq!(
+            format_cargo_diagnostics("clippy", &data, 4),
+            "✓ cargo clippy: No issues found"
+        );
+    }
+
+    #[test]
+    fn test_cargo_diagnostic_parser_degraded_and_passthrough() {
+        let partial = format!("{}{{\"reason\": broken\n", CARGO_JSON);
+        let result = CargoDiagnosticParser::parse(&partial);
+        assert_eq!(result.tier(), 2);
+        assert_eq!(result.unwrap().errors, 1);
+
+        let text = "error: could not find `Cargo.toml` in `/tmp` or any parent directory\n";
+        assert_eq!(CargoDiagnosticParser::parse(text).tier(), 3);
+    }
+
+    #[test]
+    fn test_failed_build_script_keeps_stderr_errors() {
+        let stdout = r#"{"reason":"compiler-artifact","package_id":"libc 0.2.153","target":{"name":"libc"},"fresh":true}
+{"reason":"build-script-executed","package_id":"libc 0.2.153","linked_libs":[],"linked_paths":[],"cfgs":[],"env":[],"out_dir":"/tmp/demo/target/debug/build/libc-1/out"}
+{"reason":"build-finished","success":false}
+"#;
+        let stderr = "   Compiling libc v0.2.153
+   Compiling demo v0.1.0 (/tmp/demo)
+error: failed to run custom build command for `demo v0.1.0 (/tmp/demo)`
+
+Caused by:
+  process didn't exit successfully: `/tmp/demo/target/debug/build/demo-1/build-script-build` (exit status: 101)
+  --- stderr
+  thread 'main' panicked at build.rs:2:5:
+  missing protoc
+  note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+";
+        let data = CargoDiagnosticParser::parse(stdout).unwrap();
+        assert_eq!(data.errors, 0);
+
+        let out = format_diagnostics_run("build", &data, 2, stderr, 101);
+        assert!(!out.starts_with('✓'), "got: {}", out);
+        assert!(out.starts_with("cargo build failed\nerror: failed to run custom build command"));
+        assert!(out.contains("Caused by:"));
+        assert!(out.contains("missing protoc"));
+        assert!(!out.contains("Compiling"));
+
+        // Successful runs keep the compact summary
+        assert_eq!(
+            format_diagnostics_run("build", &data, 2, stderr, 0),
+            "✓ cargo build (2 crates compiled)"
+        );
+    }
+
+    #[test]
+    fn test_json_message_format_before_double_dash() {
+        let args: Vec<String> = ["--all-targets", "--", "-D", "warnings"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            with_json_message_format(&args),
+            vec!["--all-targets", JSON_MESSAGE_FORMAT, "--", "-D", "warnings"]
+        );
+    }
+
+    #[test]
+    fn test_rendered_cargo_output_for_tracking() {
+        let rendered = rendered_cargo_output(CARGO_JSON, "error: could not compile `rtk`\n");
+        assert!(rendered.starts_with("warning: unused variable: `x`\n"));
+        assert!(rendered.contains("error[E0308]: mismatched types"));
+        assert!(rendered.ends_with("error: could not compile `rtk`\n"));
+        assert!(!rendered.contains("\"reason\""));
+    }
+
+    // ── cargo tree ───────────────────────────────────────────────
+
+    const CARGO_TREE_WORKSPACE: &str = "0app v0.1.0 (/ws/app)|
+1anyhow v1.0.100|default,std
+1core v0.1.0 (/ws/core)|
+2regex v1.12.2|default,std,unicode
+3regex-syntax v0.8.8|default,std
+2hashbrown v0.14.5|inline-more
+1hashlink v0.9.1|
+2hashbrown v0.14.5|inline-more (*)
+1anyhow v1.0.100|default,std (*)
+
+0core v0.1.0 (/ws/core)|
+1regex v1.12.2|default,std,unicode (*)
+1indexmap v2.13.0|default,std
+2hashbrown v0.16.1|default
+";
+
+    #[test]
+    fn test_parse_tree_line() {
+        assert_eq!(
+            parse_tree_line("2clap_derive v4.5.49 (proc-macro)|default (*)"),
+            Some(TreeNode {
+                depth: 2,
+                name: "clap_derive".to_string(),
+                version: "4.5.49".to_string(),
+                features: "default".to_string(),
+            })
+        );
+        assert_eq!(parse_tree_line("error: package not found"), None);
+    }
+
+    #[test]
+    fn test_format_cargo_tree_members_and_duplicates() {
+        let out = format_cargo_tree(CARGO_TREE_WORKSPACE, false, false);
+        assert_eq!(
+            out,
+            concat!(
+                "📦 app v0.1.0: 3 direct deps\n",
+                "  anyhow 1.0.100, core 0.1.0, hashlink 0.9.1\n",
+                "📦 core v0.1.0: 2 direct deps\n",
+                "  regex 1.12.2, indexmap 2.13.0\n",
+                "7 crates total, 1 duplicated\n",
+                "⚠ Duplicated crates (1):\n",
+                "  hashbrown: 0.14.5 ← core, hashlink; 0.16.1 ← indexmap"
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_cargo_tree_duplicates_only_with_features() {
+        let out = format_cargo_tree(CARGO_TREE_WORKSPACE, true, true);
+        assert!(out.starts_with("⚠ Duplicated crates (1):"));
+        assert!(!out.contains("📦"));
+        assert!(out.contains("Features:\n  anyhow 1.0.100: default,std"));
+        assert!(out.contains("  regex 1.12.2: default,std,unicode"));
+
+        let single = "0app v0.1.0 (/ws/app)|\n1anyhow v1.0.100|std\n";
+        assert_eq!(
+            format_cargo_tree(single, false, true),
+            "✓ No duplicated crates"
+        );
+    }
+
+    #[test]
+    fn test_format_cargo_tree_inverted_shortest_chains() {
+        let raw = "0hashbrown v0.14.5|
+1hashlink v0.9.1|
+2rusqlite v0.31.0|
+3db v0.1.0 (/ws/db)|
+4app v0.1.0 (/ws/app)|
+1lru v0.12.0|
+2cache v0.3.0|
+3db v0.1.0 (/ws/db)| (*)
+3app v0.1.0 (/ws/app)| (*)
+";
+        // cache → lru is shorter than db → rusqlite → hashlink
+        assert_eq!(
+            format_cargo_tree_inverted(raw),
+            concat!(
+                "🔎 hashbrown v0.14.5: 2 direct dependents, shortest chains:\n",
+                "  app → cache → lru → hashbrown"
+            )
+        );
+    }
+
+    #[test]
+    fn test_format_cargo_tree_error_passthrough() {
+        let raw = "error: package ID specification `nope` did not match any packages";
+        assert_eq!(format_cargo_tree(raw, false, false), raw);
+        assert_eq!(format_cargo_tree_inverted(raw), raw);
+    }
 }
@@ -0,0 +1,963 @@
+//! C/C++ builds: gcc, g++, clang, clang++, make, cmake and ninja.
+//!
+//! Compiler diagnostics (`file:line:col: severity: message [-Wflag]`) are
+//! grouped by flag and file. Template instantiation backtraces ("In
+//! instantiation of" / "required from here" / clang's "requested here"
+//! notes) collapse to their user-code frames, progress and directory noise
+//! is dropped, and the first fatal error is summarized before the rest.
+
+use crate::tracking;
+use crate::utils::{strip_ansi, truncate};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::process::Command;
+
+/// Diagnostic groups (severity + flag) shown before summarizing the rest
+const MAX_GROUPS: usize = 15;
+
+/// Rows (file + message) shown per group
+const MAX_ROWS_PER_GROUP: usize = 10;
+
+/// User-code instantiation frames kept per diagnostic
+const MAX_FRAMES: usize = 3;
+
+/// Failed targets listed
+const MAX_TARGETS: usize = 5;
+
+/// Lines kept from runs without diagnostics (`make test`, `gcc --version`)
+const MAX_OUTPUT_LINES: usize = 50;
+
+/// Path prefixes/markers of toolchain and system headers
+const SYSTEM_PATH_MARKERS: &[&str] = &[
+    "/usr/include/",
+    "/usr/lib/",
+    "/usr/local/include/",
+    "/opt/",
+    "/include/c++/",
+    "/lib/gcc/",
+    "/Library/Developer/",
+    "/Applications/Xcode",
+    "/nix/store/",
+];
+
+lazy_static! {
+    static ref DIAGNOSTIC: Regex = Regex::new(
+        r"^(.+?):(\d+):(?:(\d+):)? (fatal error|error|warning|note|remark): (.*)$"
+    )
+    .unwrap();
+    /// Driver-level messages: `g++: error: foo.cpp: No such file or directory`
+    static ref DRIVER: Regex = Regex::new(
+        r"^(?:\S*/)?(?:cc1\w*|gcc|g\+\+|cc|c\+\+|clang(?:\+\+)?)(?:-\d+)?: (fatal error|error|warning): (.*)$"
+    )
+    .unwrap();
+    static ref FLAG: Regex = Regex::new(r"\s*\[(-W[^\]]+)\]$").unwrap();
+    static ref INSTANTIATION: Regex = Regex::new(r"^(.+?): In instantiation of .+:$").unwrap();
+    static ref REQUIRED_FROM: Regex =
+        Regex::new(r"^(.+?):(\d+):(\d+):\s+required (?:from|by) (.+)$").unwrap();
+    static ref CLANG_REQUESTED: Regex =
+        Regex::new(r"^in instantiation of .+ requested here$").unwrap();
+    // Linkers
+    static ref UNDEFINED_REF: Regex = Regex::new(
+        r"(?:([^\s:]+)(?::\([^)]*\))?: )?undefined reference to [`'‘]([^'’]+)['’]"
+    )
+    .unwrap();
+    static ref LLD_UNDEFINED: Regex = Regex::new(r"error: undefined symbol: (.+)$").unwrap();
+    static ref LLD_REFERENCED: Regex = Regex::new(r"^>>> referenced by (\S+)").unwrap();
+    static ref MACH_UNDEFINED: Regex = Regex::new(r#"^\s+"(.+)", referenced from:$"#).unwrap();
+    static ref MACH_REFERENCED: Regex = Regex::new(r"^\s+.+ in (\S+)$").unwrap();
+    static ref LINKER: Regex =
+        Regex::new(r"^(?:\S*/)?(?:ld|ld\.\w+|ld64\.lld|lld)(?::| error:) (?:error: )?(.*)$")
+            .unwrap();
+    // Build tools
+    static ref NINJA_PROGRESS: Regex = Regex::new(r"^\[(\d+)/(\d+)\] ").unwrap();
+    static ref NINJA_FAILED: Regex = Regex::new(r"^FAILED: (.+)$").unwrap();
+    static ref MAKE_ERROR: Regex =
+        Regex::new(r"^(?:g?make|mingw32-make)(?:\[\d+\])?: \*\*\* (?:\[(?:[^\]]*?: )?([^\]]+)\] )?(.*)$")
+            .unwrap();
+    /// Directory changes, progress, cmake status and compile command echoes
+    static ref BUILD_NOISE: Regex = Regex::new(
+        r"^(?:(?:g?make|mingw32-make)(?:\[\d+\])?: (?:Entering|Leaving) directory|(?:g?make|mingw32-make)(?:\[\d+\])?: Nothing to be done|ninja: Entering directory|\[\s*\d+%\]|\[\d+/\d+\] |-- |Scanning dependencies|Consolidate compiler generated|(?:\S*/)?(?:cc|gcc|g\+\+|c\+\+|clang|clang\+\+)(?:-\d+)?\s.*\s-[co]\s|(?:\S*/)?(?:ar|ranlib)\s)"
+    )
+    .unwrap();
+    static ref CMAKE_MESSAGE: Regex =
+        Regex::new(r"^CMake (Error|Warning)(?: \(dev\))? at (.+?):(\d+) \((.+)\):$").unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Diagnostic {
+    file: String,
+    line: usize,
+    column: usize,
+    is_error: bool,
+    fatal: bool,
+    /// `-Wunused-variable`; `-Werror=` is folded into `is_error`
+    flag: String,
+    message: String,
+    /// User-code instantiation frames ("src/main.cpp:20:10")
+    via: Vec<String>,
+    notes: usize,
+}
+
+#[derive(Debug, Default)]
+struct CBuild {
+    diagnostics: Vec<Diagnostic>,
+    /// Undefined symbol → objects referencing it
+    undefined: Vec<(String, Vec<String>)>,
+    linker_errors: Vec<String>,
+    failed_targets: Vec<String>,
+    /// make/cmake errors that aren't compiler diagnostics
+    build_errors: Vec<String>,
+    steps: Option<usize>,
+    no_work: bool,
+}
+
+impl CBuild {
+    fn errors(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.is_error).count()
+            + self.undefined.len()
+            + self.linker_errors.len()
+    }
+
+    fn warnings(&self) -> usize {
+        self.diagnostics.iter().filter(|d| !d.is_error).count()
+    }
+
+    fn push_undefined(&mut self, symbol: &str, object: Option<&str>) {
+        let idx = match self.undefined.iter().position(|(s, _)| s == symbol) {
+            Some(idx) => idx,
+            None => {
+                self.undefined.push((symbol.to_string(), Vec::new()));
+                self.undefined.len() - 1
+            }
+        };
+        if let Some(object) = object {
+            let objects = &mut self.undefined[idx].1;
+            if !objects.iter().any(|o| o == object) {
+                objects.push(object.to_string());
+            }
+        }
+    }
+}
+
+fn is_user_path(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('<')
+        && !SYSTEM_PATH_MARKERS.iter().any(|m| path.contains(m))
+}
+
+/// Path relative to the build root (`/home/u/proj/src/a.c` → `src/a.c`)
+fn relative_path(path: &str, root: &str) -> String {
+    let root = root.trim_end_matches('/');
+    match path.strip_prefix(root) {
+        Some(rest) if !root.is_empty() && rest.starts_with('/') => rest[1..].to_string(),
+        _ => path.to_string(),
+    }
+}
+
+/// `-Werror=unused-variable` / `-Werror,-Wunused-variable` → `-Wunused-variable`
+fn normalize_flag(flag: &str) -> String {
+    let flag = flag.rsplit(',').next().unwrap_or(flag);
+    match flag.strip_prefix("-Werror=") {
+        Some(rest) => format!("-W{}", rest),
+        None => flag.to_string(),
+    }
+}
+
+fn parse_build(output: &str, root: &str) -> CBuild {
+    let mut build = CBuild::default();
+    // gcc "required from" frames seen since the last diagnostic
+    let mut pending_frames: Vec<String> = Vec::new();
+    let mut pending_symbol: Option<String> = None;
+    let lines: Vec<String> = output.lines().map(strip_ansi).collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        i += 1;
+
+        if let Some(caps) = NINJA_PROGRESS.captures(line) {
+            build.steps = caps[2].parse().ok();
+            continue;
+        }
+        if line == "ninja: no work to do." {
+            build.no_work = true;
+            continue;
+        }
+        if let Some(caps) = NINJA_FAILED.captures(line) {
+            push_target(&mut build, caps[1].trim());
+            continue;
+        }
+        if let Some(caps) = MAKE_ERROR.captures(line) {
+            match caps.get(1) {
+                Some(target) => push_target(&mut build, target.as_str().trim()),
+                None => {
+                    let message = caps[2].trim().to_string();
+                    if !build.build_errors.contains(&message) {
+                        build.build_errors.push(message);
+                    }
+                }
+            }
+            continue;
+        }
+        if let Some(caps) = CMAKE_MESSAGE.captures(line) {
+            // Indented message body up to the first blank line
+            let mut body: Vec<&str> = Vec::new();
+            while i < lines.len() && !lines[i].trim().is_empty() {
+                body.push(lines[i].trim());
+                i += 1;
+            }
+            build.diagnostics.push(Diagnostic {
+                file: relative_path(&caps[2], root),
+                line: caps[3].parse().unwrap_or(0),
+                column: 0,
+                is_error: &caps[1] == "Error",
+                fatal: false,
+                flag: format!("cmake:{}", &caps[4]),
+                message: truncate(&body.join(" "), 200),
+                via: Vec::new(),
+                notes: 0,
+            });
+            continue;
+        }
+
+        // Template instantiation context (gcc)
+        if INSTANTIATION.is_match(line) {
+            pending_frames.clear();
+            continue;
+        }
+        if let Some(caps) = REQUIRED_FROM.captures(line) {
+            if is_user_path(&caps[1]) {
+                pending_frames.push(format!(
+                    "{}:{}:{}",
+                    relative_path(&caps[1], root),
+                    &caps[2],
+                    &caps[3]
+                ));
+            }
+            continue;
+        }
+
+        if let Some(caps) = DIAGNOSTIC.captures(line) {
+            let severity = &caps[4];
+            let file = relative_path(&caps[1], root);
+            let location = match caps.get(3) {
+                Some(col) => format!("{}:{}:{}", file, &caps[2], col.as_str()),
+                None => format!("{}:{}", file, &caps[2]),
+            };
+            if severity == "note" || severity == "remark" {
+                // Notes belong to the diagnostic above
+                if let Some(last) = build.diagnostics.last_mut() {
+                    if CLANG_REQUESTED.is_match(&caps[5]) {
+                        if is_user_path(&caps[1]) && !last.via.contains(&location) {
+                            last.via.push(location);
+                        }
+                    } else {
+                        last.notes += 1;
+                    }
+                }
+                continue;
+            }
+            let message = caps[5].to_string();
+            let flag = FLAG
+                .captures(&message)
+                .map(|c| normalize_flag(&c[1]))
+                .unwrap_or_default();
+            let message = FLAG.replace(&message, "").to_string();
+            let via = std::mem::take(&mut pending_frames);
+            push_diagnostic(
+                &mut build,
+                Diagnostic {
+                    file,
+                    line: caps[2].parse().unwrap_or(0),
+                    column: caps
+                        .get(3)
+                        .and_then(|c| c.as_str().parse().ok())
+                        .unwrap_or(0),
+                    is_error: severity != "warning",
+                    fatal: severity == "fatal error",
+                    flag,
+                    message,
+                    via,
+                    notes: 0,
+                },
+            );
+            continue;
+        }
+
+        if let Some(caps) = DRIVER.captures(line) {
+            let message = caps[2].trim();
+            // Consequences of errors already listed
+            if message.starts_with("linker command failed")
+                || message.starts_with("ld returned")
+                || message.contains("warnings being treated as errors")
+            {
+                continue;
+            }
+            push_diagnostic(
+                &mut build,
+                Diagnostic {
+                    file: String::new(),
+                    line: 0,
+                    column: 0,
+                    is_error: &caps[1] != "warning",
+                    fatal: &caps[1] == "fatal error",
+                    flag: String::new(),
+                    message: message.to_string(),
+                    via: Vec::new(),
+                    notes: 0,
+                },
+            );
+            continue;
+        }
+
+        // Linker errors
+        if let Some(caps) = UNDEFINED_REF.captures(line) {
+            build.push_undefined(&caps[2], caps.get(1).map(|m| m.as_str()));
+            continue;
+        }
+        if let Some(caps) = LLD_UNDEFINED.captures(line) {
+            let symbol = caps[1].trim().to_string();
+            build.push_undefined(&symbol, None);
+            pending_symbol = Some(symbol);
+            continue;
+        }
+        if let Some(caps) = MACH_UNDEFINED.captures(line) {
+            let symbol = caps[1].to_string();
+            build.push_undefined(&symbol, None);
+            pending_symbol = Some(symbol);
+            continue;
+        }
+        if let Some(symbol) = &pending_symbol {
+            let object = LLD_REFERENCED
+                .captures(line)
+                .or_else(|| MACH_REFERENCED.captures(line))
+                .map(|c| c[1].to_string());
+            if let Some(object) = object {
+                let symbol = symbol.clone();
+                build.push_undefined(&symbol, Some(&object));
+                continue;
+            }
+            if !line.starts_with(">>>") {
+                pending_symbol = None;
+            }
+        }
+        if let Some(caps) = LINKER.captures(line) {
+            let message = caps[1].trim();
+            // "in function `main':" only introduces the undefined reference below
+            if !message.contains("in function")
+                && !message.is_empty()
+                && !build.linker_errors.iter().any(|e| e == message)
+            {
+                build.linker_errors.push(message.to_string());
+            }
+        }
+    }
+    build
+}
+
+/// Skip the same diagnostic from a header included by many translation units
+fn push_diagnostic(build: &mut CBuild, diagnostic: Diagnostic) {
+    let duplicate = build.diagnostics.iter().any(|d| {
+        d.file == diagnostic.file
+            && d.line == diagnostic.line
+            && d.column == diagnostic.column
+            && d.message == diagnostic.message
+    });
+    if !duplicate {
+        build.diagnostics.push(diagnostic);
+    }
+}
+
+fn push_target(build: &mut CBuild, target: &str) {
+    if !build.failed_targets.iter().any(|t| t == target) {
+        build.failed_targets.push(target.to_string());
+    }
+}
+
+/// `src/a.c:3:5` — or the user frame when the error sits in a system header
+fn display_location(d: &Diagnostic) -> (String, Option<String>) {
+    let position = match (d.line, d.column) {
+        (0, _) => String::new(),
+        (line, 0) => format!(":{}", line),
+        (line, col) => format!(":{}:{}", line, col),
+    };
+    let location = format!("{}{}", d.file, position);
+    if !is_user_path(&d.file) && !d.file.is_empty() {
+        if let Some(user) = d.via.last() {
+            let header = d.file.rsplit('/').next().unwrap_or(&d.file);
+            return (user.clone(), Some(format!("{}{}", header, position)));
+        }
+    }
+    (location, None)
+}
+
+fn format_build(tool: &str, build: &CBuild) -> String {
+    let errors = build.errors();
+    let warnings = build.warnings();
+    let steps = build
+        .steps
+        .map(|n| format!(" ({} steps)", n))
+        .unwrap_or_default();
+
+    if errors == 0 && warnings == 0 && build.failed_targets.is_empty() {
+        if build.build_errors.is_empty() {
+            return if build.no_work {
+                format!("✓ {}: no work to do", tool)
+            } else {
+                format!("✓ {}: ok{}", tool, steps)
+            };
+        }
+        let mut out = format!("{}: build failed{}\n", tool, steps);
+        for error in &build.build_errors {
+            out.push_str(&format!("  {}\n", truncate(error, 160)));
+        }
+        return out.trim().to_string();
+    }
+
+    let mut files: Vec<String> = build
+        .diagnostics
+        .iter()
+        .filter_map(|d| {
+            let (location, _) = display_location(d);
+            location.split(':').next().map(|f| f.to_string())
+        })
+        .filter(|f| !f.is_empty())
+        .collect();
+    files.sort_unstable();
+    files.dedup();
+
+    let mut out = format!(
+        "{}: {} errors, {} warnings ({} files){}\n",
+        tool,
+        errors,
+        warnings,
+        files.len(),
+        steps
+    );
+
+    let first = build
+        .diagnostics
+        .iter()
+        .find(|d| d.fatal)
+        .or_else(|| build.diagnostics.iter().find(|d| d.is_error));
+    if let Some(first) = first {
+        let (location, _) = display_location(first);
+        let label = if first.fatal {
+            "First fatal error"
+        } else {
+            "First error"
+        };
+        if location.is_empty() {
+            out.push_str(&format!("{}: {}\n", label, truncate(&first.message, 160)));
+        } else {
+            out.push_str(&format!(
+                "{}: {} {}\n",
+                label,
+                location,
+                truncate(&first.message, 160)
+            ));
+        }
+    }
+    out.push_str("═══════════════════════════════════════\n");
+
+    // (is_error, flag) → diagnostics, errors first then by frequency
+    let mut groups: Vec<((bool, &str), Vec<&Diagnostic>)> = Vec::new();
+    for d in &build.diagnostics {
+        let key = (d.is_error, d.flag.as_str());
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, list)) => list.push(d),
+            None => groups.push((key, vec![d])),
+        }
+    }
+    groups.sort_by(|a, b| b.0 .0.cmp(&a.0 .0).then(b.1.len().cmp(&a.1.len())));
+
+    for ((is_error, flag), diagnostics) in groups.iter().take(MAX_GROUPS) {
+        let level = if *is_error { "error" } else { "warning" };
+        if flag.is_empty() {
+            out.push_str(&format!("{} ×{}\n", level, diagnostics.len()));
+        } else {
+            out.push_str(&format!("{}[{}] ×{}\n", level, flag, diagnostics.len()));
+        }
+
+        // Same file + message: positions collapse onto one line (repeats from
+        // several system headers count once); rows ordered by file
+        let mut rows: Vec<(String, &str, Vec<String>, usize, &Diagnostic)> = Vec::new();
+        for d in diagnostics {
+            let (location, _) = display_location(d);
+            let (file, position) = match location.split_once(':') {
+                Some((file, position)) => (file.to_string(), position.to_string()),
+                None => (location, String::new()),
+            };
+            match rows
+                .iter_mut()
+                .find(|(f, m, _, _, _)| *f == file && *m == d.message)
+            {
+                Some((_, _, positions, repeats, _)) => {
+                    if positions.contains(&position) {
+                        *repeats += 1;
+                    } else {
+                        positions.push(position);
+                    }
+                }
+                None => rows.push((file, &d.message, vec![position], 0, d)),
+            }
+        }
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (file, _, positions, repeats, d) in rows.iter().take(MAX_ROWS_PER_GROUP) {
+            let (_, header) = display_location(d);
+            let mut text = match header {
+                Some(header) => format!("{} (in {})", d.message, header),
+                None => d.message.clone(),
+            };
+            if *repeats > 0 {
+                text.push_str(&format!(" ×{}", repeats + 1));
+            }
+            let positions: Vec<&str> = positions
+                .iter()
+                .map(|p| p.as_str())
+                .filter(|p| !p.is_empty())
+                .collect();
+            if file.is_empty() {
+                out.push_str(&format!("  {}\n", truncate(&text, 160)));
+            } else if positions.is_empty() {
+                out.push_str(&format!("  {} {}\n", file, truncate(&text, 160)));
+            } else {
+                out.push_str(&format!(
+                    "  {}:{} {}\n",
+                    file,
+                    positions.join(", "),
+                    truncate(&text, 160)
+                ));
+            }
+            let shown: Vec<String> = positions
+                .iter()
+                .map(|p| format!("{}:{}", file, p))
+                .collect();
+            let frames: Vec<&str> = d
+                .via
+                .iter()
+                .filter(|f| !shown.contains(f))
+                .take(MAX_FRAMES)
+                .map(|f| f.as_str())
+                .collect();
+            if !frames.is_empty() {
+                out.push_str(&format!("    required from {}\n", frames.join(" ← ")));
+            }
+            if d.notes > 0 {
+                out.push_str(&format!("    (+{} notes)\n", d.notes));
+            }
+        }
+        if rows.len() > MAX_ROWS_PER_GROUP {
+            out.push_str(&format!(
+                "  ... +{} more\n",
+                rows.len() - MAX_ROWS_PER_GROUP
+            ));
+        }
+    }
+    if groups.len() > MAX_GROUPS {
+        out.push_str(&format!("... +{} more groups\n", groups.len() - MAX_GROUPS));
+    }
+
+    if !build.undefined.is_empty() || !build.linker_errors.is_empty() {
+        out.push_str(&format!(
+            "linker ×{}\n",
+            build.undefined.len() + build.linker_errors.len()
+        ));
+        for (symbol, objects) in &build.undefined {
+            if objects.is_empty() {
+                out.push_str(&format!("  undefined reference to {}\n", symbol));
+            } else {
+                out.push_str(&format!(
+                    "  undefined reference to {} ({})\n",
+                    symbol,
+                    objects.join(", ")
+                ));
+            }
+        }
+        for error in &build.linker_errors {
+            out.push_str(&format!("  {}\n", truncate(error, 160)));
+        }
+    }
+
+    if let Some(failed) = format_failed_targets(build) {
+        out.push_str(&failed);
+    }
+    out.trim().to_string()
+}
+
+/// `Failed: test, lint (+2 more)`
+fn format_failed_targets(build: &CBuild) -> Option<String> {
+    if build.failed_targets.is_empty() {
+        return None;
+    }
+    let shown: Vec<&str> = build
+        .failed_targets
+        .iter()
+        .take(MAX_TARGETS)
+        .map(|t| t.as_str())
+        .collect();
+    let more = build.failed_targets.len().saturating_sub(MAX_TARGETS);
+    Some(if more > 0 {
+        format!("Failed: {} (+{} more)", shown.join(", "), more)
+    } else {
+        format!("Failed: {}", shown.join(", "))
+    })
+}
+
+/// Diagnostics summary; runs without any (`make test`, `gcc --version`,
+/// `gcc -E`) keep their output minus build noise, plus the failed targets
+fn filter_cc_output(tool: &str, raw: &str, root: &str) -> String {
+    let build = parse_build(raw, root);
+    let recognized =
+        build.errors() + build.warnings() > 0 || !build.build_errors.is_empty() || build.no_work;
+    if recognized {
+        return format_build(tool, &build);
+    }
+
+    let lines: Vec<String> = raw
+        .lines()
+        .map(strip_ansi)
+        .filter(|l| !l.trim().is_empty() && !BUILD_NOISE.is_match(l))
+        .collect();
+    if lines.is_empty() {
+        return format_build(tool, &build);
+    }
+    let mut out = lines
+        .iter()
+        .take(MAX_OUTPUT_LINES)
+        .map(|l| l.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if lines.len() > MAX_OUTPUT_LINES {
+        out.push_str(&format!(
+            "\n... +{} more lines",
+            lines.len() - MAX_OUTPUT_LINES
+        ));
+    }
+    if let Some(failed) = format_failed_targets(&build) {
+        out.push('\n');
+        out.push_str(&failed);
+    }
+    out
+}
+
+pub fn run(tool: &str, args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("Running: {} {}", tool, args.join(" "));
+    }
+
+    let output = Command::new(tool)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {}. Is it installed?", tool))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+
+    let root = std::env::current_dir()
+        .map(|d| d.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut filtered = filter_cc_output(tool, &raw, &root);
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, tool, exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &format!("{} {}", tool, args.join(" ")),
+        &format!("rtk {} {}", tool, args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ninja_groups_by_flag_and_drops_progress() {
+        let output = r#"[1/4] Building CXX object CMakeFiles/app.dir/src/util.cpp.o
+/home/dev/app/src/util.cpp:8:9: warning: unused variable 'tmp' [-Wunused-variable]
+    8 |     int tmp = 0;
+      |         ^~~
+[2/4] Building CXX object CMakeFiles/app.dir/src/main.cpp.o
+FAILED: CMakeFiles/app.dir/src/main.cpp.o
+/usr/bin/c++ -O2 -Wall -c /home/dev/app/src/main.cpp -o CMakeFiles/app.dir/src/main.cpp.o
+In file included from /home/dev/app/src/main.cpp:2:
+/home/dev/app/src/util.h:3:5: warning: unused variable 'tmp' [-Wunused-variable]
+/home/dev/app/src/main.cpp: In function 'int main()':
+/home/dev/app/src/main.cpp:10:5: error: 'foo' was not declared in this scope
+   10 |     foo();
+      |     ^~~
+/home/dev/app/src/main.cpp:12:5: error: 'foo' was not declared in this scope
+/home/dev/app/src/main.cpp:15:12: warning: comparison of integer expressions of different signedness [-Wsign-compare]
+ninja: build stopped: subcommand failed.
+"#;
+        let build = parse_build(output, "/home/dev/app");
+        assert_eq!(build.steps, Some(4));
+        assert_eq!(
+            build.failed_targets,
+            vec!["CMakeFiles/app.dir/src/main.cpp.o"]
+        );
+
+        let out = format_build("ninja", &build);
+        assert_eq!(
+            out,
+            concat!(
+                "ninja: 2 errors, 3 warnings (3 files) (4 steps)\n",
+                "First error: src/main.cpp:10:5 'foo' was not declared in this scope\n",
+                "═══════════════════════════════════════\n",
+                "error ×2\n",
+                "  src/main.cpp:10:5, 12:5 'foo' was not declared in this scope\n",
+                "warning[-Wunused-variable] ×2\n",
+                "  src/util.cpp:8:9 unused variable 'tmp'\n",
+                "  src/util.h:3:5 unused variable 'tmp'\n",
+                "warning[-Wsign-compare] ×1\n",
+                "  src/main.cpp:15:12 comparison of integer expressions of different signedness\n",
+                "Failed: CMakeFiles/app.dir/src/main.cpp.o",
+            )
+        );
+    }
+
+    #[test]
+    fn test_gcc_instantiation_chain_collapsed_to_user_frames() {
+        let output = r#"In file included from /usr/include/c++/11/algorithm:62,
+                 from src/main.cpp:1:
+/usr/include/c++/11/bits/stl_algo.h: In instantiation of 'void std::__insertion_sort(_RandomAccessIterator, _RandomAccessIterator, _Compare) [with _RandomAccessIterator = Item*]':
+/usr/include/c++/11/bits/stl_algo.h:1866:25:   required from 'void std::__final_insertion_sort(_RandomAccessIterator, _RandomAccessIterator, _Compare)'
+/usr/include/c++/11/bits/stl_algo.h:1957:31:   required from 'void std::__sort(_RandomAccessIterator, _RandomAccessIterator, _Compare)'
+src/sorter.h:14:14:   required from 'void sort_items(T&) [with T = std::vector<Item>]'
+src/main.cpp:20:15:   required from here
+/usr/include/c++/11/bits/stl_algo.h:1828:24: error: no match for 'operator<' (operand types are 'Item' and 'Item')
+ 1828 |               if (__comp(__i, __first))
+      |                   ~~~~~~^~~~~~~~~~~~~~
+/usr/include/c++/11/bits/stl_algo.h:1828:24: note: candidate: 'bool operator<(const Foo&, const Foo&)'
+/usr/include/c++/11/bits/stl_algo.h:1828:24: note:   no known conversion for argument 1 from 'Item' to 'const Foo&'
+"#;
+        let build = parse_build(output, "/home/dev/app");
+        assert_eq!(build.diagnostics.len(), 1);
+        assert_eq!(
+            build.diagnostics[0].via,
+            vec!["src/sorter.h:14:14", "src/main.cpp:20:15"]
+        );
+        let out = format_build("g++", &build);
+        assert!(
+            out.contains(concat!(
+                "  src/main.cpp:20:15 no match for 'operator<' (operand types are 'Item' and 'Item') (in stl_algo.h:1828:24)\n",
+                "    required from src/sorter.h:14:14\n",
+                "    (+2 notes)",
+            )),
+            "{}",
+            out
+        );
+        assert!(!out.contains("__insertion_sort"), "{}", out);
+    }
+
+    #[test]
+    fn test_clang_requested_here_notes_become_frames() {
+        let output = r#"src/proc.h:5:7: error: no member named 'run' in 'Bar'
+    t.run();
+    ~ ^
+src/main.cpp:20:3: note: in instantiation of function template specialization 'process<Bar>' requested here
+  process(b);
+  ^
+1 error generated.
+"#;
+        let build = parse_build(output, "/home/dev/app");
+        let out = format_build("clang++", &build);
+        assert!(
+            out.contains(concat!(
+                "  src/proc.h:5:7 no member named 'run' in 'Bar'\n",
+                "    required from src/main.cpp:20:3",
+            )),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn test_fatal_error_summarized_first() {
+        let output = r#"src/a.c:3:10: warning: implicit declaration of function 'bar' [-Wimplicit-function-declaration]
+src/b.c:1:10: fatal error: missing.h: No such file or directory
+    1 | #include "missing.h"
+      |          ^~~~~~~~~~~
+compilation terminated.
+make[2]: *** [CMakeFiles/app.dir/build.make:90: CMakeFiles/app.dir/src/b.c.o] Error 1
+make[1]: *** [CMakeFiles/Makefile2:83: CMakeFiles/app.dir/all] Error 2
+make: *** [Makefile:91: all] Error 2
+"#;
+        let build = parse_build(output, "/home/dev/app");
+        let out = format_build("make", &build);
+        assert!(
+            out.starts_with(concat!(
+                "make: 1 errors, 1 warnings (2 files)\n",
+                "First fatal error: src/b.c:1:10 missing.h: No such file or directory\n",
+            )),
+            "{}",
+            out
+        );
+        assert!(
+            out.ends_with("Failed: CMakeFiles/app.dir/src/b.c.o, CMakeFiles/app.dir/all, all"),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn test_werror_flag_and_linker_errors() {
+        let output = r#"src/main.c:4:9: error: unused variable 'x' [-Werror=unused-variable]
+cc1: all warnings being treated as errors
+/usr/bin/ld: main.o: in function `main':
+main.c:(.text+0x15): undefined reference to `compute'
+/usr/bin/ld: util.o: in function `helper':
+util.c:(.text+0x9): undefined reference to `compute'
+/usr/bin/ld: cannot find -lfoo: No such file or directory
+collect2: error: ld returned 1 exit status
+"#;
+        let build = parse_build(output, "/home/dev/app");
+        assert_eq!(build.diagnostics[0].flag, "-Wunused-variable");
+        assert!(build.diagnostics[0].is_error);
+        let out = format_build("gcc", &build);
+        assert!(
+            out.contains(concat!(
+                "linker ×2\n",
+                "  undefined reference to compute (main.c, util.c)\n",
+                "  cannot find -lfoo: No such file or directory",
+            )),
+            "{}",
+            out
+        );
+        assert!(!out.contains("collect2"), "{}", out);
+    }
+
+    #[test]
+    fn test_cmake_configure_error_and_clean_build() {
+        let output = r#"-- The C compiler identification is GNU 11.4.0
+-- Detecting C compiler ABI info - done
+CMake Error at CMakeLists.txt:12 (find_package):
+  By not providing "FindFoo.cmake" in CMAKE_MODULE_PATH this project has
+  asked CMake to find a package configuration file provided by "Foo", but
+  CMake did not find one.
+
+-- Configuring incomplete, errors occurred!
+"#;
+        let build = parse_build(output, "/home/dev/app");
+        let out = format_build("cmake", &build);
+        assert!(
+            out.contains("error[cmake:find_package] ×1\n  CMakeLists.txt:12 By not providing"),
+            "{}",
+            out
+        );
+
+        let clean = parse_build(
+            "[1/2] Building C object a.o\n[2/2] Linking C executable app\n",
+            "",
+        );
+        assert_eq!(format_build("ninja", &clean), "✓ ninja: ok (2 steps)");
+        let nothing = parse_build("ninja: no work to do.\n", "");
+        assert_eq!(format_build("ninja", &nothing), "✓ ninja: no work to do");
+    }
+
+    #[test]
+    fn test_output_without_diagnostics_keeps_non_noise_lines() {
+        let output = r#"make[1]: Entering directory '/home/dev/app'
+[ 50%] Building C object CMakeFiles/app.dir/main.c.o
+gcc -O2 -Wall -c main.c -o main.o
+./run_tests
+All 12 tests passed
+make[1]: Leaving directory '/home/dev/app'
+"#;
+        assert_eq!(
+            filter_cc_output("make", output, "/home/dev/app"),
+            "./run_tests\nAll 12 tests passed"
+        );
+        assert_eq!(
+            filter_cc_output("gcc", "gcc (Ubuntu 11.4.0-1ubuntu1~22.04) 11.4.0\n", ""),
+            "gcc (Ubuntu 11.4.0-1ubuntu1~22.04) 11.4.0"
+        );
+        assert_eq!(
+            filter_cc_output("make", "gcc -c a.c -o a.o\ngcc a.o -o app\n", ""),
+            "✓ make: ok"
+        );
+    }
+
+    #[test]
+    fn test_failing_recipe_without_diagnostics_keeps_output() {
+        let output = r#"./run_tests
+test_parse ... FAILED: expected 3, got 4
+1 of 12 tests failed
+make: *** [Makefile:7: test] Error 1
+"#;
+        assert_eq!(
+            filter_cc_output("make", output, ""),
+            concat!(
+                "./run_tests\n",
+                "test_parse ... FAILED: expected 3, got 4\n",
+                "1 of 12 tests failed\n",
+                "make: *** [Makefile:7: test] Error 1\n",
+                "Failed: test"
+            )
+        );
+    }
+
+    #[test]
+    fn test_make_savings_above_60pct() {
+        let input = r#"make[1]: Entering directory '/home/user/project'
+make[2]: Entering directory '/home/user/project/src'
+gcc -O2 -Wall -c foo.c -o foo.o
+
+make[2]: Nothing to be done for 'install'.
+make[3]: Entering directory '/home/user/project/src/lib'
+ar rcs libfoo.a foo.o bar.o baz.o
+make[3]: Leaving directory '/home/user/project/src/lib'
+make[2]: Leaving directory '/home/user/project/src'
+
+make[1]: Leaving directory '/home/user/project'
+gcc -O2 -Wall -c bar.c -o bar.o
+
+gcc -O2 -Wall -c baz.c -o baz.o
+
+make[1]: Entering directory '/home/user/project/test'
+make[2]: Entering directory '/home/user/project/test/unit'
+./run_tests --verbose
+make[2]: Nothing to be done for 'check'.
+make[2]: Leaving directory '/home/user/project/test/unit'
+make[1]: Leaving directory '/home/user/project/test'
+
+ld -o myapp foo.o bar.o baz.o -lfoo
+
+make[1]: Entering directory '/home/user/project/docs'
+doxygen Doxyfile
+make[1]: Leaving directory '/home/user/project/docs'
+"#;
+        let out = filter_cc_output("make", input, "/home/user/project");
+        let input_words = input.split_whitespace().count();
+        let out_words = out.split_whitespace().count();
+        let savings = 100.0 - (out_words as f64 / input_words as f64 * 100.0);
+        assert!(
+            savings >= 60.0,
+            "make: expected >=60% savings, got {:.1}% (in={} out={})",
+            savings,
+            input_words,
+            out_words
+        );
+    }
+}
@@ -0,0 +1,463 @@
+//! CI job log compression for `gh run view --log` / `--log-failed` and
+//! `glab ci trace`.
+//!
+//! gh prints every line as `job\tstep\ttimestamp content`; GitLab traces
+//! delimit steps with `section_start:<ts>:<name>` markers. Lines are grouped
+//! into steps, timestamps and ANSI codes are stripped, setup/checkout/cache
+//! steps are dropped, and each failing step keeps only its error context and
+//! tail. Steps that look like cargo, pytest, tsc or go test output go through
+//! the same filters as `rtk cargo test`, `rtk pytest`, etc.
+
+use crate::utils::strip_ansi;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Lines kept from the end of a step with no recognizable tool output
+const TAIL_LINES: usize = 15;
+/// Context lines kept before each error line
+const ERROR_CONTEXT: usize = 2;
+/// Cap on lines per step in the generic fallback
+const MAX_STEP_LINES: usize = 60;
+
+lazy_static! {
+    static ref TIMESTAMP: Regex =
+        Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z ?").unwrap();
+    static ref SETUP_STEP: Regex = Regex::new(
+        r"(?i)^(set up job|complete job|post |run actions/(checkout|cache|setup-[\w-]+|upload-artifact|download-artifact)@)|checkout|cache|^setup "
+    )
+    .unwrap();
+    static ref ERROR_LINE: Regex =
+        Regex::new(r"(?i)(\berror\b|\bfailed\b|\bfailure\b|panicked|exception|traceback)").unwrap();
+    static ref EXIT_CODE: Regex = Regex::new(r"Process completed with exit code (\d+)").unwrap();
+    static ref TSC_ERROR: Regex = Regex::new(r"error TS\d+:").unwrap();
+    static ref GITLAB_SECTION: Regex =
+        Regex::new(r"section_(start|end):\d+:([\w.-]+)(\[[^\]]*\])?\r?").unwrap();
+    static ref GO_TEST: Regex = Regex::new(r"^(--- FAIL: |FAIL\s+\S+\s+[\d.]+s|ok\s+\S+\s+[\d.]+s)").unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogStep {
+    pub job: String,
+    pub step: String,
+    pub lines: Vec<String>,
+}
+
+impl LogStep {
+    /// GitHub marks the failing step with `##[error]` annotations
+    fn failed(&self) -> bool {
+        self.lines.iter().any(|l| l.starts_with("##[error]"))
+    }
+
+    fn exit_code(&self) -> Option<&str> {
+        self.lines
+            .iter()
+            .find_map(|l| EXIT_CODE.captures(l))
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str())
+    }
+}
+
+/// Group `job\tstep\ttimestamp content` lines into steps, in log order
+pub fn parse_gh_run_log(raw: &str) -> Vec<LogStep> {
+    let mut steps: Vec<LogStep> = Vec::new();
+
+    for line in raw.lines() {
+        let mut parts = line.splitn(3, '\t');
+        let (job, step, content) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(job), Some(step), Some(content)) => (job, step, content),
+            // Continuation without the job/step prefix belongs to the current step
+            _ => match steps.last_mut() {
+                Some(last) => {
+                    last.lines.push(clean_line(line));
+                    continue;
+                }
+                None => continue,
+            },
+        };
+
+        let content = clean_line(content);
+        match steps.last_mut() {
+            Some(last) if last.job == job && last.step == step => last.lines.push(content),
+            _ => steps.push(LogStep {
+                job: job.to_string(),
+                step: step.to_string(),
+                lines: vec![content],
+            }),
+        }
+    }
+
+    steps
+}
+
+fn clean_line(line: &str) -> String {
+    let line = TIMESTAMP.replace(line, "");
+    strip_ansi(&line).trim_end().to_string()
+}
+
+pub fn is_setup_step(name: &str) -> bool {
+    SETUP_STEP.is_match(name)
+}
+
+/// Compress a whole `gh run view --log[-failed]` output
+pub fn compress_gh_run_log(raw: &str) -> String {
+    let steps = parse_gh_run_log(raw);
+    if steps.is_empty() {
+        return "No log output".to_string();
+    }
+
+    let any_failed = steps.iter().any(|s| s.failed());
+    let mut setup_skipped = 0;
+    let mut out = Vec::new();
+
+    for step in &steps {
+        let keep = if any_failed {
+            step.failed()
+        } else {
+            // No annotations (e.g. cancelled run): keep non-setup steps
+            !is_setup_step(&step.step)
+        };
+        if !keep {
+            if is_setup_step(&step.step) {
+                setup_skipped += 1;
+            }
+            continue;
+        }
+
+        let marker = if step.failed() { "❌" } else { "▶" };
+        let exit = step
+            .exit_code()
+            .map(|c| format!(", exit {}", c))
+            .unwrap_or_default();
+        out.push(format!(
+            "{} {} / {} ({} lines{})",
+            marker,
+            step.job,
+            step.step,
+            step.lines.len(),
+            exit
+        ));
+        for line in compress_step_output(&step.lines).lines() {
+            out.push(format!("  {}", line));
+        }
+    }
+
+    let kept = out.iter().filter(|l| !l.starts_with("  ")).count();
+    let mut header = format!("📋 {} steps, {} shown", steps.len(), kept);
+    if setup_skipped > 0 {
+        header.push_str(&format!(" ({} setup steps skipped)", setup_skipped));
+    }
+    out.insert(0, header);
+    out.join("\n")
+}
+
+/// GitLab runner sections that only prepare or clean up the job
+const GITLAB_SETUP_SECTIONS: &[&str] = &[
+    "resolve_secrets",
+    "prepare_executor",
+    "prepare_script",
+    "get_sources",
+    "restore_cache",
+    "download_artifacts",
+    "archive_cache",
+    "archive_cache_on_failure",
+    "upload_artifacts_on_success",
+    "upload_artifacts_on_failure",
+    "cleanup_file_variables",
+];
+
+/// Group a `glab ci trace` log into runner sections, in log order.
+/// Lines outside any section land in a section named `job`.
+pub fn parse_gitlab_trace(raw: &str) -> Vec<LogStep> {
+    let mut steps: Vec<LogStep> = Vec::new();
+    let mut current = "job".to_string();
+
+    for line in raw.lines() {
+        let mut text = line.to_string();
+        if let Some(caps) = GITLAB_SECTION.captures(line) {
+            current = if &caps[1] == "start" {
+                caps[2].to_string()
+            } else {
+                "job".to_string()
+            };
+            text = GITLAB_SECTION.replace_all(line, "").to_string();
+        }
+        let text = strip_ansi(&text).trim_end().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        match steps.last_mut() {
+            Some(last) if last.step == current => last.lines.push(text),
+            _ => steps.push(LogStep {
+                job: String::new(),
+                step: current.clone(),
+                lines: vec![text],
+            }),
+        }
+    }
+
+    steps
+}
+
+/// Compress a `glab ci trace` log: runner setup sections dropped, script
+/// sections reduced like failing GitHub steps, job verdict kept.
+pub fn compress_gitlab_trace(raw: &str) -> String {
+    let steps = parse_gitlab_trace(raw);
+    if steps.is_empty() {
+        return "No log output".to_string();
+    }
+
+    let mut out = Vec::new();
+    let mut setup_skipped = 0;
+    let mut verdict = None;
+    for step in &steps {
+        if GITLAB_SETUP_SECTIONS.contains(&step.step.as_str()) {
+            setup_skipped += 1;
+            continue;
+        }
+        let (verdicts, lines): (Vec<&String>, Vec<&String>) = step
+            .lines
+            .iter()
+            .partition(|l| l.starts_with("ERROR: Job failed") || l.starts_with("Job succeeded"));
+        if let Some(last) = verdicts.last() {
+            verdict = Some(last.to_string());
+        }
+        if lines.is_empty() {
+            continue;
+        }
+        let lines: Vec<String> = lines.into_iter().cloned().collect();
+        out.push(format!("▶ {} ({} lines)", step.step, lines.len()));
+        for line in compress_step_output(&lines).lines() {
+            out.push(format!("  {}", line));
+        }
+    }
+
+    let shown = out.iter().filter(|l| !l.starts_with("  ")).count();
+    let mut header = format!("📋 {} sections shown", shown);
+    if setup_skipped > 0 {
+        header.push_str(&format!(" ({} setup sections skipped)", setup_skipped));
+    }
+    out.insert(0, header);
+    if let Some(verdict) = verdict {
+        let icon = if verdict.starts_with("ERROR") {
+            "❌"
+        } else {
+            "✓"
+        };
+        out.push(format!("{} {}", icon, verdict));
+    }
+    out.join("\n")
+}
+
+/// Compress the lines of one step: known tools through their rtk filter,
+/// anything else down to error context plus the tail.
+pub fn compress_step_output(lines: &[String]) -> String {
+    let body: Vec<&str> = lines
+        .iter()
+        .map(|l| l.as_str())
+        .filter(|l| !l.starts_with("##[group]") && !l.starts_with("##[endgroup]"))
+        .filter(|l| !EXIT_CODE.is_match(l))
+        .collect();
+    let text = body.join("\n");
+
+    if let Some(filtered) = route_known_tool(&text) {
+        return filtered;
+    }
+
+    error_context(&body)
+}
+
+fn route_known_tool(text: &str) -> Option<String> {
+    if text.contains("test result:")
+        || text
+            .lines()
+            .any(|l| l.starts_with("running ") && l.ends_with(" tests"))
+    {
+        return Some(crate::cargo_cmd::filter_cargo_test(text));
+    }
+    if text.contains("error[E") || text.contains("error: could not compile") {
+        return Some(crate::cargo_cmd::filter_cargo_build(text));
+    }
+    if text.contains("short test summary info") || text.contains("= FAILURES =") {
+        return Some(crate::pytest_cmd::filter_pytest_output(text));
+    }
+    if TSC_ERROR.is_match(text) {
+        return Some(crate::tsc_cmd::filter_tsc_output(text));
+    }
+    if text.lines().any(|l| GO_TEST.is_match(l)) {
+        return Some(crate::runner::extract_test_summary(text, "go test"));
+    }
+    None
+}
+
+/// Error lines with a little leading context, then the tail of the step
+fn error_context(lines: &[&str]) -> String {
+    let mut keep = vec![false; lines.len()];
+    for (idx, line) in lines.iter().enumerate() {
+        if line.starts_with("##[error]") || ERROR_LINE.is_match(line) {
+            for flag in keep
+                .iter_mut()
+                .take(idx + 1)
+                .skip(idx.saturating_sub(ERROR_CONTEXT))
+            {
+                *flag = true;
+            }
+        }
+    }
+    for flag in keep.iter_mut().skip(lines.len().saturating_sub(TAIL_LINES)) {
+        *flag = true;
+    }
+
+    let mut out = Vec::new();
+    let mut gap = false;
+    for (line, kept) in lines.iter().zip(&keep) {
+        if !kept {
+            gap = true;
+            continue;
+        }
+        if gap && !out.is_empty() {
+            out.push("...".to_string());
+        }
+        gap = false;
+        let line = line.strip_prefix("##[error]").unwrap_or(line);
+        if !line.trim().is_empty() {
+            out.push(line.to_string());
+        }
+    }
+
+    if out.len() > MAX_STEP_LINES {
+        let dropped = out.len() - MAX_STEP_LINES;
+        let tail = out.split_off(dropped);
+        out = vec![format!("... ({} earlier lines)", dropped)];
+        out.extend(tail);
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gh_log(entries: &[(&str, &str, &str)]) -> String {
+        entries
+            .iter()
+            .map(|(job, step, line)| {
+                format!("{}\t{}\t2026-03-01T10:00:00.1234567Z {}", job, step, line)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_parse_strips_timestamps_and_ansi() {
+        let raw = gh_log(&[
+            ("build", "Run cargo test", "\x1b[32mCompiling\x1b[0m rtk"),
+            ("build", "Run cargo test", "done"),
+            ("lint", "Run clippy", "ok"),
+        ]);
+        let steps = parse_gh_run_log(&raw);
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].lines, vec!["Compiling rtk", "done"]);
+        assert_eq!(steps[1].job, "lint");
+    }
+
+    #[test]
+    fn test_setup_steps() {
+        assert!(is_setup_step("Set up job"));
+        assert!(is_setup_step("Run actions/checkout@v4"));
+        assert!(is_setup_step("Run Swatinem/rust-cache@v2"));
+        assert!(is_setup_step("Post Run actions/checkout@v4"));
+        assert!(!is_setup_step("Run cargo test"));
+    }
+
+    #[test]
+    fn test_only_failing_step_kept_and_cargo_routed() {
+        let mut entries = vec![
+            ("test", "Set up job", "Runner image"),
+            ("test", "Run actions/checkout@v4", "Syncing repository"),
+            ("test", "Run cargo test", "##[group]Run cargo test"),
+            ("test", "Run cargo test", "##[endgroup]"),
+            ("test", "Run cargo test", "running 3 tests"),
+            ("test", "Run cargo test", "test a ... ok"),
+            ("test", "Run cargo test", "test b ... ok"),
+            ("test", "Run cargo test", "test c ... FAILED"),
+            ("test", "Run cargo test", ""),
+            ("test", "Run cargo test", "failures:"),
+            ("test", "Run cargo test", ""),
+            ("test", "Run cargo test", "---- c stdout ----"),
+            ("test", "Run cargo test", "thread 'c' panicked at src/lib.rs:10:5:"),
+            ("test", "Run cargo test", "assertion failed: false"),
+            ("test", "Run cargo test", ""),
+            ("test", "Run cargo test", "failures:"),
+            ("test", "Run cargo test", "    c"),
+            ("test", "Run cargo test", ""),
+            ("test", "Run cargo test", "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s"),
+            ("test", "Run cargo test", "##[error]Process completed with exit code 101."),
+        ];
+        entries.push(("test", "Post Run actions/checkout@v4", "Cleaning up"));
+        let result = compress_gh_run_log(&gh_log(&entries));
+
+        assert!(result.starts_with("📋 4 steps, 1 shown (3 setup steps skipped)"));
+        assert!(result.contains("❌ test / Run cargo test (18 lines, exit 101)"));
+        assert!(result.contains("panicked at src/lib.rs:10:5"));
+        assert!(!result.contains("Syncing repository"));
+        assert!(!result.contains("test a ... ok"));
+    }
+
+    #[test]
+    fn test_gitlab_trace_drops_setup_sections() {
+        let raw = "\x1b[0KRunning with gitlab-runner 16.0\n\
+section_start:1700000000:prepare_executor\r\x1b[0K\x1b[36;1mPreparing the \"docker\" executor\x1b[0;m\n\
+Using docker image rust:1.80\n\
+section_end:1700000001:prepare_executor\r\x1b[0K\n\
+section_start:1700000001:get_sources\r\x1b[0KGetting source from Git repository\n\
+Fetching changes with git depth set to 20...\n\
+section_end:1700000002:get_sources\r\x1b[0K\n\
+section_start:1700000002:step_script\r\x1b[0KExecuting \"step_script\" stage\n\
+$ ./deploy.sh\n\
+uploading assets\n\
+ERROR: bucket not found\n\
+section_end:1700000003:step_script\r\x1b[0K\n\
+ERROR: Job failed: exit code 1\n";
+        let result = compress_gitlab_trace(raw);
+        assert!(
+            result.starts_with("📋 2 sections shown (2 setup sections skipped)"),
+            "{}",
+            result
+        );
+        assert!(result.contains("▶ step_script (4 lines)"));
+        assert!(result.contains("ERROR: bucket not found"));
+        assert!(!result.contains("Fetching changes"));
+        assert!(!result.contains("docker image"));
+        assert!(result.ends_with("❌ ERROR: Job failed: exit code 1"));
+    }
+
+    #[test]
+    fn test_generic_step_keeps_errors_and_tail() {
+        let mut entries: Vec<(&str, &str, String)> = (0..100)
+            .map(|i| ("deploy", "Run ./deploy.sh", format!("progress {}", i)))
+            .collect();
+        entries.insert(
+            20,
+            (
+                "deploy",
+                "Run ./deploy.sh",
+                "ERROR: bucket not found".to_string(),
+            ),
+        );
+        entries.push((
+            "deploy",
+            "Run ./deploy.sh",
+            "##[error]Process completed with exit code 1.".to_string(),
+        ));
+        let borrowed: Vec<(&str, &str, &str)> = entries
+            .iter()
+            .map(|(j, s, l)| (*j, *s, l.as_str()))
+            .collect();
+        let result = compress_gh_run_log(&gh_log(&borrowed));
+
+        assert!(result.contains("progress 18\n  progress 19\n  ERROR: bucket not found\n  ..."));
+        assert!(result.contains("progress 99"));
+        assert!(!result.contains("progress 50"));
+        assert!(result.lines().count() < 30);
+    }
+}
@@ -8,9 +8,17 @@ pub enum ContainerCmd {
     DockerPs,
     DockerImages,
     DockerLogs,
+    DockerBuild,
+    DockerRun,
+    DockerInspect,
+    DockerPull,
+    DockerPush,
     KubectlPods,
     KubectlServices,
     KubectlLogs,
+    KubectlDescribe,
+    KubectlApply,
+    KubectlDiff,
 }
 
 pub fn run(cmd: ContainerCmd, args: &[String], verbose: u8) -> Result<()> {
@@ -18,9 +26,17 @@ pub fn run(cmd: ContainerCmd, args: &[String], verbose: u8) -> Result<()> {
         ContainerCmd::DockerPs => docker_ps(verbose),
         ContainerCmd::DockerImages => docker_images(verbose),
         ContainerCmd::DockerLogs => docker_logs(args, verbose),
+        ContainerCmd::DockerBuild => docker_build(args, verbose),
+        ContainerCmd::DockerRun => docker_run(args, verbose),
+        ContainerCmd::DockerInspect => docker_inspect(args, verbose),
+        ContainerCmd::DockerPull => docker_transfer("pull", args, verbose),
+        ContainerCmd::DockerPush => docker_transfer("push", args, verbose),
         ContainerCmd::KubectlPods => kubectl_pods(args, verbose),
         ContainerCmd::KubectlServices => kubectl_services(args, verbose),
         ContainerCmd::KubectlLogs => kubectl_logs(args, verbose),
+        ContainerCmd::KubectlDescribe => kubectl_describe(args, verbose),
+        ContainerCmd::KubectlApply => kubectl_apply_or_diff("apply", args, verbose),
+        ContainerCmd::KubectlDiff => kubectl_apply_or_diff("diff", args, verbose),
     }
 }
 
@@ -705,11 +721,706 @@ pub fn run_compose_passthrough(args: &[OsString], verbose: u8) -> Result<()> {
     Ok(())
 }
 
+lazy_static::lazy_static! {
+    /// BuildKit plain progress: `#5 [2/4] RUN make`, `#5 CACHED`, `#5 0.52 log line`
+    static ref BUILDKIT_LINE: regex::Regex = regex::Regex::new(r"^#(\d+) (.*)$").unwrap();
+    static ref BUILDKIT_STEP: regex::Regex =
+        regex::Regex::new(r"^\[(?:[\w.-]+ )?\d+/\d+\] ").unwrap();
+    static ref BUILDKIT_LOG_TIME: regex::Regex = regex::Regex::new(r"^\d+\.\d+ ").unwrap();
+    /// Classic builder: `Step 2/5 : RUN make`
+    static ref CLASSIC_STEP: regex::Regex = regex::Regex::new(r"^Step (\d+/\d+) : (.*)$").unwrap();
+    static ref LAYER_PROGRESS: regex::Regex = regex::Regex::new(
+        r"^[0-9a-f]{12}: (Pulling fs layer|Waiting|Downloading|Verifying Checksum|Download complete|Extracting|Pull complete|Already exists|Preparing|Pushing|Pushed|Layer already exists|Mounted from .*)"
+    )
+    .unwrap();
+}
+
+/// Log lines kept from the failing build step
+const BUILD_ERROR_LINES: usize = 15;
+
+#[derive(Debug, PartialEq)]
+enum BuildStepState {
+    Running,
+    Cached,
+    Done,
+    Failed,
+}
+
+#[derive(Debug)]
+struct BuildStep {
+    name: String,
+    state: BuildStepState,
+    logs: Vec<String>,
+}
+
+fn short_image_ref(reference: &str) -> &str {
+    reference
+        .trim_start_matches("docker.io/library/")
+        .trim_start_matches("docker.io/")
+}
+
+/// Parse BuildKit `--progress=plain` or classic builder output into steps
+fn parse_docker_build(raw: &str) -> (Vec<BuildStep>, Vec<String>) {
+    let mut steps: Vec<BuildStep> = Vec::new();
+    let mut vertex_ids: Vec<(String, usize)> = Vec::new();
+    let mut tags = Vec::new();
+
+    for line in raw.lines() {
+        let line = line.trim_end();
+
+        if let Some(caps) = BUILDKIT_LINE.captures(line) {
+            let id = caps[1].to_string();
+            let body = caps[2].trim();
+            if let Some(tag) = body
+                .strip_prefix("naming to ")
+                .map(|t| t.trim_end_matches(" done").trim())
+            {
+                tags.push(short_image_ref(tag).to_string());
+                continue;
+            }
+            match vertex_ids
+                .iter()
+                .find(|(v, _)| *v == id)
+                .map(|(_, idx)| *idx)
+            {
+                Some(idx) => {
+                    let step = &mut steps[idx];
+                    if body == "CACHED" {
+                        step.state = BuildStepState::Cached;
+                    } else if body.starts_with("DONE") {
+                        step.state = BuildStepState::Done;
+                    } else if body.starts_with("ERROR") {
+                        step.state = BuildStepState::Failed;
+                        step.logs.push(body.to_string());
+                    } else {
+                        step.logs
+                            .push(BUILDKIT_LOG_TIME.replace(body, "").to_string());
+                    }
+                }
+                None if BUILDKIT_STEP.is_match(body) => {
+                    vertex_ids.push((id, steps.len()));
+                    steps.push(BuildStep {
+                        name: body.to_string(),
+                        state: BuildStepState::Running,
+                        logs: Vec::new(),
+                    });
+                }
+                None => {}
+            }
+            continue;
+        }
+
+        // Classic (non-BuildKit) builder
+        if let Some(caps) = CLASSIC_STEP.captures(line) {
+            if let Some(prev) = steps.last_mut() {
+                if prev.state == BuildStepState::Running {
+                    prev.state = BuildStepState::Done;
+                }
+            }
+            steps.push(BuildStep {
+                name: format!("[{}] {}", &caps[1], &caps[2]),
+                state: BuildStepState::Running,
+                logs: Vec::new(),
+            });
+        } else if line.trim() == "---> Using cache" {
+            if let Some(step) = steps.last_mut() {
+                step.state = BuildStepState::Cached;
+            }
+        } else if let Some(tag) = line.strip_prefix("Successfully tagged ") {
+            tags.push(short_image_ref(tag.trim()).to_string());
+        } else if line.starts_with("The command ") && line.contains("returned a non-zero code") {
+            if let Some(step) = steps.last_mut() {
+                step.state = BuildStepState::Failed;
+                step.logs.push(line.to_string());
+            }
+        } else if let Some(step) = steps.last_mut() {
+            let trimmed = line.trim();
+            if !trimmed.starts_with("--->")
+                && !trimmed.starts_with("Removing intermediate container")
+                && !trimmed.is_empty()
+            {
+                step.logs.push(trimmed.to_string());
+            }
+        }
+    }
+
+    // Classic builder marks completion by starting the next step
+    if let Some(last) = steps.last_mut() {
+        if last.state == BuildStepState::Running && !tags.is_empty() {
+            last.state = BuildStepState::Done;
+        }
+    }
+
+    (steps, tags)
+}
+
+/// `docker build`: step counts, cached vs rebuilt, and the failing step's error
+pub fn format_docker_build(raw: &str, success: bool) -> String {
+    let (steps, tags) = parse_docker_build(raw);
+    let cached = steps
+        .iter()
+        .filter(|s| s.state == BuildStepState::Cached)
+        .count();
+    let rebuilt = steps
+        .iter()
+        .filter(|s| s.state == BuildStepState::Done)
+        .count();
+    let counts = format!(
+        "{} steps ({} cached, {} rebuilt)",
+        steps.len(),
+        cached,
+        rebuilt
+    );
+
+    if success {
+        let mut out = format!("🐳 Build ok: {}", counts);
+        if !tags.is_empty() {
+            out.push_str(&format!(" → {}", tags.join(", ")));
+        }
+        return out;
+    }
+
+    let failed = steps
+        .iter()
+        .find(|s| s.state == BuildStepState::Failed)
+        .or_else(|| {
+            steps
+                .iter()
+                .rev()
+                .find(|s| s.state == BuildStepState::Running)
+        });
+    let mut out = format!("🐳 Build failed: {}", counts);
+    if let Some(step) = failed {
+        out.push_str(&format!("\n❌ {}", step.name));
+        let skip = step.logs.len().saturating_sub(BUILD_ERROR_LINES);
+        for line in &step.logs[skip..] {
+            out.push_str(&format!("\n  {}", line));
+        }
+    }
+    // Dockerfile pointer (`>>> RUN ...`) and the final solver error
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.contains(">>>") || trimmed.starts_with("ERROR: failed to") {
+            out.push_str(&format!("\n  {}", trimmed));
+        }
+    }
+    out
+}
+
+pub fn is_layer_progress_line(line: &str) -> bool {
+    let line = line.trim();
+    LAYER_PROGRESS.is_match(line)
+        || line.starts_with("Unable to find image ")
+        || line.contains(": Pulling from ")
+        || line.starts_with("Digest: sha256:")
+        || line.starts_with("Status: ")
+        || line.starts_with("Using default tag: ")
+        || line.starts_with("The push refers to repository ")
+}
+
+fn short_digest(digest: &str) -> String {
+    let hex = digest.trim_start_matches("sha256:");
+    format!("sha256:{}", &hex[..12.min(hex.len())])
+}
+
+/// `docker pull` (or the pull inside `docker run`): one line per image
+pub fn format_docker_pull(raw: &str) -> String {
+    let mut out = Vec::new();
+    let mut layers = 0;
+    let mut digest = None;
+
+    for line in raw.lines().map(str::trim) {
+        if line.contains(": Pulling from ") {
+            layers = 0;
+            digest = None;
+        } else if line.ends_with(": Pull complete") {
+            layers += 1;
+        } else if let Some(d) = line.strip_prefix("Digest: ") {
+            digest = Some(short_digest(d));
+        } else if let Some(image) = line.strip_prefix("Status: Downloaded newer image for ") {
+            let mut entry = format!("⬇ pulled {} ({} layers", short_image_ref(image), layers);
+            if let Some(ref d) = digest {
+                entry.push_str(&format!(", {}", d));
+            }
+            entry.push(')');
+            out.push(entry);
+        } else if let Some(image) = line.strip_prefix("Status: Image is up to date for ") {
+            out.push(format!("✓ {} up to date", short_image_ref(image)));
+        }
+    }
+
+    if out.is_empty() {
+        // Errors and unexpected formats: keep whatever is not layer noise
+        return raw
+            .lines()
+            .filter(|l| !is_layer_progress_line(l) && !l.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    out.join("\n")
+}
+
+/// `docker push`: one line per pushed tag
+pub fn format_docker_push(raw: &str) -> String {
+    let mut out = Vec::new();
+    let mut repo = String::new();
+    let mut pushed = 0;
+    let mut existing = 0;
+
+    for line in raw.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("The push refers to repository [") {
+            repo = short_image_ref(rest.trim_end_matches(']')).to_string();
+            pushed = 0;
+            existing = 0;
+        } else if line.ends_with(": Pushed") {
+            pushed += 1;
+        } else if line.ends_with(": Layer already exists") || line.contains(": Mounted from ") {
+            existing += 1;
+        } else if let Some((tag, rest)) = line.split_once(": digest: ") {
+            let digest = rest.split_whitespace().next().unwrap_or("");
+            out.push(format!(
+                "⬆ pushed {}:{} ({} pushed, {} existing, {})",
+                repo,
+                tag,
+                pushed,
+                existing,
+                short_digest(digest)
+            ));
+        }
+    }
+
+    if out.is_empty() {
+        return raw
+            .lines()
+            .filter(|l| !is_layer_progress_line(l) && !l.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+    out.join("\n")
+}
+
+fn human_size(bytes: u64) -> String {
+    let mb = bytes as f64 / (1024.0 * 1024.0);
+    if mb > 1024.0 {
+        format!("{:.1}GB", mb / 1024.0)
+    } else {
+        format!("{:.0}MB", mb)
+    }
+}
+
+/// `KEY=value` env entries as `KEY=***`
+fn masked_env(env: &serde_json::Value) -> Vec<String> {
+    env.as_array()
+        .map(|vars| {
+            vars.iter()
+                .filter_map(|v| v.as_str())
+                .map(|v| format!("{}=***", v.split('=').next().unwrap_or(v)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn json_str_list(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|i| i.as_str())
+            .collect::<Vec<_>>()
+            .join(" "),
+        serde_json::Value::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+fn format_inspect_container(c: &serde_json::Value) -> Vec<String> {
+    let name = c["Name"].as_str().unwrap_or("").trim_start_matches('/');
+    let id = c["Id"].as_str().unwrap_or("");
+    let state = &c["State"];
+    let status = state["Status"].as_str().unwrap_or("?");
+    let health = state["Health"]["Status"].as_str();
+
+    let mut header = format!("📦 {} ({}) {}", name, &id[..12.min(id.len())], status);
+    if let Some(h) = health {
+        header.push_str(&format!(" ({})", h));
+    }
+    if status == "exited" {
+        header.push_str(&format!(
+            " exit {}",
+            state["ExitCode"].as_i64().unwrap_or(0)
+        ));
+    }
+    let restarts = c["RestartCount"].as_i64().unwrap_or(0);
+    if restarts > 0 {
+        header.push_str(&format!(", {} restarts", restarts));
+    }
+    let mut out = vec![header];
+
+    out.push(format!(
+        "  image: {}",
+        c["Config"]["Image"].as_str().unwrap_or("?")
+    ));
+    let entrypoint = json_str_list(&c["Config"]["Entrypoint"]);
+    let cmd = json_str_list(&c["Config"]["Cmd"]);
+    let command = format!("{} {}", entrypoint, cmd);
+    if !command.trim().is_empty() {
+        out.push(format!(
+            "  cmd: {}",
+            crate::utils::truncate(command.trim(), 100)
+        ));
+    }
+
+    if let Some(ports) = c["NetworkSettings"]["Ports"].as_object() {
+        let mut list: Vec<String> = ports
+            .iter()
+            .map(|(port, bindings)| {
+                let hosts: Vec<String> = bindings
+                    .as_array()
+                    .map(|b| {
+                        b.iter()
+                            .filter_map(|h| h["HostPort"].as_str())
+                            .map(|p| p.to_string())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if hosts.is_empty() {
+                    port.clone()
+                } else {
+                    format!("{}→{}", port, hosts.join("/"))
+                }
+            })
+            .collect();
+        list.dedup();
+        if !list.is_empty() {
+            out.push(format!("  ports: {}", list.join(", ")));
+        }
+    }
+
+    if let Some(mounts) = c["Mounts"].as_array() {
+        let list: Vec<String> = mounts
+            .iter()
+            .map(|m| {
+                let source = if m["Type"].as_str() == Some("volume") {
+                    format!("vol:{}", m["Name"].as_str().unwrap_or("?"))
+                } else {
+                    m["Source"].as_str().unwrap_or("?").to_string()
+                };
+                let ro = if m["RW"].as_bool() == Some(false) {
+                    " (ro)"
+                } else {
+                    ""
+                };
+                format!(
+                    "{}→{}{}",
+                    source,
+                    m["Destination"].as_str().unwrap_or("?"),
+                    ro
+                )
+            })
+            .collect();
+        if !list.is_empty() {
+            out.push(format!("  mounts: {}", list.join(", ")));
+        }
+    }
+
+    if let Some(networks) = c["NetworkSettings"]["Networks"].as_object() {
+        let list: Vec<String> = networks
+            .iter()
+            .map(|(name, net)| match net["IPAddress"].as_str() {
+                Some(ip) if !ip.is_empty() => format!("{} {}", name, ip),
+                _ => name.clone(),
+            })
+            .collect();
+        if !list.is_empty() {
+            out.push(format!("  networks: {}", list.join(", ")));
+        }
+    }
+
+    let env = masked_env(&c["Config"]["Env"]);
+    if !env.is_empty() {
+        out.push(format!("  env: {}", env.join(", ")));
+    }
+
+    if let Some(h) = health {
+        if h != "healthy" {
+            let last = state["Health"]["Log"]
+                .as_array()
+                .and_then(|log| log.last())
+                .and_then(|entry| entry["Output"].as_str())
+                .map(|o| crate::utils::truncate(o.trim(), 120))
+                .unwrap_or_default();
+            out.push(format!(
+                "  health: {} ({} failing) {}",
+                h,
+                state["Health"]["FailingStreak"].as_i64().unwrap_or(0),
+                last
+            ));
+        }
+    }
+    if let Some(err) = state["Error"].as_str().filter(|e| !e.is_empty()) {
+        out.push(format!("  error: {}", err));
+    }
+    out
+}
+
+fn format_inspect_image(i: &serde_json::Value) -> Vec<String> {
+    let tags = json_str_list(&i["RepoTags"]);
+    let id = i["Id"].as_str().unwrap_or("").trim_start_matches("sha256:");
+    let mut out = vec![format!(
+        "🖼 {} ({}) {}, {}/{}",
+        if tags.is_empty() { "<none>" } else { &tags },
+        &id[..12.min(id.len())],
+        human_size(i["Size"].as_u64().unwrap_or(0)),
+        i["Os"].as_str().unwrap_or("?"),
+        i["Architecture"].as_str().unwrap_or("?")
+    )];
+
+    let config = &i["Config"];
+    let command = format!(
+        "{} {}",
+        json_str_list(&config["Entrypoint"]),
+        json_str_list(&config["Cmd"])
+    );
+    if !command.trim().is_empty() {
+        out.push(format!(
+            "  cmd: {}",
+            crate::utils::truncate(command.trim(), 100)
+        ));
+    }
+    if let Some(ports) = config["ExposedPorts"].as_object() {
+        let list: Vec<&str> = ports.keys().map(|k| k.as_str()).collect();
+        out.push(format!("  ports: {}", list.join(", ")));
+    }
+    let env = masked_env(&config["Env"]);
+    if !env.is_empty() {
+        out.push(format!("  env: {}", env.join(", ")));
+    }
+    if let Some(layers) = i["RootFS"]["Layers"].as_array() {
+        out.push(format!(
+            "  layers: {}, created {}",
+            layers.len(),
+            i["Created"]
+                .as_str()
+                .unwrap_or("?")
+                .split('T')
+                .next()
+                .unwrap_or("?")
+        ));
+    }
+    out
+}
+
+/// `docker inspect` JSON reduced to key fields per object; env values masked
+pub fn format_docker_inspect(raw: &str) -> String {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(raw) else {
+        return raw.to_string();
+    };
+    let Some(objects) = json.as_array() else {
+        return raw.to_string();
+    };
+
+    let mut out = Vec::new();
+    for obj in objects {
+        if obj.get("State").is_some() && obj.get("Config").is_some() {
+            out.extend(format_inspect_container(obj));
+        } else if obj.get("RepoTags").is_some() {
+            out.extend(format_inspect_image(obj));
+        } else {
+            // Volumes, networks, ...: name plus the fields available
+            let name = obj["Name"].as_str().unwrap_or("?");
+            let keys: Vec<&str> = obj
+                .as_object()
+                .map(|o| o.keys().map(|k| k.as_str()).collect())
+                .unwrap_or_default();
+            out.push(format!("🔧 {} [{}]", name, keys.join(", ")));
+        }
+    }
+    out.join("\n")
+}
+
+fn docker_build(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = Command::new("docker");
+    cmd.arg("build");
+    // Plain progress is line-oriented and parseable (the TTY view is not)
+    if !args.iter().any(|a| a.starts_with("--progress")) {
+        cmd.arg("--progress=plain");
+    }
+    cmd.args(args);
+    if verbose > 0 {
+        eprintln!("Running: docker build {}", args.join(" "));
+    }
+
+    let output = cmd.output().context("Failed to run docker build")?;
+    let raw = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rtk = format_docker_build(&raw, output.status.success());
+    println!("{}", rtk);
+    timer.track(
+        &format!("docker build {}", args.join(" ")),
+        &format!("rtk docker build {}", args.join(" ")),
+        &raw,
+        &rtk,
+    );
+
+    if !output.status.success() {
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+/// `-i`, `--tty`, or `i`/`t` inside a short-flag cluster (`-itd`, `-dit`)
+fn is_interactive_run(args: &[String]) -> bool {
+    args.iter().any(|a| {
+        a == "--interactive"
+            || a == "--tty"
+            || a.strip_prefix('-').is_some_and(|flags| {
+                !flags.starts_with('-')
+                    && flags.chars().all(|c| c.is_ascii_alphabetic())
+                    && flags.contains(['i', 't'])
+            })
+    })
+}
+
+fn docker_run(args: &[String], verbose: u8) -> Result<()> {
+    // Interactive sessions need the terminal
+    if is_interactive_run(args) {
+        let os_args: Vec<OsString> = std::iter::once(OsString::from("run"))
+            .chain(args.iter().map(OsString::from))
+            .collect();
+        return run_docker_passthrough(&os_args, verbose);
+    }
+
+    let timer = tracking::TimedExecution::start();
+
+    let output = Command::new("docker")
+        .arg("run")
+        .args(args)
+        .output()
+        .context("Failed to run docker run")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // Pull progress goes to stderr ahead of the container's own output
+    let (pull, rest): (Vec<&str>, Vec<&str>) =
+        stderr.lines().partition(|l| is_layer_progress_line(l));
+    let mut rtk = String::new();
+    if !pull.is_empty() {
+        rtk.push_str(&format_docker_pull(&pull.join("\n")));
+        rtk.push('\n');
+        eprintln!("{}", rtk.trim_end());
+    }
+    if !rest.is_empty() {
+        let rest = rest.join("\n");
+        eprintln!("{}", rest);
+        rtk.push_str(&rest);
+        rtk.push('\n');
+    }
+    print!("{}", stdout);
+    rtk.push_str(&stdout);
+
+    timer.track(
+        &format!("docker run {}", args.join(" ")),
+        &format!("rtk docker run {}", args.join(" ")),
+        &format!("{}{}", stderr, stdout),
+        &rtk,
+    );
+
+    if !output.status.success() {
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+fn docker_inspect(args: &[String], verbose: u8) -> Result<()> {
+    // Custom templates already select what the user wants
+    if args
+        .iter()
+        .any(|a| a == "-f" || a.starts_with("--format") || a.starts_with("-f="))
+    {
+        let os_args: Vec<OsString> = std::iter::once(OsString::from("inspect"))
+            .chain(args.iter().map(OsString::from))
+            .collect();
+        return run_docker_passthrough(&os_args, verbose);
+    }
+
+    let timer = tracking::TimedExecution::start();
+
+    let output = Command::new("docker")
+        .arg("inspect")
+        .args(args)
+        .output()
+        .context("Failed to run docker inspect")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let rtk = format_docker_inspect(&raw);
+    println!("{}", rtk);
+    timer.track(
+        &format!("docker inspect {}", args.join(" ")),
+        &format!("rtk docker inspect {}", args.join(" ")),
+        &raw,
+        &rtk,
+    );
+    Ok(())
+}
+
+/// `docker pull` / `docker push` reduced to one line per image
+fn docker_transfer(subcommand: &str, args: &[String], _verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let output = Command::new("docker")
+        .arg(subcommand)
+        .args(args)
+        .output()
+        .context(format!("Failed to run docker {}", subcommand))?;
+    let raw = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rtk = if subcommand == "push" {
+        format_docker_push(&raw)
+    } else {
+        format_docker_pull(&raw)
+    };
+    if output.status.success() {
+        println!("{}", rtk);
+    } else {
+        eprintln!("{}", rtk);
+    }
+    timer.track(
+        &format!("docker {} {}", subcommand, args.join(" ")),
+        &format!("rtk docker {} {}", subcommand, args.join(" ")),
+        &raw,
+        &rtk,
+    );
+
+    if !output.status.success() {
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
 /// Dispatch `kubectl get <resource>` to specialized or generic filter
 pub fn kubectl_get(resource: &str, args: &[String], verbose: u8) -> Result<()> {
     match resource {
         "pods" | "pod" | "po" => kubectl_pods(args, verbose),
         "services" | "service" | "svc" => kubectl_services(args, verbose),
+        "events" | "event" | "ev"
+            if !args.iter().any(|a| a == "-o" || a.starts_with("--output")) =>
+        {
+            kubectl_events(args, verbose)
+        }
         _ => kubectl_get_generic(resource, args, verbose),
     }
 }
@@ -973,6 +1684,619 @@ fn compact_memory(mem: &str) -> String {
     mem.to_string()
 }
 
+lazy_static::lazy_static! {
+    static ref DESCRIBE_KEY: regex::Regex =
+        regex::Regex::new(r"^(\s*)([A-Za-z][\w .()/-]*?):(\s+(.*))?$").unwrap();
+    static ref EVENT_REPEAT: regex::Regex = regex::Regex::new(r"\(x(\d+) over ").unwrap();
+    static ref APPLY_RESULT: regex::Regex = regex::Regex::new(
+        r"^(\S+/\S+) (created|configured|unchanged|deleted|pruned|serverside-applied)\b"
+    )
+    .unwrap();
+}
+
+/// `kubectl describe` fields that rarely help debugging (dropped with their children)
+const DESCRIBE_DROP_KEYS: &[&str] = &[
+    "Labels",
+    "Annotations",
+    "Volumes",
+    "Tolerations",
+    "Node-Selectors",
+    "QoS Class",
+    "Priority",
+    "Priority Class Name",
+    "Service Account",
+    "Start Time",
+    "Container ID",
+    "Image ID",
+    "Host Port",
+    "Host Ports",
+    "Mounts",
+    "Environment",
+    "Environment Variables from",
+    "SeccompProfile",
+    "Started",
+    "Finished",
+    "Runtime Class Name",
+    "IPs",
+    "Events",
+];
+
+/// Kubernetes metadata keys that change on every apply (hunks may start mid-document)
+const DIFF_NOISE_KEYS: &[&str] = &[
+    "generation",
+    "resourceVersion",
+    "managedFields",
+    "kubectl.kubernetes.io/last-applied-configuration",
+];
+
+/// Max field changes shown per resource in `kubectl diff`
+const DIFF_MAX_FIELDS: usize = 20;
+
+/// A grouped event: same type, reason and message collapse to one row
+#[derive(Debug)]
+struct EventGroup {
+    warning: bool,
+    reason: String,
+    message: String,
+    count: u64,
+}
+
+fn push_event(
+    groups: &mut Vec<EventGroup>,
+    warning: bool,
+    reason: &str,
+    message: &str,
+    count: u64,
+) {
+    match groups
+        .iter_mut()
+        .find(|g| g.warning == warning && g.reason == reason && g.message == message)
+    {
+        Some(g) => g.count += count,
+        None => groups.push(EventGroup {
+            warning,
+            reason: reason.to_string(),
+            message: message.to_string(),
+            count,
+        }),
+    }
+}
+
+/// Warnings one per line with counts; normal events folded into a single reason list
+fn format_event_groups(groups: &[EventGroup], indent: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    for g in groups.iter().filter(|g| g.warning) {
+        let count = if g.count > 1 {
+            format!(" ×{}", g.count)
+        } else {
+            String::new()
+        };
+        out.push(format!(
+            "{}⚠ {}{}: {}",
+            indent,
+            g.reason,
+            count,
+            crate::utils::truncate(&g.message, 160)
+        ));
+    }
+
+    let mut normal: Vec<(String, u64)> = Vec::new();
+    for g in groups.iter().filter(|g| !g.warning) {
+        match normal.iter_mut().find(|(r, _)| *r == g.reason) {
+            Some((_, c)) => *c += g.count,
+            None => normal.push((g.reason.clone(), g.count)),
+        }
+    }
+    if !normal.is_empty() {
+        let list: Vec<String> = normal
+            .iter()
+            .map(|(r, c)| {
+                if *c > 1 {
+                    format!("{} ×{}", r, c)
+                } else {
+                    r.clone()
+                }
+            })
+            .collect();
+        out.push(format!("{}✓ {}", indent, list.join(", ")));
+    }
+    out
+}
+
+/// Parse the column-aligned `Events:` table of `kubectl describe`
+fn parse_describe_events(lines: &[&str]) -> Vec<EventGroup> {
+    let mut groups = Vec::new();
+    let Some(header) = lines.first() else {
+        return groups;
+    };
+    let col = |name: &str| header.find(name);
+    let (Some(reason_col), Some(age_col), Some(msg_col)) =
+        (col("Reason"), col("Age"), col("Message"))
+    else {
+        return groups;
+    };
+
+    for row in lines.iter().skip(1) {
+        if row.trim_start().starts_with("----") || row.trim().is_empty() {
+            continue;
+        }
+        let slice = |from: usize, to: usize| row.get(from..to.min(row.len())).unwrap_or("").trim();
+        let warning = row.trim_start().starts_with("Warning");
+        let reason = slice(reason_col, age_col);
+        let age = slice(age_col, msg_col);
+        let message = row.get(msg_col..).unwrap_or("").trim();
+        let count = EVENT_REPEAT
+            .captures(age)
+            .and_then(|c| c[1].parse().ok())
+            .unwrap_or(1);
+        push_event(&mut groups, warning, reason, message, count);
+    }
+    groups
+}
+
+/// `kubectl describe`: conditions, container states, termination reasons, grouped events
+pub fn filter_kubectl_describe(raw: &str) -> String {
+    let lines: Vec<&str> = raw.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut drop_indent: Option<usize> = None;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        i += 1;
+        if line.trim().is_empty() {
+            drop_indent = None;
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some(d) = drop_indent {
+            if indent > d || !DESCRIBE_KEY.is_match(line) {
+                continue;
+            }
+            drop_indent = None;
+        }
+
+        let Some(caps) = DESCRIBE_KEY.captures(line) else {
+            out.push(line.to_string());
+            continue;
+        };
+        let key = caps[2].trim();
+        let value = caps.get(4).map(|m| m.as_str().trim()).unwrap_or("");
+
+        if key == "Events" && indent == 0 {
+            if value == "<none>" {
+                out.push("Events: none".to_string());
+                continue;
+            }
+            let start = i;
+            while i < lines.len() && lines[i].starts_with(' ') {
+                i += 1;
+            }
+            let groups = parse_describe_events(&lines[start..i]);
+            let warnings = groups
+                .iter()
+                .filter(|g| g.warning)
+                .map(|g| g.count)
+                .sum::<u64>();
+            out.push(format!("Events ({} warning):", warnings));
+            out.extend(format_event_groups(&groups, "  "));
+            continue;
+        }
+
+        if key == "Conditions" {
+            // Table of `Type  Status` rows → one line
+            let mut conds = Vec::new();
+            while i < lines.len() {
+                let row = lines[i].trim();
+                let row_indent = lines[i].len() - lines[i].trim_start().len();
+                if row.is_empty() || row_indent <= indent {
+                    break;
+                }
+                i += 1;
+                let cols: Vec<&str> = row.split_whitespace().collect();
+                if cols.len() >= 2 && cols[0] != "Type" && !cols[0].starts_with("----") {
+                    conds.push(format!("{}={}", cols[0], cols[1]));
+                }
+            }
+            out.push(format!(
+                "{}Conditions: {}",
+                " ".repeat(indent),
+                conds.join(", ")
+            ));
+            continue;
+        }
+
+        if DESCRIBE_DROP_KEYS.contains(&key) {
+            drop_indent = Some(indent);
+            continue;
+        }
+        out.push(line.to_string());
+    }
+
+    out.join("\n")
+}
+
+/// `kubectl get events -o json` grouped by object and reason, warnings first
+pub fn format_kubectl_events(raw: &str) -> String {
+    let json: serde_json::Value = match serde_json::from_str(raw) {
+        Ok(v) => v,
+        Err(_) => return "☸️  Failed to parse events JSON".to_string(),
+    };
+    let items = json["items"].as_array().cloned().unwrap_or_default();
+    if items.is_empty() {
+        return "☸️  No events".to_string();
+    }
+
+    // (object, latest timestamp, groups)
+    let mut objects: Vec<(String, String, Vec<EventGroup>)> = Vec::new();
+    for ev in &items {
+        let obj = &ev["involvedObject"];
+        let key = format!(
+            "{}/{}",
+            obj["kind"].as_str().unwrap_or("?"),
+            obj["name"].as_str().unwrap_or("?")
+        );
+        let key = match obj["namespace"].as_str() {
+            Some(ns) if !ns.is_empty() => format!("{} -n {}", key, ns),
+            _ => key,
+        };
+        let timestamp = ev["lastTimestamp"]
+            .as_str()
+            .or_else(|| ev["eventTime"].as_str())
+            .or_else(|| ev["metadata"]["creationTimestamp"].as_str())
+            .unwrap_or("")
+            .to_string();
+        let count = ev["count"]
+            .as_u64()
+            .or_else(|| ev["series"]["count"].as_u64())
+            .unwrap_or(1);
+        let warning = ev["type"].as_str() == Some("Warning");
+        let reason = ev["reason"].as_str().unwrap_or("?");
+        let message = ev["message"].as_str().unwrap_or("").trim();
+
+        let idx = match objects.iter().position(|(k, _, _)| *k == key) {
+            Some(idx) => idx,
+            None => {
+                objects.push((key, String::new(), Vec::new()));
+                objects.len() - 1
+            }
+        };
+        let entry = &mut objects[idx];
+        if timestamp > entry.1 {
+            entry.1 = timestamp;
+        }
+        push_event(&mut entry.2, warning, reason, message, count);
+    }
+
+    // Objects with warnings first, then most recent activity
+    objects.sort_by(|a, b| {
+        let wa = a.2.iter().any(|g| g.warning);
+        let wb = b.2.iter().any(|g| g.warning);
+        wb.cmp(&wa).then_with(|| b.1.cmp(&a.1))
+    });
+
+    let warnings = items
+        .iter()
+        .filter(|e| e["type"].as_str() == Some("Warning"))
+        .count();
+    let mut out = vec![format!(
+        "☸️  {} events ({} warning) across {} objects",
+        items.len(),
+        warnings,
+        objects.len()
+    )];
+    for (key, _, groups) in &objects {
+        out.push(format!("{}:", key));
+        out.extend(format_event_groups(groups, "  "));
+    }
+    out.join("\n")
+}
+
+/// `kubectl apply`: per-action tallies, listing only resources that changed
+pub fn format_kubectl_apply(raw: &str) -> String {
+    let mut tallies: Vec<(String, Vec<String>)> = Vec::new();
+    let mut other = Vec::new();
+
+    for line in raw.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match APPLY_RESULT.captures(line) {
+            Some(caps) => {
+                let action = caps[2].to_string();
+                let resource = caps[1].to_string();
+                match tallies.iter_mut().find(|(a, _)| *a == action) {
+                    Some((_, list)) => list.push(resource),
+                    None => tallies.push((action, vec![resource])),
+                }
+            }
+            None => other.push(line.to_string()),
+        }
+    }
+
+    if tallies.is_empty() {
+        return other.join("\n");
+    }
+
+    let summary: Vec<String> = tallies
+        .iter()
+        .map(|(a, list)| format!("{} {}", list.len(), a))
+        .collect();
+    let dry_run = if raw.contains("(dry run)") || raw.contains("(server dry run)") {
+        " (dry run)"
+    } else {
+        ""
+    };
+    let mut out = vec![format!("☸️  apply{}: {}", dry_run, summary.join(", "))];
+    for (action, list) in tallies.iter().filter(|(a, _)| a != "unchanged") {
+        out.push(format!("  {}: {}", action, list.join(", ")));
+    }
+    out.extend(other);
+    out.join("\n")
+}
+
+/// `apps.v1.Deployment.default.api` → `Deployment default/api`
+fn diff_resource_name(path: &str) -> String {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let parts: Vec<&str> = file.split('.').collect();
+    let Some(kind_idx) = parts
+        .iter()
+        .position(|p| p.chars().next().is_some_and(|c| c.is_ascii_uppercase()))
+    else {
+        return file.to_string();
+    };
+    let kind = parts[kind_idx];
+    let ns = parts.get(kind_idx + 1).copied().unwrap_or("");
+    let name = parts
+        .get(kind_idx + 2..)
+        .map(|p| p.join("."))
+        .unwrap_or_default();
+    if ns.is_empty() {
+        format!("{} {}", kind, name)
+    } else {
+        format!("{} {}/{}", kind, ns, name)
+    }
+}
+
+/// Track the YAML key path for a diff line; returns the scalar value if any
+fn yaml_path_update(stack: &mut Vec<(usize, String)>, content: &str) -> Option<String> {
+    let indent = content.len() - content.trim_start().len();
+    let mut body = content.trim();
+    let list_item = body.starts_with("- ") || body == "-";
+    // List items sit at their parent key's indent
+    while stack
+        .last()
+        .is_some_and(|(i, key)| *i > indent || (*i == indent && (!list_item || key == "[]")))
+    {
+        stack.pop();
+    }
+
+    let mut indent = indent;
+    if list_item {
+        let item = body.strip_prefix('-').unwrap_or(body);
+        stack.push((indent, "[]".to_string()));
+        indent += 2;
+        body = item.trim();
+        if body.is_empty() {
+            return None;
+        }
+    }
+
+    match body.split_once(':') {
+        Some((key, value)) if !key.contains(' ') || key.starts_with('"') => {
+            let value = value.trim();
+            stack.push((indent, key.trim_matches('"').to_string()));
+            if value.is_empty() || value == "|" || value == ">" {
+                None
+            } else {
+                Some(value.to_string())
+            }
+        }
+        _ => Some(body.to_string()),
+    }
+}
+
+fn yaml_path(stack: &[(usize, String)]) -> String {
+    let mut path = String::new();
+    for (_, key) in stack {
+        if key == "[]" {
+            path.push_str("[]");
+        } else {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(key);
+        }
+    }
+    path
+}
+
+/// Field-level changes of one resource's unified YAML diff
+fn diff_field_changes(hunk_lines: &[&str]) -> Vec<String> {
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    let mut removed: Vec<(String, String)> = Vec::new();
+    let mut added: Vec<(String, String)> = Vec::new();
+
+    for line in hunk_lines {
+        if line.starts_with("@@") {
+            stack.clear();
+            continue;
+        }
+        let (sign, content) = line.split_at(line.len().min(1));
+        let value = yaml_path_update(&mut stack, content);
+        let Some(value) = value else { continue };
+        if stack
+            .iter()
+            .any(|(_, key)| DIFF_NOISE_KEYS.contains(&key.as_str()))
+        {
+            continue;
+        }
+        let path = yaml_path(&stack);
+        match sign {
+            "-" => removed.push((path, value)),
+            "+" => added.push((path, value)),
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    for (path, old) in &removed {
+        match added.iter().position(|(p, _)| p == path) {
+            Some(idx) => {
+                let (_, new) = added.remove(idx);
+                out.push(format!("~ {}: {} → {}", path, old, new));
+            }
+            None => out.push(format!("- {}: {}", path, old)),
+        }
+    }
+    for (path, new) in added {
+        out.push(format!("+ {}: {}", path, new));
+    }
+    out
+}
+
+/// `kubectl diff`: changed resources with compact field-level changes
+pub fn format_kubectl_diff(raw: &str) -> String {
+    let mut resources: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut other = Vec::new();
+
+    for line in raw.lines() {
+        if let Some(rest) = line.strip_prefix("diff ") {
+            let name = rest.split_whitespace().last().unwrap_or(rest);
+            resources.push((diff_resource_name(name), Vec::new()));
+        } else if line.starts_with("--- ") || line.starts_with("+++ ") {
+            continue;
+        } else if let Some((_, lines)) = resources.last_mut() {
+            lines.push(line);
+        } else if !line.trim().is_empty() {
+            other.push(line.to_string());
+        }
+    }
+
+    if resources.is_empty() {
+        if other.is_empty() {
+            return "☸️  diff: no changes".to_string();
+        }
+        return other.join("\n");
+    }
+
+    let mut out = vec![format!("☸️  diff: {} resources changed", resources.len())];
+    for (name, lines) in &resources {
+        let changes = diff_field_changes(lines);
+        if changes.is_empty() {
+            out.push(format!("{}: metadata only", name));
+            continue;
+        }
+        out.push(format!("{}:", name));
+        for change in changes.iter().take(DIFF_MAX_FIELDS) {
+            out.push(format!("  {}", crate::utils::truncate(change, 160)));
+        }
+        if changes.len() > DIFF_MAX_FIELDS {
+            out.push(format!("  ... +{} more", changes.len() - DIFF_MAX_FIELDS));
+        }
+    }
+    out.extend(other);
+    out.join("\n")
+}
+
+fn kubectl_describe(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("Running: kubectl describe {}", args.join(" "));
+    }
+    let output = Command::new("kubectl")
+        .arg("describe")
+        .args(args)
+        .output()
+        .context("Failed to run kubectl describe")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if !output.status.success() {
+        eprint!("{}", stderr);
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let rtk = filter_kubectl_describe(&raw);
+    println!("{}", rtk);
+    timer.track(
+        &format!("kubectl describe {}", args.join(" ")),
+        &format!("rtk kubectl describe {}", args.join(" ")),
+        &raw,
+        &rtk,
+    );
+    Ok(())
+}
+
+fn kubectl_events(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("kubectl get events -o json {}", args.join(" "));
+    }
+    let output = Command::new("kubectl")
+        .args(["get", "events", "-o", "json"])
+        .args(args)
+        .output()
+        .context("Failed to run kubectl get events")?;
+
+    if !output.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    let rtk = format_kubectl_events(&raw);
+    println!("{}", rtk);
+    timer.track("kubectl get events", "rtk kubectl get events", &raw, &rtk);
+    Ok(())
+}
+
+/// `kubectl apply` / `kubectl diff` share capture-and-format plumbing
+fn kubectl_apply_or_diff(subcommand: &str, args: &[String], verbose: u8) -> Result<()> {
+    // Explicit output formats (apply -o yaml) are for the user to read as-is
+    if args.iter().any(|a| a == "-o" || a.starts_with("--output")) {
+        let os_args: Vec<OsString> = std::iter::once(OsString::from(subcommand))
+            .chain(args.iter().map(OsString::from))
+            .collect();
+        return run_kubectl_passthrough(&os_args, verbose);
+    }
+
+    let timer = tracking::TimedExecution::start();
+
+    let output = Command::new("kubectl")
+        .arg(subcommand)
+        .args(args)
+        .output()
+        .context(format!("Failed to run kubectl {}", subcommand))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}{}", stdout, stderr);
+
+    let rtk = if subcommand == "diff" {
+        format_kubectl_diff(&stdout)
+    } else {
+        format_kubectl_apply(&stdout)
+    };
+    if !rtk.is_empty() {
+        println!("{}", rtk);
+    }
+    if !stderr.trim().is_empty() {
+        eprintln!("{}", stderr.trim());
+    }
+    timer.track(
+        &format!("kubectl {} {}", subcommand, args.join(" ")),
+        &format!("rtk kubectl {} {}", subcommand, args.join(" ")),
+        &raw,
+        &rtk,
+    );
+
+    // `kubectl diff` exits 1 when differences exist; keep that contract
+    if !output.status.success() {
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
 /// Runs an unsupported kubectl subcommand by passing it through directly
 pub fn run_kubectl_passthrough(args: &[OsString], verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
@@ -1001,6 +2325,11 @@ pub fn run_kubectl_passthrough(args: &[OsString], verbose: u8) -> Result<()> {
 mod tests {
     use super::*;
 
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
     // ── kubectl Option handling ──────────────────────────────────
 
     #[test]
@@ -1512,4 +2841,403 @@ api-1  | Connected to database";
     fn test_compact_memory_passthrough() {
         assert_eq!(compact_memory("4Gi"), "4Gi");
     }
+
+    // ── docker build / pull / push / inspect ─────────────────────
+
+    #[test]
+    fn test_docker_build_buildkit_success() {
+        let raw = "#0 building with \"default\" instance using docker driver\n\
+#1 [internal] load build definition from Dockerfile\n\
+#1 DONE 0.0s\n\
+#5 [1/3] FROM docker.io/library/rust:1.80\n\
+#5 CACHED\n\
+#6 [2/3] COPY . .\n\
+#6 DONE 0.3s\n\
+#7 [3/3] RUN cargo build --release\n\
+#7 0.512    Compiling rtk v0.26.0\n\
+#7 DONE 41.2s\n\
+#8 exporting to image\n\
+#8 naming to docker.io/library/rtk:dev done\n\
+#8 DONE 0.1s\n";
+        let out = format_docker_build(raw, true);
+        assert_eq!(out, "🐳 Build ok: 3 steps (1 cached, 2 rebuilt) → rtk:dev");
+    }
+
+    #[test]
+    fn test_docker_build_buildkit_failure() {
+        let raw = "#5 [1/2] FROM docker.io/library/alpine\n\
+#5 CACHED\n\
+#6 [2/2] RUN make\n\
+#6 0.201 make: *** No rule to make target 'all'.  Stop.\n\
+#6 ERROR: process \"/bin/sh -c make\" did not complete successfully: exit code: 2\n\
+------\n\
+Dockerfile:3\n\
+ >>> RUN make\n\
+ERROR: failed to solve: process \"/bin/sh -c make\" did not complete successfully: exit code: 2\n";
+        let out = format_docker_build(raw, false);
+        assert!(out.starts_with("🐳 Build failed: 2 steps (1 cached, 0 rebuilt)"));
+        assert!(out.contains("❌ [2/2] RUN make"));
+        assert!(out.contains("No rule to make target"));
+        assert!(out.contains(">>> RUN make"));
+        assert!(out.contains("ERROR: failed to solve"));
+    }
+
+    #[test]
+    fn test_docker_build_classic() {
+        let raw = "Step 1/3 : FROM alpine\n ---> a24bb4013296\n\
+Step 2/3 : COPY . /app\n ---> Using cache\n ---> 1b2c3d4e5f60\n\
+Step 3/3 : RUN ls /app\n ---> Running in 0a1b2c3d\nMakefile\n\
+Removing intermediate container 0a1b2c3d\n ---> 9f8e7d6c5b4a\n\
+Successfully built 9f8e7d6c5b4a\nSuccessfully tagged app:latest\n";
+        let out = format_docker_build(raw, true);
+        assert_eq!(
+            out,
+            "🐳 Build ok: 3 steps (1 cached, 2 rebuilt) → app:latest"
+        );
+    }
+
+    #[test]
+    fn test_docker_pull_single_line() {
+        let raw = "Using default tag: latest\n\
+latest: Pulling from library/nginx\n\
+a2abf6c4d29d: Pulling fs layer\n\
+a9edb18cadd1: Pulling fs layer\n\
+a2abf6c4d29d: Downloading  31.4MB/31.4MB\n\
+a2abf6c4d29d: Pull complete\n\
+a9edb18cadd1: Pull complete\n\
+Digest: sha256:0d17b565c37bcbd895e9d92315a05c1c3c9a29f762b011a10c54a66cd53c9b31\n\
+Status: Downloaded newer image for nginx:latest\n\
+docker.io/library/nginx:latest\n";
+        assert_eq!(
+            format_docker_pull(raw),
+            "⬇ pulled nginx:latest (2 layers, sha256:0d17b565c37b)"
+        );
+    }
+
+    #[test]
+    fn test_docker_pull_up_to_date_and_error() {
+        let raw = "latest: Pulling from library/alpine\n\
+Digest: sha256:abc\nStatus: Image is up to date for alpine:latest\n";
+        assert_eq!(format_docker_pull(raw), "✓ alpine:latest up to date");
+
+        let err = "Error response from daemon: manifest for nope:latest not found\n";
+        assert_eq!(format_docker_pull(err), err.trim());
+    }
+
+    #[test]
+    fn test_docker_push_single_line() {
+        let raw = "The push refers to repository [ghcr.io/acme/api]\n\
+5f70bf18a086: Preparing\n\
+3e207b409db3: Pushing  1.2MB/4MB\n\
+3e207b409db3: Pushed\n\
+5f70bf18a086: Layer already exists\n\
+v1.2: digest: sha256:4c1e5b1d2f3a4b5c6d7e8f90 size: 1573\n";
+        assert_eq!(
+            format_docker_push(raw),
+            "⬆ pushed ghcr.io/acme/api:v1.2 (1 pushed, 1 existing, sha256:4c1e5b1d2f3a)"
+        );
+    }
+
+    #[test]
+    fn test_docker_run_interactive_flag_clusters() {
+        assert!(is_interactive_run(&args(&["-it", "alpine"])));
+        assert!(is_interactive_run(&args(&["-itd", "alpine"])));
+        assert!(is_interactive_run(&args(&["--rm", "-dit", "alpine"])));
+        assert!(is_interactive_run(&args(&["--tty", "alpine"])));
+        assert!(!is_interactive_run(&args(&["-d", "--rm", "alpine"])));
+        assert!(!is_interactive_run(&args(&["--init", "alpine"])));
+    }
+
+    #[test]
+    fn test_docker_run_pull_lines_detected() {
+        assert!(is_layer_progress_line(
+            "Unable to find image 'redis:7' locally"
+        ));
+        assert!(is_layer_progress_line("7: Pulling from library/redis"));
+        assert!(is_layer_progress_line(
+            "1f7ce2fa46ab: Extracting  1.2MB/3MB"
+        ));
+        assert!(!is_layer_progress_line("Ready to accept connections"));
+    }
+
+    #[test]
+    fn test_docker_inspect_container_masks_env() {
+        let json = serde_json::json!([{
+            "Id": "3f4e5d6c7b8a9f0e1d2c",
+            "Name": "/api",
+            "RestartCount": 2,
+            "State": {
+                "Status": "running",
+                "Health": {
+                    "Status": "unhealthy",
+                    "FailingStreak": 3,
+                    "Log": [{"Output": "curl: (7) Failed to connect\n"}]
+                }
+            },
+            "Config": {
+                "Image": "acme/api:1.4",
+                "Cmd": ["node", "server.js"],
+                "Env": ["DATABASE_URL=postgres://user:secret@db/app", "PORT=8080"]
+            },
+            "Mounts": [
+                {"Type": "volume", "Name": "pgdata", "Destination": "/data", "RW": true},
+                {"Type": "bind", "Source": "/etc/app", "Destination": "/config", "RW": false}
+            ],
+            "NetworkSettings": {
+                "Ports": {"8080/tcp": [{"HostIp": "0.0.0.0", "HostPort": "80"}]},
+                "Networks": {"bridge": {"IPAddress": "172.17.0.2"}}
+            }
+        }]);
+        let out = format_docker_inspect(&json.to_string());
+        assert!(out.starts_with("📦 api (3f4e5d6c7b8a) running (unhealthy), 2 restarts"));
+        assert!(out.contains("image: acme/api:1.4"));
+        assert!(out.contains("cmd: node server.js"));
+        assert!(out.contains("ports: 8080/tcp→80"));
+        assert!(out.contains("vol:pgdata→/data, /etc/app→/config (ro)"));
+        assert!(out.contains("env: DATABASE_URL=***, PORT=***"));
+        assert!(!out.contains("secret"));
+        assert!(out.contains("health: unhealthy (3 failing) curl: (7) Failed to connect"));
+    }
+
+    #[test]
+    fn test_docker_inspect_image() {
+        let json = serde_json::json!([{
+            "Id": "sha256:9c7a54a9a43cca047013b82af109fe963fde787f63f9e016fdc3384500c2823d",
+            "RepoTags": ["redis:7"],
+            "Created": "2026-09-01T10:00:00Z",
+            "Size": 138412032u64,
+            "Os": "linux",
+            "Architecture": "amd64",
+            "Config": {
+                "Cmd": ["redis-server"],
+                "Entrypoint": ["docker-entrypoint.sh"],
+                "ExposedPorts": {"6379/tcp": {}},
+                "Env": ["REDIS_VERSION=7.2.4"]
+            },
+            "RootFS": {"Layers": ["sha256:a", "sha256:b"]}
+        }]);
+        let out = format_docker_inspect(&json.to_string());
+        assert!(out.starts_with("🖼 redis:7 (9c7a54a9a43c) 132MB, linux/amd64"));
+        assert!(out.contains("cmd: docker-entrypoint.sh redis-server"));
+        assert!(out.contains("ports: 6379/tcp"));
+        assert!(out.contains("env: REDIS_VERSION=***"));
+        assert!(out.contains("layers: 2, created 2026-09-01"));
+    }
+
+    #[test]
+    fn test_docker_inspect_invalid_json_passthrough() {
+        assert_eq!(format_docker_inspect("not json"), "not json");
+    }
+
+    // ── kubectl describe / events / apply / diff ─────────────────
+
+    const DESCRIBE_POD: &str = "Name:             api-7d9f8-x2k4p
+Namespace:        default
+Priority:         0
+Service Account:  default
+Node:             worker-1/10.0.0.12
+Start Time:       Mon, 12 Oct 2026 10:00:00 +0000
+Labels:           app=api
+                  pod-template-hash=7d9f8
+Annotations:      <none>
+Status:           Running
+IP:               10.244.1.7
+Containers:
+  api:
+    Container ID:   containerd://4f5e6d
+    Image:          acme/api:1.4
+    Image ID:       docker.io/acme/api@sha256:abc
+    Port:           8080/TCP
+    Host Port:      0/TCP
+    State:          Waiting
+      Reason:       CrashLoopBackOff
+    Last State:     Terminated
+      Reason:       OOMKilled
+      Exit Code:    137
+      Started:      Mon, 12 Oct 2026 10:04:00 +0000
+      Finished:     Mon, 12 Oct 2026 10:04:09 +0000
+    Ready:          False
+    Restart Count:  6
+    Environment:
+      DATABASE_URL:  <set to the key 'url' in secret 'db'>
+    Mounts:
+      /var/run/secrets/kubernetes.io/serviceaccount from kube-api-access (ro)
+Conditions:
+  Type              Status
+  Initialized       True
+  Ready             False
+  ContainersReady   False
+  PodScheduled      True
+Volumes:
+  kube-api-access:
+    Type:                    Projected
+QoS Class:                   Burstable
+Node-Selectors:              <none>
+Tolerations:                 node.kubernetes.io/not-ready:NoExecute op=Exists for 300s
+Events:
+  Type     Reason     Age                  From               Message
+  ----     ------     ----                 ----               -------
+  Normal   Scheduled  10m                  default-scheduler  Successfully assigned default/api-7d9f8-x2k4p to worker-1
+  Normal   Pulled     8m (x5 over 10m)     kubelet            Container image \"acme/api:1.4\" already present on machine
+  Normal   Created    8m (x5 over 10m)     kubelet            Created container api
+  Warning  BackOff    2m (x20 over 9m)     kubelet            Back-off restarting failed container api in pod api-7d9f8-x2k4p
+  Warning  BackOff    1m                   kubelet            Back-off restarting failed container api in pod api-7d9f8-x2k4p
+";
+
+    #[test]
+    fn test_describe_keeps_states_and_reasons() {
+        let out = filter_kubectl_describe(DESCRIBE_POD);
+        assert!(out.contains("Status:           Running"));
+        assert!(out.contains("Reason:       CrashLoopBackOff"));
+        assert!(out.contains("Last State:     Terminated"));
+        assert!(out.contains("Reason:       OOMKilled"));
+        assert!(out.contains("Exit Code:    137"));
+        assert!(out.contains("Restart Count:  6"));
+        assert!(out.contains(
+            "Conditions: Initialized=True, Ready=False, ContainersReady=False, PodScheduled=True"
+        ));
+    }
+
+    #[test]
+    fn test_describe_drops_noise() {
+        let out = filter_kubectl_describe(DESCRIBE_POD);
+        for noise in [
+            "pod-template-hash",
+            "Container ID",
+            "Image ID",
+            "DATABASE_URL",
+            "serviceaccount",
+            "Tolerations",
+            "QoS Class",
+            "Projected",
+            "Started:",
+        ] {
+            assert!(!out.contains(noise), "should drop {noise}:\n{out}");
+        }
+        assert!(out.len() < DESCRIBE_POD.len() / 2);
+    }
+
+    #[test]
+    fn test_describe_groups_events() {
+        let out = filter_kubectl_describe(DESCRIBE_POD);
+        assert!(out.contains("Events (21 warning):"));
+        assert!(out.contains(
+            "  ⚠ BackOff ×21: Back-off restarting failed container api in pod api-7d9f8-x2k4p"
+        ));
+        assert!(out.contains("  ✓ Scheduled, Pulled ×5, Created ×5"));
+    }
+
+    #[test]
+    fn test_describe_no_events() {
+        let out = filter_kubectl_describe("Name: cfg\nNamespace: default\n\nEvents:  <none>\n");
+        assert_eq!(out, "Name: cfg\nNamespace: default\nEvents: none");
+    }
+
+    #[test]
+    fn test_kubectl_events_grouped_by_object() {
+        let json = serde_json::json!({"items": [
+            {"type": "Normal", "reason": "Pulled", "message": "pulled", "count": 2,
+             "lastTimestamp": "2026-10-12T10:05:00Z",
+             "involvedObject": {"kind": "Pod", "name": "web-1", "namespace": "default"}},
+            {"type": "Warning", "reason": "FailedMount", "message": "secret \"tls\" not found", "count": 4,
+             "lastTimestamp": "2026-10-12T10:01:00Z",
+             "involvedObject": {"kind": "Pod", "name": "api-1", "namespace": "default"}},
+            {"type": "Warning", "reason": "FailedMount", "message": "secret \"tls\" not found",
+             "series": {"count": 3},
+             "eventTime": "2026-10-12T10:02:00Z",
+             "involvedObject": {"kind": "Pod", "name": "api-1", "namespace": "default"}}
+        ]});
+        let out = format_kubectl_events(&json.to_string());
+        assert_eq!(
+            out,
+            "☸️  3 events (2 warning) across 2 objects\n\
+Pod/api-1 -n default:\n  ⚠ FailedMount ×7: secret \"tls\" not found\n\
+Pod/web-1 -n default:\n  ✓ Pulled ×2"
+        );
+    }
+
+    #[test]
+    fn test_kubectl_events_empty() {
+        assert_eq!(format_kubectl_events(r#"{"items": []}"#), "☸️  No events");
+    }
+
+    #[test]
+    fn test_kubectl_apply_tallies() {
+        let raw = "namespace/app unchanged\n\
+configmap/api-config configured\n\
+deployment.apps/api configured\n\
+service/api unchanged\n\
+ingress.networking.k8s.io/api created\n";
+        assert_eq!(
+            format_kubectl_apply(raw),
+            concat!(
+                "☸️  apply: 2 unchanged, 2 configured, 1 created\n",
+                "  configured: configmap/api-config, deployment.apps/api\n",
+                "  created: ingress.networking.k8s.io/api"
+            )
+        );
+    }
+
+    #[test]
+    fn test_kubectl_apply_dry_run_and_errors() {
+        let raw = "deployment.apps/api configured (server dry run)\n\
+Error from server (NotFound): namespaces \"nope\" not found\n";
+        let out = format_kubectl_apply(raw);
+        assert!(out.starts_with("☸️  apply (dry run): 1 configured"));
+        assert!(out.contains("Error from server (NotFound)"));
+    }
+
+    #[test]
+    fn test_kubectl_diff_field_level() {
+        let raw = "diff -u -N /tmp/LIVE-1/apps.v1.Deployment.default.api /tmp/MERGED-1/apps.v1.Deployment.default.api
+--- /tmp/LIVE-1/apps.v1.Deployment.default.api\t2026-10-12 10:00:00
++++ /tmp/MERGED-1/apps.v1.Deployment.default.api\t2026-10-12 10:00:00
+@@ -6,7 +6,7 @@
+   creationTimestamp: \"2026-01-01T00:00:00Z\"
+-  generation: 3
++  generation: 4
+   name: api
+ spec:
+-  replicas: 2
++  replicas: 3
+   template:
+     spec:
+       containers:
+-      - image: acme/api:1.3
++      - image: acme/api:1.4
+         name: api
+diff -u -N /tmp/LIVE-1/v1.ConfigMap.default.api-config /tmp/MERGED-1/v1.ConfigMap.default.api-config
+--- /tmp/LIVE-1/v1.ConfigMap.default.api-config
++++ /tmp/MERGED-1/v1.ConfigMap.default.api-config
+@@ -1,4 +1,5 @@
+ data:
+   LOG_LEVEL: info
++  FEATURE_X: \"true\"
+ kind: ConfigMap
+";
+        assert_eq!(
+            format_kubectl_diff(raw),
+            concat!(
+                "☸️  diff: 2 resources changed\n",
+                "Deployment default/api:\n",
+                "  ~ spec.replicas: 2 → 3\n",
+                "  ~ spec.template.spec.containers[].image: acme/api:1.3 → acme/api:1.4\n",
+                "ConfigMap default/api-config:\n",
+                "  + data.FEATURE_X: \"true\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_kubectl_diff_no_changes() {
+        assert_eq!(format_kubectl_diff(""), "☸️  diff: no changes");
+    }
+
+    #[test]
+    fn test_diff_resource_name_cluster_scoped() {
+        assert_eq!(
+            diff_resource_name("/tmp/LIVE-1/rbac.authorization.k8s.io.v1.ClusterRole..view"),
+            "ClusterRole view"
+        );
+    }
 }
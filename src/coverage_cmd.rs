@@ -0,0 +1,1117 @@
+//! Coverage report summarizer.
+//!
+//! `rtk coverage [path]` reads lcov, Cobertura XML, istanbul JSON
+//! (`coverage-summary.json` / `coverage-final.json`), coverage.py text and
+//! JSON, Go `coverage.out` and `cargo llvm-cov` tables. It prints overall
+//! line / branch coverage, the least-covered files, uncovered line ranges
+//! of files changed on the current branch, and the delta against a baseline
+//! saved with `--save-baseline`.
+
+use crate::tracking::{self, CoverageBaseline, Tracker};
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Reports looked up when no path is given (the most recent one wins)
+const WELL_KNOWN_REPORTS: &[&str] = &[
+    "lcov.info",
+    "coverage/lcov.info",
+    "target/llvm-cov/lcov.info",
+    "coverage/coverage-summary.json",
+    "coverage/coverage-final.json",
+    "coverage/cobertura-coverage.xml",
+    "coverage.xml",
+    "cobertura.xml",
+    "target/site/cobertura/coverage.xml",
+    "coverage.json",
+    "coverage.out",
+    "cover.out",
+];
+
+/// Uncovered line ranges listed per changed file
+const MAX_RANGES: usize = 12;
+
+/// Changed files listed
+const MAX_CHANGED_FILES: usize = 15;
+
+/// Files listed under "Coverage dropped" in the baseline delta
+const MAX_DROPPED_FILES: usize = 5;
+
+lazy_static! {
+    static ref GO_BLOCK: Regex = Regex::new(r"^(.+):(\d+)\.\d+,(\d+)\.\d+ (\d+) (\d+)$").unwrap();
+    static ref COVERAGE_PY_HEADER: Regex = Regex::new(r"^Name\s+Stmts\s+Miss\b").unwrap();
+    static ref LLVM_COV_HEADER: Regex = Regex::new(r"^Filename\s+Regions\b").unwrap();
+    static ref COLUMN_SPLIT: Regex = Regex::new(r"\s{2,}").unwrap();
+    static ref LLVM_UNCOVERED: Regex = Regex::new(r"^(\S+): ([\d, ]+)$").unwrap();
+    static ref CONDITION_COVERAGE: Regex = Regex::new(r"\((\d+)/(\d+)\)").unwrap();
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct FileCoverage {
+    path: String,
+    lines_total: usize,
+    lines_covered: usize,
+    branches_total: usize,
+    branches_covered: usize,
+    /// Uncovered line numbers (sorted), when the format lists them
+    uncovered: Vec<usize>,
+}
+
+impl FileCoverage {
+    fn new(path: &str) -> Self {
+        FileCoverage {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn line_pct(&self) -> f64 {
+        pct(self.lines_covered, self.lines_total)
+    }
+}
+
+#[derive(Debug)]
+struct CoverageReport {
+    format: &'static str,
+    /// "lines", or "statements" for Go and coverage.py
+    unit: &'static str,
+    files: Vec<FileCoverage>,
+}
+
+impl CoverageReport {
+    fn lines(&self) -> (usize, usize) {
+        (
+            self.files.iter().map(|f| f.lines_covered).sum(),
+            self.files.iter().map(|f| f.lines_total).sum(),
+        )
+    }
+
+    fn branches(&self) -> (usize, usize) {
+        (
+            self.files.iter().map(|f| f.branches_covered).sum(),
+            self.files.iter().map(|f| f.branches_total).sum(),
+        )
+    }
+}
+
+fn pct(covered: usize, total: usize) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        covered as f64 * 100.0 / total as f64
+    }
+}
+
+pub fn run(path: Option<&str>, top: usize, save_baseline: bool, verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let (label, content) = match path {
+        Some("-") => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .context("Failed to read coverage report from stdin")?;
+            ("-".to_string(), input)
+        }
+        _ => {
+            let file = resolve_report(path)?;
+            if verbose > 0 {
+                eprintln!("Reading coverage report {}", file.display());
+            }
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            (file.display().to_string(), content)
+        }
+    };
+
+    let cwd = std::env::current_dir()
+        .map(|d| format!("{}/", d.display()))
+        .unwrap_or_default();
+    let Some(report) = parse_report(&content, &cwd, go_module().as_deref()) else {
+        bail!(
+            "Unrecognized coverage format in {} (expected lcov, Cobertura XML, istanbul JSON, \
+             coverage.py text/JSON, Go coverage.out or cargo llvm-cov output)",
+            label
+        );
+    };
+    if verbose > 0 {
+        eprintln!(
+            "Detected {} report ({} files)",
+            report.format,
+            report.files.len()
+        );
+    }
+
+    let changed = changed_files();
+    let project = tracking::detect_project_root();
+    let tracker = Tracker::new().ok();
+    let baseline = tracker
+        .as_ref()
+        .and_then(|t| t.get_coverage_baseline(&project).ok().flatten());
+
+    let mut filtered = format_report(&report, top, changed.as_ref(), baseline.as_ref());
+
+    if save_baseline {
+        let saved = CoverageBaseline {
+            commit_sha: current_commit(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            lines: report.lines(),
+            branches: report.branches(),
+            files: report
+                .files
+                .iter()
+                .map(|f| (f.path.clone(), f.lines_covered, f.lines_total))
+                .collect(),
+        };
+        let tracker = tracker.context("Failed to open the rtk database")?;
+        tracker.save_coverage_baseline(&project, &saved)?;
+        filtered.push_str(&format!(
+            "\nBaseline saved ({:.1}% {})",
+            pct(saved.lines.0, saved.lines.1),
+            report.unit
+        ));
+    }
+
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("cat {}", label),
+        &format!("rtk coverage {}", path.unwrap_or("")),
+        &content,
+        &filtered,
+    );
+    Ok(())
+}
+
+/// Report file from an explicit path, a directory, or the well-known locations
+fn resolve_report(path: Option<&str>) -> Result<PathBuf> {
+    let dir = match path {
+        Some(p) if !Path::new(p).is_dir() => return Ok(PathBuf::from(p)),
+        Some(p) => PathBuf::from(p),
+        None => PathBuf::from("."),
+    };
+    WELL_KNOWN_REPORTS
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|p| p.is_file())
+        .max_by_key(|p| p.metadata().and_then(|m| m.modified()).ok())
+        .with_context(|| {
+            format!(
+                "No coverage report found in {} (looked for {})",
+                dir.display(),
+                WELL_KNOWN_REPORTS.join(", ")
+            )
+        })
+}
+
+/// Detect the format from the content and parse it
+fn parse_report(content: &str, cwd: &str, go_module: Option<&str>) -> Option<CoverageReport> {
+    let trimmed = content.trim_start();
+    let first_line = trimmed.lines().next().unwrap_or("");
+
+    let (format, unit, mut files) = if first_line.starts_with("mode: ") {
+        ("go", "statements", parse_go(trimmed, go_module))
+    } else if first_line.starts_with("TN:") || first_line.starts_with("SF:") {
+        ("lcov", "lines", parse_lcov(trimmed))
+    } else if trimmed.starts_with('<') {
+        ("cobertura", "lines", parse_cobertura(trimmed, cwd)?)
+    } else if trimmed.starts_with('{') {
+        let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+        if json.get("totals").is_some() && json.get("files").is_some() {
+            ("coverage.py", "statements", parse_coverage_py_json(&json))
+        } else if json.get("total").is_some() {
+            ("istanbul", "lines", parse_istanbul_summary(&json))
+        } else {
+            ("istanbul", "lines", parse_istanbul_final(&json)?)
+        }
+    } else if trimmed.lines().any(|l| COVERAGE_PY_HEADER.is_match(l)) {
+        ("coverage.py", "statements", parse_coverage_py_text(trimmed))
+    } else if trimmed.lines().any(|l| LLVM_COV_HEADER.is_match(l)) {
+        ("llvm-cov", "lines", parse_llvm_cov(trimmed))
+    } else {
+        return None;
+    };
+
+    for file in &mut files {
+        file.path = relative_path(&file.path, cwd);
+        file.uncovered.sort_unstable();
+        file.uncovered.dedup();
+    }
+    Some(CoverageReport {
+        format,
+        unit,
+        files,
+    })
+}
+
+fn relative_path(path: &str, cwd: &str) -> String {
+    let path = path.strip_prefix("file://").unwrap_or(path);
+    path.strip_prefix(cwd)
+        .unwrap_or(path)
+        .trim_start_matches("./")
+        .to_string()
+}
+
+/// lcov: `SF:` / `DA:line,hits` / `BRDA:line,block,branch,taken` / `LF` `LH` `BRF` `BRH`
+fn parse_lcov(content: &str) -> Vec<FileCoverage> {
+    let mut files = Vec::new();
+    let mut current: Option<FileCoverage> = None;
+    let mut summary = (None, None, None, None);
+
+    for line in content.lines() {
+        let line = line.trim();
+        let (key, value) = line.split_once(':').unwrap_or((line, ""));
+        let Some(file) = current.as_mut() else {
+            if key == "SF" {
+                current = Some(FileCoverage::new(value));
+            }
+            continue;
+        };
+        match key {
+            "DA" => {
+                let mut parts = value.split(',');
+                let line_no = parts.next().and_then(|n| n.parse().ok());
+                let hits = parts.next().and_then(|h| h.parse::<u64>().ok());
+                if let (Some(line_no), Some(hits)) = (line_no, hits) {
+                    file.lines_total += 1;
+                    if hits > 0 {
+                        file.lines_covered += 1;
+                    } else {
+                        file.uncovered.push(line_no);
+                    }
+                }
+            }
+            "BRDA" => {
+                file.branches_total += 1;
+                let taken = value.rsplit(',').next().unwrap_or("-");
+                if taken != "-" && taken != "0" {
+                    file.branches_covered += 1;
+                }
+            }
+            "LF" => summary.0 = value.parse().ok(),
+            "LH" => summary.1 = value.parse().ok(),
+            "BRF" => summary.2 = value.parse().ok(),
+            "BRH" => summary.3 = value.parse().ok(),
+            "end_of_record" => {
+                let mut file = current.take().unwrap_or_default();
+                // Summary lines are authoritative when present (DA can be filtered)
+                file.lines_total = summary.0.unwrap_or(file.lines_total);
+                file.lines_covered = summary.1.unwrap_or(file.lines_covered);
+                file.branches_total = summary.2.unwrap_or(file.branches_total);
+                file.branches_covered = summary.3.unwrap_or(file.branches_covered);
+                files.push(file);
+                summary = (None, None, None, None);
+            }
+            _ => {}
+        }
+    }
+    if let Some(file) = current {
+        files.push(file);
+    }
+    merge_by_path(files)
+}
+
+/// Cobertura XML (coverage.py, gcovr, Cobertura, cargo-tarpaulin, dotnet coverlet)
+fn parse_cobertura(content: &str, cwd: &str) -> Option<Vec<FileCoverage>> {
+    let doc = roxmltree::Document::parse(content).ok()?;
+    let root = doc.root_element();
+    if !root.has_tag_name("coverage") {
+        return None;
+    }
+    // Filenames are relative to the first <source>
+    let source = root
+        .descendants()
+        .find(|n| n.has_tag_name("source"))
+        .and_then(|n| n.text())
+        .map(|s| relative_path(s.trim(), cwd))
+        .filter(|s| !s.is_empty() && s != "." && !s.starts_with('/'));
+
+    let mut files = Vec::new();
+    for class in root.descendants().filter(|n| n.has_tag_name("class")) {
+        let Some(filename) = class.attribute("filename") else {
+            continue;
+        };
+        let path = match &source {
+            Some(source) if !filename.starts_with('/') => {
+                format!("{}/{}", source.trim_end_matches('/'), filename)
+            }
+            _ => filename.to_string(),
+        };
+        let mut file = FileCoverage::new(&path);
+        for line in class
+            .descendants()
+            .filter(|n| {
+                n.has_tag_name("line") && n.parent().is_some_and(|p| p.has_tag_name("lines"))
+            })
+            .filter(|n| n.parent().and_then(|p| p.parent()) == Some(class))
+        {
+            let number: usize = line.attribute("number").and_then(|n| n.parse().ok())?;
+            let hits: u64 = line
+                .attribute("hits")
+                .and_then(|h| h.parse().ok())
+                .unwrap_or(0);
+            file.lines_total += 1;
+            if hits > 0 {
+                file.lines_covered += 1;
+            } else {
+                file.uncovered.push(number);
+            }
+            if line.attribute("branch") == Some("true") {
+                if let Some(caps) = line
+                    .attribute("condition-coverage")
+                    .and_then(|c| CONDITION_COVERAGE.captures(c))
+                {
+                    file.branches_covered += caps[1].parse::<usize>().unwrap_or(0);
+                    file.branches_total += caps[2].parse::<usize>().unwrap_or(0);
+                }
+            }
+        }
+        files.push(file);
+    }
+    // Java inner classes / Python modules can repeat a filename
+    Some(merge_by_path(files))
+}
+
+/// istanbul `coverage-summary.json`: per-file totals, no line detail
+fn parse_istanbul_summary(json: &serde_json::Value) -> Vec<FileCoverage> {
+    let count = |entry: &serde_json::Value, kind: &str, field: &str| {
+        entry[kind][field].as_u64().unwrap_or(0) as usize
+    };
+    json.as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(path, _)| path.as_str() != "total")
+        .map(|(path, entry)| FileCoverage {
+            path: path.clone(),
+            lines_total: count(entry, "lines", "total"),
+            lines_covered: count(entry, "lines", "covered"),
+            branches_total: count(entry, "branches", "total"),
+            branches_covered: count(entry, "branches", "covered"),
+            uncovered: Vec::new(),
+        })
+        .collect()
+}
+
+/// istanbul `coverage-final.json`: statement and branch hit maps per file
+fn parse_istanbul_final(json: &serde_json::Value) -> Option<Vec<FileCoverage>> {
+    let object = json.as_object()?;
+    if !object.values().any(|v| v.get("statementMap").is_some()) {
+        return None;
+    }
+    let mut files = Vec::new();
+    for (path, entry) in object {
+        let (Some(statements), Some(hits)) =
+            (entry["statementMap"].as_object(), entry["s"].as_object())
+        else {
+            continue;
+        };
+        // A line is covered when any statement starting on it ran
+        let mut lines: BTreeMap<usize, bool> = BTreeMap::new();
+        for (id, location) in statements {
+            let Some(line) = location["start"]["line"].as_u64() else {
+                continue;
+            };
+            let ran = hits.get(id).and_then(|h| h.as_u64()).unwrap_or(0) > 0;
+            *lines.entry(line as usize).or_insert(false) |= ran;
+        }
+        let mut file = FileCoverage::new(entry["path"].as_str().unwrap_or(path));
+        file.lines_total = lines.len();
+        file.lines_covered = lines.values().filter(|ran| **ran).count();
+        file.uncovered = lines
+            .iter()
+            .filter(|(_, ran)| !**ran)
+            .map(|(line, _)| *line)
+            .collect();
+        for counts in entry["b"].as_object().into_iter().flat_map(|b| b.values()) {
+            for count in counts.as_array().into_iter().flatten() {
+                file.branches_total += 1;
+                if count.as_u64().unwrap_or(0) > 0 {
+                    file.branches_covered += 1;
+                }
+            }
+        }
+        files.push(file);
+    }
+    Some(files)
+}
+
+/// coverage.py `coverage json`
+fn parse_coverage_py_json(json: &serde_json::Value) -> Vec<FileCoverage> {
+    json["files"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(path, entry)| {
+            let summary = &entry["summary"];
+            let count = |field: &str| summary[field].as_u64().unwrap_or(0) as usize;
+            FileCoverage {
+                path: path.clone(),
+                lines_total: count("num_statements"),
+                lines_covered: count("covered_lines"),
+                branches_total: count("num_branches"),
+                branches_covered: count("covered_branches"),
+                uncovered: entry["missing_lines"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|l| l.as_u64().map(|l| l as usize))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// coverage.py `coverage report [-m]` table
+fn parse_coverage_py_text(content: &str) -> Vec<FileCoverage> {
+    let mut files = Vec::new();
+    let mut in_table = false;
+    for line in content.lines() {
+        if COVERAGE_PY_HEADER.is_match(line) {
+            in_table = true;
+            continue;
+        }
+        let mut tokens = line.split_whitespace();
+        let Some(name) = tokens.next() else {
+            continue;
+        };
+        if !in_table || name.starts_with('-') || name == "TOTAL" {
+            continue;
+        }
+        let (Some(stmts), Some(miss)) = (
+            tokens.next().and_then(|t| t.parse::<usize>().ok()),
+            tokens.next().and_then(|t| t.parse::<usize>().ok()),
+        ) else {
+            continue;
+        };
+        // Branch columns (--branch) and the Cover column come before "Missing"
+        let missing: String = tokens
+            .skip_while(|t| !t.ends_with('%'))
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut file = FileCoverage::new(name);
+        file.lines_total = stmts;
+        file.lines_covered = stmts.saturating_sub(miss);
+        file.uncovered = parse_missing_ranges(&missing);
+        files.push(file);
+    }
+    files
+}
+
+/// `12-14, 30, 45->47` → [12, 13, 14, 30] (branch arcs are skipped)
+fn parse_missing_ranges(missing: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    for part in missing.split(',').map(str::trim) {
+        if part.contains("->") {
+            continue;
+        }
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    lines.extend(start..=end);
+                }
+            }
+            None => lines.extend(part.parse::<usize>().ok()),
+        }
+    }
+    lines
+}
+
+/// Go `coverage.out`: `file:startLine.col,endLine.col numStmts count`
+fn parse_go(content: &str, module: Option<&str>) -> Vec<FileCoverage> {
+    // The same block appears once per test binary with -coverpkg: keep the max count
+    let mut blocks: BTreeMap<(String, usize, usize), (usize, u64)> = BTreeMap::new();
+    for line in content.lines().skip(1) {
+        let Some(caps) = GO_BLOCK.captures(line.trim()) else {
+            continue;
+        };
+        let path = match module {
+            Some(module) => caps[1]
+                .strip_prefix(module)
+                .map(|p| p.trim_start_matches('/').to_string())
+                .unwrap_or_else(|| caps[1].to_string()),
+            None => caps[1].to_string(),
+        };
+        let key = (
+            path,
+            caps[2].parse().unwrap_or(0),
+            caps[3].parse().unwrap_or(0),
+        );
+        let stmts = caps[4].parse().unwrap_or(0);
+        let count = caps[5].parse().unwrap_or(0);
+        let entry = blocks.entry(key).or_insert((stmts, 0));
+        entry.1 = entry.1.max(count);
+    }
+
+    let mut by_file: BTreeMap<String, (FileCoverage, BTreeSet<usize>, BTreeSet<usize>)> =
+        BTreeMap::new();
+    for ((path, start, end), (stmts, count)) in blocks {
+        let (file, covered_lines, missed_lines) = by_file
+            .entry(path.clone())
+            .or_insert_with(|| (FileCoverage::new(&path), BTreeSet::new(), BTreeSet::new()));
+        file.lines_total += stmts;
+        if count > 0 {
+            file.lines_covered += stmts;
+            covered_lines.extend(start..=end);
+        } else {
+            missed_lines.extend(start..=end);
+        }
+    }
+    by_file
+        .into_values()
+        .map(|(mut file, covered, missed)| {
+            // A line shared by a covered and an uncovered block counts as covered
+            file.uncovered = missed.difference(&covered).copied().collect();
+            file
+        })
+        .collect()
+}
+
+/// `cargo llvm-cov` / `llvm-cov report` table, plus `--show-missing-lines`
+fn parse_llvm_cov(content: &str) -> Vec<FileCoverage> {
+    let mut files = Vec::new();
+    let mut columns: Vec<&str> = Vec::new();
+    let mut in_uncovered = false;
+
+    for line in content.lines() {
+        if LLVM_COV_HEADER.is_match(line) {
+            columns = COLUMN_SPLIT.split(line.trim()).collect();
+            continue;
+        }
+        if line.trim_start().starts_with("Uncovered Lines:") {
+            in_uncovered = true;
+            continue;
+        }
+        if in_uncovered {
+            if let Some(caps) = LLVM_UNCOVERED.captures(line.trim()) {
+                let lines = caps[2]
+                    .split(',')
+                    .filter_map(|l| l.trim().parse().ok())
+                    .collect::<Vec<usize>>();
+                if let Some(file) = files.iter_mut().find(|f: &&mut FileCoverage| {
+                    f.path == caps[1] || caps[1].ends_with(&format!("/{}", f.path))
+                }) {
+                    file.uncovered.extend(lines);
+                }
+            }
+            continue;
+        }
+
+        let values: Vec<&str> = line.split_whitespace().collect();
+        if columns.is_empty() || values.len() != columns.len() || values[0] == "TOTAL" {
+            continue;
+        }
+        let column = |name: &str| {
+            columns
+                .iter()
+                .position(|c| *c == name)
+                .and_then(|i| values[i].parse::<usize>().ok())
+        };
+        let Some(lines) = column("Lines") else {
+            continue;
+        };
+        let mut file = FileCoverage::new(values[0]);
+        file.lines_total = lines;
+        file.lines_covered = lines.saturating_sub(column("Missed Lines").unwrap_or(0));
+        if let Some(branches) = column("Branches") {
+            file.branches_total = branches;
+            file.branches_covered = branches.saturating_sub(column("Missed Branches").unwrap_or(0));
+        }
+        files.push(file);
+    }
+    files
+}
+
+fn merge_by_path(files: Vec<FileCoverage>) -> Vec<FileCoverage> {
+    let mut order: Vec<String> = Vec::new();
+    let mut merged: HashMap<String, FileCoverage> = HashMap::new();
+    for file in files {
+        match merged.get_mut(&file.path) {
+            Some(existing) => {
+                existing.lines_total += file.lines_total;
+                existing.lines_covered += file.lines_covered;
+                existing.branches_total += file.branches_total;
+                existing.branches_covered += file.branches_covered;
+                existing.uncovered.extend(file.uncovered);
+            }
+            None => {
+                order.push(file.path.clone());
+                merged.insert(file.path.clone(), file);
+            }
+        }
+    }
+    order
+        .into_iter()
+        .filter_map(|path| merged.remove(&path))
+        .collect()
+}
+
+/// Module path from `go.mod`, used to turn import paths into repo paths
+fn go_module() -> Option<String> {
+    let go_mod = std::fs::read_to_string("go.mod").ok()?;
+    go_mod
+        .lines()
+        .find_map(|l| l.strip_prefix("module "))
+        .map(|m| m.trim().to_string())
+}
+
+/// Files changed on this branch (committed since the merge base, staged,
+/// unstaged and untracked), with the base they were compared to
+struct ChangedFiles {
+    base: String,
+    files: Vec<String>,
+}
+
+fn changed_files() -> Option<ChangedFiles> {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let base = git(&["rev-parse", "--abbrev-ref", "origin/HEAD"])
+        .into_iter()
+        .chain(
+            ["origin/main", "origin/master", "main", "master"]
+                .iter()
+                .map(|b| b.to_string()),
+        )
+        .find(|b| git(&["rev-parse", "--verify", "--quiet", b]).is_some())?;
+    let merge_base = git(&["merge-base", "HEAD", &base])?;
+
+    let mut files: Vec<String> = git(&["diff", "--name-only", &merge_base])?
+        .lines()
+        .chain(
+            git(&["ls-files", "--others", "--exclude-standard"])
+                .unwrap_or_default()
+                .lines(),
+        )
+        .map(String::from)
+        .collect();
+    files.sort();
+    files.dedup();
+
+    // git paths are relative to the repository root, report paths to the cwd
+    let prefix = git(&["rev-parse", "--show-prefix"]).unwrap_or_default();
+    let files = files
+        .into_iter()
+        .map(|f| f.strip_prefix(&prefix).map(String::from).unwrap_or(f))
+        .collect();
+    Some(ChangedFiles { base, files })
+}
+
+fn current_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Same file when equal or when one is a path suffix of the other
+fn same_file(a: &str, b: &str) -> bool {
+    a == b || a.ends_with(&format!("/{}", b)) || b.ends_with(&format!("/{}", a))
+}
+
+/// [12, 13, 14, 30] → "12-14, 30"
+fn format_ranges(lines: &[usize]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut iter = lines.iter().copied().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end = iter.next().unwrap_or(end);
+        }
+        ranges.push(if start == end {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, end)
+        });
+    }
+    let extra = ranges.len().saturating_sub(MAX_RANGES);
+    ranges.truncate(MAX_RANGES);
+    let mut text = ranges.join(", ");
+    if extra > 0 {
+        text.push_str(&format!(" (+{} more)", extra));
+    }
+    text
+}
+
+fn format_report(
+    report: &CoverageReport,
+    top: usize,
+    changed: Option<&ChangedFiles>,
+    baseline: Option<&CoverageBaseline>,
+) -> String {
+    let (lines_covered, lines_total) = report.lines();
+    let (branches_covered, branches_total) = report.branches();
+
+    let mut header = format!(
+        "Coverage ({}, {} files): {} {:.1}% ({}/{})",
+        report.format,
+        report.files.len(),
+        report.unit,
+        pct(lines_covered, lines_total),
+        lines_covered,
+        lines_total
+    );
+    if branches_total > 0 {
+        header.push_str(&format!(
+            " · branches {:.1}% ({}/{})",
+            pct(branches_covered, branches_total),
+            branches_covered,
+            branches_total
+        ));
+    }
+    let mut out = vec![header];
+
+    // Least covered: incomplete files, lowest percentage then most missed lines
+    let mut incomplete: Vec<&FileCoverage> = report
+        .files
+        .iter()
+        .filter(|f| f.lines_total > 0 && f.lines_covered < f.lines_total)
+        .collect();
+    incomplete.sort_by(|a, b| {
+        a.line_pct()
+            .total_cmp(&b.line_pct())
+            .then((b.lines_total - b.lines_covered).cmp(&(a.lines_total - a.lines_covered)))
+    });
+    if !incomplete.is_empty() && top > 0 {
+        out.push(String::new());
+        out.push("Least covered:".to_string());
+        for file in incomplete.iter().take(top) {
+            out.push(format!(
+                "  {:>5.1}%  {} ({}/{})",
+                file.line_pct(),
+                file.path,
+                file.lines_covered,
+                file.lines_total
+            ));
+        }
+        if incomplete.len() > top {
+            out.push(format!(
+                "  ... +{} more files below 100%",
+                incomplete.len() - top
+            ));
+        }
+    }
+
+    if let Some(changed) = changed {
+        let touched: Vec<&FileCoverage> = report
+            .files
+            .iter()
+            .filter(|f| changed.files.iter().any(|c| same_file(&f.path, c)))
+            .collect();
+        if !touched.is_empty() {
+            out.push(String::new());
+            out.push(format!("Changed files (vs {}):", changed.base));
+            for file in touched.iter().take(MAX_CHANGED_FILES) {
+                let mut line = format!(
+                    "  {:>5.1}%  {} ({}/{})",
+                    file.line_pct(),
+                    file.path,
+                    file.lines_covered,
+                    file.lines_total
+                );
+                if !file.uncovered.is_empty() {
+                    line.push_str(&format!("  uncovered: {}", format_ranges(&file.uncovered)));
+                }
+                out.push(line);
+            }
+            if touched.len() > MAX_CHANGED_FILES {
+                out.push(format!(
+                    "  ... +{} more changed files",
+                    touched.len() - MAX_CHANGED_FILES
+                ));
+            }
+        }
+    }
+
+    if let Some(baseline) = baseline {
+        out.push(String::new());
+        out.push(format_delta(report, baseline));
+    }
+
+    out.join("\n")
+}
+
+fn format_delta(report: &CoverageReport, baseline: &CoverageBaseline) -> String {
+    let (lines_covered, lines_total) = report.lines();
+    let (branches_covered, branches_total) = report.branches();
+    let before = pct(baseline.lines.0, baseline.lines.1);
+    let now = pct(lines_covered, lines_total);
+
+    let since = if baseline.commit_sha.is_empty() {
+        baseline.timestamp.chars().take(10).collect::<String>()
+    } else {
+        baseline.commit_sha.clone()
+    };
+    let mut text = format!(
+        "Δ vs baseline ({}): {} {:+.1} pts ({:.1}% → {:.1}%)",
+        since,
+        report.unit,
+        now - before,
+        before,
+        now
+    );
+    if branches_total > 0 && baseline.branches.1 > 0 {
+        let before = pct(baseline.branches.0, baseline.branches.1);
+        let now = pct(branches_covered, branches_total);
+        text.push_str(&format!(" · branches {:+.1} pts", now - before));
+    }
+
+    let mut dropped: Vec<(&str, f64, f64)> = report
+        .files
+        .iter()
+        .filter_map(|file| {
+            let (_, covered, total) = baseline.files.iter().find(|(p, _, _)| *p == file.path)?;
+            let before = pct(*covered, *total);
+            (file.line_pct() + 0.05 < before).then_some((
+                file.path.as_str(),
+                before,
+                file.line_pct(),
+            ))
+        })
+        .collect();
+    dropped.sort_by(|a, b| (b.1 - b.2).total_cmp(&(a.1 - a.2)));
+    if !dropped.is_empty() {
+        text.push_str("\nCoverage dropped:");
+        for (path, before, now) in dropped.iter().take(MAX_DROPPED_FILES) {
+            text.push_str(&format!("\n  {} {:.1}% → {:.1}%", path, before, now));
+        }
+        if dropped.len() > MAX_DROPPED_FILES {
+            text.push_str(&format!(
+                "\n  ... +{} more files",
+                dropped.len() - MAX_DROPPED_FILES
+            ));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CWD: &str = "/home/me/repo/";
+
+    fn parse(content: &str) -> CoverageReport {
+        parse_report(content, CWD, Some("github.com/acme/shop")).expect("format not detected")
+    }
+
+    const LCOV: &str = "TN:
+SF:/home/me/repo/src/cart.rs
+DA:1,5
+DA:2,5
+DA:3,0
+DA:4,0
+DA:5,0
+DA:9,1
+BRDA:2,0,0,1
+BRDA:2,0,1,-
+LF:6
+LH:3
+BRF:2
+BRH:1
+end_of_record
+SF:/home/me/repo/src/user.rs
+DA:1,1
+DA:2,1
+LF:2
+LH:2
+end_of_record
+";
+
+    #[test]
+    fn test_parse_lcov() {
+        let report = parse(LCOV);
+        assert_eq!(report.format, "lcov");
+        assert_eq!(report.lines(), (5, 8));
+        assert_eq!(report.branches(), (1, 2));
+        assert_eq!(report.files[0].path, "src/cart.rs");
+        assert_eq!(report.files[0].uncovered, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_parse_cobertura() {
+        let xml = r#"<?xml version="1.0" ?>
+<coverage version="7.4.0" line-rate="0.6" branch-rate="0.5">
+  <sources><source>/home/me/repo/app</source></sources>
+  <packages><package name="app"><classes>
+    <class name="cart.py" filename="cart.py" line-rate="0.6">
+      <methods/>
+      <lines>
+        <line number="1" hits="1"/>
+        <line number="2" hits="1" branch="true" condition-coverage="50% (1/2)"/>
+        <line number="3" hits="0"/>
+        <line number="4" hits="0"/>
+        <line number="7" hits="1"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+        let report = parse(xml);
+        assert_eq!(report.format, "cobertura");
+        let file = &report.files[0];
+        assert_eq!(file.path, "app/cart.py");
+        assert_eq!((file.lines_covered, file.lines_total), (3, 5));
+        assert_eq!((file.branches_covered, file.branches_total), (1, 2));
+        assert_eq!(file.uncovered, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_parse_istanbul_summary_and_final() {
+        let summary = r#"{"total": {"lines": {"total": 30, "covered": 20, "skipped": 0, "pct": 66.67}},
+          "/home/me/repo/src/a.ts": {"lines": {"total": 10, "covered": 10}, "branches": {"total": 4, "covered": 2}},
+          "/home/me/repo/src/b.ts": {"lines": {"total": 20, "covered": 10}, "branches": {"total": 0, "covered": 0}}}"#;
+        let report = parse(summary);
+        assert_eq!(report.format, "istanbul");
+        assert_eq!(report.lines(), (20, 30));
+        assert_eq!(report.branches(), (2, 4));
+        assert_eq!(report.files[1].path, "src/b.ts");
+
+        let final_json = r#"{"/home/me/repo/src/a.ts": {"path": "/home/me/repo/src/a.ts",
+          "statementMap": {"0": {"start": {"line": 1}, "end": {"line": 1}},
+                           "1": {"start": {"line": 2}, "end": {"line": 2}},
+                           "2": {"start": {"line": 2}, "end": {"line": 2}},
+                           "3": {"start": {"line": 5}, "end": {"line": 6}}},
+          "s": {"0": 1, "1": 0, "2": 3, "3": 0},
+          "b": {"0": [1, 0]}}}"#;
+        let report = parse(final_json);
+        let file = &report.files[0];
+        assert_eq!((file.lines_covered, file.lines_total), (2, 3));
+        assert_eq!(file.uncovered, vec![5]);
+        assert_eq!((file.branches_covered, file.branches_total), (1, 2));
+    }
+
+    #[test]
+    fn test_parse_coverage_py_text_and_json() {
+        let text = "Name                 Stmts   Miss Branch BrPart  Cover   Missing
+----------------------------------------------------------------
+app/__init__.py          2      0      0      0   100%
+app/cart.py             20      5      6      2    70%   12-14, 30, 41->45, 50
+----------------------------------------------------------------
+TOTAL                   22      5      6      2    74%
+";
+        let report = parse(text);
+        assert_eq!(report.format, "coverage.py");
+        assert_eq!(report.lines(), (17, 22));
+        assert_eq!(report.files[1].uncovered, vec![12, 13, 14, 30, 50]);
+
+        let json = r#"{"meta": {"version": "7.4.0"}, "files": {"app/cart.py": {
+          "executed_lines": [1, 2], "missing_lines": [3, 4],
+          "summary": {"covered_lines": 2, "num_statements": 4, "num_branches": 2, "covered_branches": 1}}},
+          "totals": {"covered_lines": 2, "num_statements": 4}}"#;
+        let report = parse(json);
+        assert_eq!(report.lines(), (2, 4));
+        assert_eq!(report.branches(), (1, 2));
+        assert_eq!(report.files[0].uncovered, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_parse_go_coverage() {
+        let out = "mode: set
+github.com/acme/shop/cart/cart.go:10.30,12.2 2 1
+github.com/acme/shop/cart/cart.go:14.2,16.3 1 0
+github.com/acme/shop/cart/cart.go:14.2,16.3 1 1
+github.com/acme/shop/cart/cart.go:18.2,20.3 3 0
+github.com/acme/shop/user.go:5.20,7.2 1 1
+";
+        let report = parse(out);
+        assert_eq!(report.format, "go");
+        assert_eq!(report.unit, "statements");
+        let cart = &report.files[0];
+        assert_eq!(cart.path, "cart/cart.go");
+        // The -coverpkg duplicate of 14-16 counts once, as covered
+        assert_eq!((cart.lines_covered, cart.lines_total), (3, 6));
+        assert_eq!(cart.uncovered, vec![18, 19, 20]);
+    }
+
+    #[test]
+    fn test_parse_llvm_cov_table() {
+        let table = "Filename                      Regions    Missed Regions     Cover   Functions  Missed Functions  Executed       Lines      Missed Lines     Cover    Branches   Missed Branches     Cover
+-------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------
+src/lib.rs                         20                 4    80.00%           5                 1    80.00%          50                10    80.00%           4                 1    75.00%
+src/main.rs                        10                 0   100.00%           2                 0   100.00%          30                 0   100.00%           0                 0         -
+-------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------
+TOTAL                              30                 4    86.67%           7                 1    85.71%          80                10    87.50%           4                 1    75.00%
+
+Uncovered Lines:
+/home/me/repo/src/lib.rs: 12, 13, 14, 40
+";
+        let report = parse(table);
+        assert_eq!(report.format, "llvm-cov");
+        assert_eq!(report.lines(), (70, 80));
+        assert_eq!(report.branches(), (3, 4));
+        assert_eq!(report.files[0].uncovered, vec![12, 13, 14, 40]);
+    }
+
+    #[test]
+    fn test_format_report_with_changes_and_baseline() {
+        let report = parse(LCOV);
+        let changed = ChangedFiles {
+            base: "origin/main".to_string(),
+            files: vec!["src/cart.rs".to_string(), "README.md".to_string()],
+        };
+        let baseline = CoverageBaseline {
+            commit_sha: "abc1234".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            lines: (6, 8),
+            branches: (2, 2),
+            files: vec![("src/cart.rs".to_string(), 4, 6)],
+        };
+        assert_eq!(
+            format_report(&report, 10, Some(&changed), Some(&baseline)),
+            "Coverage (lcov, 2 files): lines 62.5% (5/8) · branches 50.0% (1/2)\n\
+             \n\
+             Least covered:\n   \
+             50.0%  src/cart.rs (3/6)\n\
+             \n\
+             Changed files (vs origin/main):\n   \
+             50.0%  src/cart.rs (3/6)  uncovered: 3-5\n\
+             \n\
+             Δ vs baseline (abc1234): lines -12.5 pts (75.0% → 62.5%) · branches -50.0 pts\n\
+             Coverage dropped:\n  \
+             src/cart.rs 66.7% → 50.0%"
+        );
+    }
+
+    #[test]
+    fn test_format_ranges() {
+        assert_eq!(format_ranges(&[3, 4, 5, 9, 11, 12]), "3-5, 9, 11-12");
+        let many: Vec<usize> = (0..40).map(|i| i * 2).collect();
+        assert!(format_ranges(&many).ends_with("(+28 more)"));
+    }
+
+    #[test]
+    fn test_coverage_savings() {
+        let mut lcov = String::new();
+        for f in 0..80 {
+            lcov.push_str(&format!("SF:/home/me/repo/src/module_{}.rs\n", f));
+            for l in 1..=40 {
+                lcov.push_str(&format!("DA:{},{}\n", l, if l % 7 == 0 { 0 } else { 3 }));
+            }
+            lcov.push_str("LF:40\nLH:35\nend_of_record\n");
+        }
+        let filtered = format_report(&parse(&lcov), 10, None, None);
+        let savings = 100.0 - (filtered.len() as f64 / lcov.len() as f64 * 100.0);
+        assert!(
+            savings >= 90.0,
+            "expected ≥90% savings, got {:.1}%",
+            savings
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_format() {
+        assert!(parse_report("hello world", CWD, None).is_none());
+    }
+}
@@ -49,9 +49,9 @@ pub fn category_avg_tokens(category: &str, subcmd: &str) -> usize {
 
 // Patterns ordered to match RTK_RULES indices exactly.
 const PATTERNS: &[&str] = &[
-    r"^git\s+(status|log|diff|show|add|commit|push|pull|branch|fetch|stash|worktree)",
+    r"^git\s+(status|log|diff|show|add|commit|push|pull|branch|fetch|stash|worktree|blame)",
     r"^gh\s+(pr|issue|run|repo|api)",
-    r"^cargo\s+(build|test|clippy|check|fmt)",
+    r"^cargo\s+(build|test|bench|clippy|check|fmt|tree)",
     r"^pnpm\s+(list|ls|outdated|install)",
     r"^npm\s+(run|exec)",
     r"^npx\s+",
@@ -66,12 +66,30 @@ const PATTERNS: &[&str] = &[
     r"^(pnpm\s+|npx\s+)?(vitest|jest|test)(\s|$)",
     r"^(npx\s+|pnpm\s+)?playwright",
     r"^(npx\s+|pnpm\s+)?prisma",
-    r"^docker\s+(ps|images|logs)",
-    r"^kubectl\s+(get|logs)",
+    r"^docker\s+(ps|images|logs|build|run|inspect|pull|push)",
+    r"^kubectl\s+(get|logs|describe|apply|diff)",
     r"^(python3?\s+-m\s+)?mypy(\s|$)",
     r"^curl\s+",
     r"^wget\s+",
     r"^terragrunt\s+(plan|apply|init|output|validate|state)",
+    r"^glab\s+(mr|issue|ci)",
+    r"^(?:\./)?gradlew?\s+",
+    r"^(?:\./)?mvnw?\s+",
+    r"^dotnet\s+(build|test|restore|publish|pack|clean)(?:\s|$)",
+    // Builds only (bare, `all`, flags, VAR=value): other targets (`serve`,
+    // `watch`, `test`) run their own programs and stay untouched
+    r"^make(?:\s+(?:-[CfIo]\s*\S+|-j\s*\d+|-\S+|[A-Za-z_]\w*=\S*|all))*\s*$",
+    r"^cmake\s+",
+    r"^ninja(?:\s+(?:-[CfIo]\s*\S+|-j\s*\d+|-\S+|[A-Za-z_]\w*=\S*|all))*\s*$",
+    r"^gcc\s+",
+    r"^g\+\+\s+",
+    r"^clang\s+",
+    r"^clang\+\+\s+",
+    r"^(?:bundle\s+exec\s+)?(?:bin/)?rspec(?:\s|$)",
+    r"^(?:bundle\s+exec\s+)?(?:bin/)?rails\s+test(?:\s|$)",
+    r"^bun\s+(install|i|add|remove|update|test)(?:\s|$)",
+    r"^yarn(?:\s+(install|add|up|upgrade|remove|dedupe)(?:\s|$)|\s*$)",
+    r"^hyperfine\s+",
 ];
 
 const RULES: &[RtkRule] = &[
@@ -98,7 +116,12 @@ const RULES: &[RtkRule] = &[
         rtk_cmd: "rtk cargo",
         category: "Cargo",
         savings_pct: 80.0,
-        subcmd_savings: &[("test", 90.0), ("check", 80.0)],
+        subcmd_savings: &[
+            ("test", 90.0),
+            ("bench", 90.0),
+            ("check", 80.0),
+            ("tree", 90.0),
+        ],
         subcmd_status: &[("fmt", super::report::RtkStatus::Passthrough)],
     },
     RtkRule {
@@ -241,6 +264,118 @@ const RULES: &[RtkRule] = &[
         subcmd_savings: &[("plan", 85.0), ("apply", 85.0)],
         subcmd_status: &[("state", super::report::RtkStatus::Passthrough)],
     },
+    RtkRule {
+        rtk_cmd: "rtk glab",
+        category: "GitHub",
+        savings_pct: 80.0,
+        subcmd_savings: &[("mr", 85.0), ("ci", 85.0), ("issue", 80.0)],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk gradle",
+        category: "Build",
+        savings_pct: 85.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk mvn",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk dotnet",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[("test", 90.0)],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk make",
+        category: "Build",
+        savings_pct: 85.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk cmake",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk ninja",
+        category: "Build",
+        savings_pct: 85.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk gcc",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk g++",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk clang",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk clang++",
+        category: "Build",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk rspec",
+        category: "Tests",
+        savings_pct: 90.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk minitest",
+        category: "Tests",
+        savings_pct: 90.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk bun",
+        category: "PackageManager",
+        savings_pct: 80.0,
+        subcmd_savings: &[("test", 90.0)],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk yarn",
+        category: "PackageManager",
+        savings_pct: 80.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
+    RtkRule {
+        rtk_cmd: "rtk bench hyperfine",
+        category: "Build",
+        savings_pct: 85.0,
+        subcmd_savings: &[],
+        subcmd_status: &[],
+    },
 ];
 
 /// Commands to ignore (shell builtins, trivial, already rtk).
@@ -310,6 +445,18 @@ lazy_static! {
         .collect();
     static ref ENV_PREFIX: Regex =
         Regex::new(r"^(?:sudo\s+|env\s+|[A-Z_][A-Z0-9_]*=[^\s]*\s+)+").unwrap();
+    /// `bundle exec rspec`, `bin/rails test`: runners spelled with more than one word
+    static ref RUBY_RUNNER: Regex =
+        Regex::new(r"^(?:bundle\s+exec\s+)?(?:bin/)?(?:rspec|rails\s+test)(?:\s+|$)").unwrap();
+    /// `./gradlew`, `mvnw`: the project wrapper, kept with `rtk gradle --wrapper`
+    static ref JVM_WRAPPER: Regex = Regex::new(r"^(?:\./)?(?:gradlew|mvnw)(?:\s+|$)").unwrap();
+    /// Gradle/Maven application runs (`bootRun`, `spring-boot:run`, `exec:java`)
+    static ref JVM_RUN_TASK: Regex = Regex::new(
+        r"^(?:\./)?(?:gradlew?|mvnw?)(?:\s+\S+)*?\s+(?:(?:\S*:)?(?:run|bootRun)(?:\s|$)|exec:)"
+    )
+    .unwrap();
+    /// `bun test --watch`: reruns until interrupted
+    static ref BUN_WATCH: Regex = Regex::new(r"^bun\s+test\s(?:.*\s)?--(?:watch|hot)(?:\s|$)").unwrap();
 }
 
 /// Classify a single (already-split) command.
@@ -338,6 +485,11 @@ pub fn classify_command(cmd: &str) -> Classification {
         return Classification::Ignored;
     }
 
+    // Application runs stream the program's own output: leave them alone
+    if JVM_RUN_TASK.is_match(cmd_clean) || BUN_WATCH.is_match(cmd_clean) {
+        return Classification::Ignored;
+    }
+
     // Fast check with RegexSet — take the last (most specific) match
     let matches: Vec<usize> = REGEX_SET.matches(cmd_clean).into_iter().collect();
     if let Some(&idx) = matches.last() {
@@ -380,7 +532,9 @@ pub fn classify_command(cmd: &str) -> Classification {
     } else {
         // Extract base command for unsupported
         let base = extract_base_command(cmd_clean);
-        if base.is_empty() {
+        // make/ninja targets other than builds are the user's own programs
+        let program = cmd_clean.split_whitespace().next().unwrap_or("");
+        if base.is_empty() || program == "make" || program == "ninja" {
             Classification::Ignored
         } else {
             Classification::Unsupported {
@@ -488,7 +642,21 @@ pub fn rewrite_command(cmd: &str, excluded: &[String]) -> Option<String> {
             }
 
             // Handle compound commands (npx tsc, pnpm vitest, etc.)
-            let rewritten = if cmd_clean.starts_with("npx ") || cmd_clean.starts_with("pnpm ") {
+            let rewritten = if let Some(wrapper) = JVM_WRAPPER.find(cmd_clean) {
+                let rest = &cmd_clean[wrapper.end()..];
+                if rest.is_empty() {
+                    format!("{} --wrapper", rtk_equivalent)
+                } else {
+                    format!("{} --wrapper {}", rtk_equivalent, rest)
+                }
+            } else if let Some(runner) = RUBY_RUNNER.find(cmd_clean) {
+                let rest = &cmd_clean[runner.end()..];
+                if rest.is_empty() {
+                    rtk_equivalent.to_string()
+                } else {
+                    format!("{} {}", rtk_equivalent, rest)
+                }
+            } else if cmd_clean.starts_with("npx ") || cmd_clean.starts_with("pnpm ") {
                 // For npx/pnpm commands, strip the package manager prefix and tool name
                 if parts.len() >= 2 {
                     // parts[1] is "tsc --noEmit" - we need to split again to skip "tsc"
@@ -741,6 +909,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_classify_docker_build_and_inspect() {
+        for cmd in [
+            "docker build -t app .",
+            "docker inspect api",
+            "docker pull nginx",
+        ] {
+            assert!(
+                matches!(
+                    classify_command(cmd),
+                    Classification::Supported {
+                        rtk_equivalent: "rtk docker",
+                        ..
+                    }
+                ),
+                "{cmd} should be supported"
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_kubectl_describe_and_apply() {
+        for cmd in [
+            "kubectl describe pod api-1",
+            "kubectl apply -f deploy.yaml",
+            "kubectl diff -f deploy.yaml",
+        ] {
+            assert!(
+                matches!(
+                    classify_command(cmd),
+                    Classification::Supported {
+                        rtk_equivalent: "rtk kubectl",
+                        ..
+                    }
+                ),
+                "{cmd} should be supported"
+            );
+        }
+    }
+
     #[test]
     fn test_classify_sudo_stripped() {
         assert_eq!(
@@ -817,9 +1025,9 @@ mod tests {
 
     #[test]
     fn test_registry_covers_all_cargo_subcommands() {
-        // Verify that every CargoCommand variant (Build, Test, Clippy, Check, Fmt)
+        // Verify that every CargoCommand variant (Build, Test, Clippy, Check, Fmt, Tree)
         // except Other has a matching pattern in the registry
-        for subcmd in ["build", "test", "clippy", "check", "fmt"] {
+        for subcmd in ["build", "test", "clippy", "check", "fmt", "tree"] {
             let cmd = format!("cargo {subcmd}");
             match classify_command(&cmd) {
                 Classification::Supported { .. } => {}
@@ -833,7 +1041,7 @@ mod tests {
         // Verify that every GitCommand subcommand has a matching pattern
         for subcmd in [
             "status", "log", "diff", "show", "add", "commit", "push", "pull", "branch", "fetch",
-            "stash", "worktree",
+            "stash", "worktree", "blame",
         ] {
             let cmd = format!("git {subcmd}");
             match classify_command(&cmd) {
@@ -901,6 +1109,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rewrite_gradle_wrapper_and_mvn() {
+        assert_eq!(
+            rewrite_command("./gradlew :app:test --tests CartTest", &[]),
+            Some("rtk gradle --wrapper :app:test --tests CartTest".to_string())
+        );
+        assert_eq!(
+            rewrite_command("gradle build", &[]),
+            Some("rtk gradle build".to_string())
+        );
+        assert_eq!(
+            rewrite_command("mvn -q verify", &[]),
+            Some("rtk mvn -q verify".to_string())
+        );
+        assert_eq!(
+            rewrite_command("./mvnw test", &[]),
+            Some("rtk mvn --wrapper test".to_string())
+        );
+        // Application runs stream through untouched
+        assert_eq!(rewrite_command("./gradlew :app:bootRun", &[]), None);
+        assert_eq!(rewrite_command("gradle run --args=8080", &[]), None);
+        assert_eq!(rewrite_command("mvn -q spring-boot:run", &[]), None);
+        assert_eq!(rewrite_command("./mvnw compile exec:java", &[]), None);
+    }
+
+    #[test]
+    fn test_rewrite_dotnet() {
+        assert_eq!(
+            rewrite_command("dotnet test --filter Category=Unit", &[]),
+            Some("rtk dotnet test --filter Category=Unit".to_string())
+        );
+        assert_eq!(
+            rewrite_command("dotnet build", &[]),
+            Some("rtk dotnet build".to_string())
+        );
+        assert_eq!(rewrite_command("dotnet run --project App", &[]), None);
+    }
+
+    #[test]
+    fn test_rewrite_bun_and_yarn() {
+        assert_eq!(
+            rewrite_command("bun test src/cart.test.ts", &[]),
+            Some("rtk bun test src/cart.test.ts".to_string())
+        );
+        assert_eq!(
+            rewrite_command("bun install", &[]),
+            Some("rtk bun install".to_string())
+        );
+        assert_eq!(rewrite_command("yarn", &[]), Some("rtk yarn".to_string()));
+        assert_eq!(
+            rewrite_command("yarn add -D typescript", &[]),
+            Some("rtk yarn add -D typescript".to_string())
+        );
+        assert_eq!(rewrite_command("bunx prettier .", &[]), None);
+        assert_eq!(rewrite_command("bun run dev", &[]), None);
+        assert_eq!(rewrite_command("bun test --watch src", &[]), None);
+        assert_eq!(rewrite_command("yarn build", &[]), None);
+    }
+
+    #[test]
+    fn test_rewrite_benchmarks() {
+        assert_eq!(
+            rewrite_command("cargo bench --bench parse", &[]),
+            Some("rtk cargo bench --bench parse".to_string())
+        );
+        assert_eq!(
+            rewrite_command("hyperfine 'rg foo' 'grep -r foo'", &[]),
+            Some("rtk bench hyperfine 'rg foo' 'grep -r foo'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rewrite_c_cpp_builds() {
+        assert_eq!(
+            rewrite_command("make -j8", &[]),
+            Some("rtk make -j8".to_string())
+        );
+        assert_eq!(
+            rewrite_command("cmake --build build", &[]),
+            Some("rtk cmake --build build".to_string())
+        );
+        assert_eq!(
+            rewrite_command("ninja -C build", &[]),
+            Some("rtk ninja -C build".to_string())
+        );
+        assert_eq!(
+            rewrite_command("make -C lib -j 4 all CC=clang", &[]),
+            Some("rtk make -C lib -j 4 all CC=clang".to_string())
+        );
+        assert_eq!(rewrite_command("make serve", &[]), None);
+        assert_eq!(rewrite_command("make -j8 watch", &[]), None);
+        assert_eq!(classify_command("make run"), Classification::Ignored);
+        assert_eq!(
+            rewrite_command("g++ -std=c++17 -c main.cpp", &[]),
+            Some("rtk g++ -std=c++17 -c main.cpp".to_string())
+        );
+        assert_eq!(
+            rewrite_command("clang++ main.cpp", &[]),
+            Some("rtk clang++ main.cpp".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rewrite_ruby_test_runners() {
+        assert_eq!(
+            rewrite_command("bundle exec rspec spec/models/cart_spec.rb:9", &[]),
+            Some("rtk rspec spec/models/cart_spec.rb:9".to_string())
+        );
+        assert_eq!(
+            rewrite_command("bin/rspec", &[]),
+            Some("rtk rspec".to_string())
+        );
+        assert_eq!(
+            rewrite_command("bin/rails test test/models", &[]),
+            Some("rtk minitest test/models".to_string())
+        );
+        assert_eq!(
+            rewrite_command("bundle exec rails test", &[]),
+            Some("rtk minitest".to_string())
+        );
+        assert_eq!(rewrite_command("bundle install", &[]), None);
+    }
+
     #[test]
     fn test_rewrite_npx_tsc() {
         assert_eq!(
@@ -1053,6 +1384,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_classify_glab_mr() {
+        assert_eq!(
+            classify_command("glab mr view 12"),
+            Classification::Supported {
+                rtk_equivalent: "rtk glab",
+                category: "GitHub",
+                estimated_savings_pct: 85.0,
+                status: RtkStatus::Existing,
+            }
+        );
+    }
+
+    #[test]
+    fn test_rewrite_glab_ci_trace() {
+        assert_eq!(
+            rewrite_command("glab ci trace 4567", &[]),
+            Some("rtk glab ci trace 4567".to_string())
+        );
+    }
+
     #[test]
     fn test_rewrite_with_sudo() {
         assert_eq!(
@@ -0,0 +1,945 @@
+//! dotnet build / test / restore with compact output.
+//!
+//! `rtk dotnet` prints one status line per project (restore, build, tests),
+//! MSBuild diagnostics (`error CS1002 [App.csproj]`) grouped by file and
+//! deduplicated across target frameworks, and test results read from the
+//! TRX reports written to a temporary results directory, falling back to the
+//! console logger output.
+
+use crate::junit_cmd;
+use crate::parser::{
+    diagnostics, emit_degradation_warning, FormatMode, LintIssue, LintSeverity, ParseResult,
+    TestFailure, TestResult, TokenFormatter,
+};
+use crate::stack_trace::compress_stack_traces;
+use crate::tracking;
+use crate::utils::strip_ansi;
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Subcommands with a native filter; everything else runs unchanged
+const FILTERED_SUBCOMMANDS: &[&str] = &["build", "test", "restore", "publish", "pack", "clean"];
+
+/// Stack trace lines kept per failing test
+const MAX_TRACE_LINES: usize = 10;
+
+lazy_static! {
+    /// `src/Cart.cs(10,5): error CS1002: ; expected [/src/App/App.csproj::TargetFramework=net8.0]`
+    static ref DIAGNOSTIC: Regex = Regex::new(
+        r"^\s*(.+?) ?: (error|warning) ?([A-Za-z]+\d+)? ?: (.*?)(?: \[([^\[\]]+)\])?$"
+    )
+    .unwrap();
+    static ref LOCATION: Regex = Regex::new(r"^(.*)\((\d+)(?:,(\d+))?(?:,\d+,\d+)?\)$").unwrap();
+    static ref RESTORED: Regex =
+        Regex::new(r"^\s*Restored (\S+\.\w+proj) \(in .+\)\.?$").unwrap();
+    static ref UP_TO_DATE: Regex =
+        Regex::new(r"^\s*All projects are up-to-date for restore\.?$").unwrap();
+    /// `  App -> /src/App/bin/Debug/net8.0/App.dll`
+    static ref BUILT: Regex = Regex::new(r"^\s*([\w.\-]+) -> (\S.*)$").unwrap();
+    static ref FRAMEWORK_DIR: Regex = Regex::new(r"/(net[\w.\-]*\d[\w.\-]*)/").unwrap();
+    static ref OUTCOME: Regex = Regex::new(r"^(Build succeeded|Build FAILED)\.?$").unwrap();
+    static ref ELAPSED: Regex = Regex::new(r"^Time Elapsed (\S+)$").unwrap();
+    /// `Failed!  - Failed:     1, Passed:    11, Skipped:     0, Total:    12, Duration: 45 ms - App.Tests.dll (net8.0)`
+    static ref TEST_SUMMARY: Regex = Regex::new(
+        r"^(Passed|Failed)!\s+-\s+Failed:\s+(\d+), Passed:\s+(\d+), Skipped:\s+(\d+), Total:\s+(\d+), Duration:\s+(.+?) - ([\w.\-]+?)(?:\.dll)? \(([^)]+)\)$"
+    )
+    .unwrap();
+    /// `  Failed App.Tests.CartTests.Total [12 ms]`
+    static ref TEST_FAILED: Regex = Regex::new(r"^\s*Failed (\S.*?) \[[^\]]+\]$").unwrap();
+    static ref TEST_OTHER: Regex = Regex::new(r"^\s*(?:Passed|Skipped) \S.* \[[^\]]+\]$").unwrap();
+    /// `in /src/App.Tests/CartTests.cs:line 14`
+    static ref TRACE_LOCATION: Regex = Regex::new(r" in (.+):line (\d+)$").unwrap();
+}
+
+/// One project's restore / build / test status
+#[derive(Debug, Default)]
+struct ProjectStatus {
+    name: String,
+    restored: bool,
+    frameworks: Vec<String>,
+    errors: usize,
+    warnings: usize,
+    tests: Vec<String>,
+}
+
+/// A diagnostic plus the target frameworks it was reported for
+#[derive(Debug)]
+struct Diagnostic {
+    issue: LintIssue,
+    project: String,
+    frameworks: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct DotnetBuild {
+    outcome: Option<String>,
+    success: Option<bool>,
+    elapsed: Option<String>,
+    up_to_date: bool,
+    projects: Vec<ProjectStatus>,
+    diagnostics: Vec<Diagnostic>,
+    /// Test counts and failures from the console logger
+    console_tests: Option<TestResult>,
+}
+
+impl DotnetBuild {
+    fn project(&mut self, name: &str) -> &mut ProjectStatus {
+        let idx = match self.projects.iter().position(|p| p.name == name) {
+            Some(idx) => idx,
+            None => {
+                self.projects.push(ProjectStatus {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.projects.len() - 1
+            }
+        };
+        &mut self.projects[idx]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.outcome.is_none()
+            && self.projects.is_empty()
+            && self.diagnostics.is_empty()
+            && self.console_tests.is_none()
+    }
+}
+
+pub fn run(args: &[String], verbose: u8) -> Result<()> {
+    let subcommand = args.first().map(String::as_str).unwrap_or("");
+    if !FILTERED_SUBCOMMANDS.contains(&subcommand) || args.iter().any(|a| is_help(a)) {
+        return run_passthrough(args, verbose);
+    }
+
+    let timer = tracking::TimedExecution::start();
+    let started = SystemTime::now();
+
+    let mut cmd = Command::new("dotnet");
+    cmd.env("DOTNET_NOLOGO", "1")
+        .env("DOTNET_CLI_TELEMETRY_OPTOUT", "1")
+        // Terminal logger redraws progress in place; the console logger prints lines
+        .env("MSBUILDTERMINALLOGGER", "off");
+
+    // TRX goes to the user's --results-directory, else to a temporary one
+    let mut results_dir = None;
+    let mut temp_dir = None;
+    let mut extra = Vec::new();
+    if subcommand == "test" {
+        if !has_trx_logger(args) {
+            extra.extend(["--logger".to_string(), "trx".to_string()]);
+        }
+        match flag_value(cli_args(args), "--results-directory") {
+            Some(dir) => results_dir = Some(PathBuf::from(dir)),
+            None => {
+                let dir = std::env::temp_dir().join(format!("rtk-dotnet-{}", std::process::id()));
+                extra.push("--results-directory".to_string());
+                extra.push(dir.to_string_lossy().to_string());
+                results_dir = Some(dir.clone());
+                temp_dir = Some(dir);
+            }
+        }
+    }
+    cmd.args(insert_before_separator(args, &extra));
+
+    if verbose > 0 {
+        eprintln!("Running: dotnet {}", args.join(" "));
+    }
+
+    let output = cmd
+        .output()
+        .context("Failed to run dotnet. Is the .NET SDK installed?")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+
+    let trx = results_dir.and_then(|dir| trx_results(&dir, started, verbose));
+    if let Some(dir) = temp_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    let root = std::env::current_dir()
+        .map(|d| d.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut build = parse_output(&raw, &root);
+    if build.success.is_none() {
+        build.success = Some(exit_code == 0);
+    }
+
+    let mode = FormatMode::from_verbosity(verbose);
+    let tests = trx.or_else(|| build.console_tests.take());
+    let mut filtered = if build.is_empty() && tests.is_none() {
+        // Crashed before MSBuild ran: keep the tail of the output
+        format!(
+            "{}\n{}",
+            format_build(subcommand, &build, None, mode),
+            diagnostics::crash_tail(&raw)
+        )
+    } else {
+        format_build(subcommand, &build, tests.as_ref(), mode)
+    };
+
+    if let Some(tests) = &tests {
+        let failed: Vec<String> = tests.failures.iter().map(|f| f.test_name.clone()).collect();
+        crate::test_history::record_and_annotate("dotnet", args, Some(failed), &mut filtered);
+    }
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "dotnet", exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &format!("dotnet {}", args.join(" ")),
+        &format!("rtk dotnet {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// `dotnet run`, `dotnet new`, `--help`, ...: output is the answer
+fn run_passthrough(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("dotnet passthrough: {:?}", args);
+    }
+    let status = Command::new("dotnet")
+        .args(args)
+        .status()
+        .context("Failed to run dotnet. Is the .NET SDK installed?")?;
+
+    timer.track_passthrough(
+        &format!("dotnet {}", args.join(" ")),
+        &format!("rtk dotnet {} (passthrough)", args.join(" ")),
+    );
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+fn is_help(arg: &str) -> bool {
+    matches!(arg, "-h" | "--help" | "-?" | "/?")
+}
+
+/// Arguments before `--`; what follows is inline RunSettings (`NUnit.Where=...`)
+fn cli_args(args: &[String]) -> &[String] {
+    let end = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    &args[..end]
+}
+
+fn insert_before_separator(args: &[String], extra: &[String]) -> Vec<String> {
+    let cli = cli_args(args);
+    let mut out = cli.to_vec();
+    out.extend_from_slice(extra);
+    out.extend_from_slice(&args[cli.len()..]);
+    out
+}
+
+/// `--logger trx`, `-l:trx;LogFileName=out.trx`, ...
+fn has_trx_logger(args: &[String]) -> bool {
+    let mut iter = cli_args(args).iter();
+    while let Some(arg) = iter.next() {
+        let value = if arg == "--logger" || arg == "-l" {
+            iter.next().map(String::as_str)
+        } else {
+            ["--logger", "-l"].iter().find_map(|flag| {
+                arg.strip_prefix(flag)
+                    .and_then(|r| r.strip_prefix(':').or_else(|| r.strip_prefix('=')))
+            })
+        };
+        if value.is_some_and(|v| v.to_ascii_lowercase().starts_with("trx")) {
+            return true;
+        }
+    }
+    false
+}
+
+/// `--flag value` or `--flag=value`
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == flag {
+            return iter.next().map(String::as_str);
+        }
+        if let Some(value) = arg.strip_prefix(flag).and_then(|r| r.strip_prefix('=')) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn parse_output(output: &str, root: &str) -> DotnetBuild {
+    let clean = strip_ansi(output);
+    let lines: Vec<&str> = clean.lines().collect();
+    let mut build = DotnetBuild::default();
+    let mut tests: Option<TestResult> = None;
+    let mut failures: Vec<TestFailure> = Vec::new();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        i += 1;
+
+        if let Some(caps) = TEST_SUMMARY.captures(line.trim_start()) {
+            let count = |n: usize| caps[n].parse::<usize>().unwrap_or(0);
+            let (failed, passed, skipped, total) = (count(2), count(3), count(4), count(5));
+            let summary = if failed > 0 {
+                format!("tests ✗ {} failed, {} passed", failed, passed)
+            } else {
+                format!("tests ✓ {} passed", passed)
+            };
+            let skipped_note = if skipped > 0 {
+                format!(", {} skipped", skipped)
+            } else {
+                String::new()
+            };
+            build
+                .project(&caps[7])
+                .tests
+                .push(format!("{}{} ({})", summary, skipped_note, &caps[8]));
+            let t = tests.get_or_insert_with(empty_result);
+            t.total += total;
+            t.passed += passed;
+            t.failed += failed;
+            t.skipped += skipped;
+            continue;
+        }
+        if let Some(caps) = TEST_FAILED.captures(line) {
+            let (failure, consumed) = console_failure(&caps[1], &lines[i..], root);
+            failures.push(failure);
+            i += consumed;
+            continue;
+        }
+        if let Some(caps) = DIAGNOSTIC.captures(line) {
+            add_diagnostic(&mut build, &caps, root);
+            continue;
+        }
+        if let Some(caps) = RESTORED.captures(line) {
+            build.project(&project_name(&caps[1])).restored = true;
+            continue;
+        }
+        if UP_TO_DATE.is_match(line) {
+            build.up_to_date = true;
+            continue;
+        }
+        if let Some(caps) = BUILT.captures(line) {
+            let framework = FRAMEWORK_DIR
+                .captures_iter(&caps[2])
+                .last()
+                .map(|c| c[1].to_string());
+            let project = build.project(&caps[1]);
+            if let Some(framework) = framework {
+                if !project.frameworks.contains(&framework) {
+                    project.frameworks.push(framework);
+                }
+            }
+            continue;
+        }
+        if let Some(caps) = OUTCOME.captures(line) {
+            build.success = Some(&caps[1] == "Build succeeded");
+            build.outcome = Some(caps[1].to_string());
+            continue;
+        }
+        if let Some(caps) = ELAPSED.captures(line) {
+            build.elapsed = Some(caps[1].to_string());
+        }
+    }
+
+    for project in build.projects.iter_mut() {
+        let (errors, warnings) = build
+            .diagnostics
+            .iter()
+            .filter(|d| d.project == project.name)
+            .fold((0, 0), |(e, w), d| match d.issue.severity {
+                LintSeverity::Error => (e + 1, w),
+                _ => (e, w + 1),
+            });
+        project.errors = errors;
+        project.warnings = warnings;
+    }
+
+    if let Some(mut t) = tests {
+        t.failures = failures;
+        build.console_tests = Some(t);
+    }
+    build
+}
+
+fn add_diagnostic(build: &mut DotnetBuild, caps: &regex::Captures, root: &str) {
+    let (file, line, column) = match LOCATION.captures(&caps[1]) {
+        Some(loc) => (
+            relative(&loc[1], root),
+            loc[2].parse().unwrap_or(0),
+            loc.get(3)
+                .and_then(|c| c.as_str().parse().ok())
+                .unwrap_or(0),
+        ),
+        None => (relative(&caps[1], root), 0, 0),
+    };
+    let (project, framework) = match caps.get(5) {
+        Some(tag) => {
+            let (path, framework) = match tag.as_str().split_once("::TargetFramework=") {
+                Some((path, framework)) => (path, Some(framework.to_string())),
+                None => (tag.as_str(), None),
+            };
+            (project_name(path), framework)
+        }
+        None => (String::new(), None),
+    };
+    if !project.is_empty() {
+        build.project(&project);
+    }
+    let severity = if &caps[2] == "error" {
+        LintSeverity::Error
+    } else {
+        LintSeverity::Warning
+    };
+    let rule_id = caps.get(3).map(|m| m.as_str()).unwrap_or("").to_string();
+    let message = caps[4].trim().to_string();
+
+    // MSBuild repeats every diagnostic in its final summary and once per target framework
+    let existing = build.diagnostics.iter_mut().find(|d| {
+        d.issue.file_path == file
+            && d.issue.line == line
+            && d.issue.column == column
+            && d.issue.rule_id == rule_id
+            && d.issue.message == message
+    });
+    match existing {
+        Some(d) => {
+            if let Some(framework) = framework {
+                if !d.frameworks.contains(&framework) {
+                    d.frameworks.push(framework);
+                }
+            }
+        }
+        None => build.diagnostics.push(Diagnostic {
+            issue: LintIssue {
+                file_path: file,
+                line,
+                column,
+                severity,
+                rule_id,
+                message,
+                suggestion: None,
+            },
+            project,
+            frameworks: framework.into_iter().collect(),
+        }),
+    }
+}
+
+/// `Error Message:` / `Stack Trace:` block after a `Failed <test>` line
+fn console_failure(name: &str, lines: &[&str], root: &str) -> (TestFailure, usize) {
+    let mut message = Vec::new();
+    let mut trace = Vec::new();
+    let mut section = "";
+    let mut consumed = 0;
+    for line in lines {
+        let trimmed = line.trim();
+        if TEST_FAILED.is_match(line)
+            || TEST_OTHER.is_match(line)
+            || TEST_SUMMARY.is_match(trimmed)
+            || trimmed.starts_with("Results File:")
+            || trimmed.starts_with("Total tests:")
+        {
+            break;
+        }
+        consumed += 1;
+        match trimmed {
+            "Error Message:" => section = "message",
+            "Stack Trace:" => section = "trace",
+            "Standard Output Messages:" | "Standard Error Messages:" => section = "",
+            "" => {}
+            _ => match section {
+                "message" => message.push(trimmed),
+                "trace" => trace.push(*line),
+                _ => {}
+            },
+        }
+    }
+    (
+        test_failure(name, &message.join("\n"), &trace.join("\n"), root),
+        consumed,
+    )
+}
+
+fn test_failure(name: &str, message: &str, trace: &str, root: &str) -> TestFailure {
+    let file_path = trace
+        .lines()
+        .filter_map(|l| TRACE_LOCATION.captures(l))
+        .map(|c| format!("{}:{}", relative(&c[1], root), &c[2]))
+        .next()
+        .unwrap_or_default();
+    let trace = compress_stack_traces(trace.trim_end());
+    let stack_trace = if trace.trim().is_empty() {
+        None
+    } else {
+        Some(
+            trace
+                .lines()
+                .take(MAX_TRACE_LINES)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    };
+    TestFailure {
+        test_name: short_test_name(name),
+        file_path,
+        error_message: message.trim().to_string(),
+        stack_trace,
+    }
+}
+
+/// `App.Tests.CartTests.Total(qty: 2)` → `CartTests.Total(qty: 2)`
+fn short_test_name(name: &str) -> String {
+    let (path, params) = match name.find('(') {
+        Some(idx) => name.split_at(idx),
+        None => (name, ""),
+    };
+    let segments: Vec<&str> = path.split('.').collect();
+    let start = segments.len().saturating_sub(2);
+    format!("{}{}", segments[start..].join("."), params)
+}
+
+/// TRX files written during this run, merged into one result
+fn trx_results(dir: &Path, since: SystemTime, verbose: u8) -> Option<TestResult> {
+    let mut paths: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "trx"))
+        .filter(|e| {
+            e.metadata()
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .is_some_and(|m| m >= since)
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect();
+    if paths.is_empty() {
+        return None;
+    }
+    paths.sort();
+    if verbose > 0 {
+        eprintln!("TRX reports: {} file(s)", paths.len());
+    }
+
+    let root = std::env::current_dir()
+        .map(|d| d.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut results = Vec::new();
+    for path in &paths {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        match parse_trx(&content, &root) {
+            ParseResult::Full(result) => results.push(result),
+            ParseResult::Degraded(result, warnings) => {
+                if verbose > 0 {
+                    emit_degradation_warning("trx", &warnings.join(", "));
+                }
+                results.push(result);
+            }
+            ParseResult::Passthrough(_) => {}
+        }
+    }
+    if results.is_empty() {
+        None
+    } else {
+        Some(junit_cmd::merge_results(results))
+    }
+}
+
+fn parse_trx(xml: &str, root: &str) -> ParseResult<TestResult> {
+    let doc = match roxmltree::Document::parse(xml) {
+        Ok(doc) => doc,
+        Err(e) => return ParseResult::Passthrough(format!("TRX parse failed: {}", e)),
+    };
+    let mut result = empty_result();
+    let mut duration_ms = 0;
+    for node in doc
+        .descendants()
+        .filter(|n| n.tag_name().name() == "UnitTestResult")
+    {
+        result.total += 1;
+        duration_ms += node.attribute("duration").map(parse_timespan).unwrap_or(0);
+        match node.attribute("outcome").unwrap_or("") {
+            "Passed" => result.passed += 1,
+            "Failed" | "Error" | "Timeout" | "Aborted" => {
+                result.failed += 1;
+                let text = |tag: &str| {
+                    node.descendants()
+                        .find(|n| n.tag_name().name() == tag)
+                        .and_then(|n| n.text())
+                        .unwrap_or("")
+                        .to_string()
+                };
+                result.failures.push(test_failure(
+                    node.attribute("testName").unwrap_or("?"),
+                    &text("Message"),
+                    &text("StackTrace"),
+                    root,
+                ));
+            }
+            _ => result.skipped += 1,
+        }
+    }
+    result.duration_ms = Some(duration_ms);
+    ParseResult::Full(result)
+}
+
+/// `00:00:01.2340000` → 1234
+fn parse_timespan(value: &str) -> u64 {
+    let parts: Vec<f64> = value.split(':').filter_map(|p| p.parse().ok()).collect();
+    match parts.as_slice() {
+        [h, m, s] => ((h * 3600.0 + m * 60.0 + s) * 1000.0) as u64,
+        _ => 0,
+    }
+}
+
+fn empty_result() -> TestResult {
+    TestResult {
+        total: 0,
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        duration_ms: None,
+        failures: Vec::new(),
+    }
+}
+
+/// `/src/App/App.csproj` → `App`
+fn project_name(path: &str) -> String {
+    let file = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    match file.rsplit_once('.') {
+        Some((stem, ext)) if ext.ends_with("proj") => stem.to_string(),
+        _ => file.to_string(),
+    }
+}
+
+fn relative(path: &str, root: &str) -> String {
+    if root.is_empty() {
+        return path.to_string();
+    }
+    path.strip_prefix(root)
+        .map(|p| p.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn format_build(
+    subcommand: &str,
+    build: &DotnetBuild,
+    tests: Option<&TestResult>,
+    mode: FormatMode,
+) -> String {
+    let mut out = Vec::new();
+
+    let mark = match build.success {
+        Some(true) => "✓",
+        Some(false) => "✗",
+        None => "•",
+    };
+    let outcome = build
+        .outcome
+        .clone()
+        .unwrap_or_else(|| match build.success {
+            Some(true) => "ok".to_string(),
+            Some(false) => "failed".to_string(),
+            None => "no build summary".to_string(),
+        });
+    let mut details = Vec::new();
+    if !build.projects.is_empty() {
+        details.push(format!("{} projects", build.projects.len()));
+    }
+    if let Some(elapsed) = &build.elapsed {
+        details.push(elapsed.clone());
+    }
+    if details.is_empty() {
+        out.push(format!("{} dotnet {}: {}", mark, subcommand, outcome));
+    } else {
+        out.push(format!(
+            "{} dotnet {}: {} ({})",
+            mark,
+            subcommand,
+            outcome,
+            details.join(", ")
+        ));
+    }
+
+    let width = build
+        .projects
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0);
+    for project in &build.projects {
+        let mut status = Vec::new();
+        if project.restored {
+            status.push("restored".to_string());
+        } else if build.up_to_date {
+            status.push("up-to-date".to_string());
+        }
+        if project.errors > 0 {
+            status.push(format!("✗ {} errors", project.errors));
+        } else if !project.frameworks.is_empty() {
+            status.push(format!("built {}", project.frameworks.join(", ")));
+        }
+        if project.warnings > 0 {
+            status.push(format!("{} warnings", project.warnings));
+        }
+        status.extend(project.tests.iter().cloned());
+        if status.is_empty() {
+            out.push(format!("  {}", project.name));
+        } else {
+            out.push(format!(
+                "  {:width$}  {}",
+                project.name,
+                status.join(" · "),
+                width = width
+            ));
+        }
+    }
+
+    let issues: Vec<(&LintIssue, String)> = build
+        .diagnostics
+        .iter()
+        .map(|d| {
+            // Only worth saying when some target frameworks are unaffected
+            let note = if !d.frameworks.is_empty()
+                && d.frameworks.len() < framework_count(build, &d.project)
+            {
+                format!(" ({} only)", d.frameworks.join(", "))
+            } else {
+                String::new()
+            };
+            (&d.issue, note)
+        })
+        .collect();
+    out.extend(diagnostics::format_by_file(&issues));
+
+    if let Some(tests) = tests {
+        out.push(format!("Tests: {}", tests.format(mode)));
+    }
+
+    out.join("\n")
+}
+
+/// Target frameworks a project was built for or reported diagnostics for
+fn framework_count(build: &DotnetBuild, project: &str) -> usize {
+    let built = build
+        .projects
+        .iter()
+        .filter(|p| p.name == project)
+        .flat_map(|p| p.frameworks.iter());
+    let reported = build
+        .diagnostics
+        .iter()
+        .filter(|d| d.project == project)
+        .flat_map(|d| d.frameworks.iter());
+    let mut frameworks: Vec<&str> = built.chain(reported).map(String::as_str).collect();
+    frameworks.sort_unstable();
+    frameworks.dedup();
+    frameworks.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    const MULTI_TARGET_BUILD: &str = r#"  Determining projects to restore...
+  Restored /src/Shop/Shop.Core/Shop.Core.csproj (in 312 ms).
+  Restored /src/Shop/Shop.Api/Shop.Api.csproj (in 298 ms).
+/src/Shop/Shop.Core/Cart.cs(42,17): error CS0103: The name 'discount' does not exist in the current context [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net8.0]
+/src/Shop/Shop.Core/Cart.cs(42,17): error CS0103: The name 'discount' does not exist in the current context [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net6.0]
+/src/Shop/Shop.Core/Legacy.cs(7,9): warning CS0618: 'WebClient.WebClient()' is obsolete [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net8.0]
+/src/Shop/Shop.Core/Legacy.cs(7,9): warning CS0618: 'WebClient.WebClient()' is obsolete [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net6.0]
+/src/Shop/Shop.Core/Compat.cs(3,1): warning CS8600: Converting null literal or possible null value to non-nullable type. [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net8.0]
+  Shop.Api -> /src/Shop/Shop.Api/bin/Debug/net8.0/Shop.Api.dll
+
+Build FAILED.
+
+/src/Shop/Shop.Core/Cart.cs(42,17): error CS0103: The name 'discount' does not exist in the current context [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net8.0]
+/src/Shop/Shop.Core/Cart.cs(42,17): error CS0103: The name 'discount' does not exist in the current context [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net6.0]
+/src/Shop/Shop.Core/Legacy.cs(7,9): warning CS0618: 'WebClient.WebClient()' is obsolete [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net8.0]
+/src/Shop/Shop.Core/Legacy.cs(7,9): warning CS0618: 'WebClient.WebClient()' is obsolete [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net6.0]
+/src/Shop/Shop.Core/Compat.cs(3,1): warning CS8600: Converting null literal or possible null value to non-nullable type. [/src/Shop/Shop.Core/Shop.Core.csproj::TargetFramework=net8.0]
+    3 Warning(s)
+    2 Error(s)
+
+Time Elapsed 00:00:04.12
+"#;
+
+    #[test]
+    fn test_build_dedupes_diagnostics_across_frameworks() {
+        let build = parse_output(MULTI_TARGET_BUILD, "/src/Shop");
+        assert_eq!(build.diagnostics.len(), 3);
+        assert_eq!(build.success, Some(false));
+        let out = format_build("build", &build, None, FormatMode::Compact);
+        assert_eq!(
+            out,
+            "✗ dotnet build: Build FAILED (2 projects, 00:00:04.12)
+  Shop.Core  restored · ✗ 1 errors · 2 warnings
+  Shop.Api   restored · built net8.0
+Errors (1):
+  Shop.Core/Cart.cs
+    42:17 [CS0103] The name 'discount' does not exist in the current context
+Warnings (2):
+  Shop.Core/Legacy.cs
+    7:9 [CS0618] 'WebClient.WebClient()' is obsolete
+  Shop.Core/Compat.cs
+    3:1 [CS8600] Converting null literal or possible null value to non-nullable type. (net8.0 only)"
+        );
+    }
+
+    #[test]
+    fn test_build_success_and_msbuild_errors() {
+        let ok = "  Determining projects to restore...\n  All projects are up-to-date for restore.\n  App -> /src/App/bin/Debug/net8.0/App.dll\n\nBuild succeeded.\n    0 Warning(s)\n    0 Error(s)\n\nTime Elapsed 00:00:01.02\n";
+        let out = format_build(
+            "build",
+            &parse_output(ok, "/src/App"),
+            None,
+            FormatMode::Compact,
+        );
+        assert_eq!(
+            out,
+            "✓ dotnet build: Build succeeded (1 projects, 00:00:01.02)\n  App  up-to-date · built net8.0"
+        );
+
+        let missing =
+            "MSBUILD : error MSB1009: Project file does not exist.\nSwitch: Nope.csproj\n";
+        let build = parse_output(missing, "/src");
+        assert_eq!(build.diagnostics.len(), 1);
+        assert_eq!(build.diagnostics[0].issue.file_path, "MSBUILD");
+        assert_eq!(build.diagnostics[0].issue.rule_id, "MSB1009");
+    }
+
+    #[test]
+    fn test_console_test_results() {
+        let output = r#"  Determining projects to restore...
+  All projects are up-to-date for restore.
+  Shop.Core -> /src/Shop/Shop.Core/bin/Debug/net8.0/Shop.Core.dll
+  Shop.Tests -> /src/Shop/Shop.Tests/bin/Debug/net8.0/Shop.Tests.dll
+Test run for /src/Shop/Shop.Tests/bin/Debug/net8.0/Shop.Tests.dll (.NETCoreApp,Version=v8.0)
+VSTest version 17.11.0 (x64)
+
+Starting test execution, please wait...
+A total of 1 test files matched the specified pattern.
+  Failed Shop.Tests.CartTests.Total_AppliesDiscount [12 ms]
+  Error Message:
+   Assert.Equal() Failure: Values differ
+Expected: 90
+Actual:   100
+  Stack Trace:
+     at Shop.Tests.CartTests.Total_AppliesDiscount() in /src/Shop/Shop.Tests/CartTests.cs:line 14
+   at System.RuntimeMethodHandle.InvokeMethod(Object target, Void** arguments, Signature sig, Boolean isConstructor)
+   at System.Reflection.MethodBaseInvoker.InvokeWithNoArgs(Object obj, BindingFlags invokeAttr)
+
+Failed!  - Failed:     1, Passed:    11, Skipped:     1, Total:    13, Duration: 45 ms - Shop.Tests.dll (net8.0)
+"#;
+        let build = parse_output(output, "/src/Shop");
+        let tests = build.console_tests.as_ref().unwrap();
+        assert_eq!(
+            (tests.total, tests.passed, tests.failed, tests.skipped),
+            (13, 11, 1, 1)
+        );
+        let failure = &tests.failures[0];
+        assert_eq!(failure.test_name, "CartTests.Total_AppliesDiscount");
+        assert_eq!(failure.file_path, "Shop.Tests/CartTests.cs:14");
+        assert_eq!(
+            failure.error_message,
+            "Assert.Equal() Failure: Values differ\nExpected: 90\nActual:   100"
+        );
+        assert_eq!(
+            failure.stack_trace.as_deref(),
+            Some("     at Shop.Tests.CartTests.Total_AppliesDiscount() in /src/Shop/Shop.Tests/CartTests.cs:line 14\n   ... 2 frames in System")
+        );
+        let out = format_build("test", &build, None, FormatMode::Compact);
+        assert!(out.contains("  Shop.Tests  up-to-date · built net8.0 · tests ✗ 1 failed, 11 passed, 1 skipped (net8.0)"));
+    }
+
+    #[test]
+    fn test_parse_trx() {
+        let trx = r#"<?xml version="1.0" encoding="utf-8"?>
+<TestRun id="1" name="run" xmlns="http://microsoft.com/schemas/VisualStudio/TeamTest/2010">
+  <Results>
+    <UnitTestResult testId="a" testName="Shop.Tests.CartTests.Total_Sums" outcome="Passed" duration="00:00:00.0100000" />
+    <UnitTestResult testId="b" testName="Shop.Tests.CartTests.Total_AppliesDiscount(qty: 2)" outcome="Failed" duration="00:00:00.0200000">
+      <Output>
+        <ErrorInfo>
+          <Message>Assert.Equal() Failure: Values differ</Message>
+          <StackTrace>   at Shop.Tests.CartTests.Total_AppliesDiscount(Int32 qty) in /src/Shop/Shop.Tests/CartTests.cs:line 21</StackTrace>
+        </ErrorInfo>
+      </Output>
+    </UnitTestResult>
+    <UnitTestResult testId="c" testName="Shop.Tests.CartTests.Slow" outcome="NotExecuted" duration="00:00:00" />
+  </Results>
+</TestRun>"#;
+        let ParseResult::Full(result) = parse_trx(trx, "/src/Shop") else {
+            panic!("expected full parse");
+        };
+        assert_eq!(
+            (result.total, result.passed, result.failed, result.skipped),
+            (3, 1, 1, 1)
+        );
+        assert_eq!(result.duration_ms, Some(30));
+        assert_eq!(
+            result.failures[0].test_name,
+            "CartTests.Total_AppliesDiscount(qty: 2)"
+        );
+        assert_eq!(result.failures[0].file_path, "Shop.Tests/CartTests.cs:21");
+    }
+
+    #[test]
+    fn test_short_test_name_and_flags() {
+        assert_eq!(short_test_name("A.B.C.D"), "C.D");
+        assert_eq!(short_test_name("Single"), "Single");
+        assert_eq!(
+            flag_value(
+                &args(&["test", "--results-directory=out"]),
+                "--results-directory"
+            ),
+            Some("out")
+        );
+        assert_eq!(project_name("/src/App/App.fsproj"), "App");
+    }
+
+    #[test]
+    fn test_trx_logger_and_runsettings_separator() {
+        assert!(has_trx_logger(&args(&["test", "--logger", "trx"])));
+        assert!(has_trx_logger(&args(&["test", "-l:trx;LogFileName=a.trx"])));
+        assert!(!has_trx_logger(&args(&["test", "tests/Trx.Tests.csproj"])));
+        assert!(!has_trx_logger(&args(&["test", "--logger", "console"])));
+
+        let extra = args(&["--logger", "trx"]);
+        assert_eq!(
+            insert_before_separator(&args(&["test", "--", "NUnit.Where=cat==Fast"]), &extra),
+            args(&["test", "--logger", "trx", "--", "NUnit.Where=cat==Fast"])
+        );
+        assert_eq!(
+            insert_before_separator(&args(&["test", "App.sln"]), &extra),
+            args(&["test", "App.sln", "--logger", "trx"])
+        );
+    }
+}
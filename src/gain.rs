@@ -20,6 +20,7 @@ pub fn run(
     format: &str,
     global: bool,
     project: bool,
+    tests: bool,
     _verbose: u8,
 ) -> Result<()> {
     let tracker = Tracker::new().context("Failed to initialize tracking database")?;
@@ -43,6 +44,10 @@ pub fn run(
         }
     };
 
+    if tests {
+        return crate::test_history::print_report(&scope);
+    }
+
     // Handle export formats
     match format {
         "json" => return export_json(&tracker, &scope, top_n, daily, weekly, monthly, all),
@@ -0,0 +1,510 @@
+//! Generated and lock-file detection for diffs.
+//!
+//! Classifies paths as generated (built-in globs, `.gitattributes`
+//! `linguist-generated` / `-diff`, and `FilterConfig::ignore_files`) and
+//! replaces their hunks with a one-line summary. Lockfiles get a
+//! dependency-level summary: `Cargo.lock: serde 1.0.190→1.0.197, +2 crates added (foo, bar)`.
+
+use crate::semantic_diff::{self, DiffLine, FileDiff};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+
+/// Paths treated as generated even without config or attributes
+const BUILTIN_GLOBS: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "go.sum",
+    "poetry.lock",
+    "uv.lock",
+    "Pipfile.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "flake.lock",
+    "*.snap",
+    "__snapshots__/",
+    "*.min.js",
+    "*.min.css",
+    "*.js.map",
+    "*.css.map",
+    "*.pb.go",
+    "*_pb2.py",
+];
+
+const MAX_UPGRADES: usize = 8;
+const MAX_NAMES: usize = 5;
+
+pub struct GeneratedFiles {
+    globs: Gitignore,
+    /// Paths git reports as `linguist-generated` or `-diff`
+    attributed: HashSet<String>,
+}
+
+impl GeneratedFiles {
+    pub fn new(extra_globs: &[String], attributed: HashSet<String>) -> Self {
+        let mut builder = GitignoreBuilder::new("");
+        for glob in BUILTIN_GLOBS
+            .iter()
+            .copied()
+            .chain(extra_globs.iter().map(|s| s.as_str()))
+        {
+            // Invalid user globs are skipped rather than failing the whole diff
+            let _ = builder.add_line(None, glob);
+        }
+        let globs = builder.build().unwrap_or_else(|_| Gitignore::empty());
+        GeneratedFiles { globs, attributed }
+    }
+
+    pub fn is_generated(&self, path: &str) -> bool {
+        self.attributed.contains(path)
+            || self
+                .globs
+                .matched_path_or_any_parents(Path::new(path), false)
+                .is_ignore()
+    }
+}
+
+/// Parse `git check-attr linguist-generated diff -- <paths>` output into the
+/// set of paths that are generated (`linguist-generated` set/true) or
+/// excluded from diffs (`-diff`, `diff=false`).
+pub fn parse_check_attr(output: &str) -> HashSet<String> {
+    let mut generated = HashSet::new();
+    for line in output.lines() {
+        let mut parts = line.rsplitn(3, ": ");
+        let (Some(value), Some(attr), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let hit = match attr {
+            "linguist-generated" => value == "set" || value == "true",
+            "diff" => value == "unset" || value == "false",
+            _ => false,
+        };
+        if hit {
+            generated.insert(path.to_string());
+        }
+    }
+    generated
+}
+
+/// Split `diff` into the part to compact normally and one summary line per generated file.
+pub fn collapse(diff: &str, generated: &GeneratedFiles) -> (String, Vec<String>) {
+    let mut kept = String::with_capacity(diff.len());
+    let mut summaries = Vec::new();
+    let mut chunk = String::new();
+
+    let mut flush = |chunk: &mut String, kept: &mut String| {
+        if chunk.is_empty() {
+            return;
+        }
+        let file = semantic_diff::parse_unified_diff(chunk).into_iter().next();
+        match file {
+            Some(file) if generated.is_generated(file.path()) => {
+                summaries.push(summarize(&file));
+            }
+            _ => kept.push_str(chunk),
+        }
+        chunk.clear();
+    };
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            flush(&mut chunk, &mut kept);
+        }
+        if chunk.is_empty() && !line.starts_with("diff --git ") {
+            // Preamble (commit header in `git show`) is always kept
+            kept.push_str(line);
+            kept.push('\n');
+            continue;
+        }
+        chunk.push_str(line);
+        chunk.push('\n');
+    }
+    flush(&mut chunk, &mut kept);
+
+    (kept, summaries)
+}
+
+/// One line for a generated file; lockfiles list dependency changes
+pub fn summarize(file: &FileDiff) -> String {
+    let path = file.path();
+    let header = format!(
+        "📦 {} (generated, +{} -{})",
+        path,
+        file.added(),
+        file.removed()
+    );
+
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(path);
+    let Some(kind) = LockKind::from_file_name(name) else {
+        return header;
+    };
+
+    let (old_side, new_side) = sides(file);
+    let old = kind.parse(&old_side);
+    let new = kind.parse(&new_side);
+    let changes = diff_versions(&old, &new);
+    if changes.is_empty() {
+        return header;
+    }
+    format!("{}: {}", header, changes.render(kind.noun()))
+}
+
+/// Old-side (context + removed) and new-side (context + added) text of a file's hunks
+fn sides(file: &FileDiff) -> (String, String) {
+    let mut old = String::new();
+    let mut new = String::new();
+    for hunk in &file.hunks {
+        for line in &hunk.lines {
+            match line {
+                DiffLine::Context(l) => {
+                    old.push_str(l);
+                    old.push('\n');
+                    new.push_str(l);
+                    new.push('\n');
+                }
+                DiffLine::Removed(l) => {
+                    old.push_str(l);
+                    old.push('\n');
+                }
+                DiffLine::Added(l) => {
+                    new.push_str(l);
+                    new.push('\n');
+                }
+            }
+        }
+        // Hunks are not contiguous: don't let a name from one bleed into the next
+        old.push('\n');
+        new.push('\n');
+    }
+    (old, new)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LockKind {
+    /// Cargo.lock, poetry.lock, uv.lock: `name = "x"` then `version = "y"`
+    TomlPackages,
+    /// package-lock.json / npm-shrinkwrap.json
+    NpmJson,
+    PnpmYaml,
+    YarnLock,
+    GoSum,
+    GemfileLock,
+    ComposerJson,
+}
+
+lazy_static! {
+    static ref TOML_NAME: Regex = Regex::new(r#"^name = "([^"]+)""#).unwrap();
+    static ref TOML_VERSION: Regex = Regex::new(r#"^version = "([^"]+)""#).unwrap();
+    static ref NPM_KEY: Regex =
+        Regex::new(r#"^\s*"(?:.*node_modules/)?((?:@[^/"]+/)?[^/"]+)": \{"#).unwrap();
+    static ref NPM_VERSION: Regex = Regex::new(r#"^\s*"version": "([^"]+)""#).unwrap();
+    static ref PNPM_PACKAGE: Regex =
+        Regex::new(r"^\s{2}'?/?((?:@[^/@\s']+/)?[^/@\s']+)[@/](\d[^:(\s']*)").unwrap();
+    static ref YARN_HEADER: Regex = Regex::new(r#"^"?((?:@[^/@"]+/)?[^@"\s]+)@"#).unwrap();
+    static ref YARN_VERSION: Regex = Regex::new(r#"^\s+version:? "?([^"\s]+)"?"#).unwrap();
+    static ref GO_SUM: Regex = Regex::new(r"^(\S+) (v[^/\s]+)(?:/go\.mod)? h1:").unwrap();
+    static ref GEM_SPEC: Regex = Regex::new(r"^\s{4}([\w.-]+) \(([^)]+)\)$").unwrap();
+    static ref COMPOSER_NAME: Regex = Regex::new(r#"^\s*"name": "([^"]+)""#).unwrap();
+}
+
+impl LockKind {
+    fn from_file_name(name: &str) -> Option<Self> {
+        match name {
+            "Cargo.lock" | "poetry.lock" | "uv.lock" => Some(LockKind::TomlPackages),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(LockKind::NpmJson),
+            "pnpm-lock.yaml" => Some(LockKind::PnpmYaml),
+            "yarn.lock" => Some(LockKind::YarnLock),
+            "go.sum" => Some(LockKind::GoSum),
+            "Gemfile.lock" => Some(LockKind::GemfileLock),
+            "composer.lock" => Some(LockKind::ComposerJson),
+            _ => None,
+        }
+    }
+
+    fn noun(self) -> &'static str {
+        match self {
+            LockKind::TomlPackages => "crates",
+            LockKind::GoSum => "modules",
+            LockKind::GemfileLock => "gems",
+            _ => "packages",
+        }
+    }
+
+    /// name → versions seen on one side of the diff
+    fn parse(self, text: &str) -> BTreeMap<String, BTreeSet<String>> {
+        let mut found: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut current: Option<String> = None;
+
+        for line in text.lines() {
+            if line.trim().is_empty() && self != LockKind::NpmJson {
+                current = None;
+                continue;
+            }
+            let pair = match self {
+                LockKind::TomlPackages => {
+                    name_then_version(line, &mut current, &TOML_NAME, &TOML_VERSION)
+                }
+                LockKind::NpmJson => name_then_version(line, &mut current, &NPM_KEY, &NPM_VERSION),
+                LockKind::ComposerJson => {
+                    name_then_version(line, &mut current, &COMPOSER_NAME, &NPM_VERSION)
+                }
+                LockKind::YarnLock => {
+                    name_then_version(line, &mut current, &YARN_HEADER, &YARN_VERSION)
+                }
+                LockKind::PnpmYaml => PNPM_PACKAGE
+                    .captures(line)
+                    .map(|c| (c[1].to_string(), c[2].to_string())),
+                LockKind::GoSum => GO_SUM
+                    .captures(line)
+                    .map(|c| (c[1].to_string(), c[2].to_string())),
+                LockKind::GemfileLock => GEM_SPEC
+                    .captures(line)
+                    .map(|c| (c[1].to_string(), c[2].to_string())),
+            };
+            if let Some((name, version)) = pair {
+                found.entry(name).or_default().insert(version);
+            }
+        }
+        found
+    }
+}
+
+/// Two-line formats: remember the last name line, emit on the following version line
+fn name_then_version(
+    line: &str,
+    current: &mut Option<String>,
+    name_re: &Regex,
+    version_re: &Regex,
+) -> Option<(String, String)> {
+    if let Some(caps) = name_re.captures(line) {
+        *current = Some(caps[1].to_string());
+        return None;
+    }
+    let caps = version_re.captures(line)?;
+    let name = current.take()?;
+    Some((name, caps[1].to_string()))
+}
+
+#[derive(Debug, Default)]
+struct VersionChanges {
+    upgraded: Vec<(String, String, String)>,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl VersionChanges {
+    fn is_empty(&self) -> bool {
+        self.upgraded.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+
+    fn render(&self, noun: &str) -> String {
+        let mut parts: Vec<String> = self
+            .upgraded
+            .iter()
+            .take(MAX_UPGRADES)
+            .map(|(name, old, new)| format!("{} {}→{}", name, old, new))
+            .collect();
+        if self.upgraded.len() > MAX_UPGRADES {
+            parts.push(format!(
+                "+{} more updated",
+                self.upgraded.len() - MAX_UPGRADES
+            ));
+        }
+        let list = |names: &[String]| {
+            let mut shown = names.iter().take(MAX_NAMES).cloned().collect::<Vec<_>>();
+            if names.len() > MAX_NAMES {
+                shown.push("...".to_string());
+            }
+            shown.join(", ")
+        };
+        if !self.added.is_empty() {
+            parts.push(format!(
+                "+{} {} added ({})",
+                self.added.len(),
+                noun,
+                list(&self.added)
+            ));
+        }
+        if !self.removed.is_empty() {
+            parts.push(format!(
+                "-{} removed ({})",
+                self.removed.len(),
+                list(&self.removed)
+            ));
+        }
+        parts.join(", ")
+    }
+}
+
+fn diff_versions(
+    old: &BTreeMap<String, BTreeSet<String>>,
+    new: &BTreeMap<String, BTreeSet<String>>,
+) -> VersionChanges {
+    let mut changes = VersionChanges::default();
+
+    for (name, new_versions) in new {
+        match old.get(name) {
+            None => changes.added.push(name.clone()),
+            Some(old_versions) if old_versions != new_versions => {
+                let gone: Vec<&String> = old_versions.difference(new_versions).collect();
+                let came: Vec<&String> = new_versions.difference(old_versions).collect();
+                match (gone.first(), came.first()) {
+                    (Some(from), Some(to)) => {
+                        changes
+                            .upgraded
+                            .push((name.clone(), from.to_string(), to.to_string()));
+                    }
+                    // Extra copy of a crate alongside the old one, or one copy dropped
+                    (None, Some(to)) => changes.added.push(format!("{} {}", name, to)),
+                    (Some(from), None) => changes.removed.push(format!("{} {}", name, from)),
+                    (None, None) => {}
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    for name in old.keys() {
+        if !new.contains_key(name) {
+            changes.removed.push(name.clone());
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classifier() -> GeneratedFiles {
+        GeneratedFiles::new(&["*.lock".to_string()], HashSet::new())
+    }
+
+    #[test]
+    fn test_builtin_and_config_globs() {
+        let gen = GeneratedFiles::new(&["docs/api/*.html".to_string()], HashSet::new());
+        assert!(gen.is_generated("Cargo.lock"));
+        assert!(gen.is_generated("web/package-lock.json"));
+        assert!(gen.is_generated("src/__snapshots__/App.test.tsx.snap"));
+        assert!(gen.is_generated("static/app.min.js"));
+        assert!(gen.is_generated("docs/api/index.html"));
+        assert!(!gen.is_generated("src/main.rs"));
+        assert!(!gen.is_generated("package.json"));
+    }
+
+    #[test]
+    fn test_attributed_paths() {
+        let attrs = parse_check_attr(
+            "gen/schema.rs: linguist-generated: set\ngen/schema.rs: diff: unspecified\nassets/blob.bin: linguist-generated: unspecified\nassets/blob.bin: diff: unset\nsrc/lib.rs: linguist-generated: unspecified\nsrc/lib.rs: diff: unspecified\n",
+        );
+        let gen = GeneratedFiles::new(&[], attrs);
+        assert!(gen.is_generated("gen/schema.rs"));
+        assert!(gen.is_generated("assets/blob.bin"));
+        assert!(!gen.is_generated("src/lib.rs"));
+    }
+
+    #[test]
+    fn test_cargo_lock_summary() {
+        let diff = r#"diff --git a/Cargo.lock b/Cargo.lock
+index 1111111..2222222 100644
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -10,7 +10,7 @@
+ [[package]]
+ name = "serde"
+-version = "1.0.190"
++version = "1.0.197"
+ source = "registry+https://github.com/rust-lang/crates.io-index"
+@@ -40,0 +40,10 @@
++[[package]]
++name = "foo"
++version = "0.1.0"
++
++[[package]]
++name = "bar"
++version = "2.0.0"
++dependencies = [
++ "serde 1.0.197",
++]
+"#;
+        let (kept, summaries) = collapse(diff, &classifier());
+        assert!(kept.trim().is_empty(), "kept: {}", kept);
+        assert_eq!(
+            summaries,
+            vec!["📦 Cargo.lock (generated, +11 -1): serde 1.0.190→1.0.197, +2 crates added (bar, foo)"]
+        );
+    }
+
+    #[test]
+    fn test_package_lock_summary() {
+        let diff = r#"diff --git a/package-lock.json b/package-lock.json
+--- a/package-lock.json
++++ b/package-lock.json
+@@ -100,5 +100,5 @@
+     "node_modules/lodash": {
+-      "version": "4.17.20",
++      "version": "4.17.21",
+       "resolved": "https://registry.npmjs.org/lodash/-/lodash-4.17.21.tgz",
+@@ -200,4 +200,0 @@
+-    "node_modules/@types/left-pad": {
+-      "version": "1.2.0",
+-      "dev": true
+-    },
+"#;
+        let (_, summaries) = collapse(diff, &classifier());
+        assert_eq!(
+            summaries[0],
+            "📦 package-lock.json (generated, +1 -5): lodash 4.17.20→4.17.21, -1 removed (@types/left-pad)"
+        );
+    }
+
+    #[test]
+    fn test_go_sum_and_pnpm_and_yarn_and_gems() {
+        let go = "diff --git a/go.sum b/go.sum\n--- a/go.sum\n+++ b/go.sum\n@@ -1,2 +1,2 @@\n-github.com/pkg/errors v0.9.0 h1:abc=\n-github.com/pkg/errors v0.9.0/go.mod h1:def=\n+github.com/pkg/errors v0.9.1 h1:ghi=\n+github.com/pkg/errors v0.9.1/go.mod h1:jkl=\n";
+        let (_, s) = collapse(go, &classifier());
+        assert!(
+            s[0].ends_with("github.com/pkg/errors v0.9.0→v0.9.1"),
+            "{}",
+            s[0]
+        );
+
+        let pnpm = "diff --git a/pnpm-lock.yaml b/pnpm-lock.yaml\n--- a/pnpm-lock.yaml\n+++ b/pnpm-lock.yaml\n@@ -50,3 +50,3 @@ packages:\n-  /react@18.2.0:\n+  /react@18.3.1:\n     resolution: {integrity: sha512-x}\n";
+        let (_, s) = collapse(pnpm, &classifier());
+        assert!(s[0].ends_with("react 18.2.0→18.3.1"), "{}", s[0]);
+
+        let yarn = "diff --git a/yarn.lock b/yarn.lock\n--- a/yarn.lock\n+++ b/yarn.lock\n@@ -1,3 +1,3 @@\n \"@babel/core@^7.0.0\":\n-  version \"7.1.0\"\n+  version \"7.2.0\"\n";
+        let (_, s) = collapse(yarn, &classifier());
+        assert!(s[0].ends_with("@babel/core 7.1.0→7.2.0"), "{}", s[0]);
+
+        let gems = "diff --git a/Gemfile.lock b/Gemfile.lock\n--- a/Gemfile.lock\n+++ b/Gemfile.lock\n@@ -3,2 +3,2 @@\n-    rails (7.0.1)\n+    rails (7.1.0)\n";
+        let (_, s) = collapse(gems, &classifier());
+        assert!(s[0].ends_with("rails 7.0.1→7.1.0"), "{}", s[0]);
+    }
+
+    #[test]
+    fn test_non_lockfile_generated_is_one_line() {
+        let diff = "diff --git a/dist/app.min.js b/dist/app.min.js\n--- a/dist/app.min.js\n+++ b/dist/app.min.js\n@@ -1 +1 @@\n-var a=1\n+var a=2\n";
+        let (_, summaries) = collapse(diff, &classifier());
+        assert_eq!(summaries, vec!["📦 dist/app.min.js (generated, +1 -1)"]);
+    }
+
+    #[test]
+    fn test_collapse_keeps_source_files_and_preamble() {
+        let diff = "commit abc\nAuthor: x\n\ndiff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\ndiff --git a/Cargo.lock b/Cargo.lock\n--- a/Cargo.lock\n+++ b/Cargo.lock\n@@ -1 +1 @@\n-x\n+y\n";
+        let (kept, summaries) = collapse(diff, &classifier());
+        assert!(kept.starts_with("commit abc"));
+        assert!(kept.contains("src/a.rs"));
+        assert!(!kept.contains("Cargo.lock"));
+        assert_eq!(summaries, vec!["📦 Cargo.lock (generated, +1 -1)"]);
+    }
+}
@@ -3,6 +3,7 @@
 //! Provides token-optimized alternatives to verbose `gh` commands.
 //! Focuses on extracting essential information from JSON outputs.
 
+use crate::ci_log;
 use crate::git;
 use crate::tracking;
 use crate::utils::{ok_confirmation, truncate};
@@ -25,7 +26,7 @@ lazy_static! {
 /// Filter markdown body to remove noise while preserving meaningful content.
 /// Removes HTML comments, badge lines, image-only lines, horizontal rules,
 /// and collapses excessive blank lines. Preserves code blocks untouched.
-fn filter_markdown_body(body: &str) -> String {
+pub(crate) fn filter_markdown_body(body: &str) -> String {
     if body.is_empty() {
         return String::new();
     }
@@ -115,7 +116,7 @@ fn has_json_flag(args: &[String]) -> bool {
 /// Extract a positional identifier (PR/issue number) from args, returning it
 /// separately from the remaining extra flags (like -R, --repo, etc.).
 /// Handles both `view 123 -R owner/repo` and `view -R owner/repo 123`.
-fn extract_identifier_and_extra_args(args: &[String]) -> Option<(String, Vec<String>)> {
+pub(crate) fn extract_identifier_and_extra_args(args: &[String]) -> Option<(String, Vec<String>)> {
     if args.is_empty() {
         return None;
     }
@@ -194,6 +195,7 @@ fn run_pr(args: &[String], verbose: u8, ultra_compact: bool) -> Result<()> {
         "create" => pr_create(&args[1..], verbose),
         "merge" => pr_merge(&args[1..], verbose),
         "diff" => pr_diff(&args[1..], verbose),
+        "comments" => pr_comments(&args[1..], verbose),
         "comment" => pr_action("commented", args, verbose),
         "edit" => pr_action("edited", args, verbose),
         _ => run_passthrough("gh", "pr", args),
@@ -293,25 +295,28 @@ fn should_passthrough_pr_view(extra_args: &[String]) -> bool {
 fn view_pr(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
-    let (pr_number, extra_args) = match extract_identifier_and_extra_args(args) {
+    let (pr_number, mut extra_args) = match extract_identifier_and_extra_args(args) {
         Some(result) => result,
         None => return Err(anyhow::anyhow!("PR number required")),
     };
 
+    // --comments: conversation comments, then unresolved review threads
+    let with_comments = extra_args.iter().any(|a| a == "--comments");
+    extra_args.retain(|a| a != "--comments");
+
     // If the user provides --jq or --web, pass through directly.
     // Note: --json is already handled globally by run() via has_json_flag.
     if should_passthrough_pr_view(&extra_args) {
         return run_passthrough_with_extra("gh", &["pr", "view", &pr_number], &extra_args);
     }
 
+    let mut fields =
+        "number,title,state,author,body,url,mergeable,reviews,statusCheckRollup".to_string();
+    if with_comments {
+        fields.push_str(",comments");
+    }
     let mut cmd = Command::new("gh");
-    cmd.args([
-        "pr",
-        "view",
-        &pr_number,
-        "--json",
-        "number,title,state,author,body,url,mergeable,reviews,statusCheckRollup",
-    ]);
+    cmd.args(["pr", "view", &pr_number, "--json", &fields]);
     for arg in &extra_args {
         cmd.arg(arg);
     }
@@ -458,6 +463,21 @@ fn view_pr(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
         }
     }
 
+    let mut raw = raw;
+    if with_comments {
+        if let Some(formatted) = format_conversation_comments(&json["comments"]) {
+            println!("\n{}", formatted);
+            filtered.push('\n');
+            filtered.push_str(&formatted);
+        }
+        let (threads, threads_raw) = fetch_review_threads(&pr_number, &extra_args)?;
+        let formatted = format_review_threads(&threads, false);
+        println!("\n{}", formatted);
+        filtered.push('\n');
+        filtered.push_str(&formatted);
+        raw.push_str(&threads_raw);
+    }
+
     timer.track(
         &format!("gh pr view {}", pr_number),
         &format!("rtk gh pr view {}", pr_number),
@@ -861,12 +881,58 @@ fn list_runs(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
 }
 
 /// Check if run view args should bypass filtering and pass through directly.
-/// Flags like --log-failed, --log, and --json produce output that the filter
-/// would incorrectly strip.
+/// `--json` output is already what the caller asked for; `--log` /
+/// `--log-failed` are compacted separately (see [`wants_run_log`]).
 fn should_passthrough_run_view(extra_args: &[String]) -> bool {
+    extra_args.iter().any(|a| a == "--json")
+}
+
+/// `--log` / `--log-failed`: job logs, compacted instead of the run summary
+fn wants_run_log(extra_args: &[String]) -> bool {
     extra_args
         .iter()
-        .any(|a| a == "--log-failed" || a == "--log" || a == "--json")
+        .any(|a| a == "--log-failed" || a == "--log")
+}
+
+/// `gh run view --log[-failed]`: failing steps only, routed through rtk's tool filters
+fn view_run_log(run_id: &str, extra_args: &[String]) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = Command::new("gh");
+    cmd.args(["run", "view", run_id]);
+    for arg in extra_args {
+        cmd.arg(arg);
+    }
+
+    let output = cmd.output().context("Failed to run gh run view")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        timer.track(
+            &format!("gh run view {} {}", run_id, extra_args.join(" ")),
+            &format!("rtk gh run view {} {}", run_id, extra_args.join(" ")),
+            &stderr,
+            &stderr,
+        );
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let mut filtered = ci_log::compress_gh_run_log(&raw);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "gh_run_log", 1) {
+        filtered.push('\n');
+        filtered.push_str(&hint);
+    }
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("gh run view {} {}", run_id, extra_args.join(" ")),
+        &format!("rtk gh run view {} {}", run_id, extra_args.join(" ")),
+        &raw,
+        &filtered,
+    );
+    Ok(())
 }
 
 fn view_run(args: &[String], _verbose: u8) -> Result<()> {
@@ -875,10 +941,13 @@ fn view_run(args: &[String], _verbose: u8) -> Result<()> {
         None => return Err(anyhow::anyhow!("Run ID required")),
     };
 
-    // Pass through when user requests logs or JSON — the filter would strip them
+    // Pass through when user requests JSON — the filter would strip it
     if should_passthrough_run_view(&extra_args) {
         return run_passthrough_with_extra("gh", &["run", "view", &run_id], &extra_args);
     }
+    if wants_run_log(&extra_args) {
+        return view_run_log(&run_id, &extra_args);
+    }
 
     let timer = tracking::TimedExecution::start();
 
@@ -1124,9 +1193,11 @@ fn pr_merge(args: &[String], _verbose: u8) -> Result<()> {
 fn pr_diff(args: &[String], _verbose: u8) -> Result<()> {
     // --no-compact: pass full diff through (gh CLI doesn't know this flag, strip it)
     let no_compact = args.iter().any(|a| a == "--no-compact");
+    // --semantic: symbol-labelled summary via the git diff engine (also rtk-only)
+    let semantic = args.iter().any(|a| a == "--semantic");
     let gh_args: Vec<String> = args
         .iter()
-        .filter(|a| *a != "--no-compact")
+        .filter(|a| *a != "--no-compact" && *a != "--semantic")
         .cloned()
         .collect();
 
@@ -1157,7 +1228,17 @@ fn pr_diff(args: &[String], _verbose: u8) -> Result<()> {
         print!("{}", msg);
         msg.to_string()
     } else {
-        let compacted = git::compact_diff(&raw, 500);
+        let full_cmd = format!(
+            "rtk gh pr diff --no-compact {}",
+            crate::utils::shell_join(&gh_args)
+        );
+        let compacted = git::review_diff(
+            &raw,
+            500,
+            semantic,
+            full_cmd.trim_end(),
+            &git::GitGlobalOpts::default(),
+        );
         println!("{}", compacted);
         compacted
     };
@@ -1166,6 +1247,344 @@ fn pr_diff(args: &[String], _verbose: u8) -> Result<()> {
     Ok(())
 }
 
+const REVIEW_THREADS_QUERY: &str = r#"query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        pageInfo { hasNextPage }
+        nodes {
+          isResolved
+          isOutdated
+          path
+          line
+          originalLine
+          startLine
+          comments(first: 50) {
+            nodes { author { login __typename } body diffHunk }
+          }
+        }
+      }
+    }
+  }
+}"#;
+
+/// Max lines of diff hunk shown per thread
+const THREAD_HUNK_LINES: usize = 5;
+/// Max prose lines shown per comment (suggestion blocks are kept whole)
+const COMMENT_TEXT_LINES: usize = 3;
+
+/// Max conversation comments shown by `pr view --comments` (latest kept)
+const MAX_CONVERSATION_COMMENTS: usize = 20;
+
+/// Repo of the PR: `-R`/`--repo`, else the `[HOST/]OWNER/REPO` of a PR URL
+fn target_repo(pr_number: &str, extra_args: &[String]) -> Option<String> {
+    let mut iter = extra_args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "-R" || arg == "--repo" {
+            return iter.next().cloned();
+        }
+        if let Some(repo) = arg.strip_prefix("--repo=") {
+            return Some(repo.to_string());
+        }
+    }
+
+    let path = pr_number
+        .strip_prefix("https://")
+        .or_else(|| pr_number.strip_prefix("http://"))?;
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.as_slice() {
+        [host, owner, repo, "pull", ..] if *host == "github.com" => {
+            Some(format!("{}/{}", owner, repo))
+        }
+        [host, owner, repo, "pull", ..] => Some(format!("{}/{}/{}", host, owner, repo)),
+        _ => None,
+    }
+}
+
+/// Fetch review threads via GraphQL (REST review comments carry no resolved state)
+fn fetch_review_threads(pr_number: &str, extra_args: &[String]) -> Result<(Value, String)> {
+    let number = pr_number
+        .trim_start_matches('#')
+        .trim_end_matches('/')
+        .split('/')
+        .skip_while(|part| *part != "pull")
+        .nth(1)
+        .unwrap_or_else(|| pr_number.trim_start_matches('#'));
+
+    let mut cmd = Command::new("gh");
+    cmd.args([
+        "api",
+        "graphql",
+        "-F",
+        "owner={owner}",
+        "-F",
+        "name={repo}",
+        "-F",
+        &format!("number={}", number),
+        "-f",
+        &format!("query={}", REVIEW_THREADS_QUERY),
+    ]);
+    // gh api has no -R: the target repo comes from GH_REPO
+    if let Some(repo) = target_repo(pr_number, extra_args) {
+        cmd.env("GH_REPO", repo);
+    }
+
+    let output = cmd.output().context("Failed to run gh api graphql")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let json: Value =
+        serde_json::from_str(&raw).context("Failed to parse gh api graphql output")?;
+    Ok((json, raw))
+}
+
+fn is_bot(comment: &Value) -> bool {
+    comment["author"]["__typename"].as_str() == Some("Bot")
+        || comment["author"]["login"]
+            .as_str()
+            .is_some_and(|l| l.ends_with("[bot]"))
+}
+
+/// Comment body as a few lines: prose trimmed, ```suggestion blocks kept as `+` lines
+fn compact_comment_body(body: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut prose = Vec::new();
+    let mut in_suggestion = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if in_suggestion {
+            if trimmed.starts_with("```") {
+                in_suggestion = false;
+            } else {
+                lines.push(format!("  + {}", line));
+            }
+            continue;
+        }
+        if trimmed.starts_with("```suggestion") {
+            in_suggestion = true;
+            lines.push("suggestion:".to_string());
+            continue;
+        }
+        if !trimmed.is_empty() && !trimmed.starts_with("<!--") {
+            prose.push(truncate(trimmed, 160));
+        }
+    }
+
+    let hidden = prose.len().saturating_sub(COMMENT_TEXT_LINES);
+    let mut out: Vec<String> = prose.into_iter().take(COMMENT_TEXT_LINES).collect();
+    if hidden > 0 {
+        out.push(format!("[+{} lines]", hidden));
+    }
+    out.extend(lines);
+    out
+}
+
+/// PR conversation comments (`gh pr view --json comments`), latest last.
+/// Bot comments collapse to a single line.
+fn format_conversation_comments(comments: &Value) -> Option<String> {
+    let comments = comments.as_array().filter(|c| !c.is_empty())?;
+
+    let mut out = vec![format!(
+        "🗨 {} comment{}",
+        comments.len(),
+        if comments.len() == 1 { "" } else { "s" }
+    )];
+    let hidden = comments.len().saturating_sub(MAX_CONVERSATION_COMMENTS);
+    if hidden > 0 {
+        out.push(format!("  ... {} earlier comments", hidden));
+    }
+    for comment in &comments[hidden..] {
+        let author = comment["author"]["login"].as_str().unwrap_or("ghost");
+        let mut lines = compact_comment_body(comment["body"].as_str().unwrap_or("")).into_iter();
+        if is_bot(comment) {
+            out.push(format!(
+                "  🤖 @{}: {}",
+                author,
+                lines.next().unwrap_or_default()
+            ));
+            continue;
+        }
+        out.push(format!(
+            "  @{}: {}",
+            author,
+            lines.next().unwrap_or_default()
+        ));
+        for rest in lines {
+            out.push(format!("    {}", rest));
+        }
+    }
+    Some(out.join("\n"))
+}
+
+/// Last lines of the diff hunk, covering the commented line range
+fn trim_diff_hunk(hunk: &str, span: usize) -> Vec<String> {
+    let lines: Vec<&str> = hunk.lines().filter(|l| !l.starts_with("@@")).collect();
+    let keep = span.clamp(1, THREAD_HUNK_LINES);
+    lines[lines.len().saturating_sub(keep)..]
+        .iter()
+        .map(|l| truncate(l, 120))
+        .collect()
+}
+
+/// Unresolved review threads grouped by file. Resolved and outdated threads
+/// are only counted unless `show_all`.
+fn format_review_threads(json: &Value, show_all: bool) -> String {
+    let threads_json = &json["data"]["repository"]["pullRequest"]["reviewThreads"];
+    let empty = Vec::new();
+    let threads = threads_json["nodes"].as_array().unwrap_or(&empty);
+
+    let mut resolved = 0;
+    let mut outdated = 0;
+    let mut by_file: Vec<(&str, Vec<&Value>)> = Vec::new();
+    for thread in threads {
+        if !show_all {
+            if thread["isResolved"].as_bool() == Some(true) {
+                resolved += 1;
+                continue;
+            }
+            if thread["isOutdated"].as_bool() == Some(true) {
+                outdated += 1;
+                continue;
+            }
+        }
+        let path = thread["path"].as_str().unwrap_or("?");
+        match by_file.iter_mut().find(|(p, _)| *p == path) {
+            Some((_, list)) => list.push(thread),
+            None => by_file.push((path, vec![thread])),
+        }
+    }
+
+    let shown: usize = by_file.iter().map(|(_, t)| t.len()).sum();
+    let mut out = Vec::new();
+    let mut header = format!(
+        "💬 {} {} thread{} in {} file{}",
+        shown,
+        if show_all { "review" } else { "unresolved" },
+        if shown == 1 { "" } else { "s" },
+        by_file.len(),
+        if by_file.len() == 1 { "" } else { "s" }
+    );
+    if resolved + outdated > 0 {
+        header.push_str(&format!(
+            " ({} resolved, {} outdated hidden; --all to show)",
+            resolved, outdated
+        ));
+    }
+    out.push(header);
+
+    for (path, threads) in &by_file {
+        out.push(format!("📄 {}", path));
+        for thread in threads {
+            let empty = Vec::new();
+            let comments = thread["comments"]["nodes"].as_array().unwrap_or(&empty);
+            let Some(first) = comments.first() else {
+                continue;
+            };
+
+            let line = thread["line"]
+                .as_u64()
+                .or_else(|| thread["originalLine"].as_u64())
+                .unwrap_or(0);
+            let start = thread["startLine"].as_u64().unwrap_or(line);
+            let location = if start < line {
+                format!("L{}-{}", start, line)
+            } else {
+                format!("L{}", line)
+            };
+            let mut flags = String::new();
+            if thread["isResolved"].as_bool() == Some(true) {
+                flags.push_str(" [resolved]");
+            }
+            if thread["isOutdated"].as_bool() == Some(true) {
+                flags.push_str(" [outdated]");
+            }
+
+            let author = first["author"]["login"].as_str().unwrap_or("ghost");
+            let body = compact_comment_body(first["body"].as_str().unwrap_or(""));
+            if is_bot(first) {
+                // Bot threads collapse to a single line
+                let summary = body.first().cloned().unwrap_or_default();
+                out.push(format!(
+                    "  {}{} 🤖 @{}: {}",
+                    location, flags, author, summary
+                ));
+                continue;
+            }
+
+            let mut body_lines = body.into_iter();
+            out.push(format!(
+                "  {}{} @{}: {}",
+                location,
+                flags,
+                author,
+                body_lines.next().unwrap_or_default()
+            ));
+            let span = (line.saturating_sub(start) + 1) as usize;
+            for hunk_line in trim_diff_hunk(first["diffHunk"].as_str().unwrap_or(""), span) {
+                out.push(format!("    > {}", hunk_line));
+            }
+            for rest in body_lines {
+                out.push(format!("    {}", rest));
+            }
+
+            let mut bot_replies = 0;
+            for reply in comments.iter().skip(1) {
+                if is_bot(reply) {
+                    bot_replies += 1;
+                    continue;
+                }
+                let author = reply["author"]["login"].as_str().unwrap_or("ghost");
+                let mut lines =
+                    compact_comment_body(reply["body"].as_str().unwrap_or("")).into_iter();
+                out.push(format!(
+                    "    ↳ @{}: {}",
+                    author,
+                    lines.next().unwrap_or_default()
+                ));
+                for rest in lines {
+                    out.push(format!("      {}", rest));
+                }
+            }
+            if bot_replies > 0 {
+                out.push(format!("    ↳ +{} bot replies", bot_replies));
+            }
+        }
+    }
+
+    if threads_json["pageInfo"]["hasNextPage"].as_bool() == Some(true) {
+        out.push("... more threads not fetched (first 100 shown)".to_string());
+    }
+    out.join("\n")
+}
+
+fn pr_comments(args: &[String], _verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let show_all = args.iter().any(|a| a == "--all");
+    let args: Vec<String> = args.iter().filter(|a| *a != "--all").cloned().collect();
+    let (pr_number, extra_args) = match extract_identifier_and_extra_args(&args) {
+        Some(result) => result,
+        None => return Err(anyhow::anyhow!("PR number required")),
+    };
+
+    let (json, raw) = fetch_review_threads(&pr_number, &extra_args)?;
+    let filtered = format_review_threads(&json, show_all);
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("gh api graphql (pr {} review threads)", pr_number),
+        &format!("rtk gh pr comments {}", pr_number),
+        &raw,
+        &filtered,
+    );
+    Ok(())
+}
+
 /// Generic PR action handler for comment/edit
 fn pr_action(action: &str, args: &[String], _verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
@@ -1228,7 +1647,11 @@ fn run_api(args: &[String], _verbose: u8) -> Result<()> {
 }
 
 /// Pass through a command with base args + extra args, tracking as passthrough.
-fn run_passthrough_with_extra(cmd: &str, base_args: &[&str], extra_args: &[String]) -> Result<()> {
+pub(crate) fn run_passthrough_with_extra(
+    cmd: &str,
+    base_args: &[&str],
+    extra_args: &[String],
+) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
     let mut command = Command::new(cmd);
@@ -1259,7 +1682,7 @@ fn run_passthrough_with_extra(cmd: &str, base_args: &[&str], extra_args: &[Strin
     Ok(())
 }
 
-fn run_passthrough(cmd: &str, subcommand: &str, args: &[String]) -> Result<()> {
+pub(crate) fn run_passthrough(cmd: &str, subcommand: &str, args: &[String]) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
     let mut command = Command::new(cmd);
@@ -1341,13 +1764,19 @@ mod tests {
     }
 
     #[test]
-    fn test_run_view_passthrough_log_failed() {
-        assert!(should_passthrough_run_view(&["--log-failed".into()]));
+    fn test_run_view_log_failed_is_filtered() {
+        assert!(!should_passthrough_run_view(&["--log-failed".into()]));
+        assert!(wants_run_log(&["--log-failed".into()]));
     }
 
     #[test]
-    fn test_run_view_passthrough_log() {
-        assert!(should_passthrough_run_view(&["--log".into()]));
+    fn test_run_view_log_is_filtered() {
+        assert!(!should_passthrough_run_view(&["--log".into()]));
+        assert!(wants_run_log(&[
+            "--job".into(),
+            "123".into(),
+            "--log".into()
+        ]));
     }
 
     #[test]
@@ -1368,6 +1797,120 @@ mod tests {
         assert!(!should_passthrough_run_view(&["--web".into()]));
     }
 
+    // --- review thread tests ---
+
+    fn review_threads_fixture() -> Value {
+        serde_json::json!({"data": {"repository": {"pullRequest": {"reviewThreads": {
+            "pageInfo": {"hasNextPage": false},
+            "nodes": [
+                {
+                    "isResolved": false, "isOutdated": false, "path": "src/main.rs",
+                    "line": 42, "originalLine": 42, "startLine": null,
+                    "comments": {"nodes": [
+                        {"author": {"login": "alice", "__typename": "User"},
+                         "body": "Handle the None case here.\n\n```suggestion\nlet x = foo?;\n```",
+                         "diffHunk": "@@ -30,6 +30,13 @@ fn main() {\n     let a = 1;\n+    let b = 2;\n+    let x = foo.unwrap();"},
+                        {"author": {"login": "bob", "__typename": "User"},
+                         "body": "Good point, will fix", "diffHunk": ""},
+                        {"author": {"login": "coderabbitai", "__typename": "Bot"},
+                         "body": "Summary...", "diffHunk": ""}
+                    ]}
+                },
+                {
+                    "isResolved": true, "isOutdated": false, "path": "src/lib.rs",
+                    "line": 3, "originalLine": 3, "startLine": null,
+                    "comments": {"nodes": [
+                        {"author": {"login": "alice", "__typename": "User"},
+                         "body": "nit", "diffHunk": "@@ -1 +1 @@\n+x"}
+                    ]}
+                },
+                {
+                    "isResolved": false, "isOutdated": true, "path": "src/lib.rs",
+                    "line": null, "originalLine": 9, "startLine": null,
+                    "comments": {"nodes": [
+                        {"author": {"login": "alice", "__typename": "User"},
+                         "body": "old", "diffHunk": "@@ -1 +1 @@\n+y"}
+                    ]}
+                },
+                {
+                    "isResolved": false, "isOutdated": false, "path": "src/lib.rs",
+                    "line": 20, "originalLine": 20, "startLine": 18,
+                    "comments": {"nodes": [
+                        {"author": {"login": "github-actions[bot]", "__typename": "Bot"},
+                         "body": "Clippy: needless borrow\nmore details", "diffHunk": "@@ -1 +1 @@\n+z"}
+                    ]}
+                }
+            ]
+        }}}}})
+    }
+
+    #[test]
+    fn test_format_review_threads_unresolved_only() {
+        let result = format_review_threads(&review_threads_fixture(), false);
+        assert_eq!(
+            result,
+            "💬 2 unresolved threads in 2 files (1 resolved, 1 outdated hidden; --all to show)\n\
+             📄 src/main.rs\n  \
+             L42 @alice: Handle the None case here.\n    \
+             > +    let x = foo.unwrap();\n    \
+             suggestion:\n      \
+             + let x = foo?;\n    \
+             ↳ @bob: Good point, will fix\n    \
+             ↳ +1 bot replies\n\
+             📄 src/lib.rs\n  \
+             L18-20 🤖 @github-actions[bot]: Clippy: needless borrow"
+        );
+    }
+
+    #[test]
+    fn test_format_review_threads_all() {
+        let result = format_review_threads(&review_threads_fixture(), true);
+        assert!(result.starts_with("💬 4 review threads in 2 files\n"));
+        assert!(result.contains("L3 [resolved] @alice: nit"));
+        assert!(result.contains("L9 [outdated] @alice: old"));
+    }
+
+    #[test]
+    fn test_format_conversation_comments() {
+        let comments = serde_json::json!([
+            {"author": {"login": "alice"}, "body": "Looks good overall.\n\nOne question about the retry loop."},
+            {"author": {"login": "ci-helper[bot]"}, "body": "Coverage: 81%\nDetails below"}
+        ]);
+        let result = format_conversation_comments(&comments).unwrap();
+        assert!(result.starts_with("🗨 2 comments"));
+        assert!(result.contains("  @alice: Looks good overall."));
+        assert!(result.contains("    One question about the retry loop."));
+        assert!(result.contains("  🤖 @ci-helper[bot]: Coverage: 81%"));
+        assert!(!result.contains("Details below"));
+        assert!(format_conversation_comments(&serde_json::json!([])).is_none());
+    }
+
+    #[test]
+    fn test_target_repo_from_flag_or_url() {
+        let none: Vec<String> = Vec::new();
+        assert_eq!(
+            target_repo("https://github.com/other/repo/pull/5", &none),
+            Some("other/repo".to_string())
+        );
+        assert_eq!(
+            target_repo("https://ghe.example.com/team/app/pull/7/files", &none),
+            Some("ghe.example.com/team/app".to_string())
+        );
+        assert_eq!(target_repo("5", &none), None);
+        let args: Vec<String> = vec!["-R".into(), "owner/repo".into()];
+        assert_eq!(target_repo("5", &args), Some("owner/repo".to_string()));
+        let args: Vec<String> = vec!["--repo=owner/repo".into()];
+        assert_eq!(target_repo("5", &args), Some("owner/repo".to_string()));
+    }
+
+    #[test]
+    fn test_trim_diff_hunk_covers_multiline_comment() {
+        let hunk = "@@ -1,4 +1,4 @@\n a\n b\n+c\n+d";
+        assert_eq!(trim_diff_hunk(hunk, 1), vec!["+d"]);
+        assert_eq!(trim_diff_hunk(hunk, 2), vec!["+c", "+d"]);
+        assert_eq!(trim_diff_hunk(hunk, 50).len(), 4);
+    }
+
     // --- filter_markdown_body tests ---
 
     #[test]
@@ -1,6 +1,10 @@
+use crate::generated_files::{self, GeneratedFiles};
+use crate::semantic_diff;
 use crate::tracking;
 use anyhow::{Context, Result};
+use std::collections::HashSet;
 use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 #[derive(Debug, Clone, Default)]
@@ -44,6 +48,24 @@ fn git_cmd(opts: &GitGlobalOpts) -> Command {
     cmd
 }
 
+/// Shell command line for `git <subcommand> <args>` with the repository
+/// location options, for "[full diff: ...]" hints
+fn git_command_line(opts: &GitGlobalOpts, subcommand: &[&str], args: &[String]) -> String {
+    let mut words: Vec<String> = Vec::new();
+    for dir in &opts.dir {
+        words.extend(["-C".to_string(), dir.clone()]);
+    }
+    if let Some(ref git_dir) = opts.git_dir {
+        words.extend(["--git-dir".to_string(), git_dir.clone()]);
+    }
+    if let Some(ref work_tree) = opts.work_tree {
+        words.extend(["--work-tree".to_string(), work_tree.clone()]);
+    }
+    words.extend(subcommand.iter().map(|s| s.to_string()));
+    words.extend(args.iter().cloned());
+    format!("git {}", crate::utils::shell_join(&words))
+}
+
 #[derive(Debug, Clone)]
 pub enum GitCommand {
     Diff,
@@ -58,6 +80,8 @@ pub enum GitCommand {
     Fetch,
     Stash { subcommand: Option<String> },
     Worktree,
+    Conflicts,
+    Blame,
 }
 
 pub fn run(
@@ -80,6 +104,8 @@ pub fn run(
         GitCommand::Fetch => run_fetch(args, verbose, opts),
         GitCommand::Stash { subcommand } => run_stash(subcommand.as_deref(), args, verbose, opts),
         GitCommand::Worktree => run_worktree(args, verbose, opts),
+        GitCommand::Conflicts => run_conflicts(args, verbose, opts),
+        GitCommand::Blame => run_blame(args, verbose, opts),
     }
 }
 
@@ -115,7 +141,14 @@ fn run_diff(
     verbose: u8,
     opts: &GitGlobalOpts,
 ) -> Result<()> {
-    let normalized = normalize_diff_args(args);
+    // --semantic is an rtk flag: strip it before git sees it
+    let wants_semantic = args.iter().any(|a| a == "--semantic");
+    let git_args: Vec<String> = args
+        .iter()
+        .filter(|a| *a != "--semantic")
+        .cloned()
+        .collect();
+    let normalized = normalize_diff_args(&git_args);
     let args = normalized.as_slice();
     let timer = tracking::TimedExecution::start();
 
@@ -156,6 +189,42 @@ fn run_diff(
         return Ok(());
     }
 
+    if wants_semantic {
+        let mut cmd = git_cmd(opts);
+        cmd.arg("diff");
+        for arg in args {
+            cmd.arg(arg);
+        }
+
+        let output = cmd.output().context("Failed to run git diff")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            eprintln!("{}", stderr);
+            std::process::exit(output.status.code().unwrap_or(1));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let summary = if stdout.trim().is_empty() {
+            "No changes".to_string()
+        } else {
+            let full_cmd = format!(
+                "rtk {}",
+                git_command_line(opts, &["diff", "--no-compact"], args)
+            );
+            review_diff(&stdout, max_lines.unwrap_or(100), true, &full_cmd, opts)
+        };
+        println!("{}", summary);
+
+        timer.track(
+            &format!("git diff {}", args.join(" ")),
+            &format!("rtk git diff --semantic {}", args.join(" ")),
+            &stdout,
+            &summary,
+        );
+
+        return Ok(());
+    }
+
     // Default RTK behavior: stat first, then compacted diff
     let mut cmd = git_cmd(opts);
     cmd.arg("diff").arg("--stat");
@@ -187,7 +256,17 @@ fn run_diff(
     let mut final_output = stat_stdout.to_string();
     if !diff_stdout.is_empty() {
         println!("\n--- Changes ---");
-        let compacted = compact_diff(&diff_stdout, max_lines.unwrap_or(100));
+        let full_cmd = format!(
+            "rtk {}",
+            git_command_line(opts, &["diff", "--no-compact"], args)
+        );
+        let compacted = review_diff(
+            &diff_stdout,
+            max_lines.unwrap_or(100),
+            false,
+            &full_cmd,
+            opts,
+        );
         println!("{}", compacted);
         final_output.push_str("\n--- Changes ---\n");
         final_output.push_str(&compacted);
@@ -211,6 +290,14 @@ fn run_show(
 ) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
+    let wants_semantic = args.iter().any(|a| a == "--semantic");
+    let git_args: Vec<String> = args
+        .iter()
+        .filter(|a| *a != "--semantic")
+        .cloned()
+        .collect();
+    let args = git_args.as_slice();
+
     // If user wants --stat or --format only, pass through
     let wants_stat_only = args
         .iter()
@@ -307,7 +394,14 @@ fn run_show(
         if verbose > 0 {
             println!("\n--- Changes ---");
         }
-        let compacted = compact_diff(diff_text, max_lines.unwrap_or(100));
+        let full_cmd = format!("rtk proxy {}", git_command_line(opts, &["show"], args));
+        let compacted = review_diff(
+            diff_text,
+            max_lines.unwrap_or(100),
+            wants_semantic,
+            &full_cmd,
+            opts,
+        );
         println!("{}", compacted);
         final_output.push_str(&format!("\n{}", compacted));
     }
@@ -328,7 +422,9 @@ fn is_blob_show_arg(arg: &str) -> bool {
     !arg.starts_with('-') && arg.contains(':')
 }
 
-pub(crate) fn compact_diff(diff: &str, max_lines: usize) -> String {
+/// `full_cmd` is the command that prints the whole diff, named in the hint
+/// when the compacted one is cut short.
+pub(crate) fn compact_diff(diff: &str, max_lines: usize, full_cmd: &str) -> String {
     let mut result = Vec::new();
     let mut current_file = String::new();
     let mut added = 0;
@@ -397,7 +493,7 @@ pub(crate) fn compact_diff(diff: &str, max_lines: usize) -> String {
             if hunk_skipped > 0 {
                 result.push(format!("  ... ({} lines truncated)", hunk_skipped));
             }
-            result.push("\n[full diff: rtk git diff --no-compact]".to_string());
+            result.push(format!("\n[full diff: {}]", full_cmd));
             break;
         }
     }
@@ -414,6 +510,178 @@ pub(crate) fn compact_diff(diff: &str, max_lines: usize) -> String {
     result.join("\n")
 }
 
+/// Semantic diff summary (symbols touched, collapsed whitespace/rename/moved hunks).
+///
+/// Post-images come from the object database, or from the worktree for
+/// unstaged changes whose blob was never written (verified via `hash-object`).
+fn semantic_compact_diff(
+    diff: &str,
+    max_lines: usize,
+    full_cmd: &str,
+    opts: &GitGlobalOpts,
+) -> String {
+    let load = |path: &str, blob: Option<&str>| -> Option<String> {
+        let blob = blob?;
+        let output = git_cmd(opts)
+            .args(["cat-file", "-p", blob])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        if output.status.success() {
+            return Some(String::from_utf8_lossy(&output.stdout).to_string());
+        }
+
+        let toplevel = git_cmd(opts)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .ok()?;
+        let root = String::from_utf8_lossy(&toplevel.stdout).trim().to_string();
+        let full = Path::new(&root).join(path);
+        let hash = git_cmd(opts).arg("hash-object").arg(&full).output().ok()?;
+        if !String::from_utf8_lossy(&hash.stdout).starts_with(blob) {
+            return None;
+        }
+        std::fs::read_to_string(full).ok()
+    };
+
+    semantic_diff::render(diff, max_lines, full_cmd, &load)
+}
+
+/// Compact a diff for review: generated and lock files collapse to one summary
+/// line each, the rest goes through `compact_diff` or the semantic renderer.
+/// `full_cmd` is the command that prints the whole diff.
+pub(crate) fn review_diff(
+    diff: &str,
+    max_lines: usize,
+    semantic: bool,
+    full_cmd: &str,
+    opts: &GitGlobalOpts,
+) -> String {
+    let (rest, summaries) = generated_files::collapse(diff, &generated_classifier(diff, opts));
+
+    let mut out = if rest.trim().is_empty() {
+        String::new()
+    } else if semantic {
+        semantic_compact_diff(&rest, max_lines, full_cmd, opts)
+    } else {
+        compact_diff(&rest, max_lines, full_cmd)
+    };
+    if !summaries.is_empty() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&summaries.join("\n"));
+    }
+    out
+}
+
+/// Generated-file classifier for the paths in `diff`: built-in lockfile globs,
+/// `filters.ignore_files` from config, and `.gitattributes`
+/// (`linguist-generated`, `-diff`).
+fn generated_classifier(diff: &str, opts: &GitGlobalOpts) -> GeneratedFiles {
+    let paths: Vec<String> = semantic_diff::parse_unified_diff(diff)
+        .iter()
+        .map(|f| f.path().to_string())
+        .collect();
+    if paths.is_empty() {
+        return GeneratedFiles::new(&[], Default::default());
+    }
+
+    let config = crate::config::Config::load().unwrap_or_default();
+    let globs = GeneratedFiles::new(&config.filters.ignore_files, Default::default());
+    let unmatched: Vec<&String> = paths.iter().filter(|p| !globs.is_generated(p)).collect();
+    if unmatched.is_empty() {
+        return globs;
+    }
+
+    GeneratedFiles::new(
+        &config.filters.ignore_files,
+        check_attr_generated(&unmatched, opts),
+    )
+}
+
+/// `git check-attr` for diff paths, which are relative to the repository root.
+/// Skipped when no attributes file could apply to them.
+fn check_attr_generated(paths: &[&String], opts: &GitGlobalOpts) -> HashSet<String> {
+    let Some(output) = git_cmd(opts)
+        .args(["rev-parse", "--show-toplevel", "--absolute-git-dir"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+    else {
+        return HashSet::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let (Some(toplevel), Some(git_dir)) = (lines.next(), lines.next()) else {
+        return HashSet::new();
+    };
+
+    // core.attributesFile, else the XDG default git falls back to
+    let configured = git_cmd(opts)
+        .args(["config", "--path", "core.attributesFile"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|p| !p.is_empty());
+    let global = match configured {
+        Some(path) => Some(Path::new(toplevel).join(path)),
+        None => std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|d| !d.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .map(|d| d.join("git/attributes")),
+    };
+    if !has_attributes_file(
+        Path::new(toplevel),
+        Path::new(git_dir),
+        global.as_deref(),
+        paths,
+    ) {
+        return HashSet::new();
+    }
+
+    git_cmd(opts)
+        .args([
+            "-C",
+            toplevel,
+            "check-attr",
+            "linguist-generated",
+            "diff",
+            "--",
+        ])
+        .args(paths)
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| generated_files::parse_check_attr(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// `.gitattributes` in the root or a directory containing one of `paths`,
+/// `$GIT_DIR/info/attributes`, or the user's global attributes file
+fn has_attributes_file(
+    toplevel: &Path,
+    git_dir: &Path,
+    global: Option<&Path>,
+    paths: &[&String],
+) -> bool {
+    if git_dir.join("info/attributes").is_file() || global.is_some_and(|g| g.is_file()) {
+        return true;
+    }
+
+    let mut dirs: HashSet<&Path> = HashSet::new();
+    for path in paths {
+        dirs.extend(Path::new(path.as_str()).ancestors().skip(1));
+    }
+    dirs.iter()
+        .any(|dir| toplevel.join(dir).join(".gitattributes").is_file())
+}
+
 /// Parse user-provided limit from args (-N, -n N, --max-count=N, --max-count N)
 fn parse_user_limit(args: &[String]) -> Option<usize> {
     for (i, arg) in args.iter().enumerate() {
@@ -616,8 +884,12 @@ fn filter_log_output(
     entries.join("\n")
 }
 
-/// Format porcelain output into compact RTK status display
-fn format_status_output(porcelain: &str) -> String {
+/// Format porcelain output into compact RTK status display.
+///
+/// With `conflicts` (a merge/rebase/cherry-pick in progress or unmerged
+/// paths), the operation is shown under the branch and the conflict count is
+/// replaced by per-hunk excerpts and the continue/abort commands.
+fn format_status_output(porcelain: &str, conflicts: Option<&ConflictState>) -> String {
     let lines: Vec<&str> = porcelain.lines().collect();
 
     if lines.is_empty() {
@@ -633,12 +905,15 @@ fn format_status_output(porcelain: &str) -> String {
             output.push_str(&format!("📌 {}\n", branch));
         }
     }
+    if let Some(op) = conflicts.and_then(|c| c.operation.as_ref()) {
+        output.push_str(&format!("⚔️  {}\n", op.describe()));
+    }
 
     // Count changes by type
     let mut staged = 0;
     let mut modified = 0;
     let mut untracked = 0;
-    let mut conflicts = 0;
+    let mut conflict_count = 0;
 
     let mut staged_files = Vec::new();
     let mut modified_files = Vec::new();
@@ -651,12 +926,16 @@ fn format_status_output(porcelain: &str) -> String {
         let status = line.get(0..2).unwrap_or("  ");
         let file = line.get(3..).unwrap_or("");
 
+        if unmerged_kind(status).is_some() {
+            conflict_count += 1;
+            continue;
+        }
+
         match status.chars().next().unwrap_or(' ') {
             'M' | 'A' | 'D' | 'R' | 'C' => {
                 staged += 1;
                 staged_files.push(file);
             }
-            'U' => conflicts += 1,
             _ => {}
         }
 
@@ -705,8 +984,14 @@ fn format_status_output(porcelain: &str) -> String {
         }
     }
 
-    if conflicts > 0 {
-        output.push_str(&format!("⚠️  Conflicts: {} files\n", conflicts));
+    match conflicts {
+        Some(state) if !state.files.is_empty() || state.operation.is_some() => {
+            output.push_str(&format_conflict_report(state));
+        }
+        _ if conflict_count > 0 => {
+            output.push_str(&format!("⚠️  Conflicts: {} files\n", conflict_count));
+        }
+        _ => {}
     }
 
     output.trim_end().to_string()
@@ -800,7 +1085,8 @@ fn run_status(args: &[String], verbose: u8, opts: &GitGlobalOpts) -> Result<()>
     let formatted = if !stderr.is_empty() && stderr.contains("not a git repository") {
         "Not a git repository".to_string()
     } else {
-        format_status_output(&stdout)
+        let conflicts = load_conflict_state(&stdout, opts);
+        format_status_output(&stdout, conflicts.as_ref())
     };
 
     println!("{}", formatted);
@@ -811,6 +1097,441 @@ fn run_status(args: &[String], verbose: u8, opts: &GitGlobalOpts) -> Result<()>
     Ok(())
 }
 
+/// Lines shown per side of a conflict hunk
+const CONFLICT_SIDE_LINES: usize = 6;
+const CONFLICT_HUNKS_PER_FILE: usize = 5;
+const CONFLICT_FILES: usize = 10;
+
+/// History operation stopped mid-way (rebase, merge, cherry-pick, revert, am)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GitOperation {
+    kind: &'static str,
+    /// (current step, total steps) for rebase/am
+    progress: Option<(usize, usize)>,
+    /// Rebase base, merged branch, or picked/reverted commit
+    target: Option<String>,
+}
+
+impl GitOperation {
+    /// "rebase 3/7 onto main", "merge feature", "cherry-pick abc1234"
+    fn describe(&self) -> String {
+        let mut text = self.kind.to_string();
+        if let Some((step, total)) = self.progress {
+            text.push_str(&format!(" {}/{}", step, total));
+        }
+        if let Some(ref target) = self.target {
+            if self.kind == "rebase" {
+                text.push_str(" onto");
+            }
+            text.push(' ');
+            text.push_str(target);
+        }
+        text
+    }
+
+    fn commands(&self) -> String {
+        let skip = if self.kind == "merge" {
+            String::new()
+        } else {
+            format!("  |  skip: git {} --skip", self.kind)
+        };
+        format!(
+            "▶ git add <files> && git {k} --continue  |  abort: git {k} --abort{skip}",
+            k = self.kind,
+            skip = skip
+        )
+    }
+}
+
+/// Read the operation state files from `git_dir`.
+///
+/// `resolve(sha)` names a commit (e.g. via `git name-rev`); `None` falls back
+/// to the short sha.
+fn detect_operation(
+    git_dir: &Path,
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> Option<GitOperation> {
+    let read = |name: &str| {
+        std::fs::read_to_string(git_dir.join(name))
+            .ok()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let number = |name: &str| read(name).and_then(|s| s.parse::<usize>().ok());
+    let name_of = |sha: &str| resolve(sha).unwrap_or_else(|| sha.chars().take(7).collect());
+
+    for (dir, step, total) in [
+        ("rebase-merge", "msgnum", "end"),
+        ("rebase-apply", "next", "last"),
+    ] {
+        if !git_dir.join(dir).is_dir() {
+            continue;
+        }
+        let progress =
+            number(&format!("{}/{}", dir, step)).zip(number(&format!("{}/{}", dir, total)));
+        if git_dir.join(dir).join("applying").exists() {
+            return Some(GitOperation {
+                kind: "am",
+                progress,
+                target: None,
+            });
+        }
+        return Some(GitOperation {
+            kind: "rebase",
+            progress,
+            target: read(&format!("{}/onto", dir)).map(|sha| name_of(&sha)),
+        });
+    }
+
+    for (head, kind) in [
+        ("MERGE_HEAD", "merge"),
+        ("CHERRY_PICK_HEAD", "cherry-pick"),
+        ("REVERT_HEAD", "revert"),
+    ] {
+        if let Some(content) = read(head) {
+            // Octopus merges list several heads; the first is enough to orient
+            let sha = content.lines().next().unwrap_or_default();
+            let target = if kind == "merge" {
+                name_of(sha)
+            } else {
+                sha.chars().take(7).collect()
+            };
+            return Some(GitOperation {
+                kind,
+                progress: None,
+                target: Some(target),
+            });
+        }
+    }
+
+    None
+}
+
+/// Porcelain XY codes for unmerged paths
+fn unmerged_kind(xy: &str) -> Option<&'static str> {
+    match xy {
+        "UU" => Some("both modified"),
+        "AA" => Some("both added"),
+        "DD" => Some("both deleted"),
+        "AU" => Some("added by us"),
+        "UA" => Some("added by them"),
+        "DU" => Some("deleted by us"),
+        "UD" => Some("deleted by them"),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct ConflictSide {
+    /// Text after the marker (`HEAD`, `abc1234 (Fix parser)`, ...)
+    label: String,
+    /// 1-based line of the first content line
+    start: usize,
+    lines: Vec<String>,
+}
+
+#[derive(Debug)]
+struct ConflictHunk {
+    start: usize,
+    end: usize,
+    ours: ConflictSide,
+    /// Present with `merge.conflictStyle = diff3/zdiff3`
+    base: Option<ConflictSide>,
+    theirs: ConflictSide,
+}
+
+#[derive(Debug)]
+struct ConflictedFile {
+    path: String,
+    kind: &'static str,
+    hunks: Vec<ConflictHunk>,
+}
+
+#[derive(Debug)]
+struct ConflictState {
+    operation: Option<GitOperation>,
+    /// Worktree root; porcelain paths are relative to it, not to the cwd
+    root: PathBuf,
+    files: Vec<ConflictedFile>,
+}
+
+fn conflict_marker<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+/// Parse `<<<<<<<` / `|||||||` / `=======` / `>>>>>>>` regions of a worktree file
+fn parse_conflict_hunks(content: &str) -> Vec<ConflictHunk> {
+    enum Section {
+        Outside,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut hunks = Vec::new();
+    let mut section = Section::Outside;
+    let mut start = 0;
+    let mut ours = ConflictSide::default();
+    let mut base: Option<ConflictSide> = None;
+    let mut theirs = ConflictSide::default();
+
+    for (idx, line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        match section {
+            Section::Outside => {
+                if let Some(label) = conflict_marker(line, "<<<<<<<") {
+                    start = line_no;
+                    ours = ConflictSide {
+                        label: label.to_string(),
+                        start: line_no + 1,
+                        lines: Vec::new(),
+                    };
+                    base = None;
+                    section = Section::Ours;
+                }
+            }
+            Section::Ours | Section::Base => {
+                if let Some(label) = conflict_marker(line, "|||||||") {
+                    base = Some(ConflictSide {
+                        label: label.to_string(),
+                        start: line_no + 1,
+                        lines: Vec::new(),
+                    });
+                    section = Section::Base;
+                } else if line == "=======" {
+                    theirs = ConflictSide {
+                        label: String::new(),
+                        start: line_no + 1,
+                        lines: Vec::new(),
+                    };
+                    section = Section::Theirs;
+                } else if let (Section::Base, Some(side)) = (&section, base.as_mut()) {
+                    side.lines.push(line.to_string());
+                } else {
+                    ours.lines.push(line.to_string());
+                }
+            }
+            Section::Theirs => {
+                if let Some(label) = conflict_marker(line, ">>>>>>>") {
+                    theirs.label = label.to_string();
+                    hunks.push(ConflictHunk {
+                        start,
+                        end: line_no,
+                        ours: std::mem::take(&mut ours),
+                        base: base.take(),
+                        theirs: std::mem::take(&mut theirs),
+                    });
+                    section = Section::Outside;
+                } else {
+                    theirs.lines.push(line.to_string());
+                }
+            }
+        }
+    }
+
+    hunks
+}
+
+fn format_conflict_side(name: &str, side: &ConflictSide, out: &mut String) {
+    let label = if side.label.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", truncate_label(&side.label))
+    };
+    let range = match side.lines.len() {
+        0 => "(empty)".to_string(),
+        1 => format!("L{}", side.start),
+        n => format!("L{}-{}", side.start, side.start + n - 1),
+    };
+    out.push_str(&format!("    {}{} {}\n", name, label, range));
+    for line in side.lines.iter().take(CONFLICT_SIDE_LINES) {
+        out.push_str(&format!("      {}\n", line));
+    }
+    if side.lines.len() > CONFLICT_SIDE_LINES {
+        out.push_str(&format!(
+            "      ... +{} lines\n",
+            side.lines.len() - CONFLICT_SIDE_LINES
+        ));
+    }
+}
+
+fn truncate_label(label: &str) -> String {
+    if label.chars().count() > 40 {
+        format!("{}...", label.chars().take(37).collect::<String>())
+    } else {
+        label.to_string()
+    }
+}
+
+/// Conflicted files with per-hunk ours/base/theirs excerpts, then the
+/// commands to finish or abort the operation.
+fn format_conflict_report(state: &ConflictState) -> String {
+    let mut out = String::new();
+
+    if state.files.is_empty() {
+        out.push_str("✅ No conflicts left\n");
+    } else {
+        out.push_str(&format!("⚠️  Conflicts: {} files\n", state.files.len()));
+    }
+
+    for file in state.files.iter().take(CONFLICT_FILES) {
+        match file.hunks.len() {
+            0 => out.push_str(&format!("📄 {} ({})\n", file.path, file.kind)),
+            n => out.push_str(&format!(
+                "📄 {} ({}, {} conflict{})\n",
+                file.path,
+                file.kind,
+                n,
+                if n == 1 { "" } else { "s" }
+            )),
+        }
+        for (idx, hunk) in file.hunks.iter().take(CONFLICT_HUNKS_PER_FILE).enumerate() {
+            out.push_str(&format!("  #{} L{}-{}\n", idx + 1, hunk.start, hunk.end));
+            format_conflict_side("ours", &hunk.ours, &mut out);
+            if let Some(ref base) = hunk.base {
+                format_conflict_side("base", base, &mut out);
+            }
+            format_conflict_side("theirs", &hunk.theirs, &mut out);
+        }
+        if file.hunks.len() > CONFLICT_HUNKS_PER_FILE {
+            out.push_str(&format!(
+                "  ... +{} more conflicts\n",
+                file.hunks.len() - CONFLICT_HUNKS_PER_FILE
+            ));
+        }
+    }
+    if state.files.len() > CONFLICT_FILES {
+        out.push_str(&format!(
+            "... +{} more files\n",
+            state.files.len() - CONFLICT_FILES
+        ));
+    }
+
+    match state.operation {
+        Some(ref op) => out.push_str(&format!("{}\n", op.commands())),
+        None if !state.files.is_empty() => out.push_str("▶ resolve, then: git add <files>\n"),
+        None => {}
+    }
+
+    out
+}
+
+/// Conflict state of the repository, or `None` when no operation is in
+/// progress and nothing is unmerged
+fn load_conflict_state(porcelain: &str, opts: &GitGlobalOpts) -> Option<ConflictState> {
+    let files: Vec<(String, &'static str)> = porcelain
+        .lines()
+        .filter_map(|line| {
+            let kind = unmerged_kind(line.get(0..2)?)?;
+            let path = line.get(3..)?.to_string();
+            Some((path, kind))
+        })
+        .collect();
+
+    let git_dir = git_cmd(opts)
+        .args(["rev-parse", "--absolute-git-dir"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    let resolve = |sha: &str| -> Option<String> {
+        let output = git_cmd(opts)
+            .args(["name-rev", "--name-only", "--no-undefined", sha])
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (output.status.success() && !name.is_empty()).then(|| {
+            name.trim_start_matches("remotes/")
+                .trim_end_matches("^0")
+                .to_string()
+        })
+    };
+    let operation = git_dir.and_then(|dir| detect_operation(Path::new(&dir), &resolve));
+
+    if operation.is_none() && files.is_empty() {
+        return None;
+    }
+
+    let root = git_cmd(opts)
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .map(|o| PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()))
+        .unwrap_or_default();
+    let files = files
+        .into_iter()
+        .map(|(path, kind)| {
+            let hunks = std::fs::read_to_string(root.join(&path))
+                .map(|content| parse_conflict_hunks(&content))
+                .unwrap_or_default();
+            ConflictedFile { path, kind, hunks }
+        })
+        .collect();
+
+    Some(ConflictState {
+        operation,
+        root,
+        files,
+    })
+}
+
+fn run_conflicts(args: &[String], verbose: u8, opts: &GitGlobalOpts) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    // Paths are pathspecs relative to the cwd, which git status resolves
+    let output = git_cmd(opts)
+        .args(["status", "--porcelain", "-b", "--"])
+        .args(args)
+        .output()
+        .context("Failed to run git status")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+    let porcelain = String::from_utf8_lossy(&output.stdout);
+
+    let Some(state) = load_conflict_state(&porcelain, opts) else {
+        let msg = "No conflicts (no merge, rebase or cherry-pick in progress)";
+        println!("{}", msg);
+        timer.track("git status", "rtk git conflicts", &porcelain, msg);
+        return Ok(());
+    };
+
+    if verbose > 0 {
+        eprintln!("Conflicted files: {}", state.files.len());
+    }
+
+    let mut report = String::new();
+    if let Some(ref op) = state.operation {
+        report.push_str(&format!("⚔️  {}\n", op.describe()));
+    }
+    report.push_str(&format_conflict_report(&state));
+    let report = report.trim_end().to_string();
+    println!("{}", report);
+
+    // Without rtk: git status, then cat each conflicted file
+    let mut raw = git_cmd(opts)
+        .arg("status")
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    for file in &state.files {
+        if let Ok(content) = std::fs::read_to_string(state.root.join(&file.path)) {
+            raw.push_str(&content);
+        }
+    }
+    timer.track("git status", "rtk git conflicts", &raw, &report);
+
+    Ok(())
+}
+
 fn run_add(args: &[String], verbose: u8, opts: &GitGlobalOpts) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
@@ -1348,7 +2069,11 @@ fn run_stash(
                 println!("{}", msg);
                 msg.to_string()
             } else {
-                let compacted = compact_diff(&stdout, 100);
+                let full_cmd = format!(
+                    "rtk proxy {}",
+                    git_command_line(opts, &["stash", "show", "-p"], args)
+                );
+                let compacted = review_diff(&stdout, 100, false, &full_cmd, opts);
                 println!("{}", compacted);
                 compacted
             };
@@ -1546,6 +2271,275 @@ fn filter_worktree_list(output: &str) -> String {
     result.join("\n")
 }
 
+/// Max commit ranges printed by `rtk git blame`
+const BLAME_MAX_RANGES: usize = 120;
+
+/// One attributed line from `git blame --line-porcelain`
+#[derive(Debug, Clone)]
+struct BlameLine {
+    sha: String,
+    author: String,
+    time: i64,
+    summary: String,
+    boundary: bool,
+    line: usize,
+    content: String,
+}
+
+fn parse_line_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut current: Option<BlameLine> = None;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some(mut done) = current.take() {
+                done.content = content.to_string();
+                lines.push(done);
+            }
+            continue;
+        }
+        match current.as_mut() {
+            None => {
+                let mut parts = line.split_whitespace();
+                let (Some(sha), Some(_orig), Some(final_line)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    continue;
+                };
+                let Ok(final_line) = final_line.parse() else {
+                    continue;
+                };
+                current = Some(BlameLine {
+                    sha: sha.to_string(),
+                    author: String::new(),
+                    time: 0,
+                    summary: String::new(),
+                    boundary: false,
+                    line: final_line,
+                    content: String::new(),
+                });
+            }
+            Some(entry) => {
+                if let Some(author) = line.strip_prefix("author ") {
+                    entry.author = author.to_string();
+                } else if let Some(time) = line.strip_prefix("author-time ") {
+                    entry.time = time.parse().unwrap_or(0);
+                } else if let Some(summary) = line.strip_prefix("summary ") {
+                    entry.summary = summary.to_string();
+                } else if line == "boundary" {
+                    entry.boundary = true;
+                }
+            }
+        }
+    }
+
+    lines
+}
+
+/// Flags of `git blame` whose value is a separate argument
+const BLAME_FLAGS_WITH_VALUE: &[&str] = &[
+    "-L",
+    "-S",
+    "--since",
+    "--until",
+    "--contents",
+    "--ignore-rev",
+    "--ignore-revs-file",
+    "--date",
+];
+
+/// File being blamed: the argument after `--`, else the last positional
+/// argument that is not a flag value (`git blame f.rs -L 10,20`)
+fn blame_file<'a>(args: &[&'a String]) -> &'a str {
+    let mut file = "";
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if *arg == "--" {
+            return iter.next().map(|a| a.as_str()).unwrap_or(file);
+        }
+        if BLAME_FLAGS_WITH_VALUE.contains(&arg.as_str()) {
+            iter.next();
+        } else if !arg.starts_with('-') {
+            file = arg.as_str();
+        }
+    }
+    file
+}
+
+/// Approximation of plain `git blame` output (abbreviated hash, padded
+/// author, date, line number, content) for savings tracking, so the command
+/// doesn't have to run twice
+fn plain_blame_estimate(lines: &[BlameLine]) -> String {
+    let author_width = lines.iter().map(|l| l.author.len()).max().unwrap_or(0);
+    let line_width = lines.last().map_or(1, |l| l.line.to_string().len());
+    let mut out = String::new();
+    for line in lines {
+        let date = chrono::DateTime::from_timestamp(line.time, 0)
+            .map(|d| d.format("%Y-%m-%d %H:%M:%S +0000").to_string())
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{} ({:<author_width$} {} {:>line_width$}) {}\n",
+            &line.sha[..8.min(line.sha.len())],
+            line.author,
+            date,
+            line.line,
+            line.content,
+        ));
+    }
+    out
+}
+
+fn is_uncommitted(sha: &str) -> bool {
+    sha.bytes().all(|b| b == b'0')
+}
+
+/// Consecutive lines from the same commit, one line per range:
+/// `L10-42 a1b2c3d alice 2026-03-01 'fix tracking'`
+fn format_blame_ranges(lines: &[BlameLine]) -> String {
+    let mut ranges: Vec<(usize, usize, &BlameLine)> = Vec::new();
+    for line in lines {
+        match ranges.last_mut() {
+            Some((_, end, first)) if first.sha == line.sha && *end + 1 == line.line => {
+                *end = line.line;
+            }
+            _ => ranges.push((line.line, line.line, line)),
+        }
+    }
+
+    let width = ranges
+        .iter()
+        .take(BLAME_MAX_RANGES)
+        .map(|(start, end, _)| format_line_range(*start, *end).len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = Vec::new();
+    for (start, end, line) in ranges.iter().take(BLAME_MAX_RANGES) {
+        let range = format_line_range(*start, *end);
+        if is_uncommitted(&line.sha) {
+            out.push(format!("{:<width$} uncommitted", range, width = width));
+            continue;
+        }
+        let date = chrono::DateTime::from_timestamp(line.time, 0)
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        out.push(format!(
+            "{:<width$} {}{} {} {} '{}'",
+            range,
+            if line.boundary { "^" } else { "" },
+            &line.sha[..7.min(line.sha.len())],
+            line.author,
+            date,
+            truncate_label(&line.summary),
+            width = width
+        ));
+    }
+    if ranges.len() > BLAME_MAX_RANGES {
+        out.push(format!(
+            "... +{} more ranges (narrow with -L)",
+            ranges.len() - BLAME_MAX_RANGES
+        ));
+    }
+    out.join("\n")
+}
+
+fn format_line_range(start: usize, end: usize) -> String {
+    if start == end {
+        format!("L{}", start)
+    } else {
+        format!("L{}-{}", start, end)
+    }
+}
+
+/// Authors by share of blamed lines: `alice 62% (74), bob 30% (36)`
+fn format_blame_ownership(lines: &[BlameLine]) -> String {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for line in lines {
+        let author = if is_uncommitted(&line.sha) {
+            "uncommitted"
+        } else {
+            line.author.as_str()
+        };
+        match counts.iter_mut().find(|(name, _)| name == author) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((author.to_string(), 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let total = lines.len().max(1);
+    counts
+        .iter()
+        .map(|(name, count)| format!("{} {}% ({})", name, count * 100 / total, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn run_blame(args: &[String], verbose: u8, opts: &GitGlobalOpts) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    // Flags that already pick an output format: leave them to git
+    let custom_format = args.iter().any(|a| {
+        matches!(
+            a.as_str(),
+            "-p" | "--porcelain" | "--line-porcelain" | "--incremental" | "-c" | "-s" | "-e"
+        )
+    });
+    if custom_format {
+        let os_args: Vec<OsString> = std::iter::once(OsString::from("blame"))
+            .chain(args.iter().map(OsString::from))
+            .collect();
+        return run_passthrough(&os_args, verbose, opts);
+    }
+
+    // --ownership is an rtk flag: strip it before git sees it
+    let ownership = args.iter().any(|a| a == "--ownership");
+    let git_args: Vec<&String> = args.iter().filter(|a| *a != "--ownership").collect();
+
+    let output = git_cmd(opts)
+        .arg("blame")
+        .arg("--line-porcelain")
+        .args(&git_args)
+        .output()
+        .context("Failed to run git blame")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let raw = String::from_utf8_lossy(&output.stdout);
+    let lines = parse_line_porcelain(&raw);
+    if verbose > 0 {
+        eprintln!("git blame: {} lines", lines.len());
+    }
+
+    let file = blame_file(&git_args);
+    let commits: std::collections::HashSet<&str> = lines.iter().map(|l| l.sha.as_str()).collect();
+    let mut filtered = format!(
+        "📜 {} ({} lines, {} commits)\n",
+        file,
+        lines.len(),
+        commits.len()
+    );
+    if ownership {
+        filtered.push_str(&format_blame_ownership(&lines));
+    } else {
+        filtered.push_str(&format_blame_ranges(&lines));
+    }
+    println!("{}", filtered);
+
+    // Tracked against plain `git blame`, which repeats hash/author/date per line
+    timer.track(
+        &format!("git blame {}", args.join(" ")),
+        &format!("rtk git blame {}", args.join(" ")),
+        &plain_blame_estimate(&lines),
+        &filtered,
+    );
+
+    Ok(())
+}
+
 /// Runs an unsupported git subcommand by passing it through directly
 pub fn run_passthrough(args: &[OsString], verbose: u8, opts: &GitGlobalOpts) -> Result<()> {
     let timer = tracking::TimedExecution::start();
@@ -1574,6 +2568,31 @@ pub fn run_passthrough(args: &[OsString], verbose: u8, opts: &GitGlobalOpts) ->
 mod tests {
     use super::*;
 
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_git_command_line_keeps_location_and_args() {
+        let opts = GitGlobalOpts {
+            dir: vec!["../other repo".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            git_command_line(
+                &opts,
+                &["diff", "--no-compact"],
+                &args(&["--cached", "HEAD~2", "--", "src/a b.rs"])
+            ),
+            "git -C '../other repo' diff --no-compact --cached HEAD~2 -- 'src/a b.rs'"
+        );
+        assert_eq!(
+            git_command_line(&GitGlobalOpts::default(), &["show"], &[]),
+            "git show"
+        );
+    }
+
     #[test]
     fn test_compact_diff() {
         let diff = r#"diff --git a/foo.rs b/foo.rs
@@ -1584,7 +2603,7 @@ mod tests {
 +    println!("hello");
  }
 "#;
-        let result = compact_diff(diff, 100);
+        let result = compact_diff(diff, 100, "rtk git diff --no-compact");
         assert!(result.contains("foo.rs"));
         assert!(result.contains("+"));
     }
@@ -1599,7 +2618,7 @@ mod tests {
             diff.push_str(&format!("+line {}\n", i));
         }
 
-        let result = compact_diff(&diff, 500);
+        let result = compact_diff(&diff, 500, "rtk git diff --no-compact");
         // Should show first 10 lines, then exact count of remaining
         assert!(
             result.contains("50 lines truncated"),
@@ -1618,7 +2637,7 @@ mod tests {
             diff.push_str(&format!("+line {}\n", i));
         }
 
-        let result = compact_diff(&diff, 500);
+        let result = compact_diff(&diff, 500, "rtk git diff --no-compact");
         assert!(
             !result.contains("truncated"),
             "8 lines should not trigger truncation, got: {}",
@@ -1669,14 +2688,14 @@ mod tests {
     #[test]
     fn test_format_status_output_clean() {
         let porcelain = "";
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         assert_eq!(result, "Clean working tree");
     }
 
     #[test]
     fn test_format_status_output_modified_files() {
         let porcelain = "## main...origin/main\n M src/main.rs\n M src/lib.rs\n";
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         assert!(result.contains("📌 main...origin/main"));
         assert!(result.contains("📝 Modified: 2 files"));
         assert!(result.contains("src/main.rs"));
@@ -1688,7 +2707,7 @@ mod tests {
     #[test]
     fn test_format_status_output_untracked_files() {
         let porcelain = "## feature/new\n?? temp.txt\n?? debug.log\n?? test.sh\n";
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         assert!(result.contains("📌 feature/new"));
         assert!(result.contains("❓ Untracked: 3 files"));
         assert!(result.contains("temp.txt"));
@@ -1705,7 +2724,7 @@ M  staged.rs
 A  added.rs
 ?? untracked.txt
 "#;
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         assert!(result.contains("📌 main"));
         assert!(result.contains("✅ Staged: 2 files"));
         assert!(result.contains("staged.rs"));
@@ -1728,7 +2747,7 @@ M  file5.rs
 M  file6.rs
 M  file7.rs
 "#;
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         assert!(result.contains("✅ Staged: 7 files"));
         assert!(result.contains("file1.rs"));
         assert!(result.contains("file5.rs"));
@@ -1917,7 +2936,7 @@ no changes added to commit (use "git add" and/or "git commit -a")
     #[test]
     fn test_format_status_output_thai_filename() {
         let porcelain = "## main\n M สวัสดี.txt\n?? ทดสอบ.rs\n";
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         // Should not panic
         assert!(result.contains("📌 main"));
         assert!(result.contains("สวัสดี.txt"));
@@ -1927,10 +2946,190 @@ no changes added to commit (use "git add" and/or "git commit -a")
     #[test]
     fn test_format_status_output_emoji_filename() {
         let porcelain = "## main\nA  🎉-party.txt\n M 日本語ファイル.rs\n";
-        let result = format_status_output(porcelain);
+        let result = format_status_output(porcelain, None);
         assert!(result.contains("📌 main"));
     }
 
+    #[test]
+    fn test_format_status_output_unmerged_codes_not_staged() {
+        let porcelain = "## main\nAA both.rs\nDU gone.rs\nUU lib.rs\n";
+        let result = format_status_output(porcelain, None);
+        assert!(result.contains("⚠️  Conflicts: 3 files"));
+        assert!(!result.contains("Staged"));
+    }
+
+    #[test]
+    fn test_parse_conflict_hunks_diff3() {
+        let content = "fn a() {}\n<<<<<<< HEAD\nlet x = 1;\n||||||| base\nlet x = 0;\n=======\nlet x = 2;\nlet y = 3;\n>>>>>>> abc1234 (Bump x)\nfn b() {}\n<<<<<<< HEAD\n=======\nnew();\n>>>>>>> abc1234 (Bump x)\n";
+        let hunks = parse_conflict_hunks(content);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].start, hunks[0].end), (2, 9));
+        assert_eq!(hunks[0].ours.label, "HEAD");
+        assert_eq!(hunks[0].ours.lines, vec!["let x = 1;"]);
+        assert_eq!(hunks[0].base.as_ref().unwrap().lines, vec!["let x = 0;"]);
+        assert_eq!(hunks[0].theirs.start, 7);
+        assert_eq!(hunks[0].theirs.lines, vec!["let x = 2;", "let y = 3;"]);
+        assert_eq!(hunks[0].theirs.label, "abc1234 (Bump x)");
+        assert!(hunks[1].base.is_none());
+        assert!(hunks[1].ours.lines.is_empty());
+    }
+
+    #[test]
+    fn test_has_attributes_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let git_dir = root.join(".git");
+        std::fs::create_dir_all(git_dir.join("info")).unwrap();
+        std::fs::create_dir_all(root.join("web/gen")).unwrap();
+        let app = "src/app.rs".to_string();
+        let api = "web/gen/api.ts".to_string();
+
+        assert!(!has_attributes_file(root, &git_dir, None, &[&app, &api]));
+
+        std::fs::write(
+            root.join("web/.gitattributes"),
+            "gen/** linguist-generated\n",
+        )
+        .unwrap();
+        assert!(!has_attributes_file(root, &git_dir, None, &[&app]));
+        assert!(has_attributes_file(root, &git_dir, None, &[&app, &api]));
+
+        std::fs::write(root.join(".gitattributes"), "*.rs diff\n").unwrap();
+        assert!(has_attributes_file(root, &git_dir, None, &[&app]));
+    }
+
+    #[test]
+    fn test_detect_operation_rebase_progress() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let rebase = dir.path().join("rebase-merge");
+        std::fs::create_dir(&rebase).unwrap();
+        std::fs::write(rebase.join("msgnum"), "3\n").unwrap();
+        std::fs::write(rebase.join("end"), "7\n").unwrap();
+        std::fs::write(rebase.join("onto"), "0123456789abcdef\n").unwrap();
+
+        let op = detect_operation(dir.path(), &|_| Some("main".to_string())).unwrap();
+        assert_eq!(op.describe(), "rebase 3/7 onto main");
+        assert!(op.commands().contains("git rebase --continue"));
+        assert!(op.commands().contains("git rebase --abort"));
+
+        let op = detect_operation(dir.path(), &|_| None).unwrap();
+        assert_eq!(op.describe(), "rebase 3/7 onto 0123456");
+    }
+
+    #[test]
+    fn test_detect_operation_merge_and_cherry_pick() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert!(detect_operation(dir.path(), &|_| None).is_none());
+
+        std::fs::write(dir.path().join("CHERRY_PICK_HEAD"), "fedcba9876\n").unwrap();
+        let op = detect_operation(dir.path(), &|_| Some("ignored".to_string())).unwrap();
+        assert_eq!(op.describe(), "cherry-pick fedcba9");
+
+        std::fs::write(dir.path().join("MERGE_HEAD"), "abcdef0123\n").unwrap();
+        let op = detect_operation(dir.path(), &|_| Some("feature".to_string())).unwrap();
+        assert_eq!(op.describe(), "merge feature");
+        assert!(!op.commands().contains("--skip"));
+    }
+
+    #[test]
+    fn test_format_status_output_with_conflict_state() {
+        let porcelain = "## HEAD (no branch)\nM  done.rs\nUU src/lib.rs\n";
+        let state = ConflictState {
+            operation: Some(GitOperation {
+                kind: "rebase",
+                progress: Some((2, 5)),
+                target: Some("main".to_string()),
+            }),
+            root: PathBuf::new(),
+            files: vec![ConflictedFile {
+                path: "src/lib.rs".to_string(),
+                kind: "both modified",
+                hunks: parse_conflict_hunks(
+                    "<<<<<<< HEAD\nours();\n=======\ntheirs();\n>>>>>>> 1a2b3c4 (Change)\n",
+                ),
+            }],
+        };
+        let result = format_status_output(porcelain, Some(&state));
+        assert!(result.contains("⚔️  rebase 2/5 onto main"));
+        assert!(result.contains("✅ Staged: 1 files"));
+        assert!(result.contains("📄 src/lib.rs (both modified, 1 conflict)"));
+        assert!(result.contains("  #1 L1-5"));
+        assert!(result.contains("    ours (HEAD) L2\n      ours();"));
+        assert!(result.contains("    theirs (1a2b3c4 (Change)) L4\n      theirs();"));
+        assert!(result.contains("git rebase --continue"));
+    }
+
+    const LINE_PORCELAIN: &str = "a1b2c3d4e5f60718293a4b5c6d7e8f9012345678 1 1 2
+author alice
+author-mail <alice@example.com>
+author-time 1772323200
+author-tz +0000
+summary fix tracking
+filename src/lib.rs
+\tfn a() {}
+a1b2c3d4e5f60718293a4b5c6d7e8f9012345678 2 2
+author alice
+author-time 1772323200
+summary fix tracking
+filename src/lib.rs
+\tfn b() {}
+0000000000000000000000000000000000000000 3 3 1
+author Not Committed Yet
+author-time 1772409600
+summary Version of src/lib.rs from src/lib.rs
+filename src/lib.rs
+\tfn c() {}
+fedcba9876543210fedcba9876543210fedcba98 4 4 1
+author bob
+author-time 1704067200
+summary initial import
+boundary
+filename src/lib.rs
+\t
+";
+
+    #[test]
+    fn test_format_blame_ranges_groups_consecutive_lines() {
+        let lines = parse_line_porcelain(LINE_PORCELAIN);
+        assert_eq!(lines.len(), 4);
+        let result = format_blame_ranges(&lines);
+        assert_eq!(
+            result,
+            "L1-2 a1b2c3d alice 2026-03-01 'fix tracking'\nL3   uncommitted\nL4   ^fedcba9 bob 2024-01-01 'initial import'"
+        );
+    }
+
+    #[test]
+    fn test_blame_file_skips_flag_values() {
+        let label = |list: &[&str]| {
+            let owned = args(list);
+            let refs: Vec<&String> = owned.iter().collect();
+            blame_file(&refs).to_string()
+        };
+        assert_eq!(label(&["f.rs", "-L", "10,20"]), "f.rs");
+        assert_eq!(label(&["-L10,20", "HEAD~2", "f.rs"]), "f.rs");
+        assert_eq!(label(&["--since", "3.weeks", "f.rs"]), "f.rs");
+        assert_eq!(label(&["HEAD", "--", "-odd.rs", "-w"]), "-odd.rs");
+    }
+
+    #[test]
+    fn test_plain_blame_estimate() {
+        let lines = parse_line_porcelain(LINE_PORCELAIN);
+        let plain = plain_blame_estimate(&lines);
+        assert_eq!(plain.lines().count(), 4);
+        assert!(plain
+            .starts_with("a1b2c3d4 (alice             2026-03-01 00:00:00 +0000 1) fn a() {}\n"));
+    }
+
+    #[test]
+    fn test_format_blame_ownership() {
+        let lines = parse_line_porcelain(LINE_PORCELAIN);
+        assert_eq!(
+            format_blame_ownership(&lines),
+            "alice 50% (2), bob 25% (1), uncommitted 25% (1)"
+        );
+    }
+
     /// Regression test: `git branch <name>` must create, not list.
     /// Before fix, positional args fell into list mode which added `-a`,
     /// turning creation into a pattern-filtered listing (silent no-op).
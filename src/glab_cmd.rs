@@ -0,0 +1,571 @@
+//! GitLab CLI (glab) command output compression.
+//!
+//! Mirrors `gh_cmd` for GitLab: merge requests, issues and pipelines.
+//! Descriptions share gh's markdown filtering, MR diffs share the git diff
+//! compaction, and job traces go through the CI log compressor.
+
+use crate::ci_log;
+use crate::gh_cmd::{
+    extract_identifier_and_extra_args, filter_markdown_body, run_passthrough,
+    run_passthrough_with_extra,
+};
+use crate::git;
+use crate::tracking;
+use crate::utils::truncate;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::process::Command;
+
+fn has_output_flag(args: &[String]) -> bool {
+    args.iter()
+        .any(|a| a == "-F" || a == "--output" || a.starts_with("--output="))
+}
+
+pub fn run(subcommand: &str, args: &[String], verbose: u8, ultra_compact: bool) -> Result<()> {
+    // An explicit output format means the caller wants glab's raw output
+    if has_output_flag(args) {
+        return run_passthrough("glab", subcommand, args);
+    }
+
+    match subcommand {
+        "mr" => run_mr(args, verbose, ultra_compact),
+        "issue" => run_issue(args, verbose, ultra_compact),
+        "ci" => run_ci(args, verbose),
+        _ => run_passthrough("glab", subcommand, args),
+    }
+}
+
+/// Run a glab command that returns JSON, exiting with glab's status on failure
+fn glab_json(base_args: &[&str], extra_args: &[String]) -> Result<(Value, String)> {
+    let mut cmd = Command::new("glab");
+    cmd.args(base_args)
+        .args(["--output", "json"])
+        .args(extra_args);
+
+    let output = cmd
+        .output()
+        .context(format!("Failed to run glab {}", base_args.join(" ")))?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let json: Value = serde_json::from_str(&raw).context(format!(
+        "Failed to parse glab {} output",
+        base_args.join(" ")
+    ))?;
+    Ok((json, raw))
+}
+
+fn state_icon(state: &str, ultra_compact: bool) -> &'static str {
+    if ultra_compact {
+        match state {
+            "opened" => "O",
+            "merged" => "M",
+            "closed" => "C",
+            _ => "?",
+        }
+    } else {
+        match state {
+            "opened" => "🟢",
+            "merged" => "🟣",
+            "closed" => "🔴",
+            _ => "⚪",
+        }
+    }
+}
+
+fn run_mr(args: &[String], verbose: u8, ultra_compact: bool) -> Result<()> {
+    if args.is_empty() {
+        return run_passthrough("glab", "mr", args);
+    }
+
+    match args[0].as_str() {
+        "list" => list_mrs(&args[1..], verbose, ultra_compact),
+        "view" => view_mr(&args[1..], verbose, ultra_compact),
+        "diff" => mr_diff(&args[1..], verbose),
+        _ => run_passthrough("glab", "mr", args),
+    }
+}
+
+fn format_mr_list(json: &Value, ultra_compact: bool) -> String {
+    let mut out = String::from(if ultra_compact {
+        "MRs\n"
+    } else {
+        "📋 Merge Requests\n"
+    });
+    let Some(mrs) = json.as_array() else {
+        return out;
+    };
+
+    for mr in mrs.iter().take(20) {
+        let iid = mr["iid"].as_i64().unwrap_or(0);
+        let title = mr["title"].as_str().unwrap_or("???");
+        let state = mr["state"].as_str().unwrap_or("???");
+        let author = mr["author"]["username"].as_str().unwrap_or("???");
+        let draft = if mr["draft"].as_bool() == Some(true) {
+            " [draft]"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "  {} !{} {}{} ({})\n",
+            state_icon(state, ultra_compact),
+            iid,
+            truncate(title, 60),
+            draft,
+            author
+        ));
+    }
+    if mrs.len() > 20 {
+        out.push_str(&format!("  ... {} more\n", mrs.len() - 20));
+    }
+    out
+}
+
+fn list_mrs(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let (json, raw) = glab_json(&["mr", "list"], args)?;
+    let filtered = format_mr_list(&json, ultra_compact);
+    print!("{}", filtered);
+
+    timer.track("glab mr list", "rtk glab mr list", &raw, &filtered);
+    Ok(())
+}
+
+fn format_mr_view(json: &Value, ultra_compact: bool) -> String {
+    let iid = json["iid"].as_i64().unwrap_or(0);
+    let title = json["title"].as_str().unwrap_or("???");
+    let state = json["state"].as_str().unwrap_or("???");
+    let author = json["author"]["username"].as_str().unwrap_or("???");
+    let source = json["source_branch"].as_str().unwrap_or("?");
+    let target = json["target_branch"].as_str().unwrap_or("?");
+    let url = json["web_url"].as_str().unwrap_or("");
+
+    let mut out = format!(
+        "{} MR !{}: {}\n",
+        state_icon(state, ultra_compact),
+        iid,
+        title
+    );
+    out.push_str(&format!("  {} | {} → {}\n", author, source, target));
+
+    let mergeable = if json["has_conflicts"].as_bool() == Some(true) {
+        "✗ conflicts"
+    } else {
+        match json["detailed_merge_status"]
+            .as_str()
+            .or_else(|| json["merge_status"].as_str())
+        {
+            Some("mergeable") | Some("can_be_merged") => "✓",
+            Some(other) => other,
+            None => "?",
+        }
+    };
+    out.push_str(&format!("  {} | {}\n", state, mergeable));
+
+    if let Some(status) = json["head_pipeline"]["status"].as_str() {
+        out.push_str(&format!("  Pipeline: {}\n", status));
+    }
+    out.push_str(&format!("  {}\n", url));
+
+    if let Some(body) = json["description"].as_str() {
+        let body_filtered = filter_markdown_body(body);
+        if !body_filtered.is_empty() {
+            out.push('\n');
+            for line in body_filtered.lines() {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+    out
+}
+
+fn view_mr(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let (mr_id, extra_args) = match extract_identifier_and_extra_args(args) {
+        Some(result) => result,
+        None => return Err(anyhow::anyhow!("MR number required")),
+    };
+    if extra_args.iter().any(|a| a == "--web" || a == "-w") {
+        return run_passthrough_with_extra("glab", &["mr", "view", &mr_id], &extra_args);
+    }
+
+    let (json, raw) = glab_json(&["mr", "view", &mr_id], &extra_args)?;
+    let filtered = format_mr_view(&json, ultra_compact);
+    print!("{}", filtered);
+
+    timer.track(
+        &format!("glab mr view {}", mr_id),
+        &format!("rtk glab mr view {}", mr_id),
+        &raw,
+        &filtered,
+    );
+    Ok(())
+}
+
+fn mr_diff(args: &[String], _verbose: u8) -> Result<()> {
+    // Same rtk-only flags as `rtk gh pr diff`
+    let no_compact = args.iter().any(|a| a == "--no-compact");
+    let semantic = args.iter().any(|a| a == "--semantic");
+    let glab_args: Vec<String> = args
+        .iter()
+        .filter(|a| *a != "--no-compact" && *a != "--semantic")
+        .cloned()
+        .collect();
+
+    if no_compact {
+        return run_passthrough_with_extra("glab", &["mr", "diff"], &glab_args);
+    }
+
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = Command::new("glab");
+    cmd.args(["mr", "diff"]);
+    // --raw gives a plain git diff instead of the colored pager view
+    if !glab_args.iter().any(|a| a == "--raw") {
+        cmd.arg("--raw");
+    }
+    cmd.args(&glab_args);
+
+    let output = cmd.output().context("Failed to run glab mr diff")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        timer.track("glab mr diff", "rtk glab mr diff", &stderr, &stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let filtered = if raw.trim().is_empty() {
+        let msg = "No diff\n";
+        print!("{}", msg);
+        msg.to_string()
+    } else {
+        let full_cmd = format!(
+            "rtk glab mr diff --no-compact {}",
+            crate::utils::shell_join(&glab_args)
+        );
+        let compacted = git::review_diff(
+            &raw,
+            500,
+            semantic,
+            full_cmd.trim_end(),
+            &git::GitGlobalOpts::default(),
+        );
+        println!("{}", compacted);
+        compacted
+    };
+
+    timer.track("glab mr diff", "rtk glab mr diff", &raw, &filtered);
+    Ok(())
+}
+
+fn run_issue(args: &[String], verbose: u8, ultra_compact: bool) -> Result<()> {
+    if args.is_empty() {
+        return run_passthrough("glab", "issue", args);
+    }
+
+    match args[0].as_str() {
+        "list" => list_issues(&args[1..], verbose, ultra_compact),
+        "view" => view_issue(&args[1..], verbose),
+        _ => run_passthrough("glab", "issue", args),
+    }
+}
+
+fn format_issue_list(json: &Value, ultra_compact: bool) -> String {
+    let mut out = String::from(if ultra_compact {
+        "Issues\n"
+    } else {
+        "🐛 Issues\n"
+    });
+    let Some(issues) = json.as_array() else {
+        return out;
+    };
+
+    for issue in issues.iter().take(20) {
+        let iid = issue["iid"].as_i64().unwrap_or(0);
+        let title = issue["title"].as_str().unwrap_or("???");
+        let state = issue["state"].as_str().unwrap_or("???");
+        out.push_str(&format!(
+            "  {} #{} {}\n",
+            state_icon(state, ultra_compact),
+            iid,
+            truncate(title, 60)
+        ));
+    }
+    if issues.len() > 20 {
+        out.push_str(&format!("  ... {} more\n", issues.len() - 20));
+    }
+    out
+}
+
+fn list_issues(args: &[String], _verbose: u8, ultra_compact: bool) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let (json, raw) = glab_json(&["issue", "list"], args)?;
+    let filtered = format_issue_list(&json, ultra_compact);
+    print!("{}", filtered);
+
+    timer.track("glab issue list", "rtk glab issue list", &raw, &filtered);
+    Ok(())
+}
+
+fn format_issue_view(json: &Value) -> String {
+    let iid = json["iid"].as_i64().unwrap_or(0);
+    let title = json["title"].as_str().unwrap_or("???");
+    let state = json["state"].as_str().unwrap_or("???");
+    let author = json["author"]["username"].as_str().unwrap_or("???");
+    let url = json["web_url"].as_str().unwrap_or("");
+
+    let mut out = format!("{} Issue #{}: {}\n", state_icon(state, false), iid, title);
+    out.push_str(&format!("  Author: @{}\n", author));
+    out.push_str(&format!("  Status: {}\n", state));
+    if let Some(labels) = json["labels"].as_array() {
+        let labels: Vec<&str> = labels.iter().filter_map(|l| l.as_str()).collect();
+        if !labels.is_empty() {
+            out.push_str(&format!("  Labels: {}\n", labels.join(", ")));
+        }
+    }
+    out.push_str(&format!("  URL: {}\n", url));
+
+    if let Some(body) = json["description"].as_str() {
+        let body_filtered = filter_markdown_body(body);
+        if !body_filtered.is_empty() {
+            out.push_str("\n  Description:\n");
+            for line in body_filtered.lines() {
+                out.push_str(&format!("    {}\n", line));
+            }
+        }
+    }
+    out
+}
+
+fn view_issue(args: &[String], _verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let (issue_id, extra_args) = match extract_identifier_and_extra_args(args) {
+        Some(result) => result,
+        None => return Err(anyhow::anyhow!("Issue number required")),
+    };
+    if extra_args.iter().any(|a| a == "--web" || a == "-w") {
+        return run_passthrough_with_extra("glab", &["issue", "view", &issue_id], &extra_args);
+    }
+
+    let (json, raw) = glab_json(&["issue", "view", &issue_id], &extra_args)?;
+    let filtered = format_issue_view(&json);
+    print!("{}", filtered);
+
+    timer.track(
+        &format!("glab issue view {}", issue_id),
+        &format!("rtk glab issue view {}", issue_id),
+        &raw,
+        &filtered,
+    );
+    Ok(())
+}
+
+fn run_ci(args: &[String], verbose: u8) -> Result<()> {
+    if args.is_empty() {
+        return run_passthrough("glab", "ci", args);
+    }
+
+    match args[0].as_str() {
+        // --live redraws in place; leave it to glab
+        "status" if !args.iter().any(|a| a == "--live" || a == "-l") => {
+            ci_status(&args[1..], verbose)
+        }
+        "trace" => ci_trace(&args[1..], verbose),
+        // `ci view` is an interactive TUI
+        _ => run_passthrough("glab", "ci", args),
+    }
+}
+
+/// `glab ci status`: non-successful jobs plus the pipeline verdict
+fn filter_ci_status(output: &str) -> String {
+    let mut passed = 0;
+    let mut jobs = Vec::new();
+    let mut state = None;
+    let mut url = None;
+
+    for line in output.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("Pipeline State:") {
+            state = Some(rest.trim().to_string());
+        } else if line.starts_with("https://") || line.starts_with("http://") {
+            url = Some(line.to_string());
+        } else if line.starts_with("(success)") {
+            passed += 1;
+        } else if line.starts_with('(') {
+            // "(failed) • 01m 10s\ttest\t\tunit" → "failed test/unit 01m 10s"
+            let (status, rest) = line.split_once(')').unwrap_or((line, ""));
+            let mut fields = rest
+                .trim_start_matches([' ', '•'])
+                .split('\t')
+                .filter(|f| !f.is_empty());
+            let duration = fields.next().unwrap_or("").trim();
+            let names: Vec<&str> = fields.map(str::trim).collect();
+            let status = status.trim_start_matches('(');
+            let icon = match status {
+                "running" | "pending" | "created" => "⏳",
+                "manual" => "⏸",
+                "skipped" | "canceled" => "⏭",
+                _ => "❌",
+            };
+            jobs.push(format!(
+                "  {} {} {} {}",
+                icon,
+                status,
+                names.join("/"),
+                duration
+            ));
+        }
+    }
+
+    let mut out = format!("🏃 Pipeline: {}", state.as_deref().unwrap_or("unknown"));
+    if passed > 0 {
+        out.push_str(&format!(" ({} jobs passed)", passed));
+    }
+    for job in &jobs {
+        out.push('\n');
+        out.push_str(job);
+    }
+    if let Some(url) = url {
+        out.push_str(&format!("\n  {}", url));
+    }
+    out
+}
+
+fn ci_status(args: &[String], _verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let output = Command::new("glab")
+        .args(["ci", "status"])
+        .args(args)
+        .output()
+        .context("Failed to run glab ci status")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let filtered = filter_ci_status(&crate::utils::strip_ansi(&raw));
+    println!("{}", filtered);
+
+    timer.track("glab ci status", "rtk glab ci status", &raw, &filtered);
+    Ok(())
+}
+
+fn ci_trace(args: &[String], _verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let output = Command::new("glab")
+        .args(["ci", "trace"])
+        .args(args)
+        .output()
+        .context("Failed to run glab ci trace")?;
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr.trim());
+        std::process::exit(output.status.code().unwrap_or(1));
+    }
+
+    let mut filtered = ci_log::compress_gitlab_trace(&raw);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "glab_ci_trace", 1) {
+        filtered.push('\n');
+        filtered.push_str(&hint);
+    }
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("glab ci trace {}", args.join(" ")),
+        &format!("rtk glab ci trace {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_mr_list() {
+        let mrs = json!([
+            {"iid": 12, "title": "Add tracking", "state": "opened", "draft": true,
+             "author": {"username": "alice"}},
+            {"iid": 11, "title": "Fix parser", "state": "merged", "draft": false,
+             "author": {"username": "bob"}}
+        ]);
+        assert_eq!(
+            format_mr_list(&mrs, false),
+            "📋 Merge Requests\n  🟢 !12 Add tracking [draft] (alice)\n  🟣 !11 Fix parser (bob)\n"
+        );
+    }
+
+    #[test]
+    fn test_format_mr_view_filters_description() {
+        let mr = json!({
+            "iid": 7, "title": "Bump deps", "state": "opened",
+            "author": {"username": "alice"},
+            "source_branch": "deps", "target_branch": "main",
+            "detailed_merge_status": "mergeable", "has_conflicts": false,
+            "head_pipeline": {"status": "failed"},
+            "web_url": "https://gitlab.com/org/repo/-/merge_requests/7",
+            "description": "<!-- template -->\nUpdates serde.\n\n![badge](https://img.shields.io/x)\n"
+        });
+        let result = format_mr_view(&mr, false);
+        assert!(result.starts_with("🟢 MR !7: Bump deps\n  alice | deps → main\n  opened | ✓\n"));
+        assert!(result.contains("  Pipeline: failed\n"));
+        assert!(result.contains("  Updates serde."));
+        assert!(!result.contains("template"));
+        assert!(!result.contains("shields.io"));
+    }
+
+    #[test]
+    fn test_format_issue_view() {
+        let issue = json!({
+            "iid": 3, "title": "Crash on start", "state": "closed",
+            "author": {"username": "bob"}, "labels": ["bug", "p1"],
+            "web_url": "https://gitlab.com/org/repo/-/issues/3",
+            "description": "Steps:\n1. run"
+        });
+        let result = format_issue_view(&issue);
+        assert!(result.starts_with("🔴 Issue #3: Crash on start\n"));
+        assert!(result.contains("  Labels: bug, p1\n"));
+        assert!(result.contains("    1. run"));
+    }
+
+    #[test]
+    fn test_filter_ci_status_hides_successful_jobs() {
+        let output = "(success) • 00m 35s\tbuild\t\tcompile\n\
+(failed) • 01m 10s\ttest\t\tunit\n\
+(running) • 00m 05s\ttest\t\tintegration\n\
+https://gitlab.com/org/repo/-/pipelines/123\n\
+SHA: 0123456789abcdef\n\
+Pipeline State: failed\n";
+        assert_eq!(
+            filter_ci_status(output),
+            "🏃 Pipeline: failed (1 jobs passed)\n  ❌ failed test/unit 01m 10s\n  ⏳ running test/integration 00m 05s\n  https://gitlab.com/org/repo/-/pipelines/123"
+        );
+    }
+
+    #[test]
+    fn test_output_flag_passthrough() {
+        assert!(has_output_flag(&["--output".into(), "json".into()]));
+        assert!(has_output_flag(&["-F".into(), "json".into()]));
+        assert!(!has_output_flag(&["--state".into(), "opened".into()]));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::sanitizer;
+use crate::stack_trace;
 use crate::tracking;
 use crate::utils::truncate;
 use anyhow::{Context, Result};
@@ -6,6 +8,12 @@ use std::collections::HashMap;
 use std::ffi::OsString;
 use std::process::Command;
 
+/// Lines of a compressed panic trace shown per failed test
+const MAX_TRACE_LINES: usize = 10;
+
+/// Assertion/error lines shown per failed test
+const MAX_FAILURE_LINES: usize = 5;
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct GoTestEvent {
@@ -39,6 +47,10 @@ struct PackageResult {
 }
 
 pub fn run_test(args: &[String], verbose: u8) -> Result<()> {
+    if is_bench_run(args) {
+        return run_bench(args, verbose);
+    }
+
     let timer = tracking::TimedExecution::start();
 
     let mut cmd = Command::new("go");
@@ -69,7 +81,13 @@ pub fn run_test(args: &[String], verbose: u8) -> Result<()> {
         .status
         .code()
         .unwrap_or(if output.status.success() { 0 } else { 1 });
-    let filtered = filter_go_test_json(&stdout);
+    let mut filtered = filter_go_test_json(&stdout);
+    crate::test_history::record_and_annotate(
+        "go",
+        args,
+        crate::test_history::go_failed_tests(&stdout),
+        &mut filtered,
+    );
 
     if let Some(hint) = crate::tee::tee_and_hint(&raw, "go_test", exit_code) {
         println!("{}\n{}", filtered, hint);
@@ -97,6 +115,91 @@ pub fn run_test(args: &[String], verbose: u8) -> Result<()> {
     Ok(())
 }
 
+/// `-bench` selects benchmarks (`-benchmem` / `-benchtime` only tune them)
+fn is_bench_run(args: &[String]) -> bool {
+    args.iter().any(|a| {
+        let flag = a.trim_start_matches('-');
+        a.starts_with('-')
+            && (flag == "bench"
+                || flag.starts_with("bench=")
+                || flag == "test.bench"
+                || flag.starts_with("test.bench="))
+    })
+}
+
+/// `go test -bench`: plain text output (benchmark lines are split across
+/// `-json` events), summarized as one row per benchmark
+fn run_bench(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("Running: go test {}", args.join(" "));
+    }
+
+    let output = Command::new("go")
+        .arg("test")
+        .args(args)
+        .output()
+        .context("Failed to run go test. Is Go installed?")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+    let report = crate::bench_cmd::parse_output(&stdout);
+    let mut filtered = if report.benches.is_empty() {
+        filter_go_bench_fallback(&stdout)
+    } else {
+        crate::bench_cmd::format_report(&report)
+    };
+    if exit_code != 0 && !report.benches.is_empty() {
+        filtered.push_str(&crate::bench_cmd::failure_lines(&stdout, &stderr));
+    }
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "go_test", exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    if !stderr.trim().is_empty() {
+        eprintln!("{}", stderr.trim());
+    }
+
+    timer.track(
+        &format!("go test {}", args.join(" ")),
+        &format!("rtk go test {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+/// No benchmark lines (build failure, no match): drop the environment header
+fn filter_go_bench_fallback(output: &str) -> String {
+    output
+        .lines()
+        .filter(|l| {
+            !["goos:", "goarch:", "pkg:", "cpu:"]
+                .iter()
+                .any(|prefix| l.starts_with(prefix))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
 pub fn run_build(args: &[String], verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
@@ -392,9 +495,33 @@ fn filter_go_test_json(output: &str) -> String {
         for (test, outputs) in &pkg_result.failed_tests {
             result.push_str(&format!("  ❌ {}\n", test));
 
+            // -race: one record per distinct DATA RACE, then the rest of the output
+            let (reports, joined) = sanitizer::extract_reports(&outputs.join("\n"));
+            if let Some(reports) = reports {
+                for line in reports.lines() {
+                    result.push_str(&format!("     {}\n", truncate(line, 100)));
+                }
+            }
+
+            // Panics: show the compressed trace instead of keyword-matched lines
+            if stack_trace::has_stack_trace(&joined) {
+                for line in stack_trace::compress_stack_traces(&joined)
+                    .lines()
+                    .filter(|l| {
+                        !l.trim().is_empty()
+                            && !l.starts_with("=== RUN")
+                            && !l.starts_with("--- FAIL")
+                    })
+                    .take(MAX_TRACE_LINES)
+                {
+                    result.push_str(&format!("     {}\n", truncate(line, 100)));
+                }
+                continue;
+            }
+
             // Show failure output (limit to key lines)
-            let relevant_lines: Vec<&String> = outputs
-                .iter()
+            let relevant_lines: Vec<&str> = joined
+                .lines()
                 .filter(|line| {
                     let lower = line.to_lowercase();
                     !line.trim().is_empty()
@@ -407,7 +534,7 @@ fn filter_go_test_json(output: &str) -> String {
                             || line.trim().starts_with("at ")
                             || line.contains("_test.go:")) // Preserve test location (file:line)
                 })
-                .take(5)
+                .take(MAX_FAILURE_LINES)
                 .collect();
 
             for line in relevant_lines {
@@ -417,6 +544,18 @@ fn filter_go_test_json(output: &str) -> String {
 
         // Show package-level output (timeout/panic messages) if no test failures
         if pkg_result.failed_tests.is_empty() && !pkg_result.package_output.is_empty() {
+            // Timeouts dump every goroutine: group identical stacks
+            let joined = pkg_result.package_output.join("\n");
+            if stack_trace::has_stack_trace(&joined) {
+                for line in stack_trace::compress_stack_traces(&joined)
+                    .lines()
+                    .filter(|l| !l.trim().is_empty())
+                    .take(MAX_TRACE_LINES * 2)
+                {
+                    result.push_str(&format!("  {}\n", truncate(line, 120)));
+                }
+                continue;
+            }
             for line in &pkg_result.package_output {
                 let trimmed = line.trim();
                 if !trimmed.is_empty()
@@ -527,6 +666,34 @@ fn compact_package_name(package: &str) -> String {
 mod tests {
     use super::*;
 
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_is_bench_run() {
+        assert!(is_bench_run(&args(&["-bench=.", "./..."])));
+        assert!(is_bench_run(&args(&["-run", "^$", "-bench", "Parse"])));
+        assert!(is_bench_run(&args(&["-test.bench=."])));
+        assert!(!is_bench_run(&args(&["-benchmem", "./..."])));
+        assert!(!is_bench_run(&args(&[
+            "-benchtime=2s",
+            "-run",
+            "TestBench"
+        ])));
+    }
+
+    #[test]
+    fn test_filter_go_bench_fallback() {
+        let output = "goos: linux\ngoarch: amd64\npkg: example.com/demo\n\
+                      cpu: Test CPU\nPASS\nok  \texample.com/demo\t0.010s\n";
+        assert_eq!(
+            filter_go_bench_fallback(output),
+            "PASS\nok  \texample.com/demo\t0.010s"
+        );
+    }
+
     #[test]
     fn test_filter_go_test_all_pass() {
         let output = r#"{"Time":"2024-01-01T10:00:00Z","Action":"run","Package":"example.com/foo","Test":"TestBar"}
@@ -701,4 +868,84 @@ main.go:10:5: undefined: missingFunc"#;
             result
         );
     }
+
+    #[test]
+    fn test_filter_go_test_timeout_groups_goroutines() {
+        let mut lines = vec![
+            r#"{"Action":"output","Package":"example.com/foo","Output":"panic: test timed out after 10s\n"}"#.to_string(),
+        ];
+        for id in 20..26 {
+            for out in [
+                format!("goroutine {} [chan receive]:\\n", id),
+                "example.com/foo.worker(0xc000010000)\\n".to_string(),
+                "\\t/app/worker.go:14 +0x25\\n".to_string(),
+                "created by example.com/foo.TestSlow in goroutine 7\\n".to_string(),
+                "\\t/app/worker_test.go:9 +0x3d\\n".to_string(),
+            ] {
+                lines.push(format!(
+                    r#"{{"Action":"output","Package":"example.com/foo","Output":"{}"}}"#,
+                    out
+                ));
+            }
+        }
+        lines.push(r#"{"Action":"fail","Package":"example.com/foo","Elapsed":10.5}"#.to_string());
+
+        let result = filter_go_test_json(&lines.join("\n"));
+        assert!(
+            result.contains("panic: test timed out after 10s"),
+            "{}",
+            result
+        );
+        assert!(
+            result.contains("goroutine 20, 21, 22, … [chan receive] ×6:"),
+            "{}",
+            result
+        );
+        assert_eq!(result.matches("/app/worker.go:14").count(), 1, "{}", result);
+    }
+
+    #[test]
+    fn test_filter_go_test_race_reports_deduplicated() {
+        let mut lines =
+            vec![r#"{"Action":"run","Package":"example.com/foo","Test":"TestCache"}"#.to_string()];
+        for goroutine in [8, 9] {
+            for out in [
+                "==================\\n".to_string(),
+                "WARNING: DATA RACE\\n".to_string(),
+                format!("Write at 0x00c000012345 by goroutine {}:\\n", goroutine),
+                "  example.com/foo.(*Cache).Set()\\n".to_string(),
+                "      /app/cache.go:25 +0x64\\n".to_string(),
+                "\\n".to_string(),
+                "Previous read at 0x00c000012345 by goroutine 7:\\n".to_string(),
+                "  example.com/foo.(*Cache).Get()\\n".to_string(),
+                "      /app/cache.go:18 +0x3c\\n".to_string(),
+                "==================\\n".to_string(),
+                "    cache_test.go:30: expected 2, got 1\\n".to_string(),
+            ] {
+                lines.push(format!(
+                    r#"{{"Action":"output","Package":"example.com/foo","Test":"TestCache","Output":"{}"}}"#,
+                    out
+                ));
+            }
+        }
+        lines.push(
+            r#"{"Action":"fail","Package":"example.com/foo","Test":"TestCache","Elapsed":0.1}"#
+                .to_string(),
+        );
+        lines.push(r#"{"Action":"fail","Package":"example.com/foo","Elapsed":0.1}"#.to_string());
+
+        let result = filter_go_test_json(&lines.join("\n"));
+        assert!(result.contains("⚠ go race: DATA RACE ×2"), "{}", result);
+        assert!(
+            result.contains("write: /app/cache.go:25 foo.(*Cache).Set"),
+            "{}",
+            result
+        );
+        assert!(!result.contains("0x00c000012345"), "{}", result);
+        assert!(
+            result.contains("cache_test.go:30: expected 2, got 1"),
+            "{}",
+            result
+        );
+    }
 }
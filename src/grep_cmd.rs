@@ -1,7 +1,10 @@
+use crate::filter::Language;
+use crate::symbols::{self, Symbol};
 use crate::tracking;
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::collections::HashMap;
+use std::path::Path;
 use std::process::{Command, Stdio};
 
 #[allow(clippy::too_many_arguments)]
@@ -12,6 +15,8 @@ pub fn run(
     max_results: usize,
     context_only: bool,
     file_type: Option<&str>,
+    by_symbol: bool,
+    signatures: bool,
     extra_args: &[String],
     verbose: u8,
 ) -> Result<()> {
@@ -31,6 +36,11 @@ pub fn run(
         rg_cmd.arg("--type").arg(ft);
     }
 
+    // Symbol grouping reads each file back, so it needs the path even for single-file searches
+    if by_symbol || signatures {
+        rg_cmd.arg("--with-filename");
+    }
+
     for arg in extra_args {
         // Fix: skip grep-ism -r flag (rg is recursive by default; rg -r means --replace)
         if arg == "-r" || arg == "--recursive" {
@@ -43,8 +53,13 @@ pub fn run(
         .stdin(Stdio::null())
         .output()
         .or_else(|_| {
+            let grep_flags = if by_symbol || signatures {
+                "-rnH"
+            } else {
+                "-rn"
+            };
             Command::new("grep")
-                .args(["-rn", pattern, path])
+                .args([grep_flags, pattern, path])
                 .stdin(Stdio::null())
                 .output()
         })
@@ -98,6 +113,32 @@ pub fn run(
         by_file.entry(file).or_default().push((line_num, cleaned));
     }
 
+    let rtk_output = if by_symbol || signatures {
+        format_by_symbol(&by_file, total, max_results, max_line_len, signatures)
+    } else {
+        format_by_file(&by_file, total, max_results)
+    };
+
+    print!("{}", rtk_output);
+    timer.track(
+        &format!("grep -rn '{}' {}", pattern, path),
+        "rtk grep",
+        &raw_output,
+        &rtk_output,
+    );
+
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+
+    Ok(())
+}
+
+fn format_by_file(
+    by_file: &HashMap<String, Vec<(usize, String)>>,
+    total: usize,
+    max_results: usize,
+) -> String {
     let mut rtk_output = String::new();
     rtk_output.push_str(&format!("🔍 {} in {}F:\n\n", total, by_file.len()));
 
@@ -131,19 +172,139 @@ pub fn run(
         rtk_output.push_str(&format!("... +{}\n", total - shown));
     }
 
-    print!("{}", rtk_output);
-    timer.track(
-        &format!("grep -rn '{}' {}", pattern, path),
-        "rtk grep",
-        &raw_output,
-        &rtk_output,
-    );
+    rtk_output
+}
 
-    if exit_code != 0 {
-        std::process::exit(exit_code);
+/// Hits that landed in the same symbol body (or at top level when `symbol` is None)
+struct SymbolGroup<'a> {
+    symbol: Option<&'a Symbol>,
+    hits: Vec<&'a (usize, String)>,
+}
+
+/// Group one file's hits by their innermost enclosing symbol, in source order
+fn group_by_symbol<'a>(
+    matches: &'a [(usize, String)],
+    outline: &'a [Symbol],
+) -> Vec<SymbolGroup<'a>> {
+    let mut groups: Vec<SymbolGroup<'a>> = Vec::new();
+
+    for hit in matches {
+        let symbol = symbols::enclosing(outline, hit.0);
+        match groups.iter_mut().find(|g| g.symbol == symbol) {
+            Some(group) => group.hits.push(hit),
+            None => groups.push(SymbolGroup {
+                symbol,
+                hits: vec![hit],
+            }),
+        }
     }
 
-    Ok(())
+    groups.sort_by_key(|g| g.symbol.map_or(0, |s| s.start));
+    groups
+}
+
+/// Render hits grouped under their enclosing function/impl/class.
+/// Each symbol shows one representative hit (or its signature) plus the other hit lines.
+fn format_by_symbol(
+    by_file: &HashMap<String, Vec<(usize, String)>>,
+    total: usize,
+    max_results: usize,
+    max_line_len: usize,
+    signatures: bool,
+) -> String {
+    let mut files: Vec<_> = by_file.iter().collect();
+    files.sort_by_key(|(f, _)| *f);
+
+    let outlines: Vec<Vec<Symbol>> = files
+        .iter()
+        .map(|(file, _)| {
+            let lang = Path::new(file.as_str())
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(Language::from_extension)
+                .unwrap_or(Language::Unknown);
+            std::fs::read_to_string(file.as_str())
+                .map(|content| symbols::outline(&content, lang))
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut body = String::new();
+    let mut symbol_count = 0;
+    let mut shown = 0;
+
+    for ((file, matches), outline) in files.iter().zip(&outlines) {
+        if shown >= max_results {
+            break;
+        }
+
+        let groups = group_by_symbol(matches, outline);
+        symbol_count += groups.iter().filter(|g| g.symbol.is_some()).count();
+        body.push_str(&format!("📄 {} ({}):\n", compact_path(file), matches.len()));
+
+        for group in &groups {
+            if shown >= max_results {
+                break;
+            }
+            shown += group.hits.len();
+
+            let line_list = group
+                .hits
+                .iter()
+                .map(|(n, _)| n.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let count = if group.hits.len() > 1 {
+                format!(" ×{}", group.hits.len())
+            } else {
+                String::new()
+            };
+
+            match group.symbol {
+                Some(sym) => {
+                    body.push_str(&format!(
+                        "  {} [{}-{}]{}: L{}\n",
+                        sym.label(),
+                        sym.start,
+                        sym.end,
+                        count,
+                        line_list
+                    ));
+                    if signatures {
+                        body.push_str(&format!(
+                            "    {}\n",
+                            clean_line(sym.signature_line(), max_line_len, false, "")
+                        ));
+                    } else {
+                        let (n, content) = group.hits[0];
+                        body.push_str(&format!("    {:>4}: {}\n", n, content));
+                    }
+                }
+                None => {
+                    body.push_str(&format!("  (top level){}: L{}\n", count, line_list));
+                    for (n, content) in group.hits.iter().take(10) {
+                        body.push_str(&format!("    {:>4}: {}\n", n, content));
+                    }
+                    if group.hits.len() > 10 {
+                        body.push_str(&format!("    +{}\n", group.hits.len() - 10));
+                    }
+                }
+            }
+        }
+        body.push('\n');
+    }
+
+    let mut rtk_output = format!(
+        "🔍 {} in {}F, {} symbols:\n\n",
+        total,
+        by_file.len(),
+        symbol_count
+    );
+    rtk_output.push_str(&body);
+    if total > shown {
+        rtk_output.push_str(&format!("... +{}\n", total - shown));
+    }
+    rtk_output
 }
 
 fn clean_line(line: &str, max_len: usize, context_only: bool, pattern: &str) -> String {
@@ -231,6 +392,33 @@ mod tests {
         assert!(compact.len() <= 60);
     }
 
+    fn hits(lines: &[usize]) -> Vec<(usize, String)> {
+        lines.iter().map(|n| (*n, format!("hit {}", n))).collect()
+    }
+
+    #[test]
+    fn test_group_by_symbol_dedupes_hits_in_same_body() {
+        let code = "use x;\n\nfn alpha() {\n    a();\n    a();\n}\n\nfn beta() {\n    a();\n}\n";
+        let outline = symbols::outline(code, Language::Rust);
+        let matches = hits(&[1, 4, 5, 9]);
+        let groups = group_by_symbol(&matches, &outline);
+
+        assert_eq!(groups.len(), 3);
+        assert!(groups[0].symbol.is_none());
+        assert_eq!(groups[1].symbol.unwrap().name, "alpha");
+        assert_eq!(groups[1].hits.len(), 2);
+        assert_eq!(groups[2].symbol.unwrap().name, "beta");
+    }
+
+    #[test]
+    fn test_group_by_symbol_without_outline_is_top_level() {
+        let matches = hits(&[3, 7]);
+        let groups = group_by_symbol(&matches, &[]);
+        assert_eq!(groups.len(), 1);
+        assert!(groups[0].symbol.is_none());
+        assert_eq!(groups[0].hits.len(), 2);
+    }
+
     #[test]
     fn test_extra_args_accepted() {
         // Test that the function signature accepts extra_args
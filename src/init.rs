@@ -51,10 +51,17 @@ rtk git add . && rtk git commit -m "msg" && rtk git push
 rtk cargo build         # Cargo build output
 rtk cargo check         # Cargo check output
 rtk cargo clippy        # Clippy warnings grouped by file (80%)
+rtk cargo tree          # Direct deps + duplicated crates (-i: shortest chains)
 rtk tsc                 # TypeScript errors grouped by file/code (83%)
 rtk lint                # ESLint/Biome violations grouped (84%)
+rtk sarif <file|dir>    # SARIF logs from any analyzer, grouped by rule/file (80%+)
 rtk prettier --check    # Files needing format only (70%)
 rtk next build          # Next.js build with route metrics (87%)
+rtk gradle build        # Gradle tasks, diagnostics by file, test results (85%)
+rtk mvn package         # Maven goals, diagnostics by file, surefire results (80%)
+rtk dotnet build        # Per-project status, MSBuild diagnostics, TRX test results (80%)
+rtk make                # gcc/clang errors grouped by flag, templates collapsed (85%)
+rtk ninja -C build      # Ninja/CMake builds, failed targets + diagnostics (85%)
 ```
 
 ### Test (90-99% savings)
@@ -62,7 +69,12 @@ rtk next build          # Next.js build with route metrics (87%)
 rtk cargo test          # Cargo test failures only (90%)
 rtk vitest run          # Vitest failures only (99.5%)
 rtk playwright test     # Playwright failures only (94%)
-rtk test <cmd>          # Generic test wrapper - failures only
+rtk rspec               # RSpec failures, app frames, re-run command (90%)
+rtk minitest            # rails test failures + re-run command (90%)
+rtk test <cmd>          # Generic test wrapper - failures only (uses JUnit XML if written)
+rtk junit <file|glob>   # JUnit XML reports → compact pass/fail summary
+rtk coverage [path]     # Coverage totals, least-covered + changed files (auto-detects format)
+rtk bench <cmd>         # Benchmarks: mean ± stddev + change, noise collapsed (cargo bench, hyperfine, ...)
 ```
 
 ### Git (59-80% savings)
@@ -92,11 +104,22 @@ rtk gh issue list       # Compact issue list (80%)
 rtk gh api              # Compact API responses (26%)
 ```
 
+### GitLab (80-85% savings)
+```bash
+rtk glab mr view <num>  # Compact MR view
+rtk glab mr diff <num>  # Compacted MR diff
+rtk glab ci status      # Failed/running jobs only
+rtk glab ci trace <job> # Job log without runner setup
+```
+
 ### JavaScript/TypeScript Tooling (70-90% savings)
 ```bash
 rtk pnpm list           # Compact dependency tree (70%)
 rtk pnpm outdated       # Compact outdated packages (80%)
 rtk pnpm install        # Compact install output (90%)
+rtk bun install         # Compact install output (80%)
+rtk bun test            # Bun test failures only (90%)
+rtk yarn install        # Yarn step timings + YN warnings/errors (80%)
 rtk npm run <script>    # Compact npm script output
 rtk npx <cmd>           # Compact npx command output
 rtk prisma              # Prisma without ASCII art (88%)
@@ -126,8 +149,13 @@ rtk diff                # Ultra-compact diffs
 rtk docker ps           # Compact container list
 rtk docker images       # Compact image list
 rtk docker logs <c>     # Deduplicated logs
+rtk docker build .      # Step counts + failing step only
+rtk docker inspect <c>  # Key fields, env values masked
+rtk docker pull <img>   # One line per image (also push)
 rtk kubectl get         # Compact resource list
 rtk kubectl logs        # Deduplicated pod logs
+rtk kubectl describe    # States, conditions, grouped events
+rtk kubectl apply       # created/configured/unchanged tallies (also diff)
 ```
 
 ### Network (65-70% savings)
@@ -140,6 +168,7 @@ rtk wget <url>          # Compact download output (65%)
 ```bash
 rtk gain                # View token savings statistics
 rtk gain --history      # View command history with savings
+rtk gain --tests        # Flaky and newly broken tests from recorded runs
 rtk discover            # Analyze Claude Code sessions for missed RTK usage
 rtk proxy <cmd>         # Run command without filtering (for debugging)
 rtk init                # Add RTK instructions to CLAUDE.md
@@ -154,7 +183,7 @@ rtk init --global       # Add RTK to ~/.claude/CLAUDE.md
 | Build | next, tsc, lint, prettier | 70-87% |
 | Git | status, log, diff, add, commit | 59-80% |
 | GitHub | gh pr, gh run, gh issue | 26-87% |
-| Package Managers | pnpm, npm, npx | 70-90% |
+| Package Managers | pnpm, bun, yarn, npm, npx | 70-90% |
 | Files | ls, read, grep, find | 60-75% |
 | Infrastructure | docker, kubectl | 85% |
 | Network | curl, wget | 65-70% |
@@ -1100,14 +1129,27 @@ mod tests {
         for cmd in [
             "rtk cargo",
             "rtk gh",
+            "rtk glab",
+            "rtk gradle",
+            "rtk mvn",
+            "rtk dotnet",
+            "rtk make",
+            "rtk ninja",
             "rtk vitest",
             "rtk tsc",
             "rtk lint",
+            "rtk sarif",
             "rtk prettier",
             "rtk next",
             "rtk playwright",
+            "rtk rspec",
+            "rtk minitest",
+            "rtk coverage",
+            "rtk bench",
             "rtk prisma",
             "rtk pnpm",
+            "rtk bun",
+            "rtk yarn",
             "rtk npm",
             "rtk curl",
             "rtk git",
@@ -0,0 +1,611 @@
+//! JUnit XML report ingestion.
+//!
+//! Gradle, Maven surefire, dotnet, rspec, phpunit, ctest, jest and pytest
+//! (`--junitxml`) can all write JUnit XML. `rtk junit <file|glob>` parses
+//! those reports into `TestResult`; `rtk test` / `rtk err` use the same
+//! parser when the command wrote a report during the run.
+
+use crate::parser::{
+    emit_degradation_warning, emit_passthrough_warning, truncate_output, FormatMode, OutputParser,
+    ParseResult, TestFailure, TestResult, TokenFormatter,
+};
+use crate::stack_trace::compress_stack_traces;
+use crate::tracking;
+use anyhow::{bail, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Stack trace lines kept per failure
+const MAX_TRACE_LINES: usize = 10;
+
+/// Report directories used by common runners (scanned for fresh `*.xml`)
+const WELL_KNOWN_REPORT_DIRS: &[&str] = &[
+    "target/surefire-reports",
+    "target/failsafe-reports",
+    "build/test-results",
+    "test-results",
+    "reports/junit",
+];
+
+lazy_static! {
+    /// `--junitxml=out.xml`, `--junit-xml out.xml`, `--output-junit out.xml`
+    static ref REPORT_FLAG: Regex =
+        Regex::new(r#"--(?:junit-?xml|output-junit)[= ]["']?([^\s"']+)"#).unwrap();
+    /// dotnet: `--logger "junit;LogFilePath=out.xml"`
+    static ref DOTNET_LOGGER: Regex = Regex::new(r"LogFilePath=([^\s;\x22']+)").unwrap();
+    /// pytest: `- generated xml file: /path/report.xml -`
+    static ref GENERATED_XML: Regex = Regex::new(r"generated xml file: (\S+\.xml)").unwrap();
+    static ref TESTCASE_TAG: Regex = Regex::new(r#"<testcase\b[^>]*\bname="([^"]*)""#).unwrap();
+    static ref FAILED_CASE: Regex = Regex::new(
+        r#"<testcase\b[^>]*\bname="([^"]*)"[^>]*>\s*<(?:failure|error)\b(?:[^>]*\bmessage="([^"]*)")?"#
+    )
+    .unwrap();
+}
+
+/// Parser for JUnit XML reports
+pub struct JunitParser;
+
+impl OutputParser for JunitParser {
+    type Output = TestResult;
+
+    fn parse(input: &str) -> ParseResult<TestResult> {
+        match roxmltree::Document::parse(input) {
+            Ok(doc) => ParseResult::Full(result_from_document(&doc)),
+            Err(e) => {
+                // Tier 2: reports cut short by a killed runner still list their cases
+                match extract_cases_regex(input) {
+                    Some(result) => {
+                        ParseResult::Degraded(result, vec![format!("XML parse failed: {}", e)])
+                    }
+                    None => ParseResult::Passthrough(truncate_output(input, 500)),
+                }
+            }
+        }
+    }
+}
+
+fn result_from_document(doc: &roxmltree::Document) -> TestResult {
+    let mut result = TestResult {
+        total: 0,
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        duration_ms: None,
+        failures: Vec::new(),
+    };
+    let mut duration_secs = 0.0;
+    let mut has_time = false;
+
+    for case in doc.descendants().filter(|n| n.has_tag_name("testcase")) {
+        result.total += 1;
+        if let Some(time) = case.attribute("time").and_then(|t| t.parse::<f64>().ok()) {
+            duration_secs += time;
+            has_time = true;
+        }
+
+        let problem = case
+            .children()
+            .find(|c| c.has_tag_name("failure") || c.has_tag_name("error"));
+        if let Some(problem) = problem {
+            result.failed += 1;
+            result.failures.push(failure_from_case(&case, &problem));
+        } else if case.children().any(|c| c.has_tag_name("skipped")) {
+            result.skipped += 1;
+        } else {
+            result.passed += 1;
+        }
+    }
+
+    if has_time {
+        result.duration_ms = Some((duration_secs * 1000.0).round() as u64);
+    }
+    result
+}
+
+/// Slowest test cases at or above `min_ms`, slowest first
+pub fn slowest_cases(xml: &str, limit: usize, min_ms: u64) -> Vec<(String, u64)> {
+    let Ok(doc) = roxmltree::Document::parse(xml) else {
+        return Vec::new();
+    };
+    let mut cases: Vec<(String, u64)> = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("testcase"))
+        .filter_map(|case| {
+            let secs = case.attribute("time")?.parse::<f64>().ok()?;
+            let ms = (secs * 1000.0).round() as u64;
+            let name = case_name(
+                case.attribute("classname").unwrap_or(""),
+                case.attribute("name").unwrap_or("?"),
+            );
+            (ms >= min_ms).then_some((name, ms))
+        })
+        .collect();
+    cases.sort_by_key(|c| std::cmp::Reverse(c.1));
+    cases.truncate(limit);
+    cases
+}
+
+/// `com.acme.FooTest` + `testBar` → `FooTest.testBar`
+fn case_name(classname: &str, name: &str) -> String {
+    let short = classname.rsplit('.').next().unwrap_or(classname);
+    if short.is_empty() || name.starts_with(short) {
+        name.to_string()
+    } else {
+        format!("{}.{}", short, name)
+    }
+}
+
+fn failure_from_case(case: &roxmltree::Node, problem: &roxmltree::Node) -> TestFailure {
+    let classname = case.attribute("classname").unwrap_or("");
+    let body = compress_stack_traces(problem.text().unwrap_or("").trim());
+    let message = problem.attribute("message").map(str::trim).unwrap_or("");
+
+    let mut error_message = if message.is_empty() {
+        body.lines().next().unwrap_or("").trim().to_string()
+    } else {
+        message.to_string()
+    };
+    if let Some(kind) = problem.attribute("type") {
+        if !kind.is_empty() && !error_message.starts_with(kind) {
+            error_message = format!("{}: {}", kind, error_message);
+        }
+    }
+
+    let stack_trace = if body.is_empty() {
+        None
+    } else {
+        Some(
+            body.lines()
+                .take(MAX_TRACE_LINES)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    };
+
+    TestFailure {
+        test_name: case_name(classname, case.attribute("name").unwrap_or("?")),
+        file_path: case.attribute("file").unwrap_or(classname).to_string(),
+        error_message,
+        stack_trace,
+    }
+}
+
+/// Tier 2: count cases and pick failing ones out of malformed XML
+fn extract_cases_regex(input: &str) -> Option<TestResult> {
+    let total = TESTCASE_TAG.find_iter(input).count();
+    if total == 0 {
+        return None;
+    }
+    let failures: Vec<TestFailure> = FAILED_CASE
+        .captures_iter(input)
+        .map(|caps| TestFailure {
+            test_name: caps[1].to_string(),
+            file_path: String::new(),
+            error_message: caps.get(2).map(|m| m.as_str()).unwrap_or("").to_string(),
+            stack_trace: None,
+        })
+        .collect();
+    let skipped = input.matches("<skipped").count();
+    Some(TestResult {
+        total,
+        passed: total.saturating_sub(failures.len() + skipped),
+        failed: failures.len(),
+        skipped,
+        duration_ms: None,
+        failures,
+    })
+}
+
+/// Combine several reports (one per module / test class) into one result
+pub fn merge_results(results: Vec<TestResult>) -> TestResult {
+    let mut merged = TestResult {
+        total: 0,
+        passed: 0,
+        failed: 0,
+        skipped: 0,
+        duration_ms: None,
+        failures: Vec::new(),
+    };
+    for r in results {
+        merged.total += r.total;
+        merged.passed += r.passed;
+        merged.failed += r.failed;
+        merged.skipped += r.skipped;
+        if let Some(ms) = r.duration_ms {
+            merged.duration_ms = Some(merged.duration_ms.unwrap_or(0) + ms);
+        }
+        merged.failures.extend(r.failures);
+    }
+    merged
+}
+
+/// Parse report files into one result; Passthrough only if none parse at all
+pub fn parse_reports(paths: &[PathBuf]) -> ParseResult<TestResult> {
+    let mut results = Vec::new();
+    let mut warnings = Vec::new();
+    let mut raw_fallback = String::new();
+
+    for path in paths {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) => {
+                warnings.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+        };
+        match JunitParser::parse(&content) {
+            ParseResult::Full(r) => results.push(r),
+            ParseResult::Degraded(r, w) => {
+                results.push(r);
+                warnings.extend(w.into_iter().map(|w| format!("{}: {}", path.display(), w)));
+            }
+            ParseResult::Passthrough(raw) => {
+                warnings.push(format!("{}: not a JUnit report", path.display()));
+                raw_fallback.push_str(&raw);
+            }
+        }
+    }
+
+    if results.is_empty() {
+        return ParseResult::Passthrough(raw_fallback);
+    }
+    let merged = merge_results(results);
+    if warnings.is_empty() {
+        ParseResult::Full(merged)
+    } else {
+        ParseResult::Degraded(merged, warnings)
+    }
+}
+
+/// `**/` → any directories, `*` / `?` → within one path component
+fn glob_to_regex(pattern: &str) -> Regex {
+    let mut re = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).unwrap_or_else(|_| Regex::new("^$").unwrap())
+}
+
+/// Expand files, directories (all `*.xml` below) and quoted globs
+pub fn expand_report_paths(patterns: &[String]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?']) {
+            let path = PathBuf::from(pattern);
+            if path.is_dir() {
+                paths.extend(xml_files_under(&path, None));
+            } else {
+                paths.push(path);
+            }
+            continue;
+        }
+
+        // Walk from the longest glob-free prefix
+        let base: PathBuf = Path::new(pattern)
+            .components()
+            .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?']))
+            .collect();
+        let matcher = glob_to_regex(pattern.trim_start_matches("./"));
+        let root = if base.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            base
+        };
+        for entry in walkdir::WalkDir::new(&root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let rel = entry.path().to_string_lossy().to_string();
+            if matcher.is_match(rel.trim_start_matches("./")) {
+                paths.push(entry.path().to_path_buf());
+            }
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+pub fn xml_files_under(dir: &Path, since: Option<SystemTime>) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|x| x == "xml"))
+        .filter(|e| match since {
+            Some(since) => e
+                .metadata()
+                .ok()
+                .and_then(|m| m.modified().ok())
+                .is_some_and(|m| m >= since),
+            None => true,
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+/// Report paths named explicitly on the command line (`--junitxml=`, `LogFilePath=`)
+pub fn named_report_paths(command: &str) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = REPORT_FLAG
+        .captures_iter(command)
+        .chain(DOTNET_LOGGER.captures_iter(command))
+        .map(|c| PathBuf::from(&c[1]))
+        .filter(|p| p.is_file())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Report paths named on the command line or in the output, else fresh files
+/// in well-known report directories (written at or after `since`)
+pub fn detect_report_paths(command: &str, output: &str, since: SystemTime) -> Vec<PathBuf> {
+    let mut paths = named_report_paths(command);
+    paths.extend(
+        GENERATED_XML
+            .captures_iter(output)
+            .map(|c| PathBuf::from(&c[1]))
+            .filter(|p| p.is_file()),
+    );
+
+    if paths.is_empty() {
+        for dir in WELL_KNOWN_REPORT_DIRS {
+            paths.extend(xml_files_under(Path::new(dir), Some(since)));
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+/// Structured summary for `rtk test` when the run wrote JUnit reports
+pub fn summarize_detected_reports(
+    command: &str,
+    output: &str,
+    since: SystemTime,
+    verbose: u8,
+) -> Option<String> {
+    summarize_reports(&detect_report_paths(command, output, since), verbose)
+}
+
+/// Structured summary for `rtk err`: only reports the command line names
+pub fn summarize_named_reports(command: &str, verbose: u8) -> Option<String> {
+    summarize_reports(&named_report_paths(command), verbose)
+}
+
+fn summarize_reports(paths: &[PathBuf], verbose: u8) -> Option<String> {
+    if paths.is_empty() {
+        return None;
+    }
+    if verbose > 0 {
+        eprintln!("JUnit reports: {} file(s)", paths.len());
+    }
+    match parse_reports(paths) {
+        ParseResult::Full(data) => Some(data.format(FormatMode::from_verbosity(verbose))),
+        ParseResult::Degraded(data, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning("junit", &warnings.join(", "));
+            }
+            Some(data.format(FormatMode::from_verbosity(verbose)))
+        }
+        ParseResult::Passthrough(_) => None,
+    }
+}
+
+pub fn run(patterns: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let paths = expand_report_paths(patterns);
+    if paths.is_empty() {
+        bail!("No JUnit reports matched: {}", patterns.join(" "));
+    }
+    if verbose > 0 {
+        eprintln!("Parsing {} JUnit report(s)", paths.len());
+    }
+
+    let mut raw = String::new();
+    for path in &paths {
+        raw.push_str(
+            &std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+        );
+    }
+
+    let mode = FormatMode::from_verbosity(verbose);
+    let (filtered, failed) = match parse_reports(&paths) {
+        ParseResult::Full(data) => (data.format(mode), data.failed),
+        ParseResult::Degraded(data, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning("junit", &warnings.join(", "));
+            }
+            (data.format(mode), data.failed)
+        }
+        ParseResult::Passthrough(raw) => {
+            emit_passthrough_warning("junit", "No parseable JUnit XML");
+            (raw, 0)
+        }
+    };
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("cat {}", patterns.join(" ")),
+        &format!("rtk junit {}", patterns.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    // Mirror the report: failing tests → non-zero, like the runner itself
+    if failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUREFIRE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.acme.CartTest" tests="4" failures="1" errors="1" skipped="1" time="0.412">
+  <properties><property name="java.version" value="21"/></properties>
+  <testcase name="addsItem" classname="com.acme.CartTest" time="0.010"/>
+  <testcase name="appliesDiscount" classname="com.acme.CartTest" time="0.200">
+    <failure message="expected: &lt;90&gt; but was: &lt;100&gt;" type="org.opentest4j.AssertionFailedError">org.opentest4j.AssertionFailedError: expected: &lt;90&gt; but was: &lt;100&gt;
+	at com.acme.CartTest.appliesDiscount(CartTest.java:42)
+</failure>
+    <system-out>debug noise</system-out>
+  </testcase>
+  <testcase name="loadsFromDb" classname="com.acme.CartTest" time="0.202">
+    <error message="Connection refused" type="java.net.ConnectException"/>
+  </testcase>
+  <testcase name="exportsCsv" classname="com.acme.CartTest" time="0">
+    <skipped/>
+  </testcase>
+</testsuite>
+"#;
+
+    #[test]
+    fn test_parse_surefire_report() {
+        let result = JunitParser::parse(SUREFIRE);
+        assert_eq!(result.tier(), 1);
+        let data = result.unwrap();
+        assert_eq!(
+            (data.total, data.passed, data.failed, data.skipped),
+            (4, 1, 2, 1)
+        );
+        assert_eq!(data.duration_ms, Some(412));
+
+        let first = &data.failures[0];
+        assert_eq!(first.test_name, "CartTest.appliesDiscount");
+        assert_eq!(first.file_path, "com.acme.CartTest");
+        assert_eq!(
+            first.error_message,
+            "org.opentest4j.AssertionFailedError: expected: <90> but was: <100>"
+        );
+        assert!(first
+            .stack_trace
+            .as_deref()
+            .unwrap()
+            .contains("CartTest.java:42"));
+
+        let second = &data.failures[1];
+        assert_eq!(
+            second.error_message,
+            "java.net.ConnectException: Connection refused"
+        );
+        assert_eq!(second.stack_trace, None);
+    }
+
+    #[test]
+    fn test_parse_nested_testsuites_with_file_attr() {
+        let xml = r#"<testsuites>
+  <testsuite name="pytest">
+    <testcase classname="tests.test_api" name="test_login" file="tests/test_api.py" time="0.5">
+      <failure message="assert 401 == 200">tests/test_api.py:12: in test_login
+    assert resp.status == 200
+E   assert 401 == 200</failure>
+    </testcase>
+    <testcase classname="tests.test_api" name="test_logout" time="0.1"/>
+  </testsuite>
+</testsuites>"#;
+        let data = JunitParser::parse(xml).unwrap();
+        assert_eq!((data.total, data.failed), (2, 1));
+        assert_eq!(data.failures[0].test_name, "test_api.test_login");
+        assert_eq!(data.failures[0].file_path, "tests/test_api.py");
+        assert_eq!(data.failures[0].error_message, "assert 401 == 200");
+    }
+
+    #[test]
+    fn test_truncated_report_degrades() {
+        let cut = &SUREFIRE[..SUREFIRE.find("<skipped/>").unwrap()];
+        let result = JunitParser::parse(cut);
+        assert_eq!(result.tier(), 2);
+        let data = result.unwrap();
+        assert_eq!(data.total, 4);
+        assert_eq!(data.failed, 2);
+        assert_eq!(data.failures[1].test_name, "loadsFromDb");
+    }
+
+    #[test]
+    fn test_not_junit_passthrough() {
+        assert_eq!(JunitParser::parse("plain text").tier(), 3);
+    }
+
+    #[test]
+    fn test_compact_summary() {
+        let data = JunitParser::parse(SUREFIRE).unwrap();
+        let out = data.format(FormatMode::Compact);
+        assert!(out.starts_with("PASS (1) FAIL (2)"));
+        assert!(out.contains("1. CartTest.appliesDiscount"));
+        assert!(!out.contains("debug noise"));
+    }
+
+    #[test]
+    fn test_merge_results() {
+        let a = JunitParser::parse(SUREFIRE).unwrap();
+        let b = JunitParser::parse(SUREFIRE).unwrap();
+        let merged = merge_results(vec![a, b]);
+        assert_eq!((merged.total, merged.failed), (8, 4));
+        assert_eq!(merged.duration_ms, Some(824));
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = glob_to_regex("build/test-results/**/*.xml");
+        assert!(re.is_match("build/test-results/test/TEST-Foo.xml"));
+        assert!(re.is_match("build/test-results/TEST-Foo.xml"));
+        assert!(!re.is_match("build/other/TEST-Foo.xml"));
+        assert!(!glob_to_regex("reports/*.xml").is_match("reports/a/b.xml"));
+    }
+
+    #[test]
+    fn test_detect_report_from_flag_and_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = dir.path().join("report.xml");
+        std::fs::write(&report, SUREFIRE).unwrap();
+        let since = SystemTime::now() - std::time::Duration::from_secs(60);
+
+        let cmd = format!("pytest --junitxml={} tests/", report.display());
+        assert_eq!(detect_report_paths(&cmd, "", since), vec![report.clone()]);
+        assert_eq!(named_report_paths(&cmd), vec![report.clone()]);
+        assert!(named_report_paths("mvn test").is_empty());
+
+        let output = format!("- generated xml file: {} -", report.display());
+        assert_eq!(
+            detect_report_paths("pytest", &output, since),
+            vec![report.clone()]
+        );
+
+        let fresh = xml_files_under(dir.path(), Some(since));
+        assert_eq!(fresh, vec![report.clone()]);
+        let future = SystemTime::now() + std::time::Duration::from_secs(60);
+        assert!(xml_files_under(dir.path(), Some(future)).is_empty());
+    }
+
+    #[test]
+    fn test_expand_report_paths_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested/TEST-a.xml"), SUREFIRE).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "x").unwrap();
+        let paths = expand_report_paths(&[dir.path().display().to_string()]);
+        assert_eq!(paths, vec![dir.path().join("nested/TEST-a.xml")]);
+    }
+}
@@ -0,0 +1,954 @@
+//! Gradle and Maven builds with compact output.
+//!
+//! `rtk gradle` / `rtk mvn` reduce a build to the task/goal outcome, javac /
+//! kotlinc diagnostics grouped by file and severity, one dependency
+//! resolution block, and test results read from the JUnit XML reports the
+//! build wrote (`build/test-results`, `target/surefire-reports`).
+
+use crate::junit_cmd;
+use crate::parser::{
+    diagnostics, emit_degradation_warning, FormatMode, LintIssue, LintSeverity, ParseResult,
+    TokenFormatter,
+};
+use crate::tracking;
+use crate::utils::{strip_ansi, truncate};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Lines kept from Gradle's "What went wrong" / Maven's goal failures
+const MAX_FAILURE_LINES: usize = 8;
+
+/// Tasks/goals whose output *is* the answer (listings, trees, help)
+const PASSTHROUGH_TASKS: &[&str] = &[
+    "tasks",
+    "dependencies",
+    "dependencyInsight",
+    "projects",
+    "properties",
+    "help",
+    "outgoingVariants",
+    "buildEnvironment",
+    "dependency:tree",
+    "dependency:list",
+    "dependency:analyze",
+    "help:effective-pom",
+    "help:describe",
+    "versions:display-dependency-updates",
+    "-v",
+    "-version",
+    "--version",
+    "-h",
+    "--help",
+];
+
+/// Report directories searched for fresh JUnit XML (relative to each module)
+const GRADLE_REPORT_DIRS: &[&str] = &["build/test-results"];
+const MAVEN_REPORT_DIRS: &[&str] = &["target/surefire-reports", "target/failsafe-reports"];
+
+/// Directories never searched for module report dirs
+const SKIP_DIRS: &[&str] = &[".git", ".gradle", ".idea", "node_modules", "src"];
+
+lazy_static! {
+    // Gradle
+    static ref GRADLE_TASK: Regex = Regex::new(r"^> Task (\S+)(?: (\S.*))?$").unwrap();
+    static ref GRADLE_OUTCOME: Regex = Regex::new(r"^BUILD (?:SUCCESSFUL|FAILED) in \S+").unwrap();
+    static ref GRADLE_TASK_COUNT: Regex = Regex::new(r"^\d+ actionable tasks?: .+$").unwrap();
+    // javac: `/path/Cart.java:42: error: cannot find symbol`
+    static ref JAVAC: Regex =
+        Regex::new(r"^(\S.*?\.java):(\d+): (error|warning): (.*)$").unwrap();
+    // kotlinc: `e: file:///path/Foo.kt:10:5 msg` and `e: /path/Foo.kt: (10, 5): msg`
+    static ref KOTLINC: Regex = Regex::new(
+        r"^([ew]): (?:file://)?(\S+?\.kts?)(?::(\d+):(\d+)|: \((\d+), (\d+)\):) (.*)$"
+    )
+    .unwrap();
+    static ref LINT_CATEGORY: Regex = Regex::new(r"^\[([\w-]+)\] (.*)$").unwrap();
+    static ref GRADLE_NOT_FOUND: Regex =
+        Regex::new(r"^> Could not (find|resolve) ([\w.\-]+:[\w.\-]+(?::\S+?)?)\.?$").unwrap();
+    // Maven
+    static ref MAVEN_LINE: Regex = Regex::new(r"^\[(INFO|WARNING|WARN|ERROR)\] ?(.*)$").unwrap();
+    static ref MAVEN_OUTCOME: Regex = Regex::new(r"^BUILD (SUCCESS|FAILURE)$").unwrap();
+    static ref MAVEN_TOTAL_TIME: Regex = Regex::new(r"^Total time:\s+(.+)$").unwrap();
+    static ref MAVEN_GOAL: Regex = Regex::new(r"^--- (\S+) .*@ (\S+) ---$").unwrap();
+    static ref MAVEN_REACTOR: Regex =
+        Regex::new(r"^(\S.*?) \.{2,} ?(SUCCESS|FAILURE|SKIPPED)\b").unwrap();
+    // `/path/Main.java:[10,5] cannot find symbol`
+    static ref MAVEN_COMPILER: Regex =
+        Regex::new(r"^(?:file://)?(\S.*?\.(?:java|kts?|scala|groovy)):\[(\d+),(\d+)\] (.*)$")
+            .unwrap();
+    static ref MAVEN_KOTLIN: Regex =
+        Regex::new(r"^(?:file://)?(\S+?\.kts?): \((\d+), (\d+)\) (.*)$").unwrap();
+    static ref MAVEN_FAILED_GOAL: Regex =
+        Regex::new(r"^Failed to execute goal (?:(\S+) (?:\(\S+\) )?)?on project (\S+?): (.*)$")
+            .unwrap();
+    static ref MAVEN_DEP_PROJECT: Regex =
+        Regex::new(r"Could not resolve dependencies for project (\S+):\s").unwrap();
+    static ref MAVEN_ARTIFACT: Regex =
+        Regex::new(r"\b[\w.\-]+:[\w.\-]+:(?:jar|pom|war|aar|test-jar|maven-plugin):(?:[\w.\-]+:)?[\w.\-]+\b")
+            .unwrap();
+}
+
+/// One build, reduced to what an agent acts on
+#[derive(Debug, Default)]
+struct JvmBuild {
+    /// "BUILD SUCCESSFUL in 12s" / "BUILD FAILURE in 4.1 s"
+    outcome: Option<String>,
+    success: Option<bool>,
+    /// "23 actionable tasks: 5 executed, 18 up-to-date" / "3 modules: ..."
+    steps: Option<String>,
+    /// Failed tasks (`:app:test`) or reactor modules
+    failed_steps: Vec<String>,
+    issues: Vec<LintIssue>,
+    /// "com.acme:lib:1.2 not found (required by project :app)"
+    dependency_errors: Vec<String>,
+    /// Gradle "What went wrong" / Maven goal failure lines
+    failure: Vec<String>,
+}
+
+impl JvmBuild {
+    fn push_issue(&mut self, issue: LintIssue) {
+        let duplicate = self.issues.iter().any(|i| {
+            i.file_path == issue.file_path
+                && i.line == issue.line
+                && i.column == issue.column
+                && i.message.starts_with(&issue.message)
+        });
+        if !duplicate {
+            self.issues.push(issue);
+        }
+    }
+
+    fn push_dependency_error(&mut self, error: String) {
+        if !self.dependency_errors.contains(&error) {
+            self.dependency_errors.push(error);
+        }
+    }
+}
+
+/// Path relative to the project root
+fn relative_path(path: &str, root: &str) -> String {
+    let path = path.trim_start_matches("file://");
+    let root = root.trim_end_matches('/');
+    match path.strip_prefix(root) {
+        Some(rest) if !root.is_empty() && rest.starts_with('/') => rest[1..].to_string(),
+        _ => path.to_string(),
+    }
+}
+
+fn issue(
+    file: &str,
+    line: &str,
+    column: &str,
+    severity: LintSeverity,
+    message: &str,
+    root: &str,
+) -> LintIssue {
+    // javac lint warnings: `[deprecation] foo() in Bar has been deprecated`
+    let (rule_id, message) = match LINT_CATEGORY.captures(message) {
+        Some(caps) => (caps[1].to_string(), caps[2].to_string()),
+        None => (String::new(), message.to_string()),
+    };
+    LintIssue {
+        file_path: relative_path(file, root),
+        line: line.parse().unwrap_or(0),
+        column: column.parse().unwrap_or(0),
+        severity,
+        rule_id,
+        message,
+        suggestion: None,
+    }
+}
+
+/// Append javac's `symbol:` detail to the message it belongs to
+fn append_symbol(build: &mut JvmBuild, line: &str) {
+    let trimmed = line.trim();
+    if let Some(symbol) = trimmed.strip_prefix("symbol:") {
+        if let Some(last) = build.issues.last_mut() {
+            if !last.message.contains("(symbol:") {
+                last.message = format!("{} (symbol: {})", last.message, symbol.trim());
+            }
+        }
+    }
+}
+
+fn parse_gradle(output: &str, root: &str) -> JvmBuild {
+    let mut build = JvmBuild::default();
+    let lines: Vec<String> = output.lines().map(strip_ansi).collect();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        if let Some(caps) = GRADLE_TASK.captures(line) {
+            if caps.get(2).is_some_and(|m| m.as_str() == "FAILED") {
+                build.failed_steps.push(caps[1].to_string());
+            }
+        } else if GRADLE_OUTCOME.is_match(line) {
+            build.success = Some(line.starts_with("BUILD SUCCESSFUL"));
+            build.outcome = Some(line.to_string());
+        } else if GRADLE_TASK_COUNT.is_match(line) {
+            build.steps = Some(line.to_string());
+        } else if let Some(caps) = JAVAC.captures(line) {
+            let severity = if &caps[3] == "error" {
+                LintSeverity::Error
+            } else {
+                LintSeverity::Warning
+            };
+            build.push_issue(issue(&caps[1], &caps[2], "0", severity, &caps[4], root));
+        } else if let Some(caps) = KOTLINC.captures(line) {
+            let severity = if &caps[1] == "e" {
+                LintSeverity::Error
+            } else {
+                LintSeverity::Warning
+            };
+            let (l, c) = match (caps.get(3), caps.get(4)) {
+                (Some(l), Some(c)) => (l.as_str(), c.as_str()),
+                _ => (
+                    caps.get(5).map_or("0", |m| m.as_str()),
+                    caps.get(6).map_or("0", |m| m.as_str()),
+                ),
+            };
+            build.push_issue(issue(&caps[2], l, c, severity, &caps[7], root));
+        } else if line.trim_start().starts_with("symbol:") {
+            append_symbol(&mut build, line);
+        } else if line == "* What went wrong:" {
+            i = parse_what_went_wrong(&lines, i + 1, &mut build);
+            continue;
+        }
+        i += 1;
+    }
+    build
+}
+
+/// Gradle's failure block, up to the next `* Try:` / `* Exception is:` heading
+fn parse_what_went_wrong(lines: &[String], start: usize, build: &mut JvmBuild) -> usize {
+    let mut i = start;
+    let mut block: Vec<&str> = Vec::new();
+    while i < lines.len() && !lines[i].starts_with("* ") && !lines[i].starts_with("BUILD ") {
+        let trimmed = lines[i].trim();
+        if !trimmed.is_empty() {
+            block.push(trimmed);
+        }
+        i += 1;
+    }
+
+    if !block.iter().any(|l| l.contains("Could not resolve all")) {
+        build.failure.extend(block.iter().map(|l| l.to_string()));
+        return i;
+    }
+
+    // Dependency resolution: one line per missing module
+    let mut pending: Vec<String> = Vec::new();
+    let mut in_required_by = false;
+    for line in &block {
+        if let Some(caps) = GRADLE_NOT_FOUND.captures(line) {
+            flush_gradle_dependency(build, &mut pending, None);
+            let what = if &caps[1] == "find" {
+                "not found"
+            } else {
+                "unresolved"
+            };
+            pending.push(format!("{} {}", &caps[2], what));
+            in_required_by = false;
+        } else if *line == "Required by:" {
+            in_required_by = true;
+        } else if in_required_by && !line.starts_with('>') && !line.starts_with('-') {
+            flush_gradle_dependency(build, &mut pending, Some(line));
+        } else if line.contains("Could not resolve all") {
+            in_required_by = false;
+        }
+    }
+    flush_gradle_dependency(build, &mut pending, None);
+    i
+}
+
+fn flush_gradle_dependency(build: &mut JvmBuild, pending: &mut Vec<String>, by: Option<&str>) {
+    for what in pending.drain(..) {
+        match by {
+            Some(by) => build.push_dependency_error(format!("{} (required by {})", what, by)),
+            None => build.push_dependency_error(what),
+        }
+    }
+}
+
+/// `org.apache.maven.plugins:maven-surefire-plugin:3.0.0:test` → `surefire:test`
+fn short_goal(goal: &str) -> String {
+    let parts: Vec<&str> = goal.split(':').collect();
+    if parts.len() < 2 {
+        return goal.to_string();
+    }
+    let artifact = if parts.len() >= 4 { parts[1] } else { parts[0] };
+    let plugin = artifact
+        .trim_start_matches("maven-")
+        .trim_end_matches("-maven-plugin")
+        .trim_end_matches("-plugin");
+    format!("{}:{}", plugin, parts[parts.len() - 1])
+}
+
+fn parse_maven(output: &str, root: &str) -> JvmBuild {
+    let mut build = JvmBuild::default();
+    let mut goals = 0;
+    let mut modules: Vec<(String, String)> = Vec::new();
+    let mut total_time: Option<String> = None;
+
+    for raw_line in output.lines() {
+        let raw_line = strip_ansi(raw_line);
+        let (level, line) = match MAVEN_LINE.captures(&raw_line) {
+            Some(caps) => (caps[1].to_string(), caps[2].trim_end().to_string()),
+            None => (String::new(), raw_line.trim_end().to_string()),
+        };
+        let line = line.as_str();
+
+        if let Some(caps) = MAVEN_OUTCOME.captures(line) {
+            build.success = Some(&caps[1] == "SUCCESS");
+            build.outcome = Some(line.to_string());
+        } else if let Some(caps) = MAVEN_TOTAL_TIME.captures(line) {
+            total_time = Some(caps[1].trim().to_string());
+        } else if MAVEN_GOAL.is_match(line) {
+            goals += 1;
+        } else if let Some(caps) = MAVEN_REACTOR.captures(line) {
+            modules.push((caps[1].to_string(), caps[2].to_string()));
+        } else if let Some(caps) = MAVEN_COMPILER.captures(line) {
+            let severity = if level == "ERROR" {
+                LintSeverity::Error
+            } else {
+                LintSeverity::Warning
+            };
+            build.push_issue(issue(
+                &caps[1], &caps[2], &caps[3], severity, &caps[4], root,
+            ));
+        } else if let Some(caps) = MAVEN_KOTLIN.captures(line) {
+            let severity = if level == "ERROR" {
+                LintSeverity::Error
+            } else {
+                LintSeverity::Warning
+            };
+            build.push_issue(issue(
+                &caps[1], &caps[2], &caps[3], severity, &caps[4], root,
+            ));
+        } else if line.trim_start().starts_with("symbol:") {
+            append_symbol(&mut build, line);
+        } else if let Some(caps) = MAVEN_FAILED_GOAL.captures(line) {
+            let message = caps[3].split(" -> [Help").next().unwrap_or("").trim();
+            if MAVEN_DEP_PROJECT.is_match(message) {
+                parse_maven_dependencies(message, &mut build);
+                continue;
+            }
+            let failure = match caps.get(1) {
+                Some(goal) => format!("{} on {}: {}", short_goal(goal.as_str()), &caps[2], message),
+                None => format!("{}: {}", &caps[2], message),
+            };
+            if !build.failure.contains(&failure) {
+                build.failure.push(failure);
+            }
+        }
+    }
+
+    if let (Some(outcome), Some(time)) = (&build.outcome, total_time) {
+        build.outcome = Some(format!("{} in {}", outcome, time));
+    }
+    if !modules.is_empty() {
+        let count = |status: &str| modules.iter().filter(|(_, s)| s == status).count();
+        let mut parts = vec![format!("{} ok", count("SUCCESS"))];
+        for (status, label) in [("FAILURE", "failed"), ("SKIPPED", "skipped")] {
+            if count(status) > 0 {
+                parts.push(format!("{} {}", count(status), label));
+            }
+        }
+        build.steps = Some(format!("{} modules: {}", modules.len(), parts.join(", ")));
+        build.failed_steps.extend(
+            modules
+                .iter()
+                .filter(|(_, s)| s == "FAILURE")
+                .map(|(name, _)| name.clone()),
+        );
+    } else if goals > 0 {
+        build.steps = Some(format!("{} goals", goals));
+    }
+    build
+}
+
+/// `Could not resolve dependencies for project a:b:jar:1: The following
+/// artifacts could not be resolved: x:y:jar:2 (absent): ...` → one line per artifact
+fn parse_maven_dependencies(message: &str, build: &mut JvmBuild) {
+    let project = MAVEN_DEP_PROJECT
+        .captures(message)
+        .map(|c| c[1].to_string())
+        .unwrap_or_default();
+    let mut found = false;
+    for m in MAVEN_ARTIFACT.find_iter(message) {
+        if m.as_str() == project {
+            continue;
+        }
+        found = true;
+        build.push_dependency_error(format!(
+            "{} unresolved (required by {})",
+            m.as_str(),
+            project
+        ));
+    }
+    if !found {
+        build.push_dependency_error(truncate(message, 200));
+    }
+}
+
+fn format_build(tool: &str, build: &JvmBuild, tests: Option<&str>) -> String {
+    let mut out = Vec::new();
+
+    let mark = match build.success {
+        Some(true) => "✓",
+        Some(false) => "✗",
+        None => "•",
+    };
+    let outcome = build.outcome.as_deref().unwrap_or("no build summary");
+    match &build.steps {
+        Some(steps) => out.push(format!("{} {}: {} ({})", mark, tool, outcome, steps)),
+        None => out.push(format!("{} {}: {}", mark, tool, outcome)),
+    }
+    if !build.failed_steps.is_empty() {
+        out.push(format!("Failed: {}", build.failed_steps.join(", ")));
+    }
+
+    let issues: Vec<(&LintIssue, String)> =
+        build.issues.iter().map(|i| (i, String::new())).collect();
+    out.extend(diagnostics::format_by_file(&issues));
+
+    if !build.dependency_errors.is_empty() {
+        out.push("Dependency resolution failed:".to_string());
+        for error in &build.dependency_errors {
+            out.push(format!("  {}", error));
+        }
+    }
+
+    if let Some(tests) = tests {
+        out.push(format!("Tests: {}", tests));
+    }
+
+    // Compile/dependency failures are already shown above
+    if !build.failure.is_empty() && build.issues.is_empty() && build.dependency_errors.is_empty() {
+        out.push("What went wrong:".to_string());
+        for line in build.failure.iter().take(MAX_FAILURE_LINES) {
+            out.push(format!("  {}", truncate(line, 200)));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// Module report dirs (`build/test-results`, `app/target/surefire-reports`, ...)
+fn report_dirs(root: &Path, suffixes: &[&str]) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(root)
+        .max_depth(6)
+        .into_iter()
+        .filter_entry(|e| {
+            !e.file_type().is_dir()
+                || !SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+        .filter(|e| suffixes.iter().any(|s| e.path().ends_with(s)))
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+/// Test results from JUnit XML written during this run
+fn fresh_test_results(suffixes: &[&str], since: SystemTime, verbose: u8) -> Option<String> {
+    let mut paths: Vec<PathBuf> = report_dirs(Path::new("."), suffixes)
+        .iter()
+        .flat_map(|dir| junit_cmd::xml_files_under(dir, Some(since)))
+        .collect();
+    if paths.is_empty() {
+        return None;
+    }
+    paths.sort();
+    if verbose > 0 {
+        eprintln!("JUnit reports: {} file(s)", paths.len());
+    }
+    let mode = FormatMode::from_verbosity(verbose);
+    match junit_cmd::parse_reports(&paths) {
+        ParseResult::Full(data) => Some(data.format(mode)),
+        ParseResult::Degraded(data, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning("junit", &warnings.join(", "));
+            }
+            Some(data.format(mode))
+        }
+        ParseResult::Passthrough(_) => None,
+    }
+}
+
+fn is_passthrough(args: &[String]) -> bool {
+    args.iter().any(|a| PASSTHROUGH_TASKS.contains(&a.as_str()))
+}
+
+/// Tasks/goals that run the application (`run`, `bootRun`, `:app:run`,
+/// `spring-boot:run`, `exec:java`): the output is the program's own and
+/// servers never exit, so they stream through untouched
+fn is_run_task(args: &[String]) -> bool {
+    args.iter().any(|a| {
+        a == "run"
+            || a == "bootRun"
+            || a.ends_with(":run")
+            || a.ends_with(":bootRun")
+            || a.starts_with("exec:")
+    })
+}
+
+/// rtk-only `--wrapper`: the original command used `./gradlew` / `./mvnw`
+fn split_wrapper_flag(args: &[String]) -> (bool, Vec<String>) {
+    let wrapper = args.iter().any(|a| a == "--wrapper");
+    let rest = args.iter().filter(|a| *a != "--wrapper").cloned().collect();
+    (wrapper, rest)
+}
+
+/// Project wrapper (`./gradlew`, `./mvnw`) when asked for with `--wrapper`,
+/// else the tool on PATH
+fn executable(use_wrapper: bool, wrapper: &str, tool: &str) -> String {
+    if !use_wrapper {
+        tool.to_string()
+    } else if Path::new(wrapper).is_file() {
+        format!("./{}", wrapper)
+    } else {
+        wrapper.to_string()
+    }
+}
+
+/// Run the tool with inherited stdio (application output, live)
+fn run_streaming(tool: &str, program: &str, args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("Running: {} {}", program, args.join(" "));
+    }
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to run {}. Is it installed?", program))?;
+
+    timer.track_passthrough(
+        &format!("{} {}", tool, args.join(" ")),
+        &format!("rtk {} {} (passthrough)", tool, args.join(" ")),
+    );
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+pub fn run_gradle(args: &[String], verbose: u8) -> Result<()> {
+    let (use_wrapper, args) = split_wrapper_flag(args);
+    let args = args.as_slice();
+    let program = executable(use_wrapper, "gradlew", "gradle");
+    if is_run_task(args) {
+        return run_streaming("gradle", &program, args, verbose);
+    }
+
+    let mut cmd_args: Vec<String> = Vec::new();
+    // Plain console: no progress bar redraws, `> Task` lines for every task
+    if !args.iter().any(|a| a.starts_with("--console")) && !is_passthrough(args) {
+        cmd_args.push("--console=plain".to_string());
+    }
+    cmd_args.extend(args.iter().cloned());
+    run_build(
+        "gradle",
+        &program,
+        args,
+        &cmd_args,
+        verbose,
+        GRADLE_REPORT_DIRS,
+        parse_gradle,
+    )
+}
+
+pub fn run_mvn(args: &[String], verbose: u8) -> Result<()> {
+    let (use_wrapper, args) = split_wrapper_flag(args);
+    let args = args.as_slice();
+    let program = executable(use_wrapper, "mvnw", "mvn");
+    if is_run_task(args) {
+        return run_streaming("mvn", &program, args, verbose);
+    }
+
+    let mut cmd_args: Vec<String> = Vec::new();
+    // Batch mode: no colors, no interactive transfer progress
+    if !args.iter().any(|a| a == "-B" || a == "--batch-mode") {
+        cmd_args.push("-B".to_string());
+    }
+    cmd_args.extend(args.iter().cloned());
+    run_build(
+        "mvn",
+        &program,
+        args,
+        &cmd_args,
+        verbose,
+        MAVEN_REPORT_DIRS,
+        parse_maven,
+    )
+}
+
+fn run_build(
+    tool: &str,
+    program: &str,
+    args: &[String],
+    cmd_args: &[String],
+    verbose: u8,
+    report_dirs: &[&str],
+    parse: fn(&str, &str) -> JvmBuild,
+) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+    let started = SystemTime::now();
+
+    if verbose > 0 {
+        eprintln!("Running: {} {}", program, cmd_args.join(" "));
+    }
+
+    let output = Command::new(program)
+        .args(cmd_args)
+        .output()
+        .with_context(|| format!("Failed to run {}. Is it installed?", program))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+
+    let mut filtered = if is_passthrough(args) {
+        raw.trim().to_string()
+    } else {
+        let root = std::env::current_dir()
+            .map(|d| d.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let build = parse(&raw, &root);
+        let tests = fresh_test_results(report_dirs, started, verbose);
+        if build.outcome.is_none() && tests.is_none() {
+            // Crashed before the build summary: keep the tail of the output
+            format!(
+                "{}\n{}",
+                format_build(tool, &build, None),
+                diagnostics::crash_tail(&raw)
+            )
+        } else {
+            format_build(tool, &build, tests.as_deref())
+        }
+    };
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, tool, exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &format!("{} {}", tool, args.join(" ")),
+        &format!("rtk {} {}", tool, args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    const GRADLE_COMPILE_FAILURE: &str = r#"Starting a Gradle Daemon (subsequent builds will be faster)
+> Task :app:compileJava
+/home/dev/shop/app/src/main/java/com/acme/Cart.java:42: error: cannot find symbol
+        total = applyDiscount(total);
+                ^
+  symbol:   method applyDiscount(int)
+  location: class Cart
+/home/dev/shop/app/src/main/java/com/acme/Cart.java:57: error: incompatible types: String cannot be converted to int
+        int n = name;
+                ^
+/home/dev/shop/app/src/main/java/com/acme/Legacy.java:12: warning: [deprecation] Date(String) in Date has been deprecated
+        new Date("2020-01-01");
+        ^
+2 errors
+1 warning
+
+> Task :app:compileJava FAILED
+
+FAILURE: Build failed with an exception.
+
+* What went wrong:
+Execution failed for task ':app:compileJava'.
+> Compilation failed; see the compiler error output for details.
+
+* Try:
+> Run with --stacktrace option to get the stack trace.
+> Run with --info or --debug option to get more log output.
+
+BUILD FAILED in 3s
+1 actionable task: 1 executed
+"#;
+
+    #[test]
+    fn test_gradle_compile_errors_grouped_by_file() {
+        let build = parse_gradle(GRADLE_COMPILE_FAILURE, "/home/dev/shop");
+        assert_eq!(build.success, Some(false));
+        assert_eq!(build.failed_steps, vec![":app:compileJava"]);
+        assert_eq!(build.issues.len(), 3);
+
+        let out = format_build("gradle", &build, None);
+        assert_eq!(
+            out,
+            concat!(
+                "✗ gradle: BUILD FAILED in 3s (1 actionable task: 1 executed)\n",
+                "Failed: :app:compileJava\n",
+                "Errors (2):\n",
+                "  app/src/main/java/com/acme/Cart.java\n",
+                "    42 cannot find symbol (symbol: method applyDiscount(int))\n",
+                "    57 incompatible types: String cannot be converted to int\n",
+                "Warnings (1):\n",
+                "  app/src/main/java/com/acme/Legacy.java\n",
+                "    12 [deprecation] Date(String) in Date has been deprecated",
+            )
+        );
+    }
+
+    #[test]
+    fn test_gradle_success_and_kotlin_warning() {
+        let output = r#"> Task :compileKotlin
+w: file:///home/dev/lib/src/main/kotlin/Util.kt:8:9 Variable 'tmp' is never used
+> Task :compileJava NO-SOURCE
+> Task :test UP-TO-DATE
+Download https://repo.maven.apache.org/maven2/org/jetbrains/kotlin/kotlin-stdlib/1.9.0/kotlin-stdlib-1.9.0.jar
+
+BUILD SUCCESSFUL in 12s
+5 actionable tasks: 2 executed, 3 up-to-date
+"#;
+        let build = parse_gradle(output, "/home/dev/lib");
+        let out = format_build("gradle", &build, Some("PASS (12) FAIL (0)"));
+        assert_eq!(
+            out,
+            concat!(
+                "✓ gradle: BUILD SUCCESSFUL in 12s (5 actionable tasks: 2 executed, 3 up-to-date)\n",
+                "Warnings (1):\n",
+                "  src/main/kotlin/Util.kt\n",
+                "    8:9 Variable 'tmp' is never used\n",
+                "Tests: PASS (12) FAIL (0)",
+            )
+        );
+    }
+
+    #[test]
+    fn test_gradle_dependency_resolution_block() {
+        let output = r#"> Task :app:compileJava FAILED
+
+FAILURE: Build failed with an exception.
+
+* What went wrong:
+Execution failed for task ':app:compileJava'.
+> Could not resolve all files for configuration ':app:compileClasspath'.
+   > Could not find com.acme:billing:2.3.1.
+     Searched in the following locations:
+       - https://repo.maven.apache.org/maven2/com/acme/billing/2.3.1/billing-2.3.1.pom
+       - https://plugins.gradle.org/m2/com/acme/billing/2.3.1/billing-2.3.1.pom
+     Required by:
+         project :app
+   > Could not resolve org.acme:auth:1.+.
+     Required by:
+         project :app > com.acme:core:1.0
+
+* Try:
+> Run with --scan to get full insights.
+
+BUILD FAILED in 1s
+"#;
+        let build = parse_gradle(output, "/home/dev/shop");
+        assert_eq!(
+            build.dependency_errors,
+            vec![
+                "com.acme:billing:2.3.1 not found (required by project :app)",
+                "org.acme:auth:1.+ unresolved (required by project :app > com.acme:core:1.0)",
+            ]
+        );
+        let out = format_build("gradle", &build, None);
+        assert!(!out.contains("https://"), "{}", out);
+        assert!(!out.contains("What went wrong"), "{}", out);
+    }
+
+    #[test]
+    fn test_gradle_test_failure_keeps_what_went_wrong() {
+        let output = r#"> Task :app:test FAILED
+
+CartTest > appliesDiscount() FAILED
+    org.opentest4j.AssertionFailedError at CartTest.java:42
+
+4 tests completed, 1 failed
+
+FAILURE: Build failed with an exception.
+
+* What went wrong:
+Execution failed for task ':app:test'.
+> There were failing tests. See the report at: file:///home/dev/shop/app/build/reports/tests/test/index.html
+
+BUILD FAILED in 5s
+3 actionable tasks: 1 executed, 2 up-to-date
+"#;
+        let build = parse_gradle(output, "/home/dev/shop");
+        let out = format_build("gradle", &build, Some("PASS (3) FAIL (1)"));
+        assert!(out.contains("Failed: :app:test"), "{}", out);
+        assert!(out.contains("Tests: PASS (3) FAIL (1)"), "{}", out);
+        assert!(
+            out.contains("  Execution failed for task ':app:test'."),
+            "{}",
+            out
+        );
+    }
+
+    const MAVEN_COMPILE_FAILURE: &str = r#"[INFO] Scanning for projects...
+[INFO]
+[INFO] -------------------------< com.acme:shop >--------------------------
+[INFO] Building shop 1.0-SNAPSHOT
+[INFO] --------------------------------[ jar ]---------------------------------
+[INFO] Downloading from central: https://repo.maven.apache.org/maven2/org/apache/maven/plugins/maven-compiler-plugin/3.11.0/maven-compiler-plugin-3.11.0.pom
+[INFO] Downloaded from central: https://repo.maven.apache.org/maven2/org/apache/maven/plugins/maven-compiler-plugin/3.11.0/maven-compiler-plugin-3.11.0.pom (10 kB at 120 kB/s)
+[INFO]
+[INFO] --- maven-resources-plugin:3.3.1:resources (default-resources) @ shop ---
+[INFO] Copying 1 resource
+[INFO]
+[INFO] --- maven-compiler-plugin:3.11.0:compile (default-compile) @ shop ---
+[INFO] Changes detected - recompiling the module! :source
+[WARNING] /home/dev/shop/src/main/java/com/acme/Legacy.java:[12,9] [deprecation] Date(String) in Date has been deprecated
+[INFO] -------------------------------------------------------------
+[ERROR] COMPILATION ERROR :
+[INFO] -------------------------------------------------------------
+[ERROR] /home/dev/shop/src/main/java/com/acme/Cart.java:[42,17] cannot find symbol
+  symbol:   method applyDiscount(int)
+  location: class com.acme.Cart
+[INFO] 1 error
+[INFO] -------------------------------------------------------------
+[INFO] ------------------------------------------------------------------------
+[INFO] BUILD FAILURE
+[INFO] ------------------------------------------------------------------------
+[INFO] Total time:  2.543 s
+[INFO] Finished at: 2024-01-15T10:30:00Z
+[INFO] ------------------------------------------------------------------------
+[ERROR] Failed to execute goal org.apache.maven.plugins:maven-compiler-plugin:3.11.0:compile (default-compile) on project shop: Compilation failure
+[ERROR] /home/dev/shop/src/main/java/com/acme/Cart.java:[42,17] cannot find symbol
+[ERROR]   symbol:   method applyDiscount(int)
+[ERROR]   location: class com.acme.Cart
+[ERROR]
+[ERROR] -> [Help 1]
+[ERROR]
+[ERROR] To see the full stack trace of the errors, re-run Maven with the -e switch.
+"#;
+
+    #[test]
+    fn test_maven_compile_errors_deduplicated() {
+        let build = parse_maven(MAVEN_COMPILE_FAILURE, "/home/dev/shop");
+        assert_eq!(build.issues.len(), 2);
+        let out = format_build("mvn", &build, None);
+        assert_eq!(
+            out,
+            concat!(
+                "✗ mvn: BUILD FAILURE in 2.543 s (2 goals)\n",
+                "Errors (1):\n",
+                "  src/main/java/com/acme/Cart.java\n",
+                "    42:17 cannot find symbol (symbol: method applyDiscount(int))\n",
+                "Warnings (1):\n",
+                "  src/main/java/com/acme/Legacy.java\n",
+                "    12:9 [deprecation] Date(String) in Date has been deprecated",
+            )
+        );
+    }
+
+    #[test]
+    fn test_maven_reactor_and_test_failure() {
+        let output = r#"[INFO] Reactor Summary for parent 1.0:
+[INFO]
+[INFO] core ............................................... SUCCESS [  1.234 s]
+[INFO] app ................................................ FAILURE [  0.512 s]
+[INFO] web ................................................ SKIPPED
+[INFO] ------------------------------------------------------------------------
+[INFO] BUILD FAILURE
+[INFO] ------------------------------------------------------------------------
+[INFO] Total time:  3.101 s
+[ERROR] Failed to execute goal org.apache.maven.plugins:maven-surefire-plugin:3.0.0:test (default-test) on project app: There are test failures.
+[ERROR]
+[ERROR] Please refer to /home/dev/shop/app/target/surefire-reports for the individual test results.
+"#;
+        let build = parse_maven(output, "/home/dev/shop");
+        let out = format_build("mvn", &build, Some("PASS (3) FAIL (1)"));
+        assert_eq!(
+            out,
+            concat!(
+                "✗ mvn: BUILD FAILURE in 3.101 s (3 modules: 1 ok, 1 failed, 1 skipped)\n",
+                "Failed: app\n",
+                "Tests: PASS (3) FAIL (1)\n",
+                "What went wrong:\n",
+                "  surefire:test on app: There are test failures.",
+            )
+        );
+    }
+
+    #[test]
+    fn test_maven_dependency_resolution_block() {
+        let output = "[ERROR] Failed to execute goal on project app: Could not resolve dependencies for project com.acme:app:jar:1.0: The following artifacts could not be resolved: com.acme:billing:jar:2.3.1 (absent), com.acme:auth:jar:1.4 (absent): Could not find artifact com.acme:billing:jar:2.3.1 in central (https://repo.maven.apache.org/maven2) -> [Help 1]\n";
+        let build = parse_maven(output, "/home/dev/shop");
+        assert_eq!(
+            build.dependency_errors,
+            vec![
+                "com.acme:billing:jar:2.3.1 unresolved (required by com.acme:app:jar:1.0)",
+                "com.acme:auth:jar:1.4 unresolved (required by com.acme:app:jar:1.0)",
+            ]
+        );
+        assert!(build.failure.is_empty());
+    }
+
+    #[test]
+    fn test_short_goal() {
+        assert_eq!(
+            short_goal("org.apache.maven.plugins:maven-surefire-plugin:3.0.0:test"),
+            "surefire:test"
+        );
+        assert_eq!(
+            short_goal("org.jetbrains.kotlin:kotlin-maven-plugin:1.9.0:compile"),
+            "kotlin:compile"
+        );
+    }
+
+    #[test]
+    fn test_passthrough_tasks() {
+        assert!(is_passthrough(&["dependencies".to_string()]));
+        assert!(is_passthrough(&["dependency:tree".to_string()]));
+        assert!(!is_passthrough(&["test".to_string()]));
+    }
+
+    #[test]
+    fn test_run_tasks_and_wrapper_flag() {
+        assert!(is_run_task(&args(&[":app:run", "--args=--port 8080"])));
+        assert!(is_run_task(&args(&["bootRun"])));
+        assert!(is_run_task(&args(&["-q", "spring-boot:run"])));
+        assert!(is_run_task(&args(&["compile", "exec:java"])));
+        assert!(!is_run_task(&args(&["build", "--rerun-tasks"])));
+        assert!(!is_run_task(&args(&["test"])));
+
+        assert_eq!(
+            split_wrapper_flag(&args(&["--wrapper", "build"])),
+            (true, args(&["build"]))
+        );
+        assert_eq!(
+            split_wrapper_flag(&args(&["build"])),
+            (false, args(&["build"]))
+        );
+        assert_eq!(executable(false, "gradlew", "gradle"), "gradle");
+    }
+}
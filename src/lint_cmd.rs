@@ -1,5 +1,6 @@
 use crate::mypy_cmd;
 use crate::ruff_cmd;
+use crate::sarif_cmd;
 use crate::tracking;
 use crate::utils::{package_manager_exec, truncate};
 use anyhow::{Context, Result};
@@ -53,13 +54,13 @@ fn is_python_linter(linter: &str) -> bool {
     matches!(linter, "ruff" | "pylint" | "mypy" | "flake8")
 }
 
-/// Strip package manager prefixes (npx, bunx, pnpm, pnpm exec, yarn) from args.
+/// Strip package manager prefixes (npx, bunx, bun x, pnpm, pnpm exec, yarn) from args.
 /// Returns the number of args to skip.
 fn strip_pm_prefix(args: &[String]) -> usize {
-    let pm_names = ["npx", "bunx", "pnpm", "yarn"];
+    let pm_names = ["npx", "bunx", "bun", "pnpm", "yarn"];
     let mut skip = 0;
     for arg in args {
-        if pm_names.contains(&arg.as_str()) || arg == "exec" {
+        if pm_names.contains(&arg.as_str()) || arg == "exec" || arg == "x" {
             skip += 1;
         } else {
             break;
@@ -99,20 +100,27 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
         package_manager_exec(linter)
     };
 
+    // A requested SARIF log is parsed as-is instead of forcing the linter's JSON
+    let sarif = sarif_cmd::wants_sarif(effective_args);
+
     // Add format flags based on linter
     match linter {
         "eslint" => {
-            cmd.arg("-f").arg("json");
+            if !sarif {
+                cmd.arg("-f").arg("json");
+            }
         }
         "ruff" => {
             // Force JSON output for ruff check
-            if !effective_args.contains(&"--output-format".to_string()) {
+            if sarif {
+                cmd.arg("check");
+            } else if !effective_args.contains(&"--output-format".to_string()) {
                 cmd.arg("check").arg("--output-format=json");
             }
         }
         "pylint" => {
             // Force JSON2 output for pylint
-            if !effective_args.contains(&"--output-format".to_string()) {
+            if !sarif && !effective_args.contains(&"--output-format".to_string()) {
                 cmd.arg("--output-format=json2");
             }
         }
@@ -140,10 +148,10 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
 
     for arg in &effective_args[start_idx..] {
         // Skip --output-format if we already added it
-        if linter == "ruff" && arg.starts_with("--output-format") {
+        if !sarif && linter == "ruff" && arg.starts_with("--output-format") {
             continue;
         }
-        if linter == "pylint" && arg.starts_with("--output-format") {
+        if !sarif && linter == "pylint" && arg.starts_with("--output-format") {
             continue;
         }
         cmd.arg(arg);
@@ -187,19 +195,23 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
     let raw = format!("{}\n{}", stdout, stderr);
 
     // Dispatch to appropriate filter based on linter
-    let filtered = match linter {
-        "eslint" => filter_eslint_json(&stdout),
-        "ruff" => {
-            // Reuse ruff_cmd's JSON parser
-            if !stdout.trim().is_empty() {
-                ruff_cmd::filter_ruff_check_json(&stdout)
-            } else {
-                "✓ Ruff: No issues found".to_string()
+    let filtered = if sarif || sarif_cmd::looks_like_sarif(&stdout) {
+        sarif_cmd::filter_sarif(&stdout, verbose)
+    } else {
+        match linter {
+            "eslint" => filter_eslint_json(&stdout),
+            "ruff" => {
+                // Reuse ruff_cmd's JSON parser
+                if !stdout.trim().is_empty() {
+                    ruff_cmd::filter_ruff_check_json(&stdout)
+                } else {
+                    "✓ Ruff: No issues found".to_string()
+                }
             }
+            "pylint" => filter_pylint_json(&stdout),
+            "mypy" => mypy_cmd::filter_mypy_output(&raw),
+            _ => filter_generic_lint(&raw),
         }
-        "pylint" => filter_pylint_json(&stdout),
-        "mypy" => mypy_cmd::filter_mypy_output(&raw),
-        _ => filter_generic_lint(&raw),
     };
 
     let exit_code = output
@@ -626,6 +638,12 @@ mod tests {
         assert_eq!(strip_pm_prefix(&args), 2);
     }
 
+    #[test]
+    fn test_strip_pm_prefix_bun_x() {
+        let args: Vec<String> = vec!["bun".into(), "x".into(), "eslint".into()];
+        assert_eq!(strip_pm_prefix(&args), 2);
+    }
+
     #[test]
     fn test_strip_pm_prefix_none() {
         let args: Vec<String> = vec!["eslint".into(), "src/".into()];
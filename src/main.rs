@@ -1,21 +1,29 @@
 mod aws_cmd;
+mod bench_cmd;
+mod bun_cmd;
 mod cargo_cmd;
+mod cc_cmd;
 mod cc_economics;
 mod ccusage;
+mod ci_log;
 mod config;
 mod container;
+mod coverage_cmd;
 mod curl_cmd;
 mod deps;
 mod diff_cmd;
 mod discover;
 mod display_helpers;
+mod dotnet_cmd;
 mod env_cmd;
 mod filter;
 mod find_cmd;
 mod format_cmd;
 mod gain;
+mod generated_files;
 mod gh_cmd;
 mod git;
+mod glab_cmd;
 mod go_cmd;
 mod golangci_cmd;
 mod grep_cmd;
@@ -23,6 +31,8 @@ mod gt_cmd;
 mod hook_audit_cmd;
 mod init;
 mod json_cmd;
+mod junit_cmd;
+mod jvm_cmd;
 mod learn;
 mod lint_cmd;
 mod local_llm;
@@ -41,10 +51,17 @@ mod psql_cmd;
 mod pytest_cmd;
 mod read;
 mod rewrite_cmd;
+mod ruby_cmd;
 mod ruff_cmd;
 mod runner;
+mod sanitizer;
+mod sarif_cmd;
+mod semantic_diff;
+mod stack_trace;
 mod summary;
+mod symbols;
 mod tee;
+mod test_history;
 mod toml_filter;
 mod tracking;
 mod tree;
@@ -53,6 +70,7 @@ mod utils;
 mod vitest_cmd;
 mod wc_cmd;
 mod wget_cmd;
+mod yarn_cmd;
 
 use anyhow::{Context, Result};
 use clap::error::ErrorKind;
@@ -166,12 +184,35 @@ enum Commands {
         args: Vec<String>,
     },
 
+    /// GitLab CLI (glab) commands with token-optimized output
+    Glab {
+        /// Subcommand: mr, issue, ci
+        subcommand: String,
+        /// Additional arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
     /// pnpm commands with ultra-compact output
     Pnpm {
         #[command(subcommand)]
         command: PnpmCommands,
     },
 
+    /// Bun install/test/run with compact output (other subcommands run unchanged)
+    Bun {
+        /// bun subcommand and arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Yarn install/add/up/remove with compact output (classic and Berry)
+    Yarn {
+        /// yarn subcommand and arguments (other subcommands run unchanged)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
     /// Run command and show only errors/warnings
     Err {
         /// Command to run
@@ -186,6 +227,40 @@ enum Commands {
         command: Vec<String>,
     },
 
+    /// Summarize JUnit XML reports (files, directories or quoted globs)
+    Junit {
+        /// Report files, directories or globs (e.g. 'build/test-results/**/*.xml')
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+
+    /// Summarize coverage reports (lcov, Cobertura, istanbul, coverage.py, Go, llvm-cov)
+    Coverage {
+        /// Report file or directory ("-" reads stdin; default: well-known report locations)
+        path: Option<String>,
+        /// Number of least-covered files to show
+        #[arg(short, long, default_value = "10")]
+        top: usize,
+        /// Save this report as the project's baseline for later deltas
+        #[arg(long)]
+        save_baseline: bool,
+    },
+
+    /// Compact benchmark results (criterion, hyperfine, go test -bench, pytest-benchmark)
+    Bench {
+        /// Benchmark command to run, a hyperfine/pytest-benchmark JSON export, or a
+        /// criterion directory (default: target/criterion)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Summarize SARIF logs from any static analyzer, grouped by rule and file
+    Sarif {
+        /// SARIF files or directories (scanned for *.sarif)
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+
     /// Show JSON structure without values
     Json {
         /// JSON file
@@ -274,6 +349,12 @@ enum Commands {
         /// Show line numbers (always on, accepted for grep/rg compatibility)
         #[arg(short = 'n', long)]
         line_numbers: bool,
+        /// Group hits by enclosing function/impl/class instead of by line
+        #[arg(long)]
+        by_symbol: bool,
+        /// With --by-symbol: show the enclosing signature instead of the matched line
+        #[arg(long)]
+        signatures: bool,
         /// Extra ripgrep arguments (e.g., -i, -A 3, -w, --glob)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         extra_args: Vec<String>,
@@ -364,6 +445,9 @@ enum Commands {
         /// Scope statistics to the current project directory (overrides auto-detection fallback)
         #[arg(short, long)]
         project: bool,
+        /// List flaky and newly broken tests from recorded test runs
+        #[arg(long)]
+        tests: bool,
     },
 
     /// Claude Code economics: spending (ccusage) vs savings (rtk) analysis
@@ -559,6 +643,20 @@ enum Commands {
         args: Vec<String>,
     },
 
+    /// RSpec with failures, trimmed backtraces and a re-run command
+    Rspec {
+        /// RSpec arguments (uses bin/rspec or bundle exec when available)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Minitest / rails test with failures, trimmed backtraces and a re-run command
+    Minitest {
+        /// Test arguments (runs bin/rails test, else rake test)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
     /// Pip package manager with compact output (auto-detects uv)
     Pip {
         /// Pip arguments (e.g., list, outdated, install)
@@ -603,6 +701,78 @@ enum Commands {
         args: Vec<String>,
     },
 
+    /// Gradle build/test with task summary, grouped diagnostics and test results
+    Gradle {
+        /// Gradle tasks and arguments (rtk-only: --wrapper runs ./gradlew)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Maven build/test with goal summary, grouped diagnostics and test results
+    Mvn {
+        /// Maven goals and arguments (rtk-only: --wrapper runs ./mvnw)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// dotnet build/test/restore with per-project status, grouped diagnostics and test results
+    Dotnet {
+        /// dotnet subcommand and arguments (other subcommands run unchanged)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// make with compiler diagnostics grouped by flag and file
+    Make {
+        /// make arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// CMake (configure or --build) with grouped compiler diagnostics
+    Cmake {
+        /// cmake arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Ninja without progress lines, compiler diagnostics grouped
+    Ninja {
+        /// ninja arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// gcc with diagnostics grouped and template backtraces collapsed
+    Gcc {
+        /// gcc arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// g++ with diagnostics grouped and template backtraces collapsed
+    #[command(name = "g++")]
+    Gxx {
+        /// g++ arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// clang with diagnostics grouped and template backtraces collapsed
+    Clang {
+        /// clang arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// clang++ with diagnostics grouped and template backtraces collapsed
+    #[command(name = "clang++")]
+    Clangxx {
+        /// clang++ arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
     /// Show hook rewrite audit metrics (requires RTK_HOOK_AUDIT=1)
     #[command(name = "hook-audit")]
     HookAudit {
@@ -688,6 +858,18 @@ enum GitCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Blame grouped into commit ranges (--ownership: author line shares)
+    Blame {
+        /// Git blame arguments (supports -L, --since, rev, file)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Merge/rebase/cherry-pick conflicts: progress, ours/theirs hunks, next commands
+    Conflicts {
+        /// Limit to these paths
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Passthrough: runs any unsupported git subcommand directly
     #[command(external_subcommand)]
     Other(Vec<OsString>),
@@ -743,6 +925,36 @@ enum DockerCommands {
     Images,
     /// Show container logs (deduplicated)
     Logs { container: String },
+    /// Build image (step counts, cached vs rebuilt, failing step only)
+    Build {
+        /// docker build arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Run container (image pull progress collapsed to one line)
+    Run {
+        /// docker run arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Inspect objects (key fields, env values masked)
+    Inspect {
+        /// docker inspect arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Pull image → one line per image
+    Pull {
+        /// docker pull arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Push image → one line per tag
+    Push {
+        /// docker push arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Docker Compose commands with compact output
     Compose {
         #[command(subcommand)]
@@ -804,6 +1016,24 @@ enum KubectlCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Describe resources (conditions, container states, grouped events)
+    Describe {
+        /// kubectl describe arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Apply manifests (created/configured/unchanged tallies)
+    Apply {
+        /// kubectl apply arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Diff manifests against the cluster (field-level changes)
+    Diff {
+        /// kubectl diff arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Passthrough: runs any unsupported kubectl subcommand directly
     #[command(external_subcommand)]
     Other(Vec<OsString>),
@@ -879,6 +1109,12 @@ enum CargoCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Bench with one row per benchmark (mean ± stddev, change; noise collapsed)
+    Bench {
+        /// Additional cargo bench arguments
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Clippy with warnings grouped by lint rule
     Clippy {
         /// Additional cargo clippy arguments
@@ -903,6 +1139,12 @@ enum CargoCommands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
+    /// Dependency tree: direct deps per member, duplicated crates, shortest -i chains
+    Tree {
+        /// Additional cargo tree arguments (rtk-only: --show-features)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Passthrough: runs any unsupported cargo subcommand directly
     #[command(external_subcommand)]
     Other(Vec<OsString>),
@@ -910,7 +1152,8 @@ enum CargoCommands {
 
 #[derive(Subcommand)]
 enum GoCommands {
-    /// Run tests with compact output (90% token reduction via JSON streaming)
+    /// Run tests with compact output (90% token reduction via JSON streaming;
+    /// with -bench, one row per benchmark)
     Test {
         /// Additional go test arguments
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
@@ -1114,6 +1357,12 @@ fn main() -> Result<()> {
                 GitCommands::Worktree { args } => {
                     git::run(git::GitCommand::Worktree, &args, None, cli.verbose, &opts)?;
                 }
+                GitCommands::Blame { args } => {
+                    git::run(git::GitCommand::Blame, &args, None, cli.verbose, &opts)?;
+                }
+                GitCommands::Conflicts { args } => {
+                    git::run(git::GitCommand::Conflicts, &args, None, cli.verbose, &opts)?;
+                }
                 GitCommands::Other(args) => {
                     git::run_passthrough(&args, cli.verbose, &opts)?;
                 }
@@ -1124,6 +1373,10 @@ fn main() -> Result<()> {
             gh_cmd::run(&subcommand, &args, cli.verbose, cli.ultra_compact)?;
         }
 
+        Commands::Glab { subcommand, args } => {
+            glab_cmd::run(&subcommand, &args, cli.verbose, cli.ultra_compact)?;
+        }
+
         Commands::Pnpm { command } => match command {
             PnpmCommands::List { depth, args } => {
                 pnpm_cmd::run(pnpm_cmd::PnpmCommand::List { depth }, &args, cli.verbose)?;
@@ -1149,6 +1402,14 @@ fn main() -> Result<()> {
             }
         },
 
+        Commands::Bun { args } => {
+            bun_cmd::run(&args, cli.verbose)?;
+        }
+
+        Commands::Yarn { args } => {
+            yarn_cmd::run(&args, cli.verbose)?;
+        }
+
         Commands::Err { command } => {
             let cmd = command.join(" ");
             runner::run_err(&cmd, cli.verbose)?;
@@ -1159,6 +1420,26 @@ fn main() -> Result<()> {
             runner::run_test(&cmd, cli.verbose)?;
         }
 
+        Commands::Junit { paths } => {
+            junit_cmd::run(&paths, cli.verbose)?;
+        }
+
+        Commands::Coverage {
+            path,
+            top,
+            save_baseline,
+        } => {
+            coverage_cmd::run(path.as_deref(), top, save_baseline, cli.verbose)?;
+        }
+
+        Commands::Bench { args } => {
+            bench_cmd::run(&args, cli.verbose)?;
+        }
+
+        Commands::Sarif { paths } => {
+            sarif_cmd::run(&paths, cli.verbose)?;
+        }
+
         Commands::Json { file, depth } => {
             if file == Path::new("-") {
                 json_cmd::run_stdin(depth, cli.verbose)?;
@@ -1205,6 +1486,21 @@ fn main() -> Result<()> {
             DockerCommands::Logs { container: c } => {
                 container::run(container::ContainerCmd::DockerLogs, &[c], cli.verbose)?;
             }
+            DockerCommands::Build { args } => {
+                container::run(container::ContainerCmd::DockerBuild, &args, cli.verbose)?;
+            }
+            DockerCommands::Run { args } => {
+                container::run(container::ContainerCmd::DockerRun, &args, cli.verbose)?;
+            }
+            DockerCommands::Inspect { args } => {
+                container::run(container::ContainerCmd::DockerInspect, &args, cli.verbose)?;
+            }
+            DockerCommands::Pull { args } => {
+                container::run(container::ContainerCmd::DockerPull, &args, cli.verbose)?;
+            }
+            DockerCommands::Push { args } => {
+                container::run(container::ContainerCmd::DockerPush, &args, cli.verbose)?;
+            }
             DockerCommands::Compose { command: compose } => match compose {
                 ComposeCommands::Ps => {
                     container::run_compose_ps(cli.verbose)?;
@@ -1256,6 +1552,15 @@ fn main() -> Result<()> {
             KubectlCommands::Get { resource, args } => {
                 container::kubectl_get(&resource, &args, cli.verbose)?;
             }
+            KubectlCommands::Describe { args } => {
+                container::run(container::ContainerCmd::KubectlDescribe, &args, cli.verbose)?;
+            }
+            KubectlCommands::Apply { args } => {
+                container::run(container::ContainerCmd::KubectlApply, &args, cli.verbose)?;
+            }
+            KubectlCommands::Diff { args } => {
+                container::run(container::ContainerCmd::KubectlDiff, &args, cli.verbose)?;
+            }
             KubectlCommands::Other(args) => {
                 container::run_kubectl_passthrough(&args, cli.verbose)?;
             }
@@ -1274,6 +1579,8 @@ fn main() -> Result<()> {
             context_only,
             file_type,
             line_numbers: _, // no-op: line numbers always enabled in grep_cmd::run
+            by_symbol,
+            signatures,
             extra_args,
         } => {
             grep_cmd::run(
@@ -1283,6 +1590,8 @@ fn main() -> Result<()> {
                 max,
                 context_only,
                 file_type.as_deref(),
+                by_symbol,
+                signatures,
                 &extra_args,
                 cli.verbose,
             )?;
@@ -1337,6 +1646,7 @@ fn main() -> Result<()> {
             format,
             global,
             project,
+            tests,
         } => {
             gain::run(
                 graph,
@@ -1350,6 +1660,7 @@ fn main() -> Result<()> {
                 &format,
                 global,
                 project,
+                tests,
                 cli.verbose,
             )?;
         }
@@ -1448,6 +1759,9 @@ fn main() -> Result<()> {
             CargoCommands::Test { args } => {
                 cargo_cmd::run(cargo_cmd::CargoCommand::Test, &args, cli.verbose)?;
             }
+            CargoCommands::Bench { args } => {
+                cargo_cmd::run(cargo_cmd::CargoCommand::Bench, &args, cli.verbose)?;
+            }
             CargoCommands::Clippy { args } => {
                 cargo_cmd::run(cargo_cmd::CargoCommand::Clippy, &args, cli.verbose)?;
             }
@@ -1460,6 +1774,9 @@ fn main() -> Result<()> {
             CargoCommands::Nextest { args } => {
                 cargo_cmd::run(cargo_cmd::CargoCommand::Nextest, &args, cli.verbose)?;
             }
+            CargoCommands::Tree { args } => {
+                cargo_cmd::run(cargo_cmd::CargoCommand::Tree, &args, cli.verbose)?;
+            }
             CargoCommands::Other(args) => {
                 cargo_cmd::run_passthrough(&args, cli.verbose)?;
             }
@@ -1626,6 +1943,14 @@ fn main() -> Result<()> {
             pytest_cmd::run(&args, cli.verbose)?;
         }
 
+        Commands::Rspec { args } => {
+            ruby_cmd::run_rspec(&args, cli.verbose)?;
+        }
+
+        Commands::Minitest { args } => {
+            ruby_cmd::run_minitest(&args, cli.verbose)?;
+        }
+
         Commands::Pip { args } => {
             pip_cmd::run(&args, cli.verbose)?;
         }
@@ -1681,6 +2006,46 @@ fn main() -> Result<()> {
             golangci_cmd::run(&args, cli.verbose)?;
         }
 
+        Commands::Gradle { args } => {
+            jvm_cmd::run_gradle(&args, cli.verbose)?;
+        }
+
+        Commands::Mvn { args } => {
+            jvm_cmd::run_mvn(&args, cli.verbose)?;
+        }
+
+        Commands::Dotnet { args } => {
+            dotnet_cmd::run(&args, cli.verbose)?;
+        }
+
+        Commands::Make { args } => {
+            cc_cmd::run("make", &args, cli.verbose)?;
+        }
+
+        Commands::Cmake { args } => {
+            cc_cmd::run("cmake", &args, cli.verbose)?;
+        }
+
+        Commands::Ninja { args } => {
+            cc_cmd::run("ninja", &args, cli.verbose)?;
+        }
+
+        Commands::Gcc { args } => {
+            cc_cmd::run("gcc", &args, cli.verbose)?;
+        }
+
+        Commands::Gxx { args } => {
+            cc_cmd::run("g++", &args, cli.verbose)?;
+        }
+
+        Commands::Clang { args } => {
+            cc_cmd::run("clang", &args, cli.verbose)?;
+        }
+
+        Commands::Clangxx { args } => {
+            cc_cmd::run("clang++", &args, cli.verbose)?;
+        }
+
         Commands::HookAudit { since } => {
             hook_audit_cmd::run(since, cli.verbose)?;
         }
@@ -2219,11 +2584,11 @@ mod tests {
 
     #[test]
     fn test_fallback_on_unknown_subcommand() {
-        let raw: Vec<OsString> = ["rtk", "make", "build"]
+        let raw: Vec<OsString> = ["rtk", "bazel", "build"]
             .iter()
             .map(OsString::from)
             .collect();
-        let err = expect_parse_err(&["rtk", "make", "build"]);
+        let err = expect_parse_err(&["rtk", "bazel", "build"]);
         assert!(should_fallback(&err, &raw));
     }
 
@@ -2263,11 +2628,11 @@ mod tests {
     #[test]
     fn test_no_fallback_when_env_set() {
         let _lock = ENV_MUTEX.lock().unwrap();
-        let raw: Vec<OsString> = ["rtk", "make", "build"]
+        let raw: Vec<OsString> = ["rtk", "bazel", "build"]
             .iter()
             .map(OsString::from)
             .collect();
-        let err = expect_parse_err(&["rtk", "make", "build"]);
+        let err = expect_parse_err(&["rtk", "bazel", "build"]);
         unsafe { std::env::set_var("RTK_NO_FALLBACK", "1") };
         let result = should_fallback(&err, &raw);
         unsafe { std::env::remove_var("RTK_NO_FALLBACK") };
@@ -0,0 +1,145 @@
+//! Compiler diagnostics grouped by severity and file, shared by the build
+//! wrappers (`rtk dotnet`, `rtk gradle` / `rtk mvn`).
+
+use super::types::{LintIssue, LintSeverity};
+use crate::utils::truncate;
+
+/// Files listed per severity before summarizing the rest
+const MAX_FILES: usize = 10;
+
+/// Diagnostics listed per file
+const MAX_ISSUES_PER_FILE: usize = 5;
+
+/// Non-empty lines kept when a build died before printing anything parseable
+const CRASH_TAIL_LINES: usize = 20;
+
+/// Errors, then warnings, each grouped by file in first-seen order:
+///
+/// ```text
+/// Errors (2):
+///   src/Cart.cs
+///     10:5 [CS1002] ; expected
+/// ```
+///
+/// Each issue comes with a suffix appended to its message (empty for none).
+pub fn format_by_file(issues: &[(&LintIssue, String)]) -> Vec<String> {
+    let mut out = Vec::new();
+    for (severity, title) in [
+        (LintSeverity::Error, "Errors"),
+        (LintSeverity::Warning, "Warnings"),
+    ] {
+        let issues: Vec<&(&LintIssue, String)> = issues
+            .iter()
+            .filter(|(issue, _)| issue.severity == severity)
+            .collect();
+        if issues.is_empty() {
+            continue;
+        }
+        out.push(format!("{} ({}):", title, issues.len()));
+
+        let mut by_file: Vec<(&str, Vec<&(&LintIssue, String)>)> = Vec::new();
+        for entry in issues {
+            match by_file.iter_mut().find(|(f, _)| *f == entry.0.file_path) {
+                Some((_, list)) => list.push(entry),
+                None => by_file.push((&entry.0.file_path, vec![entry])),
+            }
+        }
+        for (file, issues) in by_file.iter().take(MAX_FILES) {
+            out.push(format!("  {}", file));
+            for (issue, suffix) in issues.iter().take(MAX_ISSUES_PER_FILE) {
+                let pos = if issue.column > 0 {
+                    format!("{}:{} ", issue.line, issue.column)
+                } else if issue.line > 0 {
+                    format!("{} ", issue.line)
+                } else {
+                    String::new()
+                };
+                let message = if issue.rule_id.is_empty() {
+                    format!("{}{}", issue.message, suffix)
+                } else {
+                    format!("[{}] {}{}", issue.rule_id, issue.message, suffix)
+                };
+                out.push(format!("    {}{}", pos, truncate(&message, 140)));
+            }
+            if issues.len() > MAX_ISSUES_PER_FILE {
+                out.push(format!(
+                    "    ... +{} more",
+                    issues.len() - MAX_ISSUES_PER_FILE
+                ));
+            }
+        }
+        if by_file.len() > MAX_FILES {
+            out.push(format!("  ... +{} more files", by_file.len() - MAX_FILES));
+        }
+    }
+    out
+}
+
+/// Last non-empty lines of a build that crashed before its summary
+pub fn crash_tail(raw: &str) -> String {
+    let lines: Vec<&str> = raw.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(CRASH_TAIL_LINES)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(file: &str, line: usize, column: usize, severity: LintSeverity) -> LintIssue {
+        LintIssue {
+            file_path: file.to_string(),
+            line,
+            column,
+            severity,
+            rule_id: String::new(),
+            message: "msg".to_string(),
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn test_format_by_file_groups_and_caps() {
+        let mut issues: Vec<LintIssue> = (1..=7)
+            .map(|n| issue("A.java", n, 0, LintSeverity::Error))
+            .collect();
+        issues.push(issue("B.java", 3, 9, LintSeverity::Warning));
+        issues.push(issue("A.java", 0, 0, LintSeverity::Warning));
+        let entries: Vec<(&LintIssue, String)> = issues
+            .iter()
+            .map(|i| {
+                (
+                    i,
+                    if i.column > 0 { " (net8.0 only)" } else { "" }.to_string(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            format_by_file(&entries),
+            vec![
+                "Errors (7):",
+                "  A.java",
+                "    1 msg",
+                "    2 msg",
+                "    3 msg",
+                "    4 msg",
+                "    5 msg",
+                "    ... +2 more",
+                "Warnings (2):",
+                "  B.java",
+                "    3:9 msg (net8.0 only)",
+                "  A.java",
+                "    msg",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crash_tail_keeps_last_non_empty_lines() {
+        let raw: String = (1..=30).map(|n| format!("line {}\n\n", n)).collect();
+        let tail = crash_tail(&raw);
+        assert_eq!(tail.lines().count(), 20);
+        assert!(tail.starts_with("line 11\n"));
+        assert!(tail.ends_with("line 30"));
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! The three-tier system ensures RTK never returns false data silently.
 
+pub mod diagnostics;
 #[allow(dead_code)]
 pub mod formatter;
 pub mod types;
@@ -42,6 +42,9 @@ pub struct LintIssue {
     pub severity: LintSeverity,
     pub rule_id: String,
     pub message: String,
+    /// One-line fix suggested by the tool (e.g. rustc `help:`)
+    #[serde(default)]
+    pub suggestion: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -1,5 +1,5 @@
 use crate::tracking;
-use crate::utils::{detect_package_manager, strip_ansi};
+use crate::utils::{package_manager_local_exec, strip_ansi};
 use anyhow::{Context, Result};
 use regex::Regex;
 use serde::Deserialize;
@@ -233,24 +233,7 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
 
     // Skip `which playwright` — it can find pyenv shims or other non-Node
     // binaries. Always resolve through the package manager.
-    let pm = detect_package_manager();
-    let mut cmd = match pm {
-        "pnpm" => {
-            let mut c = std::process::Command::new("pnpm");
-            c.arg("exec").arg("--").arg("playwright");
-            c
-        }
-        "yarn" => {
-            let mut c = std::process::Command::new("yarn");
-            c.arg("exec").arg("--").arg("playwright");
-            c
-        }
-        _ => {
-            let mut c = std::process::Command::new("npx");
-            c.arg("--no-install").arg("--").arg("playwright");
-            c
-        }
-    };
+    let mut cmd = package_manager_local_exec("playwright");
 
     // Only inject --reporter=json for `playwright test` runs
     let is_test = args.first().map(|a| a == "test").unwrap_or(false);
@@ -284,8 +267,17 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
     // Parse output using PlaywrightParser
     let parse_result = PlaywrightParser::parse(&stdout);
     let mode = FormatMode::from_verbosity(verbose);
+    let failed_tests = match &parse_result {
+        ParseResult::Full(data) | ParseResult::Degraded(data, _) if is_test => Some(
+            data.failures
+                .iter()
+                .map(|f| f.test_name.clone())
+                .collect::<Vec<_>>(),
+        ),
+        _ => None,
+    };
 
-    let filtered = match parse_result {
+    let mut filtered = match parse_result {
         ParseResult::Full(data) => {
             if verbose > 0 {
                 eprintln!("playwright test (Tier 1: Full JSON parse)");
@@ -304,6 +296,8 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
         }
     };
 
+    crate::test_history::record_and_annotate("playwright", args, failed_tests, &mut filtered);
+
     println!("{}", filtered);
 
     timer.track(
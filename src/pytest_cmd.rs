@@ -1,7 +1,15 @@
+use crate::junit_cmd::{slowest_cases, JunitParser};
+use crate::parser::{
+    emit_degradation_warning, emit_passthrough_warning, truncate_output, FormatMode, OutputParser,
+    ParseResult, TestFailure, TestResult, TokenFormatter,
+};
+use crate::stack_trace::compress_stack_traces;
 use crate::tracking;
 use crate::utils::truncate;
 use anyhow::{Context, Result};
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::SystemTime;
 
 #[derive(Debug, PartialEq)]
 enum ParseState {
@@ -11,6 +19,9 @@ enum ParseState {
     Summary,
 }
 
+/// Tests slower than this are listed under "Slowest:"
+const SLOW_TEST_MS: u64 = 1000;
+
 pub fn run(args: &[String], verbose: u8) -> Result<()> {
     let timer = tracking::TimedExecution::start();
 
@@ -35,6 +46,16 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
         cmd.arg("-q");
     }
 
+    // Structured results: reuse the user's --junitxml, otherwise write a temporary one
+    // (xunit1 adds file/line attributes to each testcase)
+    let user_report = user_junitxml_path(args);
+    let temp_report = std::env::temp_dir().join(format!("rtk-pytest-{}.xml", std::process::id()));
+    if user_report.is_none() {
+        cmd.arg(format!("--junitxml={}", temp_report.display()));
+        cmd.arg("-o").arg("junit_family=xunit1");
+    }
+    let report_path = user_report.unwrap_or_else(|| temp_report.clone());
+
     for arg in args {
         cmd.arg(arg);
     }
@@ -43,6 +64,7 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
         eprintln!("Running: pytest --tb=short -q {}", args.join(" "));
     }
 
+    let started = SystemTime::now();
     let output = cmd
         .output()
         .context("Failed to run pytest. Is it installed? Try: pip install pytest")?;
@@ -51,7 +73,43 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
     let stderr = String::from_utf8_lossy(&output.stderr);
     let raw = format!("{}\n{}", stdout, stderr);
 
-    let mut filtered = filter_pytest_output(&stdout);
+    // A stale --junitxml from an earlier run must not stand in for this one
+    let report = crate::utils::read_if_modified_since(&report_path, started);
+    if report_path == temp_report {
+        let _ = std::fs::remove_file(&temp_report);
+    }
+
+    let mode = FormatMode::from_verbosity(verbose);
+    let mut filtered = match parse_pytest(report.as_deref(), &stdout) {
+        ParseResult::Full(data) => {
+            if verbose > 0 {
+                eprintln!("pytest (Tier 1: JUnit XML report)");
+            }
+            let mut text = data.format(mode);
+            if let Some(slowest) = report.as_deref().and_then(format_slowest) {
+                text.push_str(&slowest);
+            }
+            text
+        }
+        ParseResult::Degraded(data, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning("pytest", &warnings.join(", "));
+            }
+            data.format(mode)
+        }
+        ParseResult::Passthrough(_) => {
+            if verbose > 0 {
+                emit_passthrough_warning("pytest", "no report or summary line, using text filter");
+            }
+            filter_pytest_output(&compress_stack_traces(&stdout))
+        }
+    };
+    crate::test_history::record_and_annotate(
+        "pytest",
+        args,
+        crate::test_history::pytest_failed_tests(&stdout),
+        &mut filtered,
+    );
 
     let exit_code = output
         .status
@@ -84,6 +142,94 @@ pub fn run(args: &[String], verbose: u8) -> Result<()> {
     Ok(())
 }
 
+/// `--junitxml=path` / `--junitxml path` / `--junit-xml=path` given by the user
+fn user_junitxml_path(args: &[String]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        for flag in ["--junitxml", "--junit-xml"] {
+            if let Some(path) = arg.strip_prefix(flag).and_then(|r| r.strip_prefix('=')) {
+                return Some(PathBuf::from(path));
+            }
+            if arg == flag {
+                return iter.next().map(PathBuf::from);
+            }
+        }
+    }
+    None
+}
+
+/// Tier 1: JUnit XML report. Tier 2: terminal text. Tier 3: passthrough.
+fn parse_pytest(report: Option<&str>, stdout: &str) -> ParseResult<TestResult> {
+    let mut warnings = Vec::new();
+    if let Some(xml) = report {
+        match JunitParser::parse(xml) {
+            ParseResult::Full(data) if data.total > 0 => return ParseResult::Full(data),
+            ParseResult::Degraded(data, w) if data.total > 0 => {
+                return ParseResult::Degraded(data, w)
+            }
+            _ => warnings.push("JUnit report has no test cases".to_string()),
+        }
+    } else {
+        warnings.push("no JUnit report written".to_string());
+    }
+
+    match result_from_text(stdout) {
+        Some(data) => ParseResult::Degraded(data, warnings),
+        None => ParseResult::Passthrough(truncate_output(stdout, 500)),
+    }
+}
+
+/// Build a `TestResult` from the summary line and `FAILED` / `ERROR` lines
+fn result_from_text(output: &str) -> Option<TestResult> {
+    let summary = output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|l| is_pytest_summary_line(l))?;
+    let (passed, failed, skipped) = parse_summary_line(summary);
+    if passed + failed + skipped == 0 {
+        return None;
+    }
+
+    let failures = output
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with("FAILED ") || l.starts_with("ERROR "))
+        .map(|l| {
+            let id = l.split_once(' ').map(|(_, rest)| rest).unwrap_or(l);
+            let (test_name, message) = id.split_once(" - ").unwrap_or((id, ""));
+            TestFailure {
+                test_name: test_name.to_string(),
+                file_path: test_name.split("::").next().unwrap_or("").to_string(),
+                error_message: message.to_string(),
+                stack_trace: None,
+            }
+        })
+        .collect();
+
+    Some(TestResult {
+        total: passed + failed + skipped,
+        passed,
+        failed,
+        skipped,
+        duration_ms: None,
+        failures,
+    })
+}
+
+/// "Slowest:" line for tests at or above `SLOW_TEST_MS`
+fn format_slowest(report: &str) -> Option<String> {
+    let slowest = slowest_cases(report, 3, SLOW_TEST_MS);
+    if slowest.is_empty() {
+        return None;
+    }
+    let entries: Vec<String> = slowest
+        .iter()
+        .map(|(name, ms)| format!("{} {:.1}s", name, *ms as f64 / 1000.0))
+        .collect();
+    Some(format!("\nSlowest: {}", entries.join(", ")))
+}
+
 /// Check if a command exists in PATH
 fn which_command(cmd: &str) -> Option<String> {
     Command::new("which")
@@ -97,7 +243,7 @@ fn which_command(cmd: &str) -> Option<String> {
 }
 
 /// Parse pytest output using state machine
-fn filter_pytest_output(output: &str) -> String {
+pub(crate) fn filter_pytest_output(output: &str) -> String {
     let mut state = ParseState::Header;
     let mut test_files: Vec<String> = Vec::new();
     let mut failures: Vec<String> = Vec::new();
@@ -327,6 +473,11 @@ fn parse_summary_line(summary: &str) -> (usize, usize, usize) {
 mod tests {
     use super::*;
 
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
     #[test]
     fn test_filter_pytest_all_pass() {
         let output = r#"=== test session starts ===
@@ -475,4 +626,85 @@ FAILED tests/test_foo.py::test_something - assert False
         assert!(result.contains("test_something"));
         assert!(!result.contains("No tests collected"));
     }
+
+    const PYTEST_XUNIT1: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites><testsuite name="pytest" errors="0" failures="1" skipped="1" tests="4" time="2.61">
+<testcase classname="tests.test_cart" name="test_add" file="tests/test_cart.py" line="3" time="0.002"/>
+<testcase classname="tests.test_cart" name="test_total" file="tests/test_cart.py" line="9" time="0.004"><failure message="assert 3 == 4&#10; +  where 3 = total()">def test_total():
+&gt;       assert total() == 4
+E       assert 3 == 4
+
+tests/test_cart.py:11: AssertionError</failure></testcase>
+<testcase classname="tests.test_cart" name="test_import" file="tests/test_cart.py" line="14" time="2.400"/>
+<testcase classname="tests.test_cart" name="test_slow" file="tests/test_cart.py" line="20" time="0.000"><skipped type="pytest.skip" message="slow">skipped</skipped></testcase>
+</testsuite></testsuites>"#;
+
+    #[test]
+    fn test_parse_pytest_junit_report() {
+        let result = parse_pytest(Some(PYTEST_XUNIT1), "");
+        let ParseResult::Full(data) = result else {
+            panic!("expected Full parse");
+        };
+        assert_eq!(
+            (data.total, data.passed, data.failed, data.skipped),
+            (4, 2, 1, 1)
+        );
+        let failure = &data.failures[0];
+        assert_eq!(failure.test_name, "test_cart.test_total");
+        assert_eq!(failure.file_path, "tests/test_cart.py");
+        assert!(failure.error_message.starts_with("assert 3 == 4"));
+        assert!(failure
+            .stack_trace
+            .as_deref()
+            .unwrap()
+            .contains("E       assert 3 == 4"));
+        assert_eq!(
+            format_slowest(PYTEST_XUNIT1).unwrap(),
+            "\nSlowest: test_cart.test_import 2.4s"
+        );
+    }
+
+    #[test]
+    fn test_parse_pytest_text_fallback() {
+        let stdout = "..F.\n\
+FAILED tests/test_cart.py::test_total - assert 3 == 4\n\
+1 failed, 3 passed in 0.12s\n";
+        let ParseResult::Degraded(data, warnings) = parse_pytest(None, stdout) else {
+            panic!("expected Degraded parse");
+        };
+        assert_eq!(warnings, vec!["no JUnit report written"]);
+        assert_eq!((data.passed, data.failed), (3, 1));
+        assert_eq!(data.failures[0].test_name, "tests/test_cart.py::test_total");
+        assert_eq!(data.failures[0].file_path, "tests/test_cart.py");
+        assert_eq!(data.failures[0].error_message, "assert 3 == 4");
+
+        // Empty report (collection error) also falls back to the text
+        let empty = r#"<testsuites><testsuite name="pytest" tests="0"/></testsuites>"#;
+        assert!(matches!(
+            parse_pytest(Some(empty), stdout),
+            ParseResult::Degraded(..)
+        ));
+    }
+
+    #[test]
+    fn test_parse_pytest_passthrough() {
+        let stdout = "ERROR: usage: pytest [options]\n";
+        assert!(matches!(
+            parse_pytest(None, stdout),
+            ParseResult::Passthrough(_)
+        ));
+    }
+
+    #[test]
+    fn test_user_junitxml_path() {
+        assert_eq!(
+            user_junitxml_path(&args(&["-x", "--junitxml=out/r.xml"])),
+            Some(PathBuf::from("out/r.xml"))
+        );
+        assert_eq!(
+            user_junitxml_path(&args(&["--junit-xml", "r.xml"])),
+            Some(PathBuf::from("r.xml"))
+        );
+        assert_eq!(user_junitxml_path(&args(&["-k", "cart"])), None);
+    }
 }
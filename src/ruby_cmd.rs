@@ -0,0 +1,998 @@
+//! RSpec and Minitest runners with compact output.
+//!
+//! `rtk rspec` reads RSpec's JSON formatter (written to a temporary file when
+//! the user picked their own `--format`) and falls back to the text summary.
+//! `rtk minitest` parses the Minitest / `rails test` report. Both reduce
+//! Ruby backtraces to app frames and end with a command that re-runs only
+//! the failing tests.
+
+use crate::parser::{
+    emit_degradation_warning, emit_passthrough_warning, extract_json_object, truncate_output,
+    FormatMode, ParseResult, TestFailure, TestResult, TokenFormatter,
+};
+use crate::stack_trace::compress_stack_traces;
+use crate::tracking;
+use crate::utils::strip_ansi;
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Deserialize;
+use std::path::Path;
+use std::process::Command;
+
+/// Failing locations listed in the re-run command
+const MAX_RERUN: usize = 10;
+
+/// Lines kept from errors raised outside of examples (load errors)
+const MAX_OUTSIDE_ERROR_LINES: usize = 10;
+
+/// Exception classes whose message already says everything
+const EXPECTATION_CLASSES: &[&str] = &[
+    "RSpec::Expectations::ExpectationNotMetError",
+    "RSpec::Expectations::MultipleExpectationsNotMetError",
+    "RSpec::Mocks::MockExpectationError",
+];
+
+lazy_static! {
+    // RSpec
+    static ref RSPEC_SUMMARY: Regex = Regex::new(
+        r"^(\d+) examples?, (\d+) failures?(?:, (\d+) pending)?(?:, (\d+) errors? occurred outside of examples)?"
+    )
+    .unwrap();
+    static ref RSPEC_FINISHED: Regex =
+        Regex::new(r"^Finished in (?:(\d+) minutes? )?([\d.]+) seconds?").unwrap();
+    static ref RSPEC_FAILURE_HEADER: Regex = Regex::new(r"^\s+\d+\) (.+)$").unwrap();
+    static ref RSPEC_FAILED_EXAMPLE: Regex = Regex::new(r"^rspec (\S+) # (.+)$").unwrap();
+    // Minitest
+    static ref MINITEST_SUMMARY: Regex = Regex::new(
+        r"^(\d+) (?:runs|tests), (\d+) assertions, (\d+) failures, (\d+) errors, (\d+) skips"
+    )
+    .unwrap();
+    static ref MINITEST_FINISHED: Regex = Regex::new(r"^Finished in ([\d.]+)s").unwrap();
+    static ref MINITEST_HEADER: Regex =
+        Regex::new(r"^\s*(?:\d+\) )?(Failure|Error|Skipped):$").unwrap();
+    /// `CartTest#test_total [test/models/cart_test.rb:12]:`
+    static ref MINITEST_NAME: Regex = Regex::new(r"^(.+?)(?: \[(.+):(\d+)\])?:$").unwrap();
+    static ref RAILS_RERUN: Regex = Regex::new(r"^(?:bin/)?rails test (\S+:\d+)$").unwrap();
+    /// `def test_total` / `test "adds items" do`
+    static ref MINITEST_DEF: Regex =
+        Regex::new(r#"^\s*(?:def (test_\w+)|test\s*\(?\s*(?:"([^"]+)"|'([^']+)'))"#).unwrap();
+    /// `    app/models/cart.rb:5:in 'Cart#boom'` / `# ./spec/cart_spec.rb:9`
+    static ref RUBY_FRAME: Regex =
+        Regex::new(r"^\s*(?:# )?(<internal:[^>]+>|[^\s:]+\.rb):(\d+)(?::in .+)?$").unwrap();
+}
+
+#[derive(Debug, Deserialize)]
+struct RspecJson {
+    examples: Vec<RspecExample>,
+    summary: RspecSummary,
+    #[serde(default)]
+    messages: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RspecExample {
+    full_description: String,
+    status: String,
+    file_path: String,
+    line_number: u32,
+    exception: Option<RspecException>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RspecException {
+    class: String,
+    message: String,
+    backtrace: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RspecSummary {
+    duration: f64,
+    example_count: usize,
+    failure_count: usize,
+    pending_count: usize,
+    #[serde(default)]
+    errors_outside_of_examples_count: usize,
+}
+
+/// A parsed run plus the `file:line` locations of its failures
+#[derive(Debug)]
+struct RubyRun {
+    result: TestResult,
+    rerun: Vec<String>,
+}
+
+pub fn run_rspec(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = ruby_tool("bin/rspec", &["rspec"]);
+    cmd.args(args);
+
+    // JSON goes to stdout, or to a temporary file next to the user's own formatter
+    let user_format = args
+        .iter()
+        .any(|a| a == "-f" || a.starts_with("--format") || (a.starts_with("-f") && a.len() > 2));
+    let temp_report = std::env::temp_dir().join(format!("rtk-rspec-{}.json", std::process::id()));
+    cmd.arg("--format").arg("json");
+    if user_format {
+        cmd.arg("--out").arg(&temp_report);
+    }
+
+    if verbose > 0 {
+        eprintln!("Running: rspec --format json {}", args.join(" "));
+    }
+
+    let output = cmd
+        .output()
+        .context("Failed to run rspec. Is it installed? Try: bundle add rspec")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+
+    let report = if user_format {
+        let report = std::fs::read_to_string(&temp_report).ok();
+        let _ = std::fs::remove_file(&temp_report);
+        report
+    } else {
+        stdout
+            .find("{\"version\"")
+            .and_then(|pos| extract_json_object(&stdout[pos..]))
+            .map(str::to_string)
+    };
+
+    let parsed = parse_rspec(report.as_deref(), &stdout);
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+    let filtered = finish("rspec", args, parsed, &raw, &stderr, exit_code, verbose);
+
+    timer.track(
+        &format!("rspec {}", args.join(" ")),
+        &format!("rtk rspec {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+pub fn run_minitest(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = if Path::new("bin/rails").exists() {
+        let mut c = Command::new("bin/rails");
+        c.arg("test").args(args);
+        c
+    } else {
+        let mut c = ruby_tool("", &["rake", "test"]);
+        c.args(rake_test_args(args));
+        c
+    };
+
+    if verbose > 0 {
+        eprintln!("Running: {:?}", cmd);
+    }
+
+    let output = cmd
+        .output()
+        .context("Failed to run the Minitest suite (bin/rails test or rake test)")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+
+    let parsed = parse_minitest(&raw);
+    let exit_code = output
+        .status
+        .code()
+        .unwrap_or(if output.status.success() { 0 } else { 1 });
+    let filtered = finish("minitest", args, parsed, &raw, &stderr, exit_code, verbose);
+
+    timer.track(
+        &format!("rails test {}", args.join(" ")),
+        &format!("rtk minitest {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// `rake test` takes task names, not test files: files and directories become
+/// `TEST=`, a `file:line` selects the test defined there through
+/// `TESTOPTS=--name=`, and Minitest options (`--seed 1`) go to `TESTOPTS`.
+/// `KEY=VALUE` arguments are passed through.
+fn rake_test_args(args: &[String]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut files = Vec::new();
+    let mut names = Vec::new();
+    let mut opts = Vec::new();
+    for arg in args {
+        if !arg.starts_with('-') && arg.contains('=') {
+            out.push(arg.clone());
+            continue;
+        }
+        let (path, line) = match arg.rsplit_once(':') {
+            Some((path, line)) if line.parse::<usize>().is_ok() => (path, line.parse().ok()),
+            _ => (arg.as_str(), None),
+        };
+        if path.ends_with(".rb") {
+            files.push(path.to_string());
+            if let Some(name) = line.and_then(|l| test_name_at(path, l)) {
+                names.push(name);
+            }
+        } else if !arg.starts_with('-') && Path::new(arg).is_dir() {
+            files.push(format!("{}/**/*_test.rb", arg.trim_end_matches('/')));
+        } else {
+            opts.push(arg.clone());
+        }
+    }
+
+    match files.len() {
+        0 => {}
+        1 => out.push(format!("TEST={}", files[0])),
+        _ => out.push(format!("TEST={{{}}}", files.join(","))),
+    }
+    // TESTOPTS is interpolated into a shell command by rake: quote the pattern
+    match names.len() {
+        0 => {}
+        1 => opts.push(format!("--name={}", names[0])),
+        _ => opts.push(format!("--name='/^({})$/'", names.join("|"))),
+    }
+    if !opts.is_empty() {
+        out.push(format!("TESTOPTS={}", opts.join(" ")));
+    }
+    out
+}
+
+/// Name of the Minitest test defined at or above `line` of `path`
+fn test_name_at(path: &str, line: usize) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .take(line)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .find_map(|l| MINITEST_DEF.captures(l))
+        .map(|c| match c.get(1) {
+            Some(name) => name.as_str().to_string(),
+            // ActiveSupport: `test "adds items"` defines `test_adds_items`
+            None => {
+                let desc = c.get(2).or_else(|| c.get(3)).map_or("", |m| m.as_str());
+                format!(
+                    "test_{}",
+                    desc.split_whitespace().collect::<Vec<_>>().join("_")
+                )
+            }
+        })
+        .filter(|name| name.chars().all(|ch| ch.is_alphanumeric() || ch == '_'))
+}
+
+/// `binstub` when present, else the tool through `bundle exec` in a bundled project
+fn ruby_tool(binstub: &str, tool: &[&str]) -> Command {
+    if !binstub.is_empty() && Path::new(binstub).exists() {
+        return Command::new(binstub);
+    }
+    if Path::new("Gemfile").exists() {
+        let mut c = Command::new("bundle");
+        c.arg("exec").args(tool);
+        c
+    } else {
+        let mut c = Command::new(tool[0]);
+        c.args(&tool[1..]);
+        c
+    }
+}
+
+/// Format, annotate with history, print and return the filtered output
+fn finish(
+    runner: &str,
+    args: &[String],
+    parsed: ParseResult<RubyRun>,
+    raw: &str,
+    stderr: &str,
+    exit_code: i32,
+    verbose: u8,
+) -> String {
+    let mode = FormatMode::from_verbosity(verbose);
+    let failed_tests = match &parsed {
+        ParseResult::Full(run) | ParseResult::Degraded(run, _) => Some(
+            run.result
+                .failures
+                .iter()
+                .map(|f| f.test_name.clone())
+                .collect::<Vec<_>>(),
+        ),
+        ParseResult::Passthrough(_) => None,
+    };
+
+    let mut filtered = match parsed {
+        ParseResult::Full(run) => {
+            if verbose > 0 {
+                eprintln!("{} (Tier 1: Full parse)", runner);
+            }
+            format_run(runner, &run, mode)
+        }
+        ParseResult::Degraded(run, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning(runner, &warnings.join(", "));
+            }
+            format_run(runner, &run, mode)
+        }
+        ParseResult::Passthrough(text) => {
+            emit_passthrough_warning(runner, "no report or summary line");
+            compress_stack_traces(&text)
+        }
+    };
+    crate::test_history::record_and_annotate(runner, args, failed_tests, &mut filtered);
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(raw, runner, exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+    filtered
+}
+
+fn format_run(runner: &str, run: &RubyRun, mode: FormatMode) -> String {
+    let mut out = run.result.format(mode);
+    if let Some(rerun) = rerun_command(runner, &run.rerun) {
+        out.push_str(&format!("\n\nRe-run: {}", rerun));
+    }
+    out
+}
+
+/// `rtk rspec spec/a_spec.rb:12:30 spec/b_spec.rb:5` / `rtk minitest test/a_test.rb:12`
+fn rerun_command(runner: &str, locations: &[String]) -> Option<String> {
+    if locations.is_empty() {
+        return None;
+    }
+    let mut args: Vec<String> = Vec::new();
+    if runner == "rspec" {
+        // RSpec accepts several line numbers per file: `path:12:30`
+        let mut files: Vec<(String, Vec<String>)> = Vec::new();
+        for location in locations {
+            let (file, line) = location.rsplit_once(':').unwrap_or((location, ""));
+            match files.iter_mut().find(|(f, _)| f == file) {
+                Some((_, lines)) => lines.push(line.to_string()),
+                None => files.push((file.to_string(), vec![line.to_string()])),
+            }
+        }
+        args.extend(
+            files
+                .into_iter()
+                .map(|(file, lines)| format!("{}:{}", file, lines.join(":"))),
+        );
+    } else {
+        args.extend(locations.iter().cloned());
+    }
+    let mut command = format!(
+        "rtk {} {}",
+        runner,
+        args.iter()
+            .take(MAX_RERUN)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+    if args.len() > MAX_RERUN {
+        command.push_str(&format!(" (+{} more)", args.len() - MAX_RERUN));
+    }
+    Some(command)
+}
+
+/// Tier 1: RSpec JSON. Tier 2: text summary. Tier 3: passthrough.
+fn parse_rspec(report: Option<&str>, text: &str) -> ParseResult<RubyRun> {
+    let warning = match report.map(serde_json::from_str::<RspecJson>) {
+        Some(Ok(json)) => return ParseResult::Full(rspec_from_json(json)),
+        Some(Err(e)) => format!("JSON parse failed: {}", e),
+        None => "no JSON report".to_string(),
+    };
+    match rspec_from_text(text) {
+        Some(run) => ParseResult::Degraded(run, vec![warning]),
+        None => ParseResult::Passthrough(truncate_output(text, 500)),
+    }
+}
+
+fn rspec_from_json(json: RspecJson) -> RubyRun {
+    let mut failures = Vec::new();
+    let mut rerun = Vec::new();
+    for example in json.examples.iter().filter(|e| e.status == "failed") {
+        let location = format!(
+            "{}:{}",
+            example.file_path.trim_start_matches("./"),
+            example.line_number
+        );
+        let (error_message, stack_trace) = match &example.exception {
+            Some(e) => {
+                let message = clean_message(&e.message);
+                let message = if EXPECTATION_CLASSES.contains(&e.class.as_str()) {
+                    message
+                } else {
+                    format!("{}: {}", e.class, message)
+                };
+                let trace = e
+                    .backtrace
+                    .as_ref()
+                    .filter(|b| !b.is_empty())
+                    .map(|b| compress_stack_traces(&b.join("\n")));
+                (message, trace)
+            }
+            None => (String::new(), None),
+        };
+        failures.push(TestFailure {
+            test_name: example.full_description.clone(),
+            file_path: location.clone(),
+            error_message,
+            stack_trace,
+        });
+        rerun.push(location);
+    }
+
+    let summary = &json.summary;
+    if summary.errors_outside_of_examples_count > 0 {
+        failures.push(outside_error(&json.messages.join("\n")));
+    }
+    let failed = summary.failure_count + summary.errors_outside_of_examples_count;
+    RubyRun {
+        result: TestResult {
+            total: summary.example_count,
+            passed: summary
+                .example_count
+                .saturating_sub(summary.failure_count + summary.pending_count),
+            failed,
+            skipped: summary.pending_count,
+            duration_ms: Some((summary.duration * 1000.0) as u64),
+            failures,
+        },
+        rerun,
+    }
+}
+
+/// Build a run from the "Failures:" blocks and the summary line
+fn rspec_from_text(output: &str) -> Option<RubyRun> {
+    let clean = strip_ansi(output);
+    let lines: Vec<&str> = clean.lines().collect();
+    let caps = lines.iter().rev().find_map(|l| RSPEC_SUMMARY.captures(l))?;
+    let count = |i: usize| -> usize {
+        caps.get(i)
+            .and_then(|m| m.as_str().parse().ok())
+            .unwrap_or(0)
+    };
+    let (total, failed, pending, outside) = (count(1), count(2), count(3), count(4));
+
+    let duration_ms = lines
+        .iter()
+        .find_map(|l| RSPEC_FINISHED.captures(l))
+        .and_then(|c| {
+            let minutes: f64 = c.get(1).map_or(Ok(0.0), |m| m.as_str().parse()).ok()?;
+            let seconds: f64 = c[2].parse().ok()?;
+            Some(((minutes * 60.0 + seconds) * 1000.0) as u64)
+        });
+
+    let locations: Vec<(String, String)> = lines
+        .iter()
+        .filter_map(|l| RSPEC_FAILED_EXAMPLE.captures(l))
+        .map(|c| (c[1].trim_start_matches("./").to_string(), c[2].to_string()))
+        .collect();
+
+    let mut failures = Vec::new();
+    if let Some(start) = lines.iter().position(|l| l.trim_end() == "Failures:") {
+        let mut i = start + 1;
+        while i < lines.len() {
+            let line = lines[i];
+            if !line.is_empty() && !line.starts_with(' ') {
+                break;
+            }
+            let Some(header) = RSPEC_FAILURE_HEADER.captures(line) else {
+                i += 1;
+                continue;
+            };
+            let test_name = header[1].to_string();
+            let mut body = Vec::new();
+            i += 1;
+            while i < lines.len()
+                && (lines[i].is_empty() || lines[i].starts_with(' '))
+                && !RSPEC_FAILURE_HEADER.is_match(lines[i])
+            {
+                body.push(lines[i]);
+                i += 1;
+            }
+            let (message, trace) = split_backtrace(&body);
+            // "Failure/Error: <source line>" repeats the spec; keep the explanation
+            let message: Vec<&str> = match message.split_first() {
+                Some((first, rest)) if first.starts_with("Failure/Error:") && !rest.is_empty() => {
+                    rest.to_vec()
+                }
+                _ => message,
+            };
+            let file_path = locations
+                .iter()
+                .find(|(_, name)| *name == test_name)
+                .map(|(location, _)| location.clone())
+                .unwrap_or_default();
+            failures.push(TestFailure {
+                test_name,
+                file_path,
+                error_message: message.join("\n"),
+                stack_trace: trace,
+            });
+        }
+    }
+
+    if outside > 0 {
+        let start = lines
+            .iter()
+            .position(|l| l.starts_with("An error occurred"))
+            .unwrap_or(lines.len());
+        let block: Vec<&str> = lines[start..]
+            .iter()
+            .take_while(|l| !RSPEC_FINISHED.is_match(l) && !l.starts_with("Run options"))
+            .copied()
+            .collect();
+        failures.push(outside_error(&block.join("\n")));
+    }
+
+    Some(RubyRun {
+        result: TestResult {
+            total,
+            passed: total.saturating_sub(failed + pending),
+            failed: failed + outside,
+            skipped: pending,
+            duration_ms,
+            failures,
+        },
+        rerun: locations
+            .into_iter()
+            .map(|(location, _)| location)
+            .collect(),
+    })
+}
+
+/// Load errors and `before(:suite)` failures, reported outside any example
+fn outside_error(text: &str) -> TestFailure {
+    let compressed = compress_stack_traces(text.trim());
+    let lines: Vec<&str> = compressed
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .take(MAX_OUTSIDE_ERROR_LINES)
+        .collect();
+    TestFailure {
+        test_name: "error outside of examples".to_string(),
+        file_path: String::new(),
+        error_message: lines.join("\n"),
+        stack_trace: None,
+    }
+}
+
+/// Tier 1: summary line found (Minitest has no machine-readable reporter
+/// built in). Tier 3: passthrough.
+fn parse_minitest(output: &str) -> ParseResult<RubyRun> {
+    match minitest_from_text(output) {
+        Some(run) => ParseResult::Full(run),
+        None => ParseResult::Passthrough(truncate_output(output, 500)),
+    }
+}
+
+fn minitest_from_text(output: &str) -> Option<RubyRun> {
+    let clean = strip_ansi(output);
+    let lines: Vec<&str> = clean.lines().collect();
+    let caps = lines
+        .iter()
+        .rev()
+        .find_map(|l| MINITEST_SUMMARY.captures(l.trim()))?;
+    let count = |i: usize| -> usize { caps[i].parse().unwrap_or(0) };
+    let (total, failures_count, errors, skips) = (count(1), count(3), count(4), count(5));
+    let duration_ms = lines
+        .iter()
+        .find_map(|l| MINITEST_FINISHED.captures(l))
+        .and_then(|c| c[1].parse::<f64>().ok())
+        .map(|s| (s * 1000.0) as u64);
+
+    let is_boundary = |l: &str| {
+        MINITEST_HEADER.is_match(l)
+            || RAILS_RERUN.is_match(l)
+            || MINITEST_FINISHED.is_match(l)
+            || MINITEST_SUMMARY.is_match(l.trim())
+    };
+
+    let mut failures = Vec::new();
+    let mut rerun = Vec::new();
+    let mut rails = false;
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(header) = MINITEST_HEADER.captures(lines[i]) else {
+            if let Some(c) = RAILS_RERUN.captures(lines[i]) {
+                rails = true;
+                if !rerun.contains(&c[1].to_string()) {
+                    rerun.push(c[1].to_string());
+                }
+            }
+            i += 1;
+            continue;
+        };
+        let kind = header[1].to_string();
+        i += 1;
+        while i < lines.len() && lines[i].trim().is_empty() {
+            i += 1;
+        }
+        let Some(name) = lines.get(i).and_then(|l| MINITEST_NAME.captures(l)) else {
+            continue;
+        };
+        let test_name = name[1].to_string();
+        let assert_location = name
+            .get(2)
+            .map(|file| format!("{}:{}", relative(file.as_str()), &name[3]));
+        i += 1;
+        let mut body = Vec::new();
+        while i < lines.len() && !is_boundary(lines[i]) {
+            body.push(lines[i]);
+            i += 1;
+        }
+        if kind == "Skipped" {
+            continue;
+        }
+        let (message, trace) = split_backtrace(&body);
+        let file_path = assert_location
+            .or_else(|| first_test_frame(&body))
+            .unwrap_or_default();
+        failures.push(TestFailure {
+            test_name,
+            file_path,
+            error_message: message.join("\n"),
+            stack_trace: trace,
+        });
+    }
+
+    // Rails prints `rails test file:line` for every failure; plain Minitest
+    // is re-run by test name
+    if !rails {
+        let names: Vec<&str> = failures
+            .iter()
+            .map(|f| f.test_name.rsplit('#').next().unwrap_or(&f.test_name))
+            .collect();
+        rerun = if names.is_empty() {
+            Vec::new()
+        } else {
+            vec![format!("TESTOPTS=\"--name=/{}/\"", names.join("|"))]
+        };
+    }
+
+    let failed = failures_count + errors;
+    Some(RubyRun {
+        result: TestResult {
+            total,
+            passed: total.saturating_sub(failed + skips),
+            failed,
+            skipped: skips,
+            duration_ms,
+            failures,
+        },
+        rerun,
+    })
+}
+
+/// Split a failure body into message lines and a compressed backtrace
+fn split_backtrace<'a>(body: &[&'a str]) -> (Vec<&'a str>, Option<String>) {
+    let (frames, message): (Vec<&str>, Vec<&str>) =
+        body.iter().partition(|l| RUBY_FRAME.is_match(l));
+    let message = message
+        .into_iter()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let trace = if frames.is_empty() {
+        None
+    } else {
+        let frames: Vec<&str> = frames.iter().map(|l| l.trim()).collect();
+        Some(compress_stack_traces(&frames.join("\n")))
+    };
+    (message, trace)
+}
+
+/// First backtrace frame inside a test file, as `path:line`
+fn first_test_frame(body: &[&str]) -> Option<String> {
+    body.iter()
+        .filter_map(|l| RUBY_FRAME.captures(l))
+        .find(|c| c[1].ends_with("_test.rb"))
+        .map(|c| format!("{}:{}", relative(&c[1]), &c[2]))
+}
+
+/// Paths relative to the current directory when they are inside it
+fn relative(path: &str) -> String {
+    let cwd = std::env::current_dir()
+        .map(|d| format!("{}/", d.display()))
+        .unwrap_or_default();
+    path.strip_prefix(cwd.as_str())
+        .unwrap_or(path)
+        .trim_start_matches("./")
+        .to_string()
+}
+
+/// Trim RSpec's indented, blank-padded messages to their content lines
+fn clean_message(message: &str) -> String {
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rspec_json_failures_and_rerun() {
+        let json = r#"{"version":"3.13.0","seed":4242,"examples":[
+            {"id":"./spec/models/cart_spec.rb[1:1]","description":"sums items","full_description":"Cart#total sums items","status":"passed","file_path":"./spec/models/cart_spec.rb","line_number":4,"run_time":0.001,"pending_message":null},
+            {"id":"./spec/models/cart_spec.rb[1:2]","description":"applies discounts","full_description":"Cart#total applies discounts","status":"failed","file_path":"./spec/models/cart_spec.rb","line_number":9,"run_time":0.002,"pending_message":null,
+             "exception":{"class":"RSpec::Expectations::ExpectationNotMetError","message":"\nexpected: 90\n     got: 100\n\n(compared using ==)\n","backtrace":[
+                "/usr/local/bundle/gems/rspec-support-3.13.1/lib/rspec/support.rb:110:in 'block in <module:Support>'",
+                "/usr/local/bundle/gems/rspec-support-3.13.1/lib/rspec/support.rb:119:in 'notify_failure'",
+                "./spec/models/cart_spec.rb:11:in 'block (3 levels) in <top (required)>'",
+                "/usr/local/bundle/gems/rspec-core-3.13.0/lib/rspec/core/example.rb:263:in 'instance_exec'",
+                "/usr/local/bundle/gems/rspec-core-3.13.0/lib/rspec/core/example.rb:263:in 'block in run'"]}},
+            {"id":"./spec/models/cart_spec.rb[1:3]","description":"rejects nil","full_description":"Cart#total rejects nil","status":"failed","file_path":"./spec/models/cart_spec.rb","line_number":15,"run_time":0.001,"pending_message":null,
+             "exception":{"class":"NoMethodError","message":"undefined method 'price' for nil","backtrace":["./app/models/cart.rb:8:in 'Cart#total'"]}},
+            {"id":"./spec/models/user_spec.rb[1:1]","description":"validates email","full_description":"User validates email","status":"pending","file_path":"./spec/models/user_spec.rb","line_number":3,"run_time":0.0,"pending_message":"Not yet implemented"}
+          ],
+          "summary":{"duration":0.0123,"example_count":4,"failure_count":2,"pending_count":1,"errors_outside_of_examples_count":0},
+          "summary_line":"4 examples, 2 failures, 1 pending"}"#;
+
+        let ParseResult::Full(run) = parse_rspec(Some(json), "") else {
+            panic!("expected full parse");
+        };
+        let result = &run.result;
+        assert_eq!(
+            (result.total, result.passed, result.failed, result.skipped),
+            (4, 1, 2, 1)
+        );
+        assert_eq!(result.duration_ms, Some(12));
+        assert_eq!(result.failures[0].test_name, "Cart#total applies discounts");
+        assert_eq!(
+            result.failures[0].error_message,
+            "expected: 90\ngot: 100\n(compared using ==)"
+        );
+        assert_eq!(
+            result.failures[0].stack_trace.as_deref(),
+            Some(concat!(
+                "... 2 frames in rspec-support\n",
+                "./spec/models/cart_spec.rb:11:in 'block (3 levels) in <top (required)>'\n",
+                "... 2 frames in rspec-core"
+            ))
+        );
+        assert_eq!(
+            result.failures[1].error_message,
+            "NoMethodError: undefined method 'price' for nil"
+        );
+        assert_eq!(
+            rerun_command("rspec", &run.rerun).unwrap(),
+            "rtk rspec spec/models/cart_spec.rb:9:15"
+        );
+    }
+
+    #[test]
+    fn test_rspec_text_fallback() {
+        let output = r#"Randomized with seed 4242
+..F*
+
+Pending: (Failures listed here are expected and do not affect your suite's status)
+
+  1) User validates email
+     # Not yet implemented
+     # ./spec/models/user_spec.rb:3
+
+Failures:
+
+  1) Cart#total applies discounts
+     Failure/Error: expect(cart.total).to eq(90)
+
+       expected: 90
+            got: 100
+
+       (compared using ==)
+     # ./spec/models/cart_spec.rb:11:in 'block (3 levels) in <top (required)>'
+
+Finished in 0.0123 seconds (files took 0.81 seconds to load)
+4 examples, 1 failure, 1 pending
+
+Failed examples:
+
+rspec ./spec/models/cart_spec.rb:9 # Cart#total applies discounts
+
+Randomized with seed 4242
+"#;
+        let ParseResult::Degraded(run, _) = parse_rspec(None, output) else {
+            panic!("expected degraded parse");
+        };
+        let result = &run.result;
+        assert_eq!(
+            (result.total, result.passed, result.failed, result.skipped),
+            (4, 2, 1, 1)
+        );
+        assert_eq!(result.duration_ms, Some(12));
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].test_name, "Cart#total applies discounts");
+        assert_eq!(result.failures[0].file_path, "spec/models/cart_spec.rb:9");
+        assert_eq!(
+            result.failures[0].error_message,
+            "expected: 90\ngot: 100\n(compared using ==)"
+        );
+        assert_eq!(run.rerun, vec!["spec/models/cart_spec.rb:9"]);
+    }
+
+    #[test]
+    fn test_rspec_load_error_outside_examples() {
+        let output = r#"
+An error occurred while loading ./spec/models/cart_spec.rb.
+Failure/Error: require "cart"
+
+LoadError:
+  cannot load such file -- cart
+# ./spec/models/cart_spec.rb:1:in '<top (required)>'
+No examples found.
+
+Finished in 0.00004 seconds (files took 0.1 seconds to load)
+0 examples, 0 failures, 1 error occurred outside of examples
+"#;
+        let ParseResult::Degraded(run, _) = parse_rspec(None, output) else {
+            panic!("expected degraded parse");
+        };
+        assert_eq!(run.result.failed, 1);
+        let failure = &run.result.failures[0];
+        assert_eq!(failure.test_name, "error outside of examples");
+        assert!(failure
+            .error_message
+            .starts_with("An error occurred while loading ./spec/models/cart_spec.rb."));
+        assert!(failure
+            .error_message
+            .contains("cannot load such file -- cart"));
+    }
+
+    #[test]
+    fn test_minitest_rails_failures_and_errors() {
+        let output = r#"Running 12 tests in a single process (parallelization threshold is 50)
+Run options: --seed 31337
+
+# Running:
+
+....F
+
+Failure:
+CartTest#test_applies_discount [test/models/cart_test.rb:14]:
+Expected: 90
+  Actual: 100
+
+
+bin/rails test test/models/cart_test.rb:10
+
+E
+
+Error:
+CartTest#test_rejects_nil:
+NoMethodError: undefined method 'price' for nil
+    app/models/cart.rb:8:in 'Cart#total'
+    test/models/cart_test.rb:20:in 'block in <class:CartTest>'
+
+
+bin/rails test test/models/cart_test.rb:18
+
+.....S
+
+Finished in 0.412345s, 29.1 runs/s, 41.2 assertions/s.
+12 runs, 17 assertions, 1 failures, 1 errors, 1 skips
+"#;
+        let ParseResult::Full(run) = parse_minitest(output) else {
+            panic!("expected full parse");
+        };
+        let result = &run.result;
+        assert_eq!(
+            (result.total, result.passed, result.failed, result.skipped),
+            (12, 9, 2, 1)
+        );
+        assert_eq!(result.duration_ms, Some(412));
+        assert_eq!(
+            result.failures[0].test_name,
+            "CartTest#test_applies_discount"
+        );
+        assert_eq!(result.failures[0].file_path, "test/models/cart_test.rb:14");
+        assert_eq!(
+            result.failures[0].error_message,
+            "Expected: 90\nActual: 100"
+        );
+        assert_eq!(
+            result.failures[1].error_message,
+            "NoMethodError: undefined method 'price' for nil"
+        );
+        assert_eq!(result.failures[1].file_path, "test/models/cart_test.rb:20");
+        assert_eq!(
+            rerun_command("minitest", &run.rerun).unwrap(),
+            "rtk minitest test/models/cart_test.rb:10 test/models/cart_test.rb:18"
+        );
+    }
+
+    #[test]
+    fn test_minitest_plain_reporter_reruns_by_name() {
+        let output = r#"Run options: --seed 7
+
+# Running:
+
+.F.
+
+Finished in 0.001234s, 2431.1 runs/s, 2431.1 assertions/s.
+
+  1) Failure:
+CartTest#test_total [/app/test/cart_test.rb:9]:
+Expected false to be truthy.
+
+3 runs, 3 assertions, 1 failures, 0 errors, 0 skips
+"#;
+        let ParseResult::Full(run) = parse_minitest(output) else {
+            panic!("expected full parse");
+        };
+        assert_eq!(run.result.failures.len(), 1);
+        assert_eq!(
+            run.result.failures[0].error_message,
+            "Expected false to be truthy."
+        );
+        assert_eq!(
+            rerun_command("minitest", &run.rerun).unwrap(),
+            "rtk minitest TESTOPTS=\"--name=/test_total/\""
+        );
+    }
+
+    #[test]
+    fn test_rake_test_args() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("cart_test.rb");
+        std::fs::write(
+            &file,
+            "class CartTest < Minitest::Test\n  def test_total\n    assert cart.total\n  end\n\n  test \"adds items\" do\n    assert true\n  end\nend\n",
+        )
+        .unwrap();
+        let file = file.to_string_lossy().to_string();
+        let args = |list: &[String]| rake_test_args(list);
+
+        assert_eq!(
+            args(&[format!("{}:3", file), "--seed".into(), "7".into()]),
+            vec![
+                format!("TEST={}", file),
+                "TESTOPTS=--seed 7 --name=test_total".to_string()
+            ]
+        );
+        assert_eq!(
+            args(&[format!("{}:3", file), format!("{}:7", file)]),
+            vec![
+                format!("TEST={{{},{}}}", file, file),
+                "TESTOPTS=--name='/^(test_total|test_adds_items)$/'".to_string()
+            ]
+        );
+        assert_eq!(
+            args(&["TESTOPTS=\"--name=/test_total/\"".into()]),
+            vec!["TESTOPTS=\"--name=/test_total/\""]
+        );
+        let dir_arg = dir.path().to_string_lossy().to_string();
+        assert_eq!(
+            args(std::slice::from_ref(&dir_arg)),
+            vec![format!("TEST={}/**/*_test.rb", dir_arg)]
+        );
+    }
+
+    #[test]
+    fn test_minitest_without_summary_passes_through() {
+        let output = "bin/rails aborted!\nActiveRecord::PendingMigrationError\n";
+        assert_eq!(parse_minitest(output).tier(), 3);
+    }
+}
@@ -2,6 +2,7 @@ use crate::tracking;
 use anyhow::{Context, Result};
 use regex::Regex;
 use std::process::{Command, Stdio};
+use std::time::SystemTime;
 
 /// Run a command and filter output to show only errors/warnings
 pub fn run_err(command: &str, verbose: u8) -> Result<()> {
@@ -29,12 +30,24 @@ pub fn run_err(command: &str, verbose: u8) -> Result<()> {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
     let raw = format!("{}\n{}", stdout, stderr);
-    let filtered = filter_errors(&raw);
+    let (reports, rest) = crate::sanitizer::extract_reports(&raw);
+    let mut filtered = filter_errors(&crate::stack_trace::compress_stack_traces(&rest));
+    if let Some(reports) = reports {
+        filtered = if filtered.is_empty() {
+            reports
+        } else {
+            format!("{}\n{}", reports, filtered)
+        };
+    }
+    // Only reports the command names: a leftover report must not stand in for this run
+    let report = crate::junit_cmd::summarize_named_reports(command, verbose);
     let mut rtk = String::new();
 
     if filtered.is_empty() {
         if output.status.success() {
-            rtk.push_str("✅ Command completed successfully (no errors)");
+            if report.is_none() {
+                rtk.push_str("✅ Command completed successfully (no errors)");
+            }
         } else {
             rtk.push_str(&format!(
                 "❌ Command failed (exit code: {:?})\n",
@@ -48,6 +61,12 @@ pub fn run_err(command: &str, verbose: u8) -> Result<()> {
     } else {
         rtk.push_str(&filtered);
     }
+    if let Some(report) = report {
+        if !rtk.is_empty() {
+            rtk = format!("{}\n\n", rtk.trim_end());
+        }
+        rtk.push_str(&report);
+    }
 
     let exit_code = output
         .status
@@ -73,6 +92,7 @@ pub fn run_test(command: &str, verbose: u8) -> Result<()> {
     if verbose > 0 {
         eprintln!("Running tests: {}", command);
     }
+    let started = SystemTime::now();
 
     let output = if cfg!(target_os = "windows") {
         Command::new("cmd")
@@ -97,7 +117,12 @@ pub fn run_test(command: &str, verbose: u8) -> Result<()> {
         .status
         .code()
         .unwrap_or(if output.status.success() { 0 } else { 1 });
-    let summary = extract_test_summary(&raw, command);
+    // A JUnit report written by the run beats scraping the terminal output
+    let summary =
+        match crate::junit_cmd::summarize_detected_reports(command, &raw, started, verbose) {
+            Some(report) => with_unreported_errors(report, &raw, exit_code),
+            None => extract_test_summary(&raw, command),
+        };
     if let Some(hint) = crate::tee::tee_and_hint(&raw, "test", exit_code) {
         println!("{}\n{}", summary, hint);
     } else {
@@ -111,6 +136,42 @@ pub fn run_test(command: &str, verbose: u8) -> Result<()> {
     Ok(())
 }
 
+/// Error lines kept after a JUnit summary when the run failed
+const MAX_UNREPORTED_ERROR_LINES: usize = 30;
+
+/// A failed run can break outside the reports (compile error in another module):
+/// append the output's error lines the report summary doesn't already show
+fn with_unreported_errors(report: String, raw: &str, exit_code: i32) -> String {
+    if exit_code == 0 {
+        return report;
+    }
+    let (_, rest) = crate::sanitizer::extract_reports(raw);
+    let errors = filter_errors(&crate::stack_trace::compress_stack_traces(&rest));
+    let lines: Vec<&str> = errors
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !report.contains(l.trim()))
+        .collect();
+    if lines.is_empty() {
+        return report;
+    }
+    let mut out = format!("{}\n\nErrors outside the reports:\n", report);
+    out.push_str(
+        &lines
+            .iter()
+            .take(MAX_UNREPORTED_ERROR_LINES)
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    if lines.len() > MAX_UNREPORTED_ERROR_LINES {
+        out.push_str(&format!(
+            "\n... +{} more lines",
+            lines.len() - MAX_UNREPORTED_ERROR_LINES
+        ));
+    }
+    out
+}
+
 fn filter_errors(output: &str) -> String {
     lazy_static::lazy_static! {
         static ref ERROR_PATTERNS: Vec<Regex> = vec![
@@ -168,7 +229,7 @@ fn filter_errors(output: &str) -> String {
     result.join("\n")
 }
 
-fn extract_test_summary(output: &str, command: &str) -> String {
+pub(crate) fn extract_test_summary(output: &str, command: &str) -> String {
     let mut result = Vec::new();
     let lines: Vec<&str> = output.lines().collect();
 
@@ -269,6 +330,19 @@ fn extract_test_summary(output: &str, command: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_with_unreported_errors() {
+        let report = "PASS (3) FAIL (1)\n  CartTest.total: expected 2 but was 1".to_string();
+        let raw = "[ERROR] /src/main/java/Api.java:[12,5] error: cannot find symbol\n\
+                   CartTest.total: expected 2 but was 1\n";
+        let out = with_unreported_errors(report.clone(), raw, 1);
+        assert!(out.starts_with(&report));
+        assert!(out.contains("Errors outside the reports:\n[ERROR] /src/main/java/Api.java"));
+        assert_eq!(out.matches("expected 2 but was 1").count(), 1);
+
+        assert_eq!(with_unreported_errors(report.clone(), raw, 0), report);
+    }
+
     #[test]
     fn test_filter_errors() {
         let output = "info: compiling\nerror: something failed\n  at line 10\ninfo: done";
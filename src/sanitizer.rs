@@ -0,0 +1,562 @@
+//! Race detector, sanitizer and valgrind report compression.
+//!
+//! Recognizes `go test -race` DATA RACE blocks, AddressSanitizer /
+//! LeakSanitizer / ThreadSanitizer / UndefinedBehaviorSanitizer reports and
+//! valgrind memcheck errors. Each report becomes one compact record (kind,
+//! user frames of every access / allocation section) and identical reports
+//! are merged with a count.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// User frames shown per report section
+const MAX_SECTION_FRAMES: usize = 3;
+
+/// Distinct reports shown before summarizing the rest
+const MAX_REPORTS: usize = 10;
+
+lazy_static! {
+    static ref SEPARATOR: Regex = Regex::new(r"^={10,}$").unwrap();
+    // go -race
+    static ref GO_RACE_START: Regex = Regex::new(r"^WARNING: DATA RACE$").unwrap();
+    static ref GO_RACE_SECTION: Regex = Regex::new(
+        r"^(\S.*?)(?: at 0x[0-9a-f]+)?(?: by (?:goroutine \d+|main goroutine))?:$"
+    )
+    .unwrap();
+    static ref GO_FUNC: Regex = Regex::new(r"^  (\S.*)$").unwrap();
+    static ref GO_LOC: Regex = Regex::new(r"^\s{4,}(\S+:\d+)(?: \+0x[0-9a-f]+)?$").unwrap();
+    // ASan / LSan / MSan / TSan
+    static ref SAN_ERROR: Regex =
+        Regex::new(r"^==\d+==ERROR: (\w+Sanitizer): (\S+(?: \S+)*?)(?: on (?:unknown )?address.*)?$")
+            .unwrap();
+    static ref TSAN_WARNING: Regex =
+        Regex::new(r"^WARNING: (ThreadSanitizer): (.+?)(?: \(pid=\d+\))?$").unwrap();
+    static ref SAN_FRAME: Regex =
+        Regex::new(r"^\s*#\d+ (?:0x[0-9a-f]+ in )?(\S+)(?: (.*))?$").unwrap();
+    static ref SAN_ABORT: Regex = Regex::new(r"^==\d+==(?:ABORTING|HINT|NOTE)").unwrap();
+    // UBSan
+    static ref UBSAN: Regex = Regex::new(r"^(\S+:\d+(?::\d+)?): runtime error: (.+)$").unwrap();
+    // valgrind
+    static ref VALGRIND: Regex = Regex::new(r"^==\d+==(.*)$").unwrap();
+    static ref VALGRIND_FRAME: Regex =
+        Regex::new(r"^\s+(?:at|by) 0x[0-9A-Fa-f]+: (.+?) \((.+)\)$").unwrap();
+    static ref VALGRIND_KEEP: Regex =
+        Regex::new(r"^(?:(?:definitely|indirectly|possibly) lost: [1-9]|ERROR SUMMARY: [1-9])")
+            .unwrap();
+    // normalization
+    static ref MODULE_OFFSET: Regex = Regex::new(r"\s*\([^)]*\+0x[0-9a-fA-F]+\)").unwrap();
+    static ref ADDRESS: Regex = Regex::new(r"\s*(?:at )?0x[0-9a-fA-F]+").unwrap();
+    static ref THREAD_TRAILING: Regex = Regex::new(
+        r"\s*by (?:thread T\d+|main thread|goroutine \d+|main goroutine)(?: \([^)]*\))?:?$"
+    )
+    .unwrap();
+    static ref THREAD: Regex =
+        Regex::new(r"\s*(?:thread T\d+|main thread|goroutine \d+|main goroutine)(?: \([^)]*\))?")
+            .unwrap();
+    static ref GOROUTINE_ID: Regex = Regex::new(r"^Goroutine \d+(?: \([^)]*\))?").unwrap();
+    static ref LOSS_RECORD: Regex = Regex::new(r" in loss record \d+ of \d+").unwrap();
+}
+
+/// Path markers of code that isn't the user's (toolchains, runtimes, system libs)
+const LIBRARY_PATH_MARKERS: &[&str] = &[
+    "/go/src/",
+    "/pkg/mod/",
+    "/libexec/src/",
+    "_testmain.go",
+    "/rustc/",
+    "/.cargo/registry/",
+    "/.cargo/git/",
+    "/usr/lib",
+    "/usr/include/",
+    "/lib/x86_64",
+    "/lib64/",
+    "compiler-rt",
+    "sanitizer_common",
+    "libasan",
+    "libtsan",
+    "libubsan",
+    "vgpreload",
+    "valgrind",
+    "libc.so",
+    "libstdc++",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+struct Section {
+    label: String,
+    /// `(location, function)`, innermost first
+    frames: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Report {
+    tool: String,
+    kind: String,
+    sections: Vec<Section>,
+}
+
+impl Report {
+    fn format(&self, count: usize) -> String {
+        let mut out = format!("⚠ {}: {}", self.tool, self.kind);
+        if count > 1 {
+            out.push_str(&format!(" ×{}", count));
+        }
+        for section in &self.sections {
+            let user: Vec<&(String, String)> = section
+                .frames
+                .iter()
+                .filter(|(loc, _)| is_user_location(loc))
+                .collect();
+            let shown: Vec<String> = if user.is_empty() {
+                section.frames.iter().take(1).map(format_frame).collect()
+            } else {
+                user.iter()
+                    .take(MAX_SECTION_FRAMES)
+                    .map(|f| format_frame(f))
+                    .collect()
+            };
+            if shown.is_empty() {
+                out.push_str(&format!("\n  {}", section.label));
+            } else {
+                out.push_str(&format!("\n  {}: {}", section.label, shown.join(" ← ")));
+            }
+        }
+        out
+    }
+}
+
+fn format_frame((location, function): &(String, String)) -> String {
+    let function = function.strip_suffix("()").unwrap_or(function);
+    let function = function.rsplit('/').next().unwrap_or(function);
+    if location.is_empty() {
+        function.to_string()
+    } else {
+        format!("{} {}", location, function)
+    }
+}
+
+fn is_user_location(location: &str) -> bool {
+    !location.is_empty()
+        && !location.starts_with('(')
+        && !location.starts_with("in ")
+        && !LIBRARY_PATH_MARKERS.iter().any(|m| location.contains(m))
+}
+
+/// Section label without addresses and thread ids ("freed by thread T0 here:" → "freed by")
+fn normalize_label(line: &str) -> String {
+    let label = MODULE_OFFSET.replace_all(line.trim(), "");
+    let label = ADDRESS.replace_all(&label, "");
+    let label = THREAD_TRAILING.replace(&label, "");
+    let label = THREAD.replace_all(&label, "");
+    let label = LOSS_RECORD.replace_all(&label, "");
+    label
+        .trim_end_matches(':')
+        .trim_end()
+        .trim_end_matches(" here")
+        .trim()
+        .to_string()
+}
+
+/// Pull every report out of `text`.
+///
+/// Returns the deduplicated report records (None if there were none) and the
+/// remaining text with the report blocks removed.
+pub fn extract_reports(text: &str) -> (Option<String>, String) {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut reports: Vec<(Report, usize)> = Vec::new();
+    let mut valgrind_totals: Vec<String> = Vec::new();
+    let mut rest: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        // Separators only belong to a report when one follows
+        let start = if SEPARATOR.is_match(lines[i]) && i + 1 < lines.len() {
+            i + 1
+        } else {
+            i
+        };
+        if let Some((report, consumed)) = parse_report(&lines[start..]) {
+            let mut end = start + consumed;
+            if lines.get(end).is_some_and(|l| SEPARATOR.is_match(l)) {
+                end += 1;
+            }
+            match reports.iter_mut().find(|(r, _)| *r == report) {
+                Some((_, count)) => *count += 1,
+                None => reports.push((report, 1)),
+            }
+            i = end;
+            continue;
+        }
+        if let Some(caps) = VALGRIND.captures(lines[i]) {
+            // Banner, heap summary and per-pid noise
+            let content = caps[1].trim();
+            if VALGRIND_KEEP.is_match(content) {
+                valgrind_totals.push(format!("valgrind: {}", content));
+            }
+            i += 1;
+            continue;
+        }
+        rest.push(lines[i]);
+        i += 1;
+    }
+
+    let mut rest_text = rest.join("\n");
+    if text.ends_with('\n') && !rest_text.is_empty() {
+        rest_text.push('\n');
+    }
+    if reports.is_empty() && valgrind_totals.is_empty() {
+        return (None, rest_text);
+    }
+
+    let mut out: Vec<String> = reports
+        .iter()
+        .take(MAX_REPORTS)
+        .map(|(r, count)| r.format(*count))
+        .collect();
+    if reports.len() > MAX_REPORTS {
+        out.push(format!(
+            "... +{} more distinct reports",
+            reports.len() - MAX_REPORTS
+        ));
+    }
+    out.extend(valgrind_totals);
+    (Some(out.join("\n")), rest_text)
+}
+
+fn parse_report(lines: &[&str]) -> Option<(Report, usize)> {
+    let first = *lines.first()?;
+    if GO_RACE_START.is_match(first) {
+        return Some(parse_go_race(lines));
+    }
+    if let Some(caps) = SAN_ERROR.captures(first) {
+        return Some(parse_sanitizer(lines, &caps[1], &caps[2]));
+    }
+    if let Some(caps) = TSAN_WARNING.captures(first) {
+        return Some(parse_sanitizer(lines, &caps[1], &caps[2]));
+    }
+    if let Some(caps) = UBSAN.captures(first) {
+        let report = Report {
+            tool: "UndefinedBehaviorSanitizer".to_string(),
+            kind: caps[2].to_string(),
+            sections: vec![Section {
+                label: "at".to_string(),
+                frames: vec![(caps[1].to_string(), String::new())],
+            }],
+        };
+        let summary = lines
+            .get(1)
+            .is_some_and(|l| l.starts_with("SUMMARY: UndefinedBehaviorSanitizer"));
+        return Some((report, if summary { 2 } else { 1 }));
+    }
+    parse_valgrind(lines)
+}
+
+fn parse_go_race(lines: &[&str]) -> (Report, usize) {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut i = 1;
+    while i < lines.len() && !SEPARATOR.is_match(lines[i]) {
+        let line = lines[i];
+        if let Some(caps) = GO_RACE_SECTION.captures(line) {
+            // "Goroutine 8 (running) created at" → "goroutine created at"
+            let label = GOROUTINE_ID.replace(&caps[1], "goroutine");
+            sections.push(Section {
+                label: normalize_label(&label).to_lowercase(),
+                frames: Vec::new(),
+            });
+        } else if let (Some(func), Some(loc)) = (
+            GO_FUNC.captures(line),
+            lines.get(i + 1).and_then(|l| GO_LOC.captures(l)),
+        ) {
+            if let Some(section) = sections.last_mut() {
+                section
+                    .frames
+                    .push((loc[1].to_string(), func[1].to_string()));
+            }
+            i += 1;
+        }
+        i += 1;
+    }
+    let report = Report {
+        tool: "go race".to_string(),
+        kind: "DATA RACE".to_string(),
+        sections,
+    };
+    (report, i)
+}
+
+fn parse_sanitizer(lines: &[&str], tool: &str, kind: &str) -> (Report, usize) {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut pending_label: Option<String> = None;
+    let mut i = 1;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if line.starts_with("SUMMARY: ") {
+            i += 1;
+            // Shadow byte maps, legends and the abort marker
+            while i < lines.len() {
+                let l = lines[i];
+                if l.starts_with("Shadow byte")
+                    || l.starts_with(char::is_whitespace)
+                    || SAN_ABORT.is_match(l)
+                {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            break;
+        }
+        if SEPARATOR.is_match(line) || SAN_ERROR.is_match(line) || TSAN_WARNING.is_match(line) {
+            break;
+        }
+        if let Some(caps) = SAN_FRAME.captures(line) {
+            let function = caps[1].to_string();
+            let location = caps
+                .get(2)
+                .map(|m| m.as_str().split_whitespace().next().unwrap_or(""))
+                .unwrap_or("")
+                .to_string();
+            if let Some(label) = pending_label.take() {
+                sections.push(Section {
+                    label,
+                    frames: Vec::new(),
+                });
+            } else if sections.is_empty() {
+                sections.push(Section {
+                    label: "at".to_string(),
+                    frames: Vec::new(),
+                });
+            }
+            if let Some(section) = sections.last_mut() {
+                section.frames.push((location, function));
+            }
+        } else if line.trim().starts_with("Location is") {
+            sections.push(Section {
+                label: normalize_label(line),
+                frames: Vec::new(),
+            });
+        } else if !line.trim().is_empty() {
+            pending_label = Some(normalize_label(line));
+        }
+        i += 1;
+    }
+
+    let report = Report {
+        tool: tool.to_string(),
+        kind: kind.to_string(),
+        sections,
+    };
+    (report, i)
+}
+
+/// One memcheck error: header line, frames, optional "Address ..." sub-section
+fn parse_valgrind(lines: &[&str]) -> Option<(Report, usize)> {
+    // Content after the `==pid==` prefix: " header", "  sub-header", "    at 0x...: frame"
+    let content = |line: &str| VALGRIND.captures(line).map(|c| c[1].to_string());
+    let frame = |content: &str| {
+        VALGRIND_FRAME
+            .captures(content)
+            .map(|c| (c[2].to_string(), c[1].to_string()))
+    };
+
+    let header = content(lines.first()?)?;
+    let header = header.strip_prefix(' ')?;
+    if header.is_empty() || header.starts_with(' ') {
+        return None;
+    }
+    // Only an error if frames follow the header
+    frame(&content(lines.get(1)?)?)?;
+
+    let mut sections = vec![Section {
+        label: "at".to_string(),
+        frames: Vec::new(),
+    }];
+    let mut i = 1;
+    while let Some(line) = lines.get(i).and_then(|l| content(l)) {
+        i += 1;
+        if line.trim().is_empty() {
+            break;
+        }
+        if let Some(f) = frame(&line) {
+            if let Some(section) = sections.last_mut() {
+                section.frames.push(f);
+            }
+        } else if line.starts_with("  ") && !line.starts_with("   ") {
+            sections.push(Section {
+                label: normalize_label(&line),
+                frames: Vec::new(),
+            });
+        } else {
+            i -= 1;
+            break;
+        }
+    }
+
+    let report = Report {
+        tool: "valgrind".to_string(),
+        kind: normalize_label(header),
+        sections,
+    };
+    Some((report, i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_go_data_race_deduped() {
+        let race = concat!(
+            "==================\n",
+            "WARNING: DATA RACE\n",
+            "Write at 0x00c000012345 by goroutine 8:\n",
+            "  example.com/foo.(*Cache).Set()\n",
+            "      /app/cache.go:25 +0x64\n",
+            "  example.com/foo.TestCache.func1()\n",
+            "      /app/cache_test.go:14 +0x44\n",
+            "\n",
+            "Previous read at 0x00c000012345 by goroutine 7:\n",
+            "  example.com/foo.(*Cache).Get()\n",
+            "      /app/cache.go:18 +0x3a\n",
+            "\n",
+            "Goroutine 8 (running) created at:\n",
+            "  example.com/foo.TestCache()\n",
+            "      /app/cache_test.go:12 +0x10\n",
+            "  testing.tRunner()\n",
+            "      /usr/local/go/src/testing/testing.go:1595 +0x1c\n",
+            "==================\n",
+        );
+        let input = format!(
+            "=== RUN   TestCache\n{}{}--- FAIL: TestCache (0.00s)\n",
+            race,
+            race.replace("goroutine 8", "goroutine 9")
+        );
+        let (reports, rest) = extract_reports(&input);
+        assert_eq!(
+            reports.unwrap(),
+            concat!(
+                "⚠ go race: DATA RACE ×2\n",
+                "  write: /app/cache.go:25 foo.(*Cache).Set ← /app/cache_test.go:14 foo.TestCache.func1\n",
+                "  previous read: /app/cache.go:18 foo.(*Cache).Get\n",
+                "  goroutine created at: /app/cache_test.go:12 foo.TestCache",
+            )
+        );
+        assert_eq!(rest, "=== RUN   TestCache\n--- FAIL: TestCache (0.00s)\n");
+    }
+
+    #[test]
+    fn test_asan_heap_use_after_free() {
+        let input = concat!(
+            "=================================================================\n",
+            "==12345==ERROR: AddressSanitizer: heap-use-after-free on address 0x602000000010 at pc 0x4c3b2a bp 0x7ffd sp 0x7ffd\n",
+            "READ of size 4 at 0x602000000010 thread T0\n",
+            "    #0 0x4c3b2a in main /app/main.c:10:5\n",
+            "    #1 0x7f8e in __libc_start_main (/lib/x86_64-linux-gnu/libc.so.6+0x21bf6)\n",
+            "\n",
+            "0x602000000010 is located 0 bytes inside of 4-byte region [0x602000000010,0x602000000014)\n",
+            "freed by thread T0 here:\n",
+            "    #0 0x494d6d in free (/app/a.out+0x494d6d)\n",
+            "    #1 0x4c3af1 in main /app/main.c:9:5\n",
+            "\n",
+            "previously allocated by thread T0 here:\n",
+            "    #0 0x494f2d in malloc (/app/a.out+0x494f2d)\n",
+            "    #1 0x4c3ae6 in main /app/main.c:8:14\n",
+            "\n",
+            "SUMMARY: AddressSanitizer: heap-use-after-free /app/main.c:10:5 in main\n",
+            "Shadow bytes around the buggy address:\n",
+            "  0x0c047fff7fb0: 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00\n",
+            "Shadow byte legend (one shadow byte represents 8 application bytes):\n",
+            "  Addressable:           00\n",
+            "==12345==ABORTING\n",
+            "error: test failed\n",
+        );
+        let (reports, rest) = extract_reports(input);
+        assert_eq!(
+            reports.unwrap(),
+            concat!(
+                "⚠ AddressSanitizer: heap-use-after-free\n",
+                "  READ of size 4: /app/main.c:10:5 main\n",
+                "  freed by: /app/main.c:9:5 main\n",
+                "  previously allocated by: /app/main.c:8:14 main",
+            )
+        );
+        assert_eq!(rest, "error: test failed\n");
+    }
+
+    #[test]
+    fn test_tsan_and_ubsan() {
+        let input = concat!(
+            "==================\n",
+            "WARNING: ThreadSanitizer: data race (pid=4242)\n",
+            "  Write of size 4 at 0x55d0 by thread T1:\n",
+            "    #0 worker /app/race.c:5:10 (a.out+0x1234)\n",
+            "\n",
+            "  Previous read of size 4 at 0x55d0 by main thread:\n",
+            "    #0 main /app/race.c:12:3 (a.out+0x5678)\n",
+            "\n",
+            "  Location is global 'counter' of size 4 at 0x55d0 (a.out+0x9abc)\n",
+            "\n",
+            "SUMMARY: ThreadSanitizer: data race /app/race.c:5:10 in worker\n",
+            "==================\n",
+            "/app/calc.c:7:12: runtime error: signed integer overflow: 2147483647 + 1 cannot be represented in type 'int'\n",
+            "SUMMARY: UndefinedBehaviorSanitizer: undefined-behavior /app/calc.c:7:12 in\n",
+        );
+        let (reports, rest) = extract_reports(input);
+        let reports = reports.unwrap();
+        assert!(reports.contains("⚠ ThreadSanitizer: data race\n"));
+        assert!(reports.contains("  Write of size 4: /app/race.c:5:10 worker\n"));
+        assert!(reports.contains("  Previous read of size 4: /app/race.c:12:3 main\n"));
+        assert!(reports.contains("  Location is global 'counter' of size 4"));
+        assert!(reports.contains(
+            "⚠ UndefinedBehaviorSanitizer: signed integer overflow: 2147483647 + 1 cannot be represented in type 'int'\n  at: /app/calc.c:7:12"
+        ));
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_valgrind_memcheck() {
+        let input = concat!(
+            "==1234== Memcheck, a memory error detector\n",
+            "==1234== Command: ./app\n",
+            "==1234== \n",
+            "==1234== Invalid read of size 4\n",
+            "==1234==    at 0x108668: main (main.c:6)\n",
+            "==1234==  Address 0x522d044 is 4 bytes after a block of size 4 alloc'd\n",
+            "==1234==    at 0x4C2FB0F: malloc (in /usr/lib/valgrind/vgpreload_memcheck-amd64-linux.so)\n",
+            "==1234==    by 0x10865B: main (main.c:5)\n",
+            "==1234== \n",
+            "result: 3\n",
+            "==1234== 40 bytes in 1 blocks are definitely lost in loss record 1 of 1\n",
+            "==1234==    at 0x4C2FB0F: malloc (in /usr/lib/valgrind/vgpreload_memcheck-amd64-linux.so)\n",
+            "==1234==    by 0x108676: main (main.c:8)\n",
+            "==1234== \n",
+            "==1234== LEAK SUMMARY:\n",
+            "==1234==    definitely lost: 40 bytes in 1 blocks\n",
+            "==1234==    indirectly lost: 0 bytes in 0 blocks\n",
+            "==1234== ERROR SUMMARY: 2 errors from 2 contexts (suppressed: 0 from 0)\n",
+        );
+        let (reports, rest) = extract_reports(input);
+        assert_eq!(
+            reports.unwrap(),
+            concat!(
+                "⚠ valgrind: Invalid read of size 4\n",
+                "  at: main.c:6 main\n",
+                "  Address is 4 bytes after a block of size 4 alloc'd: main.c:5 main\n",
+                "⚠ valgrind: 40 bytes in 1 blocks are definitely lost\n",
+                "  at: main.c:8 main\n",
+                "valgrind: definitely lost: 40 bytes in 1 blocks\n",
+                "valgrind: ERROR SUMMARY: 2 errors from 2 contexts (suppressed: 0 from 0)",
+            )
+        );
+        assert_eq!(rest, "result: 3\n");
+    }
+
+    #[test]
+    fn test_no_reports() {
+        let input = "test result: ok. 3 passed\n==========\n";
+        let (reports, rest) = extract_reports(input);
+        assert!(reports.is_none());
+        assert_eq!(rest, input);
+    }
+}
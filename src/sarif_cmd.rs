@@ -0,0 +1,790 @@
+//! SARIF 2.1 log ingestion.
+//!
+//! semgrep, CodeQL, gitleaks, trivy, hadolint, ruff and eslint (with the
+//! SARIF formatter) can all emit SARIF. `rtk sarif <file|dir>` parses the
+//! runs into a `LintResult` and groups results by rule and file; `rtk lint`
+//! uses the same parser when the linter was asked for SARIF or printed it.
+
+use crate::parser::{
+    emit_degradation_warning, emit_passthrough_warning, truncate_output, LintIssue, LintResult,
+    LintSeverity, OutputParser, ParseResult,
+};
+use crate::tracking;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Rules listed before collapsing into "+N more rules"
+const MAX_RULES: usize = 15;
+
+/// Files listed per rule
+const MAX_FILES_PER_RULE: usize = 4;
+
+/// Lines listed per file within a rule
+const MAX_LINES_PER_FILE: usize = 5;
+
+/// Example message length
+const MAX_MESSAGE_CHARS: usize = 160;
+
+#[derive(Debug, Deserialize)]
+struct SarifLog {
+    #[serde(default)]
+    runs: Vec<Run>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    #[serde(default)]
+    results: Option<Vec<SarifResult>>,
+    #[serde(default)]
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Debug, Deserialize)]
+struct Driver {
+    name: String,
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    #[serde(default)]
+    short_description: Option<Message>,
+    #[serde(default)]
+    default_configuration: Option<RuleConfiguration>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleConfiguration {
+    #[serde(default)]
+    level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    #[serde(default)]
+    location: Option<ArtifactLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(default)]
+    rule_id: Option<String>,
+    #[serde(default)]
+    rule_index: Option<usize>,
+    #[serde(default)]
+    rule: Option<RuleReference>,
+    #[serde(default)]
+    level: Option<String>,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    message: Message,
+    #[serde(default)]
+    locations: Vec<Location>,
+    #[serde(default)]
+    suppressions: Vec<Suppression>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleReference {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Message {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    markdown: Option<String>,
+    #[serde(default)]
+    arguments: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(default)]
+    physical_location: Option<PhysicalLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    #[serde(default)]
+    artifact_location: Option<ArtifactLocation>,
+    #[serde(default)]
+    region: Option<Region>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArtifactLocation {
+    #[serde(default)]
+    uri: Option<String>,
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    #[serde(default)]
+    start_line: Option<usize>,
+    #[serde(default)]
+    start_column: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Suppression {
+    #[serde(default)]
+    status: Option<String>,
+}
+
+/// SARIF results with the names of the tools that produced them
+#[derive(Debug)]
+pub struct SarifReport {
+    pub tools: Vec<String>,
+    pub result: LintResult,
+    /// Rule id → short description, for rules that have one
+    pub descriptions: HashMap<String, String>,
+    pub suppressed: usize,
+}
+
+/// Parser for SARIF 2.1 logs
+pub struct SarifParser;
+
+impl OutputParser for SarifParser {
+    type Output = SarifReport;
+
+    fn parse(input: &str) -> ParseResult<SarifReport> {
+        let json = match input.find('{') {
+            Some(start) => &input[start..],
+            None => return ParseResult::Passthrough(truncate_output(input, 500)),
+        };
+        match serde_json::from_str::<SarifLog>(json) {
+            Ok(log) => ParseResult::Full(report_from_log(log)),
+            Err(e) => {
+                // Tool output after the log (e.g. a summary line) → parse the first value only
+                let mut stream = serde_json::Deserializer::from_str(json).into_iter::<SarifLog>();
+                match stream.next() {
+                    Some(Ok(log)) => ParseResult::Degraded(
+                        report_from_log(log),
+                        vec![format!("trailing output after SARIF log: {}", e)],
+                    ),
+                    _ => ParseResult::Passthrough(truncate_output(input, 500)),
+                }
+            }
+        }
+    }
+}
+
+/// Whether `output` is a SARIF log (as printed by `--format sarif`)
+pub fn looks_like_sarif(output: &str) -> bool {
+    let head: String = output.trim_start().chars().take(400).collect();
+    head.starts_with('{')
+        && head.contains("\"runs\"")
+        && (head.contains("sarif") || head.contains("\"2.1.0\""))
+}
+
+/// Whether the linter arguments ask for SARIF output
+pub fn wants_sarif(args: &[String]) -> bool {
+    const FORMAT_FLAGS: &[&str] = &["-f", "--format", "--output-format", "--reporter"];
+    args.iter().enumerate().any(|(i, arg)| {
+        if arg == "--sarif" {
+            return true;
+        }
+        if let Some((flag, value)) = arg.split_once('=') {
+            return FORMAT_FLAGS.contains(&flag) && value.contains("sarif");
+        }
+        FORMAT_FLAGS.contains(&arg.as_str()) && args.get(i + 1).is_some_and(|v| v.contains("sarif"))
+    })
+}
+
+fn report_from_log(log: SarifLog) -> SarifReport {
+    let cwd = std::env::current_dir()
+        .map(|d| format!("{}/", d.display()))
+        .unwrap_or_default();
+
+    let mut tools = Vec::new();
+    let mut issues = Vec::new();
+    let mut descriptions = HashMap::new();
+    let mut suppressed = 0;
+
+    for run in log.runs {
+        if !tools.contains(&run.tool.driver.name) {
+            tools.push(run.tool.driver.name.clone());
+        }
+        let rules = &run.tool.driver.rules;
+        for rule in rules {
+            if let Some(text) = rule.short_description.as_ref().and_then(message_text) {
+                descriptions.entry(rule.id.clone()).or_insert(text);
+            }
+        }
+
+        for result in run.results.unwrap_or_default() {
+            // Only "fail" results are findings; pass / notApplicable / review are not
+            if result.kind.as_deref().is_some_and(|k| k != "fail") {
+                continue;
+            }
+            if result
+                .suppressions
+                .iter()
+                .any(|s| s.status.as_deref() != Some("rejected"))
+            {
+                suppressed += 1;
+                continue;
+            }
+
+            let rule = result
+                .rule_index
+                .or(result.rule.as_ref().and_then(|r| r.index))
+                .and_then(|i| rules.get(i))
+                .or_else(|| {
+                    let id = result.rule_id.as_deref()?;
+                    rules.iter().find(|r| r.id == id)
+                });
+            let rule_id = result
+                .rule_id
+                .clone()
+                .or_else(|| result.rule.as_ref().and_then(|r| r.id.clone()))
+                .or_else(|| rule.map(|r| r.id.clone()))
+                .unwrap_or_else(|| "(no rule)".to_string());
+
+            // SARIF: a result without a level inherits the rule's, else "warning"
+            let level = result
+                .level
+                .as_deref()
+                .or_else(|| rule?.default_configuration.as_ref()?.level.as_deref())
+                .unwrap_or("warning");
+            let severity = match level {
+                "error" => LintSeverity::Error,
+                "warning" => LintSeverity::Warning,
+                _ => LintSeverity::Info,
+            };
+
+            let physical = result
+                .locations
+                .first()
+                .and_then(|l| l.physical_location.as_ref());
+            let file_path = physical
+                .and_then(|p| p.artifact_location.as_ref())
+                .and_then(|a| {
+                    a.uri
+                        .clone()
+                        .or_else(|| run.artifacts.get(a.index?)?.location.as_ref()?.uri.clone())
+                })
+                .map(|uri| normalize_uri(&uri, &cwd))
+                .unwrap_or_default();
+            let region = physical.and_then(|p| p.region.as_ref());
+
+            let message = message_text(&result.message)
+                .or_else(|| rule?.short_description.as_ref().and_then(message_text))
+                .unwrap_or_default();
+
+            issues.push(LintIssue {
+                file_path,
+                line: region.and_then(|r| r.start_line).unwrap_or(0),
+                column: region.and_then(|r| r.start_column).unwrap_or(0),
+                severity,
+                rule_id,
+                message,
+                suggestion: None,
+            });
+        }
+    }
+
+    let files: HashSet<&str> = issues
+        .iter()
+        .map(|i| i.file_path.as_str())
+        .filter(|f| !f.is_empty())
+        .collect();
+    let result = LintResult {
+        total_files: files.len(),
+        files_with_issues: files.len(),
+        total_issues: issues.len(),
+        errors: count_severity(&issues, LintSeverity::Error),
+        warnings: count_severity(&issues, LintSeverity::Warning),
+        issues,
+    };
+
+    SarifReport {
+        tools,
+        result,
+        descriptions,
+        suppressed,
+    }
+}
+
+fn count_severity(issues: &[LintIssue], severity: LintSeverity) -> usize {
+    issues.iter().filter(|i| i.severity == severity).count()
+}
+
+/// Message text with `{0}` placeholders filled from `arguments`
+fn message_text(message: &Message) -> Option<String> {
+    let mut text = message.text.clone().or_else(|| message.markdown.clone())?;
+    for (i, arg) in message.arguments.iter().enumerate() {
+        text = text.replace(&format!("{{{}}}", i), arg);
+    }
+    Some(text)
+}
+
+/// `file:///home/me/repo/src/a.py` → `src/a.py` (relative to the working directory)
+fn normalize_uri(uri: &str, cwd: &str) -> String {
+    let path = uri
+        .strip_prefix("file://")
+        .unwrap_or(uri)
+        .replace("%20", " ");
+    let path = path.strip_prefix(cwd).unwrap_or(&path);
+    path.trim_start_matches("./").to_string()
+}
+
+/// Group results by rule (errors first), then by file, with one example message per rule
+pub fn format_report(report: &SarifReport) -> String {
+    let result = &report.result;
+    let tools = if report.tools.is_empty() {
+        "SARIF".to_string()
+    } else {
+        report.tools.join(", ")
+    };
+    let suppressed = if report.suppressed > 0 {
+        format!(" ({} suppressed)", report.suppressed)
+    } else {
+        String::new()
+    };
+
+    if result.issues.is_empty() {
+        return format!("✓ {}: No issues found{}", tools, suppressed);
+    }
+
+    let notes = result.total_issues - result.errors - result.warnings;
+    let mut out = vec![
+        format!(
+            "{}: {} errors, {} warnings, {} notes in {} file{}{}",
+            tools,
+            result.errors,
+            result.warnings,
+            notes,
+            result.files_with_issues,
+            if result.files_with_issues == 1 {
+                ""
+            } else {
+                "s"
+            },
+            suppressed
+        ),
+        "═══════════════════════════════════════".to_string(),
+    ];
+
+    // Rule → issues, in first-seen order so equal counts stay stable
+    let mut rule_order: Vec<&str> = Vec::new();
+    let mut by_rule: HashMap<&str, Vec<&LintIssue>> = HashMap::new();
+    for issue in &result.issues {
+        let entry = by_rule.entry(issue.rule_id.as_str()).or_default();
+        if entry.is_empty() {
+            rule_order.push(issue.rule_id.as_str());
+        }
+        entry.push(issue);
+    }
+    let worst = |issues: &[&LintIssue]| {
+        issues
+            .iter()
+            .map(|i| severity_rank(&i.severity))
+            .min()
+            .unwrap_or(2)
+    };
+    rule_order.sort_by_key(|rule| {
+        let issues = &by_rule[rule];
+        (worst(issues), std::cmp::Reverse(issues.len()))
+    });
+
+    for rule in rule_order.iter().take(MAX_RULES) {
+        let issues = &by_rule[rule];
+        let symbol = match worst(issues) {
+            0 => "✗",
+            1 => "⚠",
+            _ => "ℹ",
+        };
+
+        let mut file_order: Vec<&str> = Vec::new();
+        let mut lines_by_file: HashMap<&str, Vec<usize>> = HashMap::new();
+        for issue in issues {
+            let lines = lines_by_file.entry(issue.file_path.as_str()).or_default();
+            if lines.is_empty() {
+                file_order.push(issue.file_path.as_str());
+            }
+            if issue.line > 0 && !lines.contains(&issue.line) {
+                lines.push(issue.line);
+            }
+        }
+
+        out.push(format!(
+            "{} {} ({}x, {} file{})",
+            symbol,
+            rule,
+            issues.len(),
+            file_order.len(),
+            if file_order.len() == 1 { "" } else { "s" }
+        ));
+
+        // The description names the problem; fall back to the first result's message
+        let example = report
+            .descriptions
+            .get(*rule)
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or(&issues[0].message);
+        let example = example.lines().next().unwrap_or("").trim();
+        if !example.is_empty() {
+            out.push(format!(
+                "    {}",
+                truncate_chars(example, MAX_MESSAGE_CHARS)
+            ));
+        }
+
+        let mut locations: Vec<String> = file_order
+            .iter()
+            .take(MAX_FILES_PER_RULE)
+            .map(|file| {
+                let lines = &lines_by_file[file];
+                let file = if file.is_empty() { "(no file)" } else { file };
+                if lines.is_empty() {
+                    return file.to_string();
+                }
+                let mut shown = lines
+                    .iter()
+                    .take(MAX_LINES_PER_FILE)
+                    .map(|l| l.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                if lines.len() > MAX_LINES_PER_FILE {
+                    shown.push_str(&format!(",+{}", lines.len() - MAX_LINES_PER_FILE));
+                }
+                format!("{}:{}", file, shown)
+            })
+            .collect();
+        if file_order.len() > MAX_FILES_PER_RULE {
+            let more = file_order.len() - MAX_FILES_PER_RULE;
+            locations.push(format!(
+                "+{} file{}",
+                more,
+                if more == 1 { "" } else { "s" }
+            ));
+        }
+        out.push(format!("    {}", locations.join(" · ")));
+    }
+
+    if rule_order.len() > MAX_RULES {
+        out.push(format!(
+            "\n... +{} more rules",
+            rule_order.len() - MAX_RULES
+        ));
+    }
+
+    out.join("\n")
+}
+
+fn severity_rank(severity: &LintSeverity) -> u8 {
+    match severity {
+        LintSeverity::Error => 0,
+        LintSeverity::Warning => 1,
+        LintSeverity::Info => 2,
+    }
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}...", text.chars().take(max - 3).collect::<String>())
+    }
+}
+
+/// Parse and format a SARIF log printed by a linter
+pub fn filter_sarif(output: &str, verbose: u8) -> String {
+    match SarifParser::parse(output) {
+        ParseResult::Full(report) => format_report(&report),
+        ParseResult::Degraded(report, warnings) => {
+            if verbose > 0 {
+                emit_degradation_warning("sarif", &warnings.join(", "));
+            }
+            format_report(&report)
+        }
+        ParseResult::Passthrough(raw) => {
+            emit_passthrough_warning("sarif", "No parseable SARIF log");
+            raw
+        }
+    }
+}
+
+/// `*.sarif` / `*.sarif.json` files, directories scanned recursively
+fn expand_paths(paths: &[String]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        let path = PathBuf::from(path);
+        if path.is_dir() {
+            files.extend(
+                walkdir::WalkDir::new(&path)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_file())
+                    .map(|e| e.into_path())
+                    .filter(|p| {
+                        let name = p.to_string_lossy();
+                        name.ends_with(".sarif") || name.ends_with(".sarif.json")
+                    }),
+            );
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+pub fn run(paths: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    let files = expand_paths(paths);
+    if files.is_empty() {
+        bail!("No SARIF logs matched: {}", paths.join(" "));
+    }
+    if verbose > 0 {
+        eprintln!("Parsing {} SARIF log(s)", files.len());
+    }
+
+    let mut raw = String::new();
+    let mut merged: Option<SarifReport> = None;
+    let mut warnings = Vec::new();
+    for file in &files {
+        let content = std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let report = match SarifParser::parse(&content) {
+            ParseResult::Full(report) => report,
+            ParseResult::Degraded(report, w) => {
+                warnings.extend(w);
+                report
+            }
+            ParseResult::Passthrough(_) => {
+                warnings.push(format!("{}: not a SARIF log", file.display()));
+                raw.push_str(&content);
+                continue;
+            }
+        };
+        raw.push_str(&content);
+        merged = Some(match merged {
+            None => report,
+            Some(acc) => merge_reports(acc, report),
+        });
+    }
+
+    if verbose > 0 && !warnings.is_empty() {
+        emit_degradation_warning("sarif", &warnings.join(", "));
+    }
+    let Some(report) = merged else {
+        emit_passthrough_warning("sarif", "No parseable SARIF log");
+        bail!("No parseable SARIF log in: {}", paths.join(" "));
+    };
+
+    let filtered = format_report(&report);
+    println!("{}", filtered);
+
+    timer.track(
+        &format!("cat {}", paths.join(" ")),
+        &format!("rtk sarif {}", paths.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    // Mirror the analyzer: error-level results → non-zero
+    if report.result.errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn merge_reports(mut acc: SarifReport, other: SarifReport) -> SarifReport {
+    for tool in other.tools {
+        if !acc.tools.contains(&tool) {
+            acc.tools.push(tool);
+        }
+    }
+    for (rule, text) in other.descriptions {
+        acc.descriptions.entry(rule).or_insert(text);
+    }
+    acc.suppressed += other.suppressed;
+
+    let result = &mut acc.result;
+    result.issues.extend(other.result.issues);
+    let files: HashSet<&str> = result
+        .issues
+        .iter()
+        .map(|i| i.file_path.as_str())
+        .filter(|f| !f.is_empty())
+        .collect();
+    result.total_files = files.len();
+    result.files_with_issues = files.len();
+    result.total_issues = result.issues.len();
+    result.errors = count_severity(&result.issues, LintSeverity::Error);
+    result.warnings = count_severity(&result.issues, LintSeverity::Warning);
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEMGREP: &str = r#"{
+  "$schema": "https://docs.oasis-open.org/sarif/sarif/v2.1.0/os/schemas/sarif-schema-2.1.0.json",
+  "version": "2.1.0",
+  "runs": [{
+    "tool": {"driver": {"name": "Semgrep OSS", "rules": [
+      {"id": "python.lang.security.audit.eval-detected",
+       "shortDescription": {"text": "Detected the use of eval(). eval() can be dangerous if used to evaluate dynamic content."},
+       "defaultConfiguration": {"level": "error"}},
+      {"id": "python.lang.best-practice.open-never-closed",
+       "shortDescription": {"text": "file object opened without corresponding close"},
+       "defaultConfiguration": {"level": "warning"}}
+    ]}},
+    "results": [
+      {"ruleId": "python.lang.security.audit.eval-detected", "message": {"text": "Detected the use of eval()."},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/app.py"}, "region": {"startLine": 12, "startColumn": 5}}}]},
+      {"ruleId": "python.lang.security.audit.eval-detected", "message": {"text": "Detected the use of eval()."},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/app.py"}, "region": {"startLine": 40}}}]},
+      {"ruleId": "python.lang.security.audit.eval-detected", "message": {"text": "Detected the use of eval()."},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/util.py"}, "region": {"startLine": 3}}}]},
+      {"ruleId": "python.lang.best-practice.open-never-closed", "ruleIndex": 1, "message": {"text": "file object opened without corresponding close"},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/io.py"}, "region": {"startLine": 8}}}]},
+      {"ruleId": "python.lang.security.audit.eval-detected", "message": {"text": "Detected the use of eval()."},
+       "suppressions": [{"kind": "inSource"}],
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/legacy.py"}, "region": {"startLine": 1}}}]}
+    ]
+  }]
+}"#;
+
+    #[test]
+    fn test_parse_semgrep_sarif() {
+        let result = SarifParser::parse(SEMGREP);
+        assert_eq!(result.tier(), 1);
+        let report = result.unwrap();
+        assert_eq!(report.tools, vec!["Semgrep OSS"]);
+        assert_eq!(report.suppressed, 1);
+
+        let lint = &report.result;
+        assert_eq!(lint.total_issues, 4);
+        // Levels come from the rules' defaultConfiguration
+        assert_eq!((lint.errors, lint.warnings), (3, 1));
+        assert_eq!(lint.files_with_issues, 3);
+        assert_eq!(lint.issues[0].file_path, "src/app.py");
+        assert_eq!((lint.issues[0].line, lint.issues[0].column), (12, 5));
+    }
+
+    #[test]
+    fn test_format_groups_by_rule_and_file() {
+        let report = SarifParser::parse(SEMGREP).unwrap();
+        assert_eq!(
+            format_report(&report),
+            "Semgrep OSS: 3 errors, 1 warnings, 0 notes in 3 files (1 suppressed)\n\
+             ═══════════════════════════════════════\n\
+             ✗ python.lang.security.audit.eval-detected (3x, 2 files)\n    \
+             Detected the use of eval(). eval() can be dangerous if used to evaluate dynamic content.\n    \
+             src/app.py:12,40 · src/util.py:3\n\
+             ⚠ python.lang.best-practice.open-never-closed (1x, 1 file)\n    \
+             file object opened without corresponding close\n    \
+             src/io.py:8"
+        );
+    }
+
+    #[test]
+    fn test_codeql_artifact_index_and_arguments() {
+        let sarif = r#"{"version": "2.1.0", "runs": [{
+          "tool": {"driver": {"name": "CodeQL", "rules": [{"id": "js/sql-injection"}]}},
+          "artifacts": [{"location": {"uri": "file:///repo/server/db.js"}}],
+          "results": [
+            {"rule": {"id": "js/sql-injection", "index": 0}, "level": "error",
+             "message": {"text": "This query depends on {0}.", "arguments": ["a user-provided value"]},
+             "locations": [{"physicalLocation": {"artifactLocation": {"index": 0}, "region": {"startLine": 27}}}]},
+            {"ruleId": "js/sql-injection", "kind": "pass", "message": {"text": "ok"}}
+          ]
+        }]}"#;
+        let report = SarifParser::parse(sarif).unwrap();
+        let issue = &report.result.issues[0];
+        assert_eq!(report.result.total_issues, 1);
+        assert_eq!(issue.rule_id, "js/sql-injection");
+        assert_eq!(issue.file_path, "/repo/server/db.js");
+        assert_eq!(
+            issue.message,
+            "This query depends on a user-provided value."
+        );
+        assert_eq!(issue.severity, LintSeverity::Error);
+        assert!(
+            format_report(&report).starts_with("CodeQL: 1 errors, 0 warnings, 0 notes in 1 file\n")
+        );
+    }
+
+    #[test]
+    fn test_empty_run_and_detection() {
+        let clean = r#"{"version": "2.1.0", "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+          "runs": [{"tool": {"driver": {"name": "gitleaks"}}, "results": []}]}"#;
+        assert!(looks_like_sarif(clean));
+        assert!(!looks_like_sarif(
+            "[{\"filePath\": \"a.js\", \"messages\": []}]"
+        ));
+        let report = SarifParser::parse(clean).unwrap();
+        assert_eq!(format_report(&report), "✓ gitleaks: No issues found");
+    }
+
+    #[test]
+    fn test_wants_sarif() {
+        let args = |s: &str| s.split_whitespace().map(String::from).collect::<Vec<_>>();
+        assert!(wants_sarif(&args("semgrep --sarif .")));
+        assert!(wants_sarif(&args("ruff check --output-format=sarif")));
+        assert!(wants_sarif(&args(
+            "eslint -f @microsoft/eslint-formatter-sarif src"
+        )));
+        assert!(wants_sarif(&args("hadolint --format sarif Dockerfile")));
+        assert!(!wants_sarif(&args("eslint -f json src")));
+    }
+
+    #[test]
+    fn test_sarif_savings() {
+        let mut results = Vec::new();
+        for i in 0..120 {
+            results.push(format!(
+                r#"{{"ruleId": "rule-{}", "level": "warning", "message": {{"text": "Finding number {} in a generated file with a long explanation of the problem"}},
+                 "locations": [{{"physicalLocation": {{"artifactLocation": {{"uri": "src/module_{}.py"}}, "region": {{"startLine": {}, "startColumn": 1}}}}}}]}}"#,
+                i % 6,
+                i,
+                i % 10,
+                i + 1
+            ));
+        }
+        let sarif = format!(
+            r#"{{"version": "2.1.0", "runs": [{{"tool": {{"driver": {{"name": "scanner"}}}}, "results": [{}]}}]}}"#,
+            results.join(",")
+        );
+        let filtered = format_report(&SarifParser::parse(&sarif).unwrap());
+        let input_tokens = sarif.split_whitespace().count();
+        let output_tokens = filtered.split_whitespace().count();
+        let savings = 100.0 - (output_tokens as f64 / input_tokens as f64 * 100.0);
+        assert!(
+            savings >= 80.0,
+            "expected ≥80% savings, got {:.1}%",
+            savings
+        );
+    }
+}
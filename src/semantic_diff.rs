@@ -0,0 +1,904 @@
+//! Semantic unified-diff summaries.
+//!
+//! Labels each hunk with the function/class it touches (via `symbols::outline`
+//! on the post-image), collapses whitespace-only, rename-only and moved-block
+//! hunks to one line, and prints a per-file header such as
+//! `fn parse_line: modified (+12/-3), fn new_helper: added`.
+//! Shared by `rtk git diff --semantic`, `rtk git show --semantic` and
+//! `rtk gh pr diff --semantic`.
+
+use crate::filter::Language;
+use crate::symbols::{self, Symbol};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+const MAX_HUNK_LINES: usize = 10;
+const MAX_HEADER_SYMBOLS: usize = 6;
+/// Minimum non-blank lines for a removed/added run to count as a moved block
+const MIN_MOVED_LINES: usize = 3;
+
+lazy_static! {
+    static ref HUNK_HEADER: Regex =
+        Regex::new(r"^@@ -(\d+)(?:,(\d+))? \+(\d+)(?:,(\d+))? @@ ?(.*)$").unwrap();
+    static ref TOKEN: Regex = Regex::new(r"\w+|\S").unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Context(String),
+    Added(String),
+    Removed(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Hunk {
+    pub new_start: usize,
+    /// Function context git prints after the second `@@`
+    pub context: String,
+    pub lines: Vec<DiffLine>,
+}
+
+impl Hunk {
+    pub fn added(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|l| matches!(l, DiffLine::Added(_)))
+            .count()
+    }
+
+    pub fn removed(&self) -> usize {
+        self.lines
+            .iter()
+            .filter(|l| matches!(l, DiffLine::Removed(_)))
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FileDiff {
+    pub old_path: String,
+    pub new_path: String,
+    /// Abbreviated post-image blob id from the `index a..b` line
+    pub new_blob: Option<String>,
+    pub is_new: bool,
+    pub is_deleted: bool,
+    pub is_binary: bool,
+    pub similarity: Option<u8>,
+    pub hunks: Vec<Hunk>,
+}
+
+impl FileDiff {
+    pub fn added(&self) -> usize {
+        self.hunks.iter().map(|h| h.added()).sum()
+    }
+
+    pub fn removed(&self) -> usize {
+        self.hunks.iter().map(|h| h.removed()).sum()
+    }
+
+    pub fn path(&self) -> &str {
+        if self.is_deleted {
+            &self.old_path
+        } else {
+            &self.new_path
+        }
+    }
+
+    fn language(&self) -> Language {
+        Path::new(self.path())
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(Language::from_extension)
+            .unwrap_or(Language::Unknown)
+    }
+}
+
+/// Parse `git diff` / `git show` / `gh pr diff` output into files and hunks.
+/// Commit headers and anything before the first `diff --git` are ignored.
+pub fn parse_unified_diff(diff: &str) -> Vec<FileDiff> {
+    let mut files: Vec<FileDiff> = Vec::new();
+
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let (old, new) = split_diff_git_paths(rest);
+            files.push(FileDiff {
+                old_path: old,
+                new_path: new,
+                ..Default::default()
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if let Some(hunk) = file.hunks.last_mut() {
+            if let Some(added) = line.strip_prefix('+') {
+                hunk.lines.push(DiffLine::Added(added.to_string()));
+                continue;
+            }
+            if let Some(removed) = line.strip_prefix('-') {
+                hunk.lines.push(DiffLine::Removed(removed.to_string()));
+                continue;
+            }
+            if let Some(context) = line.strip_prefix(' ') {
+                hunk.lines.push(DiffLine::Context(context.to_string()));
+                continue;
+            }
+            // Some tools strip the leading space from blank context lines
+            if line.is_empty() {
+                hunk.lines.push(DiffLine::Context(String::new()));
+                continue;
+            }
+            if line.starts_with('\\') {
+                continue;
+            }
+        }
+
+        if let Some(caps) = HUNK_HEADER.captures(line) {
+            file.hunks.push(Hunk {
+                new_start: caps[3].parse().unwrap_or(0),
+                context: caps.get(5).map_or("", |m| m.as_str()).trim().to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(index) = line.strip_prefix("index ") {
+            file.new_blob = index
+                .split_whitespace()
+                .next()
+                .and_then(|range| range.split("..").nth(1))
+                .filter(|blob| !blob.chars().all(|c| c == '0'))
+                .map(|s| s.to_string());
+        } else if line.starts_with("new file mode") {
+            file.is_new = true;
+        } else if line.starts_with("deleted file mode") {
+            file.is_deleted = true;
+        } else if line.starts_with("Binary files") || line == "GIT binary patch" {
+            file.is_binary = true;
+        } else if let Some(sim) = line.strip_prefix("similarity index ") {
+            file.similarity = sim.trim_end_matches('%').parse().ok();
+        } else if let Some(from) = line.strip_prefix("rename from ") {
+            file.old_path = from.to_string();
+        } else if let Some(to) = line.strip_prefix("rename to ") {
+            file.new_path = to.to_string();
+        } else if let Some(old) = line.strip_prefix("--- a/") {
+            file.old_path = old.to_string();
+        } else if let Some(new) = line.strip_prefix("+++ b/") {
+            file.new_path = new.to_string();
+        }
+    }
+
+    files
+}
+
+/// `a/src/x.rs b/src/x.rs` → ("src/x.rs", "src/x.rs")
+fn split_diff_git_paths(rest: &str) -> (String, String) {
+    match rest.split_once(" b/") {
+        Some((old, new)) => (
+            old.strip_prefix("a/").unwrap_or(old).to_string(),
+            new.to_string(),
+        ),
+        None => (rest.to_string(), rest.to_string()),
+    }
+}
+
+/// Split a hunk at its context lines so each contiguous change is labelled and
+/// classified on its own (git merges nearby edits into one hunk).
+fn split_segments(hunk: &Hunk) -> Vec<Hunk> {
+    let mut segments: Vec<Hunk> = Vec::new();
+    let mut new_line = hunk.new_start;
+    let mut in_change = false;
+
+    for line in &hunk.lines {
+        match line {
+            DiffLine::Context(_) => {
+                new_line += 1;
+                in_change = false;
+            }
+            DiffLine::Added(_) | DiffLine::Removed(_) => {
+                if !in_change {
+                    segments.push(Hunk {
+                        new_start: new_line,
+                        context: hunk.context.clone(),
+                        lines: Vec::new(),
+                    });
+                    in_change = true;
+                }
+                if matches!(line, DiffLine::Added(_)) {
+                    new_line += 1;
+                }
+                segments.last_mut().unwrap().lines.push(line.clone());
+            }
+        }
+    }
+
+    segments
+}
+
+/// What happened to a symbol across the diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Debug, Clone)]
+struct SymbolChange {
+    label: String,
+    change: Change,
+    added: usize,
+    removed: usize,
+}
+
+/// How a hunk is shown
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HunkKind {
+    Normal,
+    WhitespaceOnly,
+    RenameOnly(Vec<(String, String)>),
+}
+
+fn changed_text(hunk: &Hunk) -> (Vec<&str>, Vec<&str>) {
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for line in &hunk.lines {
+        match line {
+            DiffLine::Removed(l) => removed.push(l.as_str()),
+            DiffLine::Added(l) => added.push(l.as_str()),
+            DiffLine::Context(_) => {}
+        }
+    }
+    (removed, added)
+}
+
+fn classify_hunk(hunk: &Hunk) -> HunkKind {
+    let (removed, added) = changed_text(hunk);
+    if removed.is_empty() && added.is_empty() {
+        return HunkKind::Normal;
+    }
+
+    let squash = |lines: &[&str]| -> String {
+        lines
+            .iter()
+            .flat_map(|l| l.chars())
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    };
+    if squash(&removed) == squash(&added) {
+        return HunkKind::WhitespaceOnly;
+    }
+
+    match identifier_renames(&removed, &added) {
+        Some(renames) => HunkKind::RenameOnly(renames),
+        None => HunkKind::Normal,
+    }
+}
+
+/// If every removed/added line pair differs only by identifier substitutions
+/// (at most two distinct ones), return them as `(old, new)` pairs.
+fn identifier_renames(removed: &[&str], added: &[&str]) -> Option<Vec<(String, String)>> {
+    if removed.is_empty() || removed.len() != added.len() {
+        return None;
+    }
+
+    let mut renames: Vec<(String, String)> = Vec::new();
+    for (old, new) in removed.iter().zip(added) {
+        let old_tokens: Vec<&str> = TOKEN.find_iter(old).map(|m| m.as_str()).collect();
+        let new_tokens: Vec<&str> = TOKEN.find_iter(new).map(|m| m.as_str()).collect();
+        if old_tokens.len() != new_tokens.len() {
+            return None;
+        }
+        for (a, b) in old_tokens.iter().zip(&new_tokens) {
+            if a == b {
+                continue;
+            }
+            let is_ident = |t: &str| {
+                t.chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && t.chars().all(|c| c.is_alphanumeric() || c == '_')
+            };
+            if !is_ident(a) || !is_ident(b) {
+                return None;
+            }
+            let pair = (a.to_string(), b.to_string());
+            if !renames.contains(&pair) {
+                renames.push(pair);
+            }
+        }
+    }
+
+    if renames.is_empty() || renames.len() > 2 {
+        None
+    } else {
+        Some(renames)
+    }
+}
+
+/// Normalized text of a contiguous removed or added run, used to match moved blocks
+fn run_key(lines: &[&str]) -> Option<String> {
+    let normalized: Vec<&str> = lines
+        .iter()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect();
+    if normalized.len() < MIN_MOVED_LINES {
+        None
+    } else {
+        Some(normalized.join("\n"))
+    }
+}
+
+/// Contiguous runs of removed (`true`) or added (`false`) lines as index ranges into `hunk.lines`
+fn change_runs(hunk: &Hunk) -> Vec<(bool, std::ops::Range<usize>)> {
+    let mut runs = Vec::new();
+    let mut i = 0;
+    while i < hunk.lines.len() {
+        let removed = match &hunk.lines[i] {
+            DiffLine::Removed(_) => true,
+            DiffLine::Added(_) => false,
+            DiffLine::Context(_) => {
+                i += 1;
+                continue;
+            }
+        };
+        let start = i;
+        while i < hunk.lines.len()
+            && matches!(
+                (&hunk.lines[i], removed),
+                (DiffLine::Removed(_), true) | (DiffLine::Added(_), false)
+            )
+        {
+            i += 1;
+        }
+        runs.push((removed, start..i));
+    }
+    runs
+}
+
+fn run_text<'a>(hunk: &'a Hunk, range: &std::ops::Range<usize>) -> Vec<&'a str> {
+    hunk.lines[range.clone()]
+        .iter()
+        .map(|l| match l {
+            DiffLine::Added(s) | DiffLine::Removed(s) | DiffLine::Context(s) => s.as_str(),
+        })
+        .collect()
+}
+
+/// Where each moved block's removed and added copies live: key → path
+struct MovedBlocks {
+    removed_in: HashMap<String, String>,
+    added_in: HashMap<String, String>,
+}
+
+impl MovedBlocks {
+    fn collect(files: &[FileDiff]) -> Self {
+        let mut removed_in = HashMap::new();
+        let mut added_in = HashMap::new();
+        for file in files {
+            for hunk in &file.hunks {
+                for (removed, range) in change_runs(hunk) {
+                    if let Some(key) = run_key(&run_text(hunk, &range)) {
+                        let map = if removed {
+                            &mut removed_in
+                        } else {
+                            &mut added_in
+                        };
+                        map.entry(key).or_insert_with(|| file.path().to_string());
+                    }
+                }
+            }
+        }
+        let moved: HashSet<String> = removed_in
+            .keys()
+            .filter(|k| added_in.contains_key(*k))
+            .cloned()
+            .collect();
+        removed_in.retain(|k, _| moved.contains(k));
+        added_in.retain(|k, _| moved.contains(k));
+        MovedBlocks {
+            removed_in,
+            added_in,
+        }
+    }
+
+    /// For a run, the marker line to show instead of its content, if it was moved
+    fn marker(&self, removed: bool, lines: &[&str], path: &str) -> Option<String> {
+        let key = run_key(lines)?;
+        let (other, arrow) = if removed {
+            (self.added_in.get(&key)?, "→")
+        } else {
+            (self.removed_in.get(&key)?, "←")
+        };
+        let place = if other == path {
+            "within file".to_string()
+        } else {
+            format!("{} {}", arrow, other)
+        };
+        Some(format!("  ↪ moved {} lines {}", lines.len(), place))
+    }
+}
+
+/// Symbols touched by each hunk, plus per-symbol change totals for the file header
+fn symbol_changes(
+    file: &FileDiff,
+    post_image: Option<&str>,
+) -> (Vec<Vec<String>>, Vec<SymbolChange>) {
+    let lang = file.language();
+    let outline: Vec<Symbol> = post_image
+        .map(|content| symbols::outline(content, lang))
+        .unwrap_or_default();
+
+    let mut changes: Vec<SymbolChange> = Vec::new();
+    let mut per_hunk: Vec<Vec<String>> = Vec::new();
+
+    fn touch(
+        changes: &mut Vec<SymbolChange>,
+        labels: &mut Vec<String>,
+        label: String,
+        added: bool,
+    ) {
+        if !labels.contains(&label) {
+            labels.push(label.clone());
+        }
+        let entry = match changes.iter_mut().find(|c| c.label == label) {
+            Some(entry) => entry,
+            None => {
+                changes.push(SymbolChange {
+                    label,
+                    change: Change::Modified,
+                    added: 0,
+                    removed: 0,
+                });
+                changes.last_mut().unwrap()
+            }
+        };
+        if added {
+            entry.added += 1;
+        } else {
+            entry.removed += 1;
+        }
+    }
+
+    let mut added_lines: HashSet<usize> = HashSet::new();
+    for hunk in &file.hunks {
+        let mut labels = Vec::new();
+        let mut new_line = hunk.new_start;
+
+        // Definitions written entirely on one side of the hunk
+        let (removed_text, added_text) = changed_text(hunk);
+        let removed_defs = symbols::outline(&removed_text.join("\n"), lang);
+        let added_defs = symbols::outline(&added_text.join("\n"), lang);
+
+        for line in &hunk.lines {
+            match line {
+                DiffLine::Context(_) => new_line += 1,
+                DiffLine::Added(_) => {
+                    added_lines.insert(new_line);
+                    let label = symbols::enclosing(&outline, new_line).map(|s| s.label());
+                    if let Some(label) = label {
+                        touch(&mut changes, &mut labels, label, true);
+                    }
+                    new_line += 1;
+                }
+                DiffLine::Removed(_) => {
+                    let label = symbols::enclosing(&outline, new_line).map(|s| s.label());
+                    if let Some(label) = label {
+                        touch(&mut changes, &mut labels, label, false);
+                    }
+                }
+            }
+        }
+
+        let same = |a: &Symbol, b: &Symbol| a.name == b.name && a.kind == b.kind;
+        for def in &removed_defs {
+            if outline.iter().chain(&added_defs).any(|s| same(s, def)) {
+                continue;
+            }
+            push_whole(&mut changes, &mut labels, def, Change::Removed);
+        }
+        // Without a post-image the outline is empty, so new definitions come from the hunk itself
+        if outline.is_empty() {
+            for def in &added_defs {
+                if removed_defs.iter().any(|s| same(s, def)) {
+                    continue;
+                }
+                push_whole(&mut changes, &mut labels, def, Change::Added);
+            }
+        }
+
+        // No post-image available: fall back to git's own function context
+        if labels.is_empty() && outline.is_empty() {
+            if let Some(sym) = symbols::outline(&hunk.context, lang).first() {
+                labels.push(sym.label());
+            } else if !hunk.context.is_empty() {
+                labels.push(crate::utils::truncate(&hunk.context, 50));
+            }
+        }
+
+        per_hunk.push(labels);
+    }
+
+    // A symbol whose every line is an added line was introduced by this diff
+    for change in changes.iter_mut() {
+        if change.change != Change::Modified {
+            continue;
+        }
+        if let Some(sym) = outline.iter().find(|s| s.label() == change.label) {
+            if (sym.start..=sym.end).all(|n| added_lines.contains(&n)) {
+                change.change = Change::Added;
+                change.added = sym.end - sym.start + 1;
+            }
+        }
+    }
+
+    // Containers and members overlap: an added container absorbs its members,
+    // otherwise the innermost changed members are listed instead of the container.
+    let spans: Vec<(Symbol, Change)> = changes
+        .iter()
+        .filter_map(|c| {
+            let sym = outline.iter().find(|s| s.label() == c.label)?;
+            Some((sym.clone(), c.change))
+        })
+        .collect();
+    changes.retain(|c| {
+        let Some(sym) = outline.iter().find(|s| s.label() == c.label) else {
+            return true;
+        };
+        let nested = |outer: &Symbol, inner: &Symbol| {
+            outer != inner && outer.start <= inner.start && inner.end <= outer.end
+        };
+        let inside_added = spans
+            .iter()
+            .any(|(outer, change)| *change == Change::Added && nested(outer, sym));
+        let has_changed_member = spans.iter().any(|(inner, _)| nested(sym, inner));
+        !inside_added && (c.change == Change::Added || !has_changed_member)
+    });
+
+    (per_hunk, changes)
+}
+
+/// Record a symbol that was added or removed as a whole
+fn push_whole(
+    changes: &mut Vec<SymbolChange>,
+    labels: &mut Vec<String>,
+    def: &Symbol,
+    change: Change,
+) {
+    let lines = def.end - def.start + 1;
+    let (added, removed) = match change {
+        Change::Added => (lines, 0),
+        _ => (0, lines),
+    };
+    changes.push(SymbolChange {
+        label: def.label(),
+        change,
+        added,
+        removed,
+    });
+    labels.push(def.label());
+}
+
+fn format_symbol_header(changes: &[SymbolChange]) -> String {
+    let mut parts: Vec<String> = changes
+        .iter()
+        .take(MAX_HEADER_SYMBOLS)
+        .map(|c| match c.change {
+            Change::Added => format!("{}: added (+{})", c.label, c.added),
+            Change::Removed => format!("{}: removed (-{})", c.label, c.removed),
+            Change::Modified => {
+                format!("{}: modified (+{}/-{})", c.label, c.added, c.removed)
+            }
+        })
+        .collect();
+    if changes.len() > MAX_HEADER_SYMBOLS {
+        parts.push(format!("+{} more", changes.len() - MAX_HEADER_SYMBOLS));
+    }
+    parts.join(", ")
+}
+
+fn hunk_label(labels: &[String], hunk: &Hunk) -> String {
+    match labels.len() {
+        0 => format!("L{}", hunk.new_start),
+        1 | 2 => labels.join(", "),
+        n => format!("{}, +{} more", labels[..2].join(", "), n - 2),
+    }
+}
+
+/// Render a semantic summary of `diff`.
+///
+/// `load_post_image(path, blob)` returns the new-side content of a file so
+/// hunks can be mapped to enclosing symbols; returning `None` falls back to
+/// the function context git prints in hunk headers. Output cut at `max_lines`
+/// ends with a hint naming `full_cmd`, the command that prints the whole diff.
+pub fn render(
+    diff: &str,
+    max_lines: usize,
+    full_cmd: &str,
+    load_post_image: &dyn Fn(&str, Option<&str>) -> Option<String>,
+) -> String {
+    let mut files = parse_unified_diff(diff);
+    for file in files.iter_mut() {
+        file.hunks = file.hunks.iter().flat_map(split_segments).collect();
+    }
+    let moved = MovedBlocks::collect(&files);
+    let mut out: Vec<String> = Vec::new();
+
+    'files: for file in &files {
+        let added = file.added();
+        let removed = file.removed();
+
+        if file.is_binary {
+            out.push(format!("\n📄 {} (binary)", file.path()));
+            continue;
+        }
+        if file.old_path != file.new_path && file.hunks.is_empty() {
+            out.push(format!(
+                "\n📄 {} → {} (renamed, {}% similar)",
+                file.old_path,
+                file.new_path,
+                file.similarity.unwrap_or(100)
+            ));
+            continue;
+        }
+
+        let post_image = if file.is_deleted {
+            None
+        } else {
+            load_post_image(&file.new_path, file.new_blob.as_deref())
+        };
+        let (per_hunk, changes) = symbol_changes(file, post_image.as_deref());
+
+        let status = if file.is_new {
+            " (new)"
+        } else if file.is_deleted {
+            " (deleted)"
+        } else {
+            ""
+        };
+        let path = if file.old_path != file.new_path {
+            format!("{} → {}", file.old_path, file.new_path)
+        } else {
+            file.path().to_string()
+        };
+        out.push(format!("\n📄 {}{} +{} -{}", path, status, added, removed));
+        if !changes.is_empty() {
+            out.push(format!("  {}", format_symbol_header(&changes)));
+        }
+        if file.is_deleted {
+            continue;
+        }
+
+        for (hunk, labels) in file.hunks.iter().zip(&per_hunk) {
+            let label = hunk_label(labels, hunk);
+            match classify_hunk(hunk) {
+                HunkKind::WhitespaceOnly => {
+                    out.push(format!(
+                        "  ≈ {}: whitespace-only ({} lines)",
+                        label,
+                        hunk.added().max(hunk.removed())
+                    ));
+                }
+                HunkKind::RenameOnly(renames) => {
+                    let pairs: Vec<String> = renames
+                        .iter()
+                        .map(|(a, b)| format!("{} → {}", a, b))
+                        .collect();
+                    out.push(format!(
+                        "  ≈ {}: rename {} ({} lines)",
+                        label,
+                        pairs.join(", "),
+                        hunk.added()
+                    ));
+                }
+                HunkKind::Normal => {
+                    out.push(format!(
+                        "  @@ {} (+{}/-{})",
+                        label,
+                        hunk.added(),
+                        hunk.removed()
+                    ));
+                    render_hunk_lines(hunk, &moved, file.path(), &mut out);
+                }
+            }
+
+            if out.len() >= max_lines {
+                out.push(format!("\n[full diff: {}]", full_cmd));
+                break 'files;
+            }
+        }
+    }
+
+    out.join("\n").trim_start_matches('\n').to_string()
+}
+
+/// Changed lines of a hunk, with moved runs replaced by a single marker
+fn render_hunk_lines(hunk: &Hunk, moved: &MovedBlocks, path: &str, out: &mut Vec<String>) {
+    let mut shown = 0;
+    let mut skipped = 0;
+
+    for (removed, range) in change_runs(hunk) {
+        let text = run_text(hunk, &range);
+        if let Some(marker) = moved.marker(removed, &text, path) {
+            out.push(marker);
+            continue;
+        }
+        let sign = if removed { '-' } else { '+' };
+        for line in text {
+            if shown < MAX_HUNK_LINES {
+                out.push(format!("  {}{}", sign, line));
+                shown += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+    }
+
+    if skipped > 0 {
+        out.push(format!("  ... ({} lines truncated)", skipped));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FULL: &str = "rtk git diff --no-compact HEAD~1";
+
+    fn no_source(_: &str, _: Option<&str>) -> Option<String> {
+        None
+    }
+
+    const PARSER_RS: &str = "pub struct Parser;\n\nimpl Parser {\n    pub fn parse_line(&self, line: &str) -> u32 {\n        let n = line.len();\n        let m = n * 2;\n        m as u32\n    }\n}\n\nfn new_helper() -> u32 {\n    42\n}\n";
+
+    #[test]
+    fn test_parse_unified_diff_basic() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\nindex 1111111..2222222 100644\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1,2 +1,3 @@ fn main() {\n fn main() {\n+    go();\n }\n";
+        let files = parse_unified_diff(diff);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].new_path, "src/a.rs");
+        assert_eq!(files[0].new_blob.as_deref(), Some("2222222"));
+        assert_eq!(files[0].hunks[0].context, "fn main() {");
+        assert_eq!(files[0].added(), 1);
+        assert_eq!(files[0].removed(), 0);
+    }
+
+    #[test]
+    fn test_render_labels_modified_and_added_symbols() {
+        let diff = "diff --git a/src/parser.rs b/src/parser.rs\nindex 1111111..2222222 100644\n--- a/src/parser.rs\n+++ b/src/parser.rs\n@@ -4,4 +4,5 @@ impl Parser {\n     pub fn parse_line(&self, line: &str) -> u32 {\n-        let n = 0;\n+        let n = line.len();\n+        let m = n * 2;\n-        n as u32\n+        m as u32\n     }\n@@ -8,0 +10,4 @@ impl Parser {\n+\n+fn new_helper() -> u32 {\n+    42\n+}\n";
+        let result = render(diff, 200, FULL, &|_, _| Some(PARSER_RS.to_string()));
+        assert!(
+            result.contains("fn Parser::parse_line: modified (+3/-2)"),
+            "got: {}",
+            result
+        );
+        assert!(
+            result.contains("fn new_helper: added (+3)"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("src/parser.rs +7 -2"), "got: {}", result);
+        assert!(
+            !result.contains("impl Parser:"),
+            "container should be folded: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_whitespace_only_hunk_collapsed() {
+        let diff = "diff --git a/a.py b/a.py\n--- a/a.py\n+++ b/a.py\n@@ -1,3 +1,3 @@ def run():\n-x = 1\n-y  =  2\n+x=1\n+y = 2\n";
+        let result = render(diff, 200, FULL, &no_source);
+        assert!(
+            result.contains("def run: whitespace-only"),
+            "got: {}",
+            result
+        );
+        assert!(!result.contains("+x=1"));
+    }
+
+    #[test]
+    fn test_hunk_split_at_context() {
+        let diff = "diff --git a/m.py b/m.py\n--- a/m.py\n+++ b/m.py\n@@ -1,7 +1,8 @@\n def a():\n-    x = 1\n+    x  =  1\n     return x\n \n \n def b():\n-    return 2\n+    y = 3\n+    return 2 + y\n";
+        let content =
+            "def a():\n    x  =  1\n    return x\n\n\ndef b():\n    y = 3\n    return 2 + y\n";
+        let result = render(diff, 200, FULL, &|_, _| Some(content.to_string()));
+        assert!(
+            result.contains("≈ def a: whitespace-only (1 lines)"),
+            "got: {}",
+            result
+        );
+        assert!(result.contains("@@ def b (+2/-1)"), "got: {}", result);
+        assert!(!result.contains("x  =  1"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_rename_only_hunk_collapsed() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,2 +1,2 @@\n-let total = count + 1;\n-println!(\"{}\", total);\n+let sum = count + 1;\n+println!(\"{}\", sum);\n";
+        let result = render(diff, 200, FULL, &no_source);
+        assert!(
+            result.contains("rename total → sum (2 lines)"),
+            "got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_real_change_is_not_rename() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-let total = count + 1;\n+let total = count - 1;\n";
+        let result = render(diff, 200, FULL, &no_source);
+        assert!(result.contains("@@ L1 (+1/-1)"), "got: {}", result);
+        assert!(result.contains("+let total = count - 1;"));
+    }
+
+    #[test]
+    fn test_moved_block_collapsed_across_files() {
+        let block = ["    let a = 1;", "    let b = 2;", "    let c = a + b;"];
+        let mut diff = String::from(
+            "diff --git a/old.rs b/old.rs\n--- a/old.rs\n+++ b/old.rs\n@@ -1,3 +0,0 @@\n",
+        );
+        for l in &block {
+            diff.push_str(&format!("-{}\n", l));
+        }
+        diff.push_str(
+            "diff --git a/new.rs b/new.rs\n--- a/new.rs\n+++ b/new.rs\n@@ -0,0 +1,3 @@\n",
+        );
+        for l in &block {
+            diff.push_str(&format!("+{}\n", l));
+        }
+        let result = render(&diff, 200, FULL, &no_source);
+        assert!(result.contains("moved 3 lines → new.rs"), "got: {}", result);
+        assert!(result.contains("moved 3 lines ← old.rs"), "got: {}", result);
+        assert!(!result.contains("let c = a + b"));
+    }
+
+    #[test]
+    fn test_removed_function_reported() {
+        let diff = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -1,4 +1,0 @@\n-fn gone() {\n-    1;\n-}\n-\n";
+        let result = render(diff, 200, FULL, &|_, _| Some("fn kept() {}\n".to_string()));
+        assert!(result.contains("fn gone: removed (-3)"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_added_function_without_post_image() {
+        let diff = "diff --git a/a.go b/a.go\n--- a/a.go\n+++ b/a.go\n@@ -10,0 +11,3 @@ func main() {\n+func Helper() int {\n+\treturn 1\n+}\n";
+        let result = render(diff, 200, FULL, &no_source);
+        assert!(
+            result.contains("func Helper: added (+3)"),
+            "got: {}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_added_container_absorbs_members() {
+        let content = "struct A;\n\nimpl A {\n    fn one(&self) {}\n    fn two(&self) {}\n}\n";
+        let diff = "diff --git a/a.rs b/a.rs\nnew file mode 100644\n--- /dev/null\n+++ b/a.rs\n@@ -0,0 +1,6 @@\n+struct A;\n+\n+impl A {\n+    fn one(&self) {}\n+    fn two(&self) {}\n+}\n";
+        let result = render(diff, 200, FULL, &|_, _| Some(content.to_string()));
+        assert!(result.contains("impl A: added (+4)"), "got: {}", result);
+        assert!(!result.contains("fn A::one"), "got: {}", result);
+    }
+
+    #[test]
+    fn test_pure_rename_and_binary() {
+        let diff = "diff --git a/old.rs b/new.rs\nsimilarity index 100%\nrename from old.rs\nrename to new.rs\ndiff --git a/logo.png b/logo.png\nBinary files a/logo.png and b/logo.png differ\n";
+        let result = render(diff, 200, FULL, &no_source);
+        assert!(result.contains("old.rs → new.rs (renamed, 100% similar)"));
+        assert!(result.contains("logo.png (binary)"));
+    }
+
+    #[test]
+    fn test_max_lines_budget() {
+        let mut diff = String::new();
+        for i in 0..50 {
+            diff.push_str(&format!(
+                "diff --git a/f{i}.rs b/f{i}.rs\n--- a/f{i}.rs\n+++ b/f{i}.rs\n@@ -1 +1 @@\n-a{i}()\n+b{i}(x)\n"
+            ));
+        }
+        let result = render(&diff, 30, FULL, &no_source);
+        assert!(result.contains("[full diff: rtk git diff --no-compact HEAD~1]"));
+        assert!(result.lines().count() < 60);
+    }
+}
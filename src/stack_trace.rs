@@ -0,0 +1,781 @@
+//! Cross-language stack trace compression.
+//!
+//! Detects Rust backtraces, Python and pytest tracebacks, Node/V8 stacks,
+//! Java and .NET exceptions, Ruby backtraces and Go goroutine dumps anywhere
+//! in the output. Runs of library and std frames collapse to "... 14 frames in
+//! tokio/std", user frames keep their file:line, repeated traces are folded
+//! into one line and goroutines with identical stacks are listed once.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Minimum consecutive frames before a run is treated as a stack trace
+const MIN_FRAMES: usize = 3;
+
+/// Library names listed in a collapsed-frames line
+const MAX_LABELS: usize = 3;
+
+/// Goroutine ids listed in a grouped goroutine header
+const MAX_GOROUTINE_IDS: usize = 3;
+
+lazy_static! {
+    /// `   3: tokio::runtime::park::wait`
+    static ref RUST_FRAME: Regex = Regex::new(r"^\s*\d+: (\S.*)$").unwrap();
+    /// `             at ./src/main.rs:5:5`
+    static ref RUST_AT: Regex = Regex::new(r"^\s+at (.+?):\d+(?::\d+)?$").unwrap();
+    /// `  File "/app/main.py", line 10, in <module>`
+    static ref PYTHON_FRAME: Regex =
+        Regex::new(r#"^(\s*)File "([^"]+)", line \d+, in (.+)$"#).unwrap();
+    /// pytest `--tb=short`: `tests/test_cart.py:11: in test_total`
+    static ref PYTEST_FRAME: Regex = Regex::new(r"^(\S+\.py):\d+: in (\S+)$").unwrap();
+    /// `    at handler (/app/node_modules/express/lib/router.js:10:5)`
+    static ref NODE_FRAME: Regex = Regex::new(
+        r"^\s+at (?:(?:async )?(.+?) \()?((?:file://)?(?:/|[A-Za-z]:\\|\.{1,2}/|node:|internal/)[^()]*?)(?::\d+){1,2}\)?$"
+    )
+    .unwrap();
+    /// `    at Array.forEach (<anonymous>)`
+    static ref NODE_NATIVE: Regex =
+        Regex::new(r"^\s+at (?:(.+) \()?(?:native|<anonymous>)\)?$").unwrap();
+    /// `	at com.acme.Cart.total(Cart.java:42)`
+    static ref JAVA_FRAME: Regex = Regex::new(
+        r"^\s+at ([\w$.<>/\-]+)\((?:[\w$.\-]+\.(?:java|kt|scala|groovy|clj):\d+|Native Method|Unknown Source)\)$"
+    )
+    .unwrap();
+    /// `net/http.(*conn).serve(0xc000120000)` / `created by main.start in goroutine 1`
+    static ref GO_FUNC: Regex =
+        Regex::new(r"^(?:created by )?(\S+?)(?:\(.*\))?(?: in goroutine \d+)?$").unwrap();
+    /// `   at Shop.Cart.Total() in /src/Shop/Cart.cs:line 14`
+    static ref DOTNET_FRAME: Regex =
+        Regex::new(r"^\s+at ([^\s(]+)\(.*?\)(?: in (.+):line \d+)?$").unwrap();
+    /// `     # ./spec/models/user_spec.rb:12:in 'block (2 levels) in <top (required)>'`
+    static ref RUBY_FRAME: Regex = Regex::new(
+        r"^\s*(?:# )?(<internal:[^>]+>|[^\s:]+\.rb):\d+(?::in [`'](.+)')?$"
+    )
+    .unwrap();
+    /// `rspec-core-3.13.0` → `rspec-core`
+    static ref GEM_VERSION: Regex = Regex::new(r"-(?:\d[\w.]*|[0-9a-f]{7,})$").unwrap();
+    /// `	/usr/local/go/src/net/http/server.go:3086 +0x4cc`
+    static ref GO_LOC: Regex = Regex::new(r"^\t(.+?):\d+(?: \+0x[0-9a-f]+)?$").unwrap();
+    /// `goroutine 18 [chan receive, 2 minutes]:`
+    static ref GOROUTINE: Regex = Regex::new(r"^goroutine (\d+) \[([^\]]+)\]:$").unwrap();
+}
+
+/// Rust crates reported as "std"
+const RUST_STD_CRATES: &[&str] = &[
+    "std",
+    "core",
+    "alloc",
+    "test",
+    "panic_unwind",
+    "rust_begin_unwind",
+    "__rust_begin_short_backtrace",
+    "__rust_end_short_backtrace",
+    "__libc_start_main",
+    "__libc_start_call_main",
+    "_start",
+    "main",
+];
+
+/// .NET namespaces treated as framework / library code
+const DOTNET_LIBRARY_PREFIXES: &[&str] = &[
+    "System.",
+    "Microsoft.",
+    "Xunit.",
+    "NUnit.",
+    "Castle.",
+    "Moq.",
+    "FluentAssertions.",
+];
+
+/// JVM package prefixes treated as library code
+const JAVA_LIBRARY_PREFIXES: &[&str] = &[
+    "java.",
+    "javax.",
+    "jdk.",
+    "sun.",
+    "com.sun.",
+    "jakarta.",
+    "kotlin.",
+    "kotlinx.",
+    "scala.",
+    "org.springframework.",
+    "org.junit.",
+    "org.apache.",
+    "org.hibernate.",
+    "org.gradle.",
+    "worker.org.gradle.",
+    "org.mockito.",
+    "io.netty.",
+    "io.micronaut.",
+    "io.quarkus.",
+    "reactor.",
+    "net.bytebuddy.",
+    "com.google.",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TraceKind {
+    Rust,
+    Python,
+    Pytest,
+    Node,
+    Java,
+    DotNet,
+    Ruby,
+    Go,
+}
+
+impl TraceKind {
+    /// Python prints the innermost frame last, everyone else first
+    fn innermost_last(self) -> bool {
+        matches!(self, TraceKind::Python | TraceKind::Pytest)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Frame {
+    lines: Vec<String>,
+    user: bool,
+    /// Library the frame belongs to ("std", "tokio", "express", ...)
+    label: String,
+    /// Function identity, used to spot repeated traces
+    key: String,
+}
+
+/// Compress every stack trace found in `text`, leaving other lines untouched.
+pub fn compress_stack_traces(text: &str) -> String {
+    compress(text).0
+}
+
+/// True if `text` contains at least one recognizable stack trace.
+pub fn has_stack_trace(text: &str) -> bool {
+    compress(text).1 > 0
+}
+
+fn compress(text: &str) -> (String, usize) {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out: Vec<String> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut traces = 0;
+    let mut i = 0;
+
+    while i < lines.len() {
+        if let Some((dump, consumed)) = compress_goroutine_dump(&lines[i..]) {
+            out.extend(dump);
+            traces += 1;
+            i += consumed;
+            continue;
+        }
+        if let Some((kind, frames, consumed)) = parse_frames(&lines[i..]) {
+            if frames.len() >= MIN_FRAMES {
+                let key = frames
+                    .iter()
+                    .map(|f| f.key.as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let seen_count = seen.entry(key).or_insert(0);
+                *seen_count += 1;
+                if *seen_count > 1 {
+                    out.push(format!(
+                        "{}... same {} frames as trace above",
+                        indent_of(&frames[0].lines[0]),
+                        frames.len()
+                    ));
+                } else {
+                    out.extend(compress_frames(&frames, kind.innermost_last()));
+                }
+                traces += 1;
+                i += consumed;
+                continue;
+            }
+        }
+        out.push(lines[i].to_string());
+        i += 1;
+    }
+
+    let mut result = out.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    (result, traces)
+}
+
+/// Consecutive frames of one kind starting at `lines[0]`
+fn parse_frames(lines: &[&str]) -> Option<(TraceKind, Vec<Frame>, usize)> {
+    let (kind, first, mut consumed) = parse_any_frame(lines)?;
+    let mut frames = vec![first];
+    while consumed < lines.len() {
+        match parse_frame(kind, &lines[consumed..]) {
+            Some((frame, n)) => {
+                frames.push(frame);
+                consumed += n;
+            }
+            None => break,
+        }
+    }
+    Some((kind, frames, consumed))
+}
+
+fn parse_any_frame(lines: &[&str]) -> Option<(TraceKind, Frame, usize)> {
+    [
+        TraceKind::Java,
+        TraceKind::DotNet,
+        TraceKind::Node,
+        TraceKind::Rust,
+        TraceKind::Python,
+        TraceKind::Pytest,
+        TraceKind::Ruby,
+        TraceKind::Go,
+    ]
+    .into_iter()
+    .find_map(|kind| parse_frame(kind, lines).map(|(frame, n)| (kind, frame, n)))
+}
+
+/// One frame of `kind` at `lines[0]` and the number of lines it spans
+fn parse_frame(kind: TraceKind, lines: &[&str]) -> Option<(Frame, usize)> {
+    let line = *lines.first()?;
+    match kind {
+        TraceKind::Rust => {
+            let symbol = RUST_FRAME.captures(line)?[1].to_string();
+            let location = lines
+                .get(1)
+                .and_then(|next| RUST_AT.captures(next))
+                .map(|c| c[1].to_string());
+            let n = if location.is_some() { 2 } else { 1 };
+            let (user, label) = classify_rust(&symbol, location.as_deref());
+            Some((frame(&lines[..n], user, label, symbol), n))
+        }
+        TraceKind::Python => {
+            let caps = PYTHON_FRAME.captures(line)?;
+            let indent = caps[1].len();
+            let path = caps[2].to_string();
+            let key = format!("{}:{}", path, &caps[3]);
+            let n = 1 + lines[1..]
+                .iter()
+                .take_while(|l| {
+                    !l.trim().is_empty() && indent_of(l).len() > indent && !PYTHON_FRAME.is_match(l)
+                })
+                .count();
+            let (user, label) = classify_python(&path);
+            Some((frame(&lines[..n], user, label, key), n))
+        }
+        TraceKind::Pytest => {
+            let caps = PYTEST_FRAME.captures(line)?;
+            let path = caps[1].to_string();
+            let key = format!("{}:{}", path, &caps[2]);
+            let n = 1 + lines[1..]
+                .iter()
+                .take_while(|l| l.starts_with(' ') && !l.trim().is_empty())
+                .count();
+            let (user, label) = classify_python(&path);
+            Some((frame(&lines[..n], user, label, key), n))
+        }
+        TraceKind::Node => {
+            if let Some(caps) = NODE_FRAME.captures(line) {
+                let path = caps[2].to_string();
+                let key = caps
+                    .get(1)
+                    .map(|m| m.as_str().to_string())
+                    .unwrap_or_else(|| path.clone());
+                let (user, label) = classify_node(&path);
+                Some((frame(&lines[..1], user, label, key), 1))
+            } else {
+                let caps = NODE_NATIVE.captures(line)?;
+                let key = caps
+                    .get(1)
+                    .map(|m| m.as_str())
+                    .unwrap_or("<anonymous>")
+                    .to_string();
+                Some((frame(&lines[..1], false, "node".to_string(), key), 1))
+            }
+        }
+        TraceKind::Java => {
+            let method = JAVA_FRAME.captures(line)?[1].to_string();
+            let (user, label) = classify_java(&method);
+            Some((frame(&lines[..1], user, label, method), 1))
+        }
+        TraceKind::DotNet => {
+            let method = DOTNET_FRAME.captures(line)?[1].to_string();
+            let (user, label) = classify_dotnet(&method);
+            Some((frame(&lines[..1], user, label, method), 1))
+        }
+        TraceKind::Ruby => {
+            let caps = RUBY_FRAME.captures(line)?;
+            let path = caps[1].to_string();
+            let key = format!("{}:{}", path, caps.get(2).map_or("", |m| m.as_str()));
+            let (user, label) = classify_ruby(&path);
+            Some((frame(&lines[..1], user, label, key), 1))
+        }
+        TraceKind::Go => {
+            if line.starts_with(char::is_whitespace) || GOROUTINE.is_match(line) {
+                return None;
+            }
+            let func = GO_FUNC.captures(line)?[1].to_string();
+            let path = GO_LOC.captures(lines.get(1)?)?[1].to_string();
+            let (user, label) = classify_go(&path);
+            Some((frame(&lines[..2], user, label, func), 2))
+        }
+    }
+}
+
+fn frame(lines: &[&str], user: bool, label: String, key: String) -> Frame {
+    Frame {
+        lines: lines.iter().map(|l| l.to_string()).collect(),
+        user,
+        label,
+        key,
+    }
+}
+
+fn indent_of(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+fn classify_rust(symbol: &str, location: Option<&str>) -> (bool, String) {
+    let crate_name = symbol
+        .trim_start_matches(['<', '&'])
+        .trim_start_matches("dyn ")
+        .split("::")
+        .next()
+        .unwrap_or(symbol);
+    let label = if RUST_STD_CRATES.contains(&crate_name) || crate_name.starts_with("__") {
+        "std".to_string()
+    } else {
+        crate_name.to_string()
+    };
+    let user = match location {
+        Some(loc) => !["/rustc/", "/.cargo/registry/", "/.cargo/git/", "/library/"]
+            .iter()
+            .any(|marker| loc.contains(marker)),
+        None => label != "std",
+    };
+    (user, label)
+}
+
+fn classify_python(path: &str) -> (bool, String) {
+    for marker in ["site-packages/", "dist-packages/"] {
+        if let Some(idx) = path.rfind(marker) {
+            let rest = &path[idx + marker.len()..];
+            let package = rest.split('/').next().unwrap_or(rest);
+            return (false, package.trim_end_matches(".py").to_string());
+        }
+    }
+    if path.starts_with('<') || path.contains("/lib/python") || path.contains("\\Lib\\") {
+        return (false, "std".to_string());
+    }
+    (true, String::new())
+}
+
+fn classify_node(path: &str) -> (bool, String) {
+    if let Some(idx) = path.rfind("node_modules/") {
+        let mut parts = path[idx + "node_modules/".len()..].split('/');
+        let first = parts.next().unwrap_or("");
+        let package = if first.starts_with('@') {
+            format!("{}/{}", first, parts.next().unwrap_or(""))
+        } else {
+            first.to_string()
+        };
+        return (false, package);
+    }
+    if path.starts_with("node:") || path.starts_with("internal/") {
+        return (false, "node".to_string());
+    }
+    (true, String::new())
+}
+
+fn classify_java(method: &str) -> (bool, String) {
+    match JAVA_LIBRARY_PREFIXES
+        .iter()
+        .find(|p| method.starts_with(*p))
+    {
+        Some(prefix) => {
+            let label = match *prefix {
+                "java." | "javax." | "jdk." | "sun." | "com.sun." => "java".to_string(),
+                _ => prefix
+                    .trim_start_matches("worker.")
+                    .trim_end_matches('.')
+                    .to_string(),
+            };
+            (false, label)
+        }
+        None => (true, String::new()),
+    }
+}
+
+fn classify_dotnet(method: &str) -> (bool, String) {
+    match DOTNET_LIBRARY_PREFIXES
+        .iter()
+        .find(|p| method.starts_with(*p))
+    {
+        Some(prefix) => (false, prefix.trim_end_matches('.').to_string()),
+        None => (true, String::new()),
+    }
+}
+
+fn classify_ruby(path: &str) -> (bool, String) {
+    if let Some(idx) = path.rfind("/gems/") {
+        let dir = path[idx + "/gems/".len()..].split('/').next().unwrap_or("");
+        return (false, GEM_VERSION.replace(dir, "").to_string());
+    }
+    if path.starts_with("<internal:")
+        || path.contains("/lib/ruby/")
+        || path.contains("/rubies/")
+        || path.contains("/rubygems/")
+    {
+        return (false, "ruby".to_string());
+    }
+    (true, String::new())
+}
+
+fn classify_go(path: &str) -> (bool, String) {
+    if let Some(idx) = path.find("/pkg/mod/") {
+        let module = path[idx + "/pkg/mod/".len()..]
+            .split('@')
+            .next()
+            .unwrap_or("");
+        let name = module.rsplit('/').next().unwrap_or(module);
+        return (false, name.to_string());
+    }
+    if path.contains("/go/src/") || path.contains("/libexec/src/") || path.ends_with("_testmain.go")
+    {
+        return (false, "std".to_string());
+    }
+    (true, String::new())
+}
+
+/// Keep user frames, collapse runs of 2+ library frames into one line.
+/// A trace without user frames keeps its innermost frame.
+fn compress_frames(frames: &[Frame], innermost_last: bool) -> Vec<String> {
+    let mut keep: Vec<bool> = frames.iter().map(|f| f.user).collect();
+    if !keep.iter().any(|k| *k) {
+        let innermost = if innermost_last { frames.len() - 1 } else { 0 };
+        keep[innermost] = true;
+    }
+
+    let mut out = Vec::new();
+    let mut run: Vec<&Frame> = Vec::new();
+    for (frame, kept) in frames.iter().zip(&keep) {
+        if *kept {
+            flush_library_run(&mut run, &mut out);
+            out.extend(frame.lines.iter().cloned());
+        } else {
+            run.push(frame);
+        }
+    }
+    flush_library_run(&mut run, &mut out);
+    out
+}
+
+fn flush_library_run(run: &mut Vec<&Frame>, out: &mut Vec<String>) {
+    match run.len() {
+        0 => {}
+        1 => out.extend(run[0].lines.iter().cloned()),
+        n => {
+            let mut labels: Vec<&str> = Vec::new();
+            for frame in run.iter() {
+                if !labels.contains(&frame.label.as_str()) {
+                    labels.push(&frame.label);
+                }
+            }
+            let mut label = labels
+                .iter()
+                .take(MAX_LABELS)
+                .copied()
+                .collect::<Vec<_>>()
+                .join("/");
+            if labels.len() > MAX_LABELS {
+                label.push_str("/…");
+            }
+            out.push(format!(
+                "{}... {} frames in {}",
+                indent_of(&run[0].lines[0]),
+                n,
+                label
+            ));
+        }
+    }
+    run.clear();
+}
+
+/// Group the goroutines of a dump by state and identical stack
+fn compress_goroutine_dump(lines: &[&str]) -> Option<(Vec<String>, usize)> {
+    GOROUTINE.captures(lines.first()?)?;
+
+    struct Group {
+        ids: Vec<String>,
+        state: String,
+        header: String,
+        frames: Vec<Frame>,
+    }
+    let mut groups: Vec<Group> = Vec::new();
+    let mut i = 0;
+
+    while let Some(caps) = lines.get(i).and_then(|l| GOROUTINE.captures(l)) {
+        let id = caps[1].to_string();
+        // "chan receive, 2 minutes" → "chan receive"
+        let state = caps[2].split(',').next().unwrap_or("").to_string();
+        let header = lines[i].to_string();
+        i += 1;
+
+        let mut frames = Vec::new();
+        while let Some((frame, n)) = parse_frame(TraceKind::Go, &lines[i..]) {
+            frames.push(frame);
+            i += n;
+        }
+
+        let key: Vec<&str> = frames.iter().map(|f| f.key.as_str()).collect();
+        let existing = groups.iter_mut().find(|g| {
+            g.state == state
+                && g.frames
+                    .iter()
+                    .map(|f| f.key.as_str())
+                    .eq(key.iter().copied())
+        });
+        match existing {
+            Some(group) => group.ids.push(id),
+            None => groups.push(Group {
+                ids: vec![id],
+                state,
+                header,
+                frames,
+            }),
+        }
+
+        // Blank lines separate goroutines; only consume them if another one follows
+        let blanks = lines[i..]
+            .iter()
+            .take_while(|l| l.trim().is_empty())
+            .count();
+        if lines.get(i + blanks).is_some_and(|l| GOROUTINE.is_match(l)) {
+            i += blanks;
+        } else {
+            break;
+        }
+    }
+
+    let mut out = Vec::new();
+    for (idx, group) in groups.iter().enumerate() {
+        if idx > 0 {
+            out.push(String::new());
+        }
+        if group.ids.len() == 1 {
+            out.push(group.header.clone());
+        } else {
+            let mut ids = group
+                .ids
+                .iter()
+                .take(MAX_GOROUTINE_IDS)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if group.ids.len() > MAX_GOROUTINE_IDS {
+                ids.push_str(", …");
+            }
+            out.push(format!(
+                "goroutine {} [{}] ×{}:",
+                ids,
+                group.state,
+                group.ids.len()
+            ));
+        }
+        if !group.frames.is_empty() {
+            out.extend(compress_frames(&group.frames, false));
+        }
+    }
+    Some((out, i))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_backtrace_collapses_std_frames() {
+        let input = concat!(
+            "thread 'main' panicked at src/main.rs:5:5:\n",
+            "boom\n",
+            "stack backtrace:\n",
+            "   0: rust_begin_unwind\n",
+            "             at /rustc/abc/library/std/src/panicking.rs:645:5\n",
+            "   1: core::panicking::panic_fmt\n",
+            "             at /rustc/abc/library/core/src/panicking.rs:72:14\n",
+            "   2: myapp::checkout\n",
+            "             at ./src/main.rs:5:5\n",
+            "   3: tokio::runtime::park::CachedParkThread::block_on\n",
+            "             at /home/u/.cargo/registry/src/index.crates.io-6f17d22bba15001f/tokio-1.36.0/src/runtime/park.rs:281:63\n",
+            "   4: core::ops::function::FnOnce::call_once\n",
+            "             at /rustc/abc/library/core/src/ops/function.rs:250:5\n",
+            "note: Some details are omitted.\n",
+        );
+        let expected = concat!(
+            "thread 'main' panicked at src/main.rs:5:5:\n",
+            "boom\n",
+            "stack backtrace:\n",
+            "   ... 2 frames in std\n",
+            "   2: myapp::checkout\n",
+            "             at ./src/main.rs:5:5\n",
+            "   ... 2 frames in tokio/std\n",
+            "note: Some details are omitted.\n",
+        );
+        assert_eq!(compress_stack_traces(input), expected);
+    }
+
+    #[test]
+    fn test_python_traceback_keeps_user_frames() {
+        let input = concat!(
+            "Traceback (most recent call last):\n",
+            "  File \"/app/main.py\", line 10, in <module>\n",
+            "    main()\n",
+            "  File \"/usr/lib/python3.11/site-packages/requests/api.py\", line 59, in request\n",
+            "    return session.request(method=method, url=url, **kwargs)\n",
+            "  File \"/usr/lib/python3.11/site-packages/requests/sessions.py\", line 589, in request\n",
+            "    resp = self.send(prep, **send_kwargs)\n",
+            "  File \"/usr/lib/python3.11/socket.py\", line 962, in getaddrinfo\n",
+            "    for res in _socket.getaddrinfo(host, port, family, type, proto, flags):\n",
+            "socket.gaierror: [Errno -2] Name or service not known",
+        );
+        let expected = concat!(
+            "Traceback (most recent call last):\n",
+            "  File \"/app/main.py\", line 10, in <module>\n",
+            "    main()\n",
+            "  ... 3 frames in requests/std\n",
+            "socket.gaierror: [Errno -2] Name or service not known",
+        );
+        assert_eq!(compress_stack_traces(input), expected);
+    }
+
+    #[test]
+    fn test_node_stack() {
+        let input = concat!(
+            "TypeError: Cannot read properties of undefined (reading 'id')\n",
+            "    at getUser (/app/src/users.js:12:18)\n",
+            "    at Layer.handle [as handle_request] (/app/node_modules/express/lib/router/layer.js:95:5)\n",
+            "    at next (/app/node_modules/express/lib/router/route.js:149:13)\n",
+            "    at Array.forEach (<anonymous>)\n",
+            "    at process.processTicksAndRejections (node:internal/process/task_queues:95:5)\n",
+        );
+        let expected = concat!(
+            "TypeError: Cannot read properties of undefined (reading 'id')\n",
+            "    at getUser (/app/src/users.js:12:18)\n",
+            "    ... 4 frames in express/node\n",
+        );
+        assert_eq!(compress_stack_traces(input), expected);
+    }
+
+    #[test]
+    fn test_java_caused_by_chain_and_dedupe() {
+        let trace = concat!(
+            "\tat org.springframework.web.servlet.FrameworkServlet.service(FrameworkServlet.java:883)\n",
+            "\tat com.acme.cart.CartController.total(CartController.java:42)\n",
+            "\tat jdk.internal.reflect.NativeMethodAccessorImpl.invoke0(Native Method)\n",
+            "\tat java.base/java.lang.reflect.Method.invoke(Method.java:568)\n",
+        );
+        let input = format!(
+            "java.lang.IllegalStateException: empty cart\n{}Caused by: java.io.IOException: gone\n{}\t... 12 more\n",
+            trace, trace
+        );
+        let expected = concat!(
+            "java.lang.IllegalStateException: empty cart\n",
+            "\tat org.springframework.web.servlet.FrameworkServlet.service(FrameworkServlet.java:883)\n",
+            "\tat com.acme.cart.CartController.total(CartController.java:42)\n",
+            "\t... 2 frames in java\n",
+            "Caused by: java.io.IOException: gone\n",
+            "\t... same 4 frames as trace above\n",
+            "\t... 12 more\n",
+        );
+        assert_eq!(compress_stack_traces(&input), expected);
+    }
+
+    #[test]
+    fn test_dotnet_stack_trace() {
+        let input = concat!(
+            "Assert.Equal() Failure: Values differ\n",
+            "   at Shop.Tests.CartTests.Total_AppliesDiscount() in /src/Shop.Tests/CartTests.cs:line 14\n",
+            "   at System.RuntimeMethodHandle.InvokeMethod(Object target, Void** arguments, Signature sig, Boolean isConstructor)\n",
+            "   at System.Reflection.MethodBaseInvoker.InvokeWithNoArgs(Object obj, BindingFlags invokeAttr)\n",
+            "   at Xunit.Sdk.TestInvoker`1.CallTestMethod(Object testClassInstance)\n",
+        );
+        let expected = concat!(
+            "Assert.Equal() Failure: Values differ\n",
+            "   at Shop.Tests.CartTests.Total_AppliesDiscount() in /src/Shop.Tests/CartTests.cs:line 14\n",
+            "   ... 3 frames in System/Xunit\n",
+        );
+        assert_eq!(compress_stack_traces(input), expected);
+    }
+
+    #[test]
+    fn test_ruby_backtrace_collapses_gem_frames() {
+        let input = concat!(
+            "     Failure/Error: raise ArgumentError, \"no items\"\n",
+            "     # ./app/models/cart.rb:14:in 'Cart#total'\n",
+            "     # ./spec/models/cart_spec.rb:9:in 'block (2 levels) in <top (required)>'\n",
+            "     # /usr/local/bundle/gems/rspec-core-3.13.0/lib/rspec/core/example.rb:263:in 'instance_exec'\n",
+            "     # /usr/local/bundle/gems/rspec-core-3.13.0/lib/rspec/core/example.rb:263:in 'block in run'\n",
+            "     # /usr/local/bundle/gems/activesupport-7.1.3/lib/active_support/callbacks.rb:121:in `block in run_callbacks'\n",
+            "     # <internal:kernel>:187:in 'Kernel#loop'\n",
+        );
+        let expected = concat!(
+            "     Failure/Error: raise ArgumentError, \"no items\"\n",
+            "     # ./app/models/cart.rb:14:in 'Cart#total'\n",
+            "     # ./spec/models/cart_spec.rb:9:in 'block (2 levels) in <top (required)>'\n",
+            "     ... 4 frames in rspec-core/activesupport/ruby\n",
+        );
+        assert_eq!(compress_stack_traces(input), expected);
+    }
+
+    #[test]
+    fn test_go_goroutine_dump_grouped() {
+        let waiting = concat!(
+            "net/http.(*conn).serve(0xc000120000, {0x7a2b40, 0xc000110000})\n",
+            "\t/usr/local/go/src/net/http/server.go:2009 +0x5f4\n",
+            "created by net/http.(*Server).Serve in goroutine 1\n",
+            "\t/usr/local/go/src/net/http/server.go:3086 +0x4cc\n",
+        );
+        let input = format!(
+            concat!(
+                "panic: nil map\n",
+                "\n",
+                "goroutine 1 [running]:\n",
+                "main.handle(...)\n",
+                "\t/app/main.go:21 +0x1d\n",
+                "github.com/gin-gonic/gin.(*Context).Next(0xc0001)\n",
+                "\t/root/go/pkg/mod/github.com/gin-gonic/gin@v1.9.1/context.go:174 +0x2b\n",
+                "github.com/gin-gonic/gin.(*Engine).handle(0xc0002)\n",
+                "\t/root/go/pkg/mod/github.com/gin-gonic/gin@v1.9.1/gin.go:620 +0x66\n",
+                "\n",
+                "goroutine 18 [IO wait, 2 minutes]:\n{}",
+                "\n",
+                "goroutine 19 [IO wait]:\n{}",
+                "\n",
+                "goroutine 20 [IO wait]:\n{}",
+                "exit status 2\n",
+            ),
+            waiting, waiting, waiting
+        );
+        let expected = concat!(
+            "panic: nil map\n",
+            "\n",
+            "goroutine 1 [running]:\n",
+            "main.handle(...)\n",
+            "\t/app/main.go:21 +0x1d\n",
+            "... 2 frames in gin\n",
+            "\n",
+            "goroutine 18, 19, 20 [IO wait] ×3:\n",
+            "net/http.(*conn).serve(0xc000120000, {0x7a2b40, 0xc000110000})\n",
+            "\t/usr/local/go/src/net/http/server.go:2009 +0x5f4\n",
+            "created by net/http.(*Server).Serve in goroutine 1\n",
+            "\t/usr/local/go/src/net/http/server.go:3086 +0x4cc\n",
+            "exit status 2\n",
+        );
+        assert_eq!(compress_stack_traces(&input), expected);
+        assert!(has_stack_trace(&input));
+    }
+
+    #[test]
+    fn test_short_or_unrelated_output_unchanged() {
+        let input = "error: build failed\n  1: not a frame\n    at least this is prose\n";
+        assert_eq!(compress_stack_traces(input), input);
+        assert!(!has_stack_trace(input));
+    }
+}
@@ -0,0 +1,621 @@
+//! Enclosing-symbol resolution for source files.
+//!
+//! Builds a heuristic outline (functions, impls, classes, modules) of a file
+//! using per-language regexes plus brace, indent or `end` matching to find
+//! where each body stops. Used to label grep hits and diff hunks with the
+//! function they land in, without pulling in a real parser.
+
+use crate::filter::Language;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Max lines scanned after a definition to find its opening brace.
+const MAX_SIGNATURE_LINES: usize = 20;
+
+/// A definition found in a source file. Line numbers are 1-based and inclusive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// Source keyword (or pseudo-keyword) of the definition: fn, impl, def, class, method...
+    pub kind: &'static str,
+    pub name: String,
+    /// Name qualified by its container (`Parser::parse_line`, `Server.Start`)
+    pub qualified: String,
+    /// First line of the definition, trimmed
+    pub signature: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Symbol {
+    /// Short human label, e.g. "fn Parser::parse_line"
+    pub fn label(&self) -> String {
+        format!("{} {}", self.kind, self.qualified)
+    }
+
+    /// Signature without the trailing body opener (`{`, `:`), for one-line display
+    pub fn signature_line(&self) -> &str {
+        self.signature
+            .trim_end()
+            .trim_end_matches('{')
+            .trim_end_matches(" do")
+            .trim_end()
+    }
+
+    fn is_container(&self) -> bool {
+        matches!(
+            self.kind,
+            "impl"
+                | "class"
+                | "struct"
+                | "trait"
+                | "mod"
+                | "module"
+                | "interface"
+                | "namespace"
+                | "enum"
+                | "union"
+                | "record"
+        )
+    }
+
+    fn contains(&self, other: &Symbol) -> bool {
+        self.start <= other.start && self.end >= other.end && self != other
+    }
+}
+
+struct DefPattern {
+    re: Regex,
+    /// Kind used when the regex has no `kw` group
+    kind: &'static str,
+}
+
+fn def(re: &str, kind: &'static str) -> DefPattern {
+    DefPattern {
+        re: Regex::new(re).unwrap(),
+        kind,
+    }
+}
+
+lazy_static! {
+    static ref RUST_DEFS: Vec<DefPattern> = vec![
+        def(
+            r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:default\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?(?P<kw>fn)\s+(?P<name>\w+)"#,
+            "fn",
+        ),
+        def(
+            r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?P<kw>struct|enum|trait|mod|union)\s+(?P<name>\w+)",
+            "struct",
+        ),
+        def(
+            r"^\s*(?:unsafe\s+)?(?P<kw>impl)\b(?:\s*<[^{]*?>)?\s+(?:[^{]+?\s+for\s+)?(?P<name>[\w:]+)",
+            "impl",
+        ),
+    ];
+    static ref PYTHON_DEFS: Vec<DefPattern> = vec![def(
+        r"^\s*(?:async\s+)?(?P<kw>def|class)\s+(?P<name>\w+)",
+        "def",
+    )];
+    static ref JS_DEFS: Vec<DefPattern> = vec![
+        def(
+            r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?(?P<kw>function)\s*\*?\s*(?P<name>[\w$]+)",
+            "function",
+        ),
+        def(
+            r"^\s*(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?P<kw>class|interface|enum|namespace)\s+(?P<name>[\w$]+)",
+            "class",
+        ),
+        def(
+            r"^\s*(?:export\s+)?(?:const|let|var)\s+(?P<name>[\w$]+)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*(?::[^=]+)?=>|[\w$]+\s*=>)",
+            "function",
+        ),
+        def(
+            r"^\s+(?:(?:public|private|protected|static|async|readonly|override|abstract|get|set)\s+)*(?P<name>[\w$]+)\s*(?:<[^>]*>)?\s*\([^)]*\)\s*(?::\s*[^{;]+)?\{\s*$",
+            "method",
+        ),
+    ];
+    static ref GO_DEFS: Vec<DefPattern> = vec![
+        def(
+            r"^func\s+(?:\(\s*(?:\w+\s+)?\*?(?P<recv>\w+)(?:\[[^\]]*\])?\s*\)\s*)?(?P<name>\w+)",
+            "func",
+        ),
+        def(
+            r"^type\s+(?P<name>\w+)(?:\[[^\]]*\])?\s+(?P<kw>struct|interface)",
+            "struct"
+        ),
+    ];
+    static ref JAVA_DEFS: Vec<DefPattern> = vec![
+        def(
+            r"^\s*(?:(?:public|private|protected|static|final|abstract|sealed|non-sealed)\s+)*(?:@\w+\s+)*(?P<kw>class|interface|enum|record)\s+(?P<name>\w+)",
+            "class",
+        ),
+        def(
+            r"^\s*(?:@\w+(?:\([^)]*\))?\s+)*(?:(?:public|private|protected|static|final|abstract|synchronized|native|default)\s+)*(?:<[^>]+>\s+)?[\w<>\[\],.?]+\s+(?P<name>\w+)\s*\([^;]*$",
+            "method",
+        ),
+    ];
+    static ref C_DEFS: Vec<DefPattern> = vec![
+        def(
+            r"^\s*(?:template\s*<[^>]*>\s*)?(?:typedef\s+)?(?P<kw>class|struct|namespace|union|enum)\s+(?:class\s+)?(?P<name>\w+)[^;]*$",
+            "struct",
+        ),
+        def(
+            r"^\s*(?:[\w:*&<>,]+[\s*&]+)+(?P<name>~?[\w:]+)\s*\([^;]*$",
+            "fn",
+        ),
+    ];
+    static ref RUBY_DEFS: Vec<DefPattern> = vec![
+        def(
+            r"^\s*(?P<kw>def)\s+(?:self\.)?(?P<name>[\w?!=\[\]]+)",
+            "def"
+        ),
+        def(r"^\s*(?P<kw>class|module)\s+(?P<name>[\w:]+)", "class"),
+    ];
+    static ref SHELL_DEFS: Vec<DefPattern> = vec![
+        def(r"^\s*function\s+(?P<name>[\w:-]+)", "function"),
+        def(r"^\s*(?P<name>[\w:-]+)\s*\(\)", "function"),
+    ];
+}
+
+/// Words that look like a call/definition to the method regexes but are control flow
+const NOT_A_NAME: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "return", "function", "else", "new", "do", "try",
+    "sizeof", "throw", "delete", "await", "typeof", "until", "unless", "elif", "with",
+];
+
+fn defs_for(lang: Language) -> &'static [DefPattern] {
+    match lang {
+        Language::Rust => &RUST_DEFS,
+        Language::Python => &PYTHON_DEFS,
+        Language::JavaScript | Language::TypeScript => &JS_DEFS,
+        Language::Go => &GO_DEFS,
+        Language::Java => &JAVA_DEFS,
+        Language::C | Language::Cpp => &C_DEFS,
+        Language::Ruby => &RUBY_DEFS,
+        Language::Shell => &SHELL_DEFS,
+        Language::Data | Language::Unknown => &[],
+    }
+}
+
+fn static_kind(kw: &str, fallback: &'static str) -> &'static str {
+    match kw {
+        "fn" => "fn",
+        "def" => "def",
+        "func" => "func",
+        "function" => "function",
+        "class" => "class",
+        "struct" => "struct",
+        "enum" => "enum",
+        "trait" => "trait",
+        "impl" => "impl",
+        "mod" => "mod",
+        "module" => "module",
+        "interface" => "interface",
+        "namespace" => "namespace",
+        "union" => "union",
+        "record" => "record",
+        _ => fallback,
+    }
+}
+
+fn path_separator(lang: Language, sym: &Symbol) -> &'static str {
+    match lang {
+        Language::Rust | Language::Cpp | Language::C => "::",
+        Language::Ruby if sym.is_container() => "::",
+        Language::Ruby => "#",
+        _ => ".",
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Build the outline of `content`. Symbols are sorted by start line.
+pub fn outline(content: &str, lang: Language) -> Vec<Symbol> {
+    let defs = defs_for(lang);
+    if defs.is_empty() {
+        return Vec::new();
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    // (symbol, explicit parent from Go receivers / C++ `Foo::bar` names)
+    let mut found: Vec<(Symbol, Option<String>)> = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || (trimmed.starts_with('#') && lang != Language::Shell) {
+            continue;
+        }
+
+        let Some((pattern, caps)) = defs
+            .iter()
+            .find_map(|p| p.re.captures(line).map(|c| (p, c)))
+        else {
+            continue;
+        };
+
+        let raw_name = caps.name("name").map(|m| m.as_str()).unwrap_or("");
+        if raw_name.is_empty() || NOT_A_NAME.contains(&raw_name) {
+            continue;
+        }
+        // Keyword-less patterns (methods, C functions) must not start with a statement keyword
+        let first_word = trimmed.split(|c: char| !c.is_alphanumeric()).next();
+        if caps.name("kw").is_none() && first_word.is_some_and(|w| NOT_A_NAME.contains(&w)) {
+            continue;
+        }
+        let kind = caps
+            .name("kw")
+            .map(|m| static_kind(m.as_str(), pattern.kind))
+            .unwrap_or(pattern.kind);
+
+        // `impl fmt::Display for Foo` / `void Foo::bar()` → last segment is the name
+        let (explicit_parent, name) = match raw_name.rsplit_once("::") {
+            Some((owner, last)) if kind != "impl" && lang != Language::Ruby => {
+                (Some(owner.to_string()), last.to_string())
+            }
+            Some((_, last)) => (None, last.to_string()),
+            None => (None, raw_name.to_string()),
+        };
+        let explicit_parent = caps
+            .name("recv")
+            .map(|m| m.as_str().to_string())
+            .or(explicit_parent);
+
+        let end = match lang {
+            Language::Python => indent_block_end(&lines, idx),
+            Language::Ruby => ruby_block_end(&lines, idx),
+            _ => brace_block_end(&lines, idx, lang),
+        };
+
+        found.push((
+            Symbol {
+                kind,
+                qualified: name.clone(),
+                name,
+                signature: line.trim().to_string(),
+                start: idx + 1,
+                end: end + 1,
+            },
+            explicit_parent,
+        ));
+    }
+
+    let containers: Vec<Symbol> = found
+        .iter()
+        .map(|(s, _)| s)
+        .filter(|s| s.is_container())
+        .cloned()
+        .collect();
+
+    found
+        .into_iter()
+        .map(|(mut sym, explicit_parent)| {
+            let parent = explicit_parent.or_else(|| {
+                containers
+                    .iter()
+                    .filter(|c| c.contains(&sym))
+                    .min_by_key(|c| c.end - c.start)
+                    .map(|c| c.name.clone())
+            });
+            if let Some(parent) = parent {
+                let sep = path_separator(lang, &sym);
+                sym.qualified = format!("{}{}{}", parent, sep, sym.name);
+            }
+            sym
+        })
+        .collect()
+}
+
+/// Innermost symbol whose body contains `line` (1-based)
+pub fn enclosing(symbols: &[Symbol], line: usize) -> Option<&Symbol> {
+    symbols
+        .iter()
+        .filter(|s| s.start <= line && line <= s.end)
+        .min_by_key(|s| (s.end - s.start, std::cmp::Reverse(s.start)))
+}
+
+/// End line (0-based) of a brace-delimited body starting at `start`.
+/// Declarations without a body (`fn foo();`, `struct Unit;`) end on their own line.
+fn brace_block_end(lines: &[&str], start: usize, lang: Language) -> usize {
+    let comment_char = if lang == Language::Shell { "#" } else { "//" };
+    let mut depth: i32 = 0;
+    let mut opened = false;
+
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        if !opened && idx > start + MAX_SIGNATURE_LINES {
+            break;
+        }
+
+        let code = strip_strings(line, lang);
+        let code = code.split(comment_char).next().unwrap_or("");
+        for ch in code.chars() {
+            match ch {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => {
+                    depth -= 1;
+                    if opened && depth <= 0 {
+                        return idx;
+                    }
+                }
+                ';' if !opened && depth == 0 => return idx,
+                _ => {}
+            }
+        }
+    }
+
+    start
+}
+
+/// Remove the contents of string and char literals so braces inside them are not counted.
+/// Rust uses `'` for lifetimes as well, so only `'x'` / `'\x'` char literals are skipped there.
+fn strip_strings(line: &str, lang: Language) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut out = String::with_capacity(line.len());
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if let Some(q) = quote {
+            if ch == '\\' {
+                i += 2;
+                continue;
+            }
+            if ch == q {
+                quote = None;
+                out.push(ch);
+            }
+            i += 1;
+            continue;
+        }
+
+        match ch {
+            '"' | '`' => quote = Some(ch),
+            '\'' if lang != Language::Rust => quote = Some(ch),
+            '\'' => {
+                let close = if chars.get(i + 1) == Some(&'\\') {
+                    i + 3
+                } else {
+                    i + 2
+                };
+                if chars.get(close) == Some(&'\'') {
+                    out.push_str("' '");
+                    i = close + 1;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        out.push(ch);
+        i += 1;
+    }
+    out
+}
+
+/// End line (0-based) of an indentation-delimited body (Python)
+fn indent_block_end(lines: &[&str], start: usize) -> usize {
+    let base = indent_of(lines[start]);
+
+    // Skip over a multi-line signature: `def foo(\n    a,\n):`
+    let mut sig_end = start;
+    let mut parens: i32 = 0;
+    for (idx, line) in lines.iter().enumerate().skip(start) {
+        let code = strip_strings(line, Language::Python);
+        parens += code.matches(['(', '[']).count() as i32;
+        parens -= code.matches([')', ']']).count() as i32;
+        sig_end = idx;
+        if parens <= 0 || idx > start + MAX_SIGNATURE_LINES {
+            break;
+        }
+    }
+
+    let mut end = sig_end;
+    for (idx, line) in lines.iter().enumerate().skip(sig_end + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent_of(line) <= base {
+            break;
+        }
+        end = idx;
+    }
+    end
+}
+
+/// End line (0-based) of a Ruby `def`/`class`/`module` body closed by `end`
+fn ruby_block_end(lines: &[&str], start: usize) -> usize {
+    let base = indent_of(lines[start]);
+    let opener = lines[start].trim();
+
+    // One-liners: `def foo = 42`, `def foo; end`
+    if opener.ends_with(" end") || opener.ends_with(";end") || opener.contains(") =") {
+        return start;
+    }
+
+    for (idx, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if indent_of(line) == base && (trimmed == "end" || trimmed.starts_with("end ")) {
+            return idx;
+        }
+        if indent_of(line) < base {
+            return idx.saturating_sub(1).max(start);
+        }
+    }
+    lines.len().saturating_sub(1).max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels_at(content: &str, lang: Language, line: usize) -> Option<String> {
+        let symbols = outline(content, lang);
+        enclosing(&symbols, line).map(|s| s.label())
+    }
+
+    #[test]
+    fn test_rust_fn_in_impl() {
+        let code = r#"use std::fmt;
+
+pub struct Parser {
+    buf: String,
+}
+
+impl Parser {
+    pub fn parse_line(&self, line: &str) -> Option<u32> {
+        let x = "{ not a brace";
+        line.parse().ok()
+    }
+}
+
+fn helper() {}
+"#;
+        assert_eq!(
+            labels_at(code, Language::Rust, 10),
+            Some("fn Parser::parse_line".to_string())
+        );
+        assert_eq!(
+            labels_at(code, Language::Rust, 4),
+            Some("struct Parser".to_string())
+        );
+        assert_eq!(
+            labels_at(code, Language::Rust, 14),
+            Some("fn helper".to_string())
+        );
+        assert_eq!(labels_at(code, Language::Rust, 1), None);
+    }
+
+    #[test]
+    fn test_rust_trait_impl_uses_type_name() {
+        let code = "impl<T: Into<String>> fmt::Display for Wrapper<T> {\n    fn fmt(&self) {\n        todo!()\n    }\n}\n";
+        assert_eq!(
+            labels_at(code, Language::Rust, 3),
+            Some("fn Wrapper::fmt".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rust_declaration_without_body() {
+        let code = "trait Run {\n    fn run(&self);\n    fn stop(&self) {\n        ()\n    }\n}\n";
+        let symbols = outline(code, Language::Rust);
+        let run = symbols.iter().find(|s| s.name == "run").unwrap();
+        assert_eq!((run.start, run.end), (2, 2));
+        assert_eq!(
+            labels_at(code, Language::Rust, 4),
+            Some("fn Run::stop".to_string())
+        );
+    }
+
+    #[test]
+    fn test_python_indent_blocks() {
+        let code = r#"import os
+
+class Loader:
+    def load(
+        self,
+        path,
+    ):
+        data = open(path)
+
+        return data
+
+def main():
+    Loader().load("x")
+"#;
+        assert_eq!(
+            labels_at(code, Language::Python, 10),
+            Some("def Loader.load".to_string())
+        );
+        assert_eq!(
+            labels_at(code, Language::Python, 13),
+            Some("def main".to_string())
+        );
+        assert_eq!(labels_at(code, Language::Python, 1), None);
+    }
+
+    #[test]
+    fn test_go_method_receiver_as_parent() {
+        let code = "package main\n\nfunc (s *Server) Start() error {\n\treturn nil\n}\n";
+        assert_eq!(
+            labels_at(code, Language::Go, 4),
+            Some("func Server.Start".to_string())
+        );
+    }
+
+    #[test]
+    fn test_typescript_class_method_and_arrow() {
+        let code = r#"export class Api {
+  async fetchUser(id: string): Promise<User> {
+    if (id) {
+      return get(id);
+    }
+  }
+}
+
+export const handler = async (req) => {
+  return 1;
+};
+"#;
+        assert_eq!(
+            labels_at(code, Language::TypeScript, 4),
+            Some("method Api.fetchUser".to_string())
+        );
+        assert_eq!(
+            labels_at(code, Language::TypeScript, 10),
+            Some("function handler".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ruby_def_end() {
+        let code = "module Billing\n  class Invoice\n    def total\n      items.sum\n    end\n  end\nend\n";
+        assert_eq!(
+            labels_at(code, Language::Ruby, 4),
+            Some("def Invoice#total".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cpp_qualified_definition() {
+        let code = "#include <x>\n\nint Widget::draw(int x) {\n    return x;\n}\n";
+        assert_eq!(
+            labels_at(code, Language::Cpp, 4),
+            Some("fn Widget::draw".to_string())
+        );
+    }
+
+    #[test]
+    fn test_control_flow_not_a_symbol() {
+        let code = "class A {\n  run() {\n    if (x) {\n      y();\n    }\n  }\n}\n";
+        let symbols = outline(code, Language::JavaScript);
+        assert!(symbols.iter().all(|s| s.name != "if"));
+    }
+
+    #[test]
+    fn test_data_has_no_symbols() {
+        assert!(outline("{\"a\": 1}", Language::Data).is_empty());
+    }
+
+    #[test]
+    fn test_char_literal_braces_ignored() {
+        let code = "impl A {\n    fn open(&self) -> char {\n        '{'\n    }\n}\n\nfn after<'a>(s: &'a str) {\n}\n";
+        let symbols = outline(code, Language::Rust);
+        let imp = symbols.iter().find(|s| s.kind == "impl").unwrap();
+        assert_eq!((imp.start, imp.end), (1, 5));
+        let after = symbols.iter().find(|s| s.name == "after").unwrap();
+        assert_eq!((after.start, after.end), (7, 8));
+    }
+
+    #[test]
+    fn test_signature_line_strips_opener() {
+        let code = "fn main() {\n}\n";
+        let symbols = outline(code, Language::Rust);
+        assert_eq!(symbols[0].signature_line(), "fn main()");
+    }
+}
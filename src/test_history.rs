@@ -0,0 +1,566 @@
+//! Per-test outcome history for flaky and newly broken test detection.
+//!
+//! Test commands (`rtk cargo test`, `rtk pytest`, `rtk go test`, ...) record
+//! which tests failed in each run. When a run fails, the compact output gets a
+//! short "History:" section built from the last runs of the same runner in the
+//! same project with the same test selection (subset runs are compared only
+//! with each other). `rtk gain --tests` lists the flakiest and newly broken tests.
+
+use crate::tracking::{detect_project_root, QueryScope, TestRunRecord, Tracker};
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::process::Command;
+
+/// Runs of the same runner considered when classifying a test
+const HISTORY_WINDOW: usize = 10;
+
+/// Max failing tests annotated in one run
+const MAX_ANNOTATED: usize = 10;
+
+/// Flags that change how results are shown, not which tests run
+const DISPLAY_FLAGS: &[&str] = &[
+    "-v",
+    "-vv",
+    "-vvv",
+    "--verbose",
+    "-q",
+    "--quiet",
+    "-s",
+    "--nocapture",
+    "--no-capture",
+    "--show-output",
+    "--no-color",
+    "-json",
+    "--json",
+];
+
+/// Display flags that take a value (`--color always` or `--color=always`)
+const DISPLAY_FLAGS_WITH_VALUE: &[&str] = &[
+    "--color",
+    "--colors",
+    "--test-threads",
+    "--tb",
+    "--durations",
+    "--format",
+    "--reporter",
+    "--message-format",
+    "--junitxml",
+    "--junit-xml",
+    "--logger",
+];
+
+/// Display flags with a value that only pytest has (`-r fE`; cargo's `-r` is `--release`)
+const PYTEST_DISPLAY_FLAGS_WITH_VALUE: &[&str] = &["-r"];
+
+lazy_static! {
+    static ref CARGO_FAILED: Regex = Regex::new(r"^test (.+?) \.\.\. FAILED$").unwrap();
+    static ref NEXTEST_FAILED: Regex =
+        Regex::new(r"^\s*FAIL \[[^\]]*\]\s+(?:\(\d+/\d+\)\s+)?(.+?)\s*$").unwrap();
+    static ref PYTEST_FAILED: Regex = Regex::new(r"^(?:FAILED|ERROR) (\S+)").unwrap();
+    static ref PYTEST_SUMMARY: Regex =
+        Regex::new(r"\b\d+ (?:passed|failed|errors?)\b.* in [\d.]+s").unwrap();
+}
+
+/// How a test behaved across its recent runs
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestTrend {
+    /// Failures interleaved with passes
+    Flaky { failed: usize, runs: usize },
+    /// Failing in the latest runs after passing at `since`
+    NewFailure { since: String, streak: usize },
+}
+
+/// Failed test names from `cargo test` output, or None if no test ran.
+pub fn cargo_failed_tests(output: &str) -> Option<Vec<String>> {
+    if !output.lines().any(|l| l.starts_with("test result:")) {
+        return None;
+    }
+    Some(collect_unique(output.lines().filter_map(|l| {
+        CARGO_FAILED
+            .captures(l.trim_end())
+            .map(|c| c[1].to_string())
+    })))
+}
+
+/// Failed test names from `cargo nextest run` output, or None if no test ran.
+pub fn nextest_failed_tests(output: &str) -> Option<Vec<String>> {
+    if !output
+        .lines()
+        .any(|l| l.trim_start().starts_with("Summary ["))
+    {
+        return None;
+    }
+    Some(collect_unique(output.lines().filter_map(|l| {
+        NEXTEST_FAILED.captures(l).map(|c| c[1].to_string())
+    })))
+}
+
+/// Failed test ids from pytest output, or None if no test ran.
+pub fn pytest_failed_tests(output: &str) -> Option<Vec<String>> {
+    if !output.lines().any(|l| PYTEST_SUMMARY.is_match(l)) {
+        return None;
+    }
+    Some(collect_unique(output.lines().filter_map(|l| {
+        PYTEST_FAILED
+            .captures(l.trim())
+            .map(|c| c[1].to_string())
+            .filter(|id| id.contains("::"))
+    })))
+}
+
+/// Failed tests from `go test -json` output as `package.TestName`, or None if no test ran.
+pub fn go_failed_tests(output: &str) -> Option<Vec<String>> {
+    let mut ran = false;
+    let mut failed = Vec::new();
+    for line in output.lines() {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let (Some(action), Some(test)) = (event["Action"].as_str(), event["Test"].as_str()) else {
+            continue;
+        };
+        match action {
+            "pass" | "skip" => ran = true,
+            "fail" => {
+                ran = true;
+                let package = event["Package"].as_str().unwrap_or_default();
+                failed.push(format!("{}.{}", package, test));
+            }
+            _ => {}
+        }
+    }
+    ran.then(|| collect_unique(failed.into_iter()))
+}
+
+/// Test-selecting part of a runner's arguments (filters, paths, `-k`, `--tests`).
+///
+/// Only runs with the same selection are compared: a test missing from a subset
+/// run did not pass, it simply did not run.
+pub fn selection_key(runner: &str, args: &[String]) -> String {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut skip_value = false;
+    for arg in args {
+        if skip_value {
+            skip_value = false;
+            continue;
+        }
+        let flag = arg.split_once('=').map_or(arg.as_str(), |(f, _)| f);
+        if arg == "--" || DISPLAY_FLAGS.contains(&arg.as_str()) {
+            continue;
+        }
+        if DISPLAY_FLAGS_WITH_VALUE.contains(&flag)
+            || (runner == "pytest" && PYTEST_DISPLAY_FLAGS_WITH_VALUE.contains(&flag))
+        {
+            skip_value = !arg.contains('=');
+            continue;
+        }
+        kept.push(arg);
+    }
+    kept.join(" ")
+}
+
+fn collect_unique(names: impl Iterator<Item = String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    names.filter(|n| seen.insert(n.clone())).collect()
+}
+
+/// Record a test run and append history notes for its failing tests.
+///
+/// `args` are the runner arguments; history is only compared across runs with the
+/// same [`selection_key`]. `failed` is None when the output didn't contain a test
+/// run (e.g. a compile error), in which case nothing is recorded. Database errors
+/// are ignored so history never gets in the way of the command itself.
+pub fn record_and_annotate(
+    runner: &str,
+    args: &[String],
+    failed: Option<Vec<String>>,
+    filtered: &mut String,
+) {
+    let Some(failed) = failed else {
+        return;
+    };
+    let Ok(tracker) = Tracker::new() else {
+        return;
+    };
+    let project = detect_project_root();
+    let selection = selection_key(runner, args);
+    if tracker
+        .record_test_run(runner, &current_commit(), &selection, &failed, &project)
+        .is_err()
+        || failed.is_empty()
+    {
+        return;
+    }
+    let Ok(runs) = tracker.get_test_runs(
+        &QueryScope::Project(project),
+        Some(runner),
+        Some(&selection),
+        HISTORY_WINDOW,
+    ) else {
+        return;
+    };
+    if let Some(notes) = format_history_notes(&failed, &runs) {
+        filtered.push_str(&notes);
+    }
+}
+
+fn current_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Classify a test from its outcomes in `runs` (newest first).
+fn classify(test: &str, runs: &[TestRunRecord]) -> Option<TestTrend> {
+    let outcomes: Vec<bool> = runs
+        .iter()
+        .map(|r| r.failed_tests.iter().any(|t| t == test))
+        .collect();
+    let flips = outcomes.windows(2).filter(|w| w[0] != w[1]).count();
+    match flips {
+        0 => None,
+        1 if outcomes[0] => {
+            let streak = outcomes.iter().take_while(|f| **f).count();
+            Some(TestTrend::NewFailure {
+                since: runs[streak].commit_sha.clone(),
+                streak,
+            })
+        }
+        1 => None,
+        _ => Some(TestTrend::Flaky {
+            failed: outcomes.iter().filter(|f| **f).count(),
+            runs: outcomes.len(),
+        }),
+    }
+}
+
+fn describe(trend: &TestTrend) -> String {
+    match trend {
+        TestTrend::Flaky { failed, runs } => {
+            format!("flaky: failed {} of last {} runs", failed, runs)
+        }
+        TestTrend::NewFailure { since, streak } => {
+            let since = if since.is_empty() {
+                "last passing run".to_string()
+            } else {
+                since.clone()
+            };
+            if *streak > 1 {
+                format!("new failure since {} (failed last {} runs)", since, streak)
+            } else {
+                format!("new failure since {}", since)
+            }
+        }
+    }
+}
+
+/// "History:" block for the failing tests of the newest run, if any has a trend.
+fn format_history_notes(failed: &[String], runs: &[TestRunRecord]) -> Option<String> {
+    let notes: Vec<String> = failed
+        .iter()
+        .take(MAX_ANNOTATED)
+        .filter_map(|test| classify(test, runs).map(|t| format!("  {} → {}", test, describe(&t))))
+        .collect();
+    if notes.is_empty() {
+        return None;
+    }
+    Some(format!("\nHistory:\n{}\n", notes.join("\n")))
+}
+
+/// Flaky and newly broken tests per project, runner and test selection.
+fn collect_trends(runs: &[TestRunRecord]) -> Vec<(String, String, TestTrend)> {
+    let mut groups: HashMap<(&str, &str, &str), Vec<TestRunRecord>> = HashMap::new();
+    for run in runs {
+        let group = groups
+            .entry((
+                run.working_dir.as_str(),
+                run.runner.as_str(),
+                run.selection.as_str(),
+            ))
+            .or_default();
+        if group.len() < HISTORY_WINDOW {
+            group.push(run.clone());
+        }
+    }
+
+    let mut trends = Vec::new();
+    for ((dir, runner, selection), group) in &groups {
+        let tests = collect_unique(group.iter().flat_map(|r| r.failed_tests.iter().cloned()));
+        for test in tests {
+            if let Some(trend) = classify(&test, group) {
+                let test = if selection.is_empty() {
+                    test
+                } else {
+                    format!("{} [{}]", test, selection)
+                };
+                let label = if dir.is_empty() {
+                    runner.to_string()
+                } else {
+                    let project = std::path::Path::new(dir)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| dir.to_string());
+                    format!("{}/{}", project, runner)
+                };
+                trends.push((label, test, trend));
+            }
+        }
+    }
+    trends
+}
+
+/// Text report for `rtk gain --tests`.
+fn format_report(runs: &[TestRunRecord], show_project: bool) -> String {
+    if runs.is_empty() {
+        return "No test runs recorded yet. Run tests through rtk (e.g. rtk cargo test) first."
+            .to_string();
+    }
+    let trends = collect_trends(runs);
+    let label = |l: &str| {
+        if show_project {
+            l.to_string()
+        } else {
+            l.rsplit('/').next().unwrap_or(l).to_string()
+        }
+    };
+
+    let mut flaky: Vec<_> = trends
+        .iter()
+        .filter_map(|(l, t, trend)| match trend {
+            TestTrend::Flaky { failed, runs } => Some((l, t, *failed, *runs)),
+            _ => None,
+        })
+        .collect();
+    flaky.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.1.cmp(b.1)));
+    let mut broken: Vec<_> = trends
+        .iter()
+        .filter_map(|(l, t, trend)| match trend {
+            TestTrend::NewFailure { .. } => Some((l, t, describe(trend))),
+            _ => None,
+        })
+        .collect();
+    broken.sort_by(|a, b| a.1.cmp(b.1));
+
+    let mut out = format!("{} test runs recorded\n", runs.len());
+    if flaky.is_empty() && broken.is_empty() {
+        out.push_str("No flaky or newly broken tests.");
+        return out;
+    }
+    if !flaky.is_empty() {
+        out.push_str(&format!("\nFlakiest tests ({}):\n", flaky.len()));
+        for (l, t, failed, runs) in flaky.iter().take(15) {
+            out.push_str(&format!(
+                "  {:<12} {}  failed {}/{}\n",
+                label(l),
+                t,
+                failed,
+                runs
+            ));
+        }
+    }
+    if !broken.is_empty() {
+        out.push_str(&format!("\nNewly broken ({}):\n", broken.len()));
+        for (l, t, note) in broken.iter().take(15) {
+            out.push_str(&format!("  {:<12} {}  {}\n", label(l), t, note));
+        }
+    }
+    out.trim_end().to_string()
+}
+
+/// Print the flaky / newly broken report for `scope`.
+pub fn print_report(scope: &QueryScope) -> Result<()> {
+    let tracker = Tracker::new().context("Failed to initialize tracking database")?;
+    let runs = tracker
+        .get_test_runs(scope, None, None, 5000)
+        .context("Failed to load test history from database")?;
+    println!(
+        "{}",
+        format_report(&runs, matches!(scope, QueryScope::Global))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Convert string slices to Vec<String> for test convenience.
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn run(commit: &str, failed: &[&str]) -> TestRunRecord {
+        subset_run(commit, "", failed)
+    }
+
+    fn subset_run(commit: &str, selection: &str, failed: &[&str]) -> TestRunRecord {
+        TestRunRecord {
+            runner: "cargo".to_string(),
+            commit_sha: commit.to_string(),
+            selection: selection.to_string(),
+            working_dir: "/projects/app".to_string(),
+            failed_tests: failed.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_cargo_failed_tests() {
+        let output = "running 3 tests\n\
+test tests::ok ... ok\n\
+test tests::bad ... FAILED\n\
+test src/lib.rs - add (line 5) ... FAILED\n\
+\n\
+test result: FAILED. 1 passed; 2 failed; 0 ignored\n";
+        assert_eq!(
+            cargo_failed_tests(output).unwrap(),
+            vec!["tests::bad", "src/lib.rs - add (line 5)"]
+        );
+        assert_eq!(cargo_failed_tests("error[E0425]: cannot find value"), None);
+    }
+
+    #[test]
+    fn test_nextest_failed_tests_dedupes_summary() {
+        let output = "        FAIL [   0.006s] (2/4) app tests::failing\n\
+     Summary [   0.007s] 4 tests run: 3 passed, 1 failed\n\
+        FAIL [   0.006s] (2/4) app tests::failing\n";
+        assert_eq!(
+            nextest_failed_tests(output).unwrap(),
+            vec!["app tests::failing"]
+        );
+    }
+
+    #[test]
+    fn test_pytest_and_go_failed_tests() {
+        let pytest = "FAILED tests/test_cart.py::test_total - assert 1 == 2\n\
+1 failed, 4 passed in 0.12s\n";
+        assert_eq!(
+            pytest_failed_tests(pytest).unwrap(),
+            vec!["tests/test_cart.py::test_total"]
+        );
+
+        let go = r#"{"Action":"pass","Package":"example.com/foo","Test":"TestOk"}
+{"Action":"fail","Package":"example.com/foo","Test":"TestFail"}
+{"Action":"fail","Package":"example.com/foo"}"#;
+        assert_eq!(
+            go_failed_tests(go).unwrap(),
+            vec!["example.com/foo.TestFail"]
+        );
+        assert_eq!(go_failed_tests("# build failed"), None);
+    }
+
+    #[test]
+    fn test_classify_flaky_and_new_failure() {
+        let runs = vec![
+            run("c5", &["a", "b"]),
+            run("c4", &[]),
+            run("c3", &["a"]),
+            run("c2", &[]),
+            run("c1", &["c"]),
+        ];
+        assert_eq!(
+            classify("a", &runs),
+            Some(TestTrend::Flaky { failed: 2, runs: 5 })
+        );
+        assert_eq!(
+            classify("b", &runs),
+            Some(TestTrend::NewFailure {
+                since: "c4".to_string(),
+                streak: 1
+            })
+        );
+        // Failed once long ago and since fixed: no trend
+        assert_eq!(classify("c", &runs), None);
+    }
+
+    #[test]
+    fn test_selection_key() {
+        assert_eq!(selection_key("cargo", &args(&[])), "");
+        assert_eq!(
+            selection_key(
+                "cargo",
+                &args(&["-v", "--color", "always", "--", "--nocapture"])
+            ),
+            ""
+        );
+        assert_eq!(
+            selection_key(
+                "cargo",
+                &args(&["parser", "--", "--test-threads=1", "--exact"])
+            ),
+            "parser --exact"
+        );
+        assert_eq!(
+            selection_key(
+                "pytest",
+                &args(&["-q", "-k", "slow and not db", "--tb=short"])
+            ),
+            "-k slow and not db"
+        );
+        assert_eq!(
+            selection_key("pytest", &args(&["-r", "fE", "tests/api"])),
+            "tests/api"
+        );
+        // cargo's -r is --release and takes no value
+        assert_eq!(
+            selection_key("cargo", &args(&["-r", "parser"])),
+            "-r parser"
+        );
+    }
+
+    #[test]
+    fn test_subset_run_after_failing_full_run() {
+        // Full run: a and b fail. Subset run `cargo test a`: a fails, b didn't run.
+        // Full run again: a and b fail. b is neither flaky nor a new failure.
+        let runs = vec![
+            run("c3", &["a", "b"]),
+            subset_run("c2", "a", &["a"]),
+            run("c1", &["a", "b"]),
+        ];
+        assert!(collect_trends(&runs).is_empty());
+
+        let full: Vec<TestRunRecord> = runs
+            .iter()
+            .filter(|r| r.selection.is_empty())
+            .cloned()
+            .collect();
+        assert_eq!(classify("b", &full), None);
+        assert_eq!(
+            format_history_notes(&["a".to_string(), "b".to_string()], &full),
+            None
+        );
+    }
+
+    #[test]
+    fn test_always_failing_test_has_no_note() {
+        let runs = vec![run("c2", &["a"]), run("c1", &["a"])];
+        assert_eq!(format_history_notes(&["a".to_string()], &runs), None);
+    }
+
+    #[test]
+    fn test_format_history_notes() {
+        let runs = vec![
+            run("c3", &["a", "b"]),
+            run("c2", &["b"]),
+            run("c1", &["a"]),
+            run("c0", &[]),
+        ];
+        let notes = format_history_notes(&["a".to_string(), "b".to_string()], &runs).unwrap();
+        assert!(notes.contains("a → flaky: failed 2 of last 4 runs"));
+        assert!(notes.contains("b → new failure since c1 (failed last 2 runs)"));
+    }
+
+    #[test]
+    fn test_format_report() {
+        let runs = vec![run("c3", &["a", "b"]), run("c2", &[]), run("c1", &["a"])];
+        let report = format_report(&runs, false);
+        assert!(report.starts_with("3 test runs recorded"));
+        assert!(report.contains("Flakiest tests (1):\n  cargo        a  failed 2/3"));
+        assert!(report.contains("Newly broken (1):\n  cargo        b  new failure since c2"));
+    }
+}
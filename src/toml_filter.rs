@@ -1,6 +1,6 @@
 /// TOML-based filter DSL for RTK.
 ///
-/// Provides a declarative pipeline of 8 stages that can be configured
+/// Provides a declarative pipeline of 9 stages that can be configured
 /// via TOML files. Lookup priority (first match wins):
 ///   1. `.rtk/filters.toml`              — project-local, committable with the repo
 ///   2. `~/.config/rtk/filters.toml`     — user-global, applies to all projects
@@ -15,13 +15,14 @@
 ///
 /// Pipeline stages (applied in order):
 ///   1. strip_ansi           — remove ANSI escape codes
-///   2. replace              — regex substitutions, line-by-line, chainable
-///   3. match_output         — short-circuit: if blob matches a pattern, return message immediately
-///   4. strip/keep_lines     — filter lines by regex
-///   5. truncate_lines_at    — truncate each line to N chars
-///   6. head/tail_lines      — keep first/last N lines
-///   7. max_lines            — absolute line cap
-///   8. on_empty             — message if result is empty
+///   2. stack_traces         — collapse library/std stack frames, fold repeated traces
+///   3. replace              — regex substitutions, line-by-line, chainable
+///   4. match_output         — short-circuit: if blob matches a pattern, return message immediately
+///   5. strip/keep_lines     — filter lines by regex
+///   6. truncate_lines_at    — truncate each line to N chars
+///   7. head/tail_lines      — keep first/last N lines
+///   8. max_lines            — absolute line cap
+///   9. on_empty             — message if result is empty
 use lazy_static::lazy_static;
 use regex::{Regex, RegexSet};
 use serde::Deserialize;
@@ -86,10 +87,13 @@ struct TomlFilterDef {
     match_command: String,
     #[serde(default)]
     strip_ansi: bool,
-    /// Regex substitutions, applied line-by-line before match_output (stage 2).
+    /// Compress stack traces (Rust, Python, Node, Java, Go) before other stages (stage 2).
+    #[serde(default)]
+    stack_traces: bool,
+    /// Regex substitutions, applied line-by-line before match_output (stage 3).
     #[serde(default)]
     replace: Vec<ReplaceRule>,
-    /// Short-circuit rules: if the full output blob matches, return the message (stage 3).
+    /// Short-circuit rules: if the full output blob matches, return the message (stage 4).
     #[serde(default)]
     match_output: Vec<MatchOutputRule>,
     #[serde(default)]
@@ -136,6 +140,7 @@ pub struct CompiledFilter {
     pub description: Option<String>,
     match_regex: Regex,
     strip_ansi: bool,
+    stack_traces: bool,
     replace: Vec<CompiledReplaceRule>,
     match_output: Vec<CompiledMatchOutputRule>,
     line_filter: LineFilter,
@@ -363,6 +368,7 @@ fn compile_filter(name: String, def: TomlFilterDef) -> Result<CompiledFilter, St
         description: def.description,
         match_regex,
         strip_ansi: def.strip_ansi,
+        stack_traces: def.stack_traces,
         replace,
         match_output,
         line_filter,
@@ -399,13 +405,14 @@ pub fn find_filter_in<'a>(
 ///
 /// Pipeline stages (in order):
 ///   1. strip_ansi           — remove ANSI escape codes
-///   2. replace              — regex substitutions, line-by-line, chainable
-///   3. match_output         — short-circuit if blob matches a pattern
-///   4. strip/keep_lines     — filter lines by regex
-///   5. truncate_lines_at    — truncate each line to N chars
-///   6. head/tail_lines      — keep first/last N lines
-///   7. max_lines            — absolute line cap
-///   8. on_empty             — message if result is empty
+///   2. stack_traces         — collapse library/std stack frames (stack_trace.rs)
+///   3. replace              — regex substitutions, line-by-line, chainable
+///   4. match_output         — short-circuit if blob matches a pattern
+///   5. strip/keep_lines     — filter lines by regex
+///   6. truncate_lines_at    — truncate each line to N chars
+///   7. head/tail_lines      — keep first/last N lines
+///   8. max_lines            — absolute line cap
+///   9. on_empty             — message if result is empty
 pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
     let mut lines: Vec<String> = stdout.lines().map(String::from).collect();
 
@@ -417,7 +424,15 @@ pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
             .collect();
     }
 
-    // 2. replace — line-by-line, rules chained sequentially
+    // 2. stack_traces — runs on the whole blob, frames span several lines
+    if filter.stack_traces {
+        lines = crate::stack_trace::compress_stack_traces(&lines.join("\n"))
+            .lines()
+            .map(String::from)
+            .collect();
+    }
+
+    // 3. replace — line-by-line, rules chained sequentially
     if !filter.replace.is_empty() {
         lines = lines
             .into_iter()
@@ -433,7 +448,7 @@ pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
             .collect();
     }
 
-    // 3. match_output — short-circuit on full blob match (first rule wins)
+    // 4. match_output — short-circuit on full blob match (first rule wins)
     //    If `unless` is set and also matches the blob, the rule is skipped.
     if !filter.match_output.is_empty() {
         let blob = lines.join("\n");
@@ -449,14 +464,14 @@ pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
         }
     }
 
-    // 4. strip OR keep (mutually exclusive)
+    // 5. strip OR keep (mutually exclusive)
     match &filter.line_filter {
         LineFilter::Strip(set) => lines.retain(|l| !set.is_match(l)),
         LineFilter::Keep(set) => lines.retain(|l| set.is_match(l)),
         LineFilter::None => {}
     }
 
-    // 5. truncate_lines_at — uses utils::truncate (unicode-safe)
+    // 6. truncate_lines_at — uses utils::truncate (unicode-safe)
     if let Some(max_chars) = filter.truncate_lines_at {
         lines = lines
             .into_iter()
@@ -464,7 +479,7 @@ pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
             .collect();
     }
 
-    // 6. head + tail
+    // 7. head + tail
     let total = lines.len();
     if let (Some(head), Some(tail)) = (filter.head_lines, filter.tail_lines) {
         if total > head + tail {
@@ -486,7 +501,7 @@ pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
         }
     }
 
-    // 7. max_lines — absolute cap applied after head/tail (includes omit messages)
+    // 8. max_lines — absolute cap applied after head/tail (includes omit messages)
     if let Some(max) = filter.max_lines {
         if lines.len() > max {
             let truncated = lines.len() - max;
@@ -495,7 +510,7 @@ pub fn apply_filter(filter: &CompiledFilter, stdout: &str) -> String {
         }
     }
 
-    // 8. on_empty
+    // 9. on_empty
     let result = lines.join("\n");
     if result.trim().is_empty() {
         if let Some(ref msg) = filter.on_empty {
@@ -679,6 +694,24 @@ strip_ansi = true
         assert_eq!(out, "Error\nnormal");
     }
 
+    #[test]
+    fn test_stack_traces_stage_collapses_library_frames() {
+        let f = first_filter(
+            r#"
+schema_version = 1
+[filters.f]
+match_command = "^cmd"
+stack_traces = true
+"#,
+        );
+        let input = "Error: boom\n    at run (/app/src/run.js:3:9)\n    at a (/app/node_modules/lib/a.js:1:1)\n    at b (/app/node_modules/lib/b.js:2:2)";
+        let out = apply_filter(&f, input);
+        assert_eq!(
+            out,
+            "Error: boom\n    at run (/app/src/run.js:3:9)\n    ... 2 frames in lib"
+        );
+    }
+
     #[test]
     fn test_strip_lines_matching_basic() {
         let f = first_filter(
@@ -1051,52 +1084,6 @@ max_lines = 999
         );
     }
 
-    #[test]
-    fn test_make_savings_above_60pct() {
-        let filters = make_filters(BUILTIN_TOML);
-        let filter = find_filter_in("make all", &filters).expect("make built-in");
-
-        let input = r#"make[1]: Entering directory '/home/user/project'
-make[2]: Entering directory '/home/user/project/src'
-gcc -O2 -Wall -c foo.c -o foo.o
-
-make[2]: Nothing to be done for 'install'.
-make[3]: Entering directory '/home/user/project/src/lib'
-ar rcs libfoo.a foo.o bar.o baz.o
-make[3]: Leaving directory '/home/user/project/src/lib'
-make[2]: Leaving directory '/home/user/project/src'
-
-make[1]: Leaving directory '/home/user/project'
-gcc -O2 -Wall -c bar.c -o bar.o
-
-gcc -O2 -Wall -c baz.c -o baz.o
-
-make[1]: Entering directory '/home/user/project/test'
-make[2]: Entering directory '/home/user/project/test/unit'
-./run_tests --verbose
-make[2]: Nothing to be done for 'check'.
-make[2]: Leaving directory '/home/user/project/test/unit'
-make[1]: Leaving directory '/home/user/project/test'
-
-ld -o myapp foo.o bar.o baz.o -lfoo
-
-make[1]: Entering directory '/home/user/project/docs'
-doxygen Doxyfile
-make[1]: Leaving directory '/home/user/project/docs'
-"#;
-        let out = apply_filter(filter, input);
-        let input_words = input.split_whitespace().count();
-        let out_words = out.split_whitespace().count();
-        let savings = 100.0 - (out_words as f64 / input_words as f64 * 100.0);
-        assert!(
-            savings >= 60.0,
-            "make filter: expected >=60% savings, got {:.1}% (in={} out={})",
-            savings,
-            input_words,
-            out_words
-        );
-    }
-
     // --- Edge cases ---
 
     #[test]
@@ -1529,18 +1516,15 @@ match_command = "^make\\b"
             "brew-install",
             "composer-install",
             "df",
-            "dotnet-build",
             "du",
             "fail2ban-client",
             "gcloud",
             "hadolint",
             "helm",
             "iptables",
-            "make",
             "markdownlint",
             "mix-compile",
             "mix-format",
-            "mvn-build",
             "ping",
             "pio-run",
             "poetry-install",
@@ -1579,8 +1563,8 @@ match_command = "^make\\b"
         let filters = make_filters(BUILTIN_TOML);
         assert_eq!(
             filters.len(),
-            62,
-            "Expected exactly 62 built-in filters, got {}. \
+            58,
+            "Expected exactly 58 built-in filters, got {}. \
              Update this count when adding/removing filters in src/filters/.",
             filters.len()
         );
@@ -1637,11 +1621,11 @@ expected = "output line 1\noutput line 2"
         let combined = format!("{}\n\n{}", BUILTIN_TOML, new_filter);
         let filters = make_filters(&combined);
 
-        // All 62 existing filters still present + 1 new = 63
+        // All 58 existing filters still present + 1 new = 59
         assert_eq!(
             filters.len(),
-            63,
-            "Expected 63 filters after concat (62 built-in + 1 new)"
+            59,
+            "Expected 59 filters after concat (58 built-in + 1 new)"
         );
 
         // New filter is discoverable
@@ -126,6 +126,40 @@ pub struct CommandRecord {
     pub savings_pct: f64,
 }
 
+/// One recorded test-suite run from `rtk test`-style commands.
+///
+/// Returned by [`Tracker::get_test_runs`] and used for flaky-test detection.
+#[derive(Debug, Clone)]
+pub struct TestRunRecord {
+    /// Test runner name (e.g., "cargo", "pytest")
+    pub runner: String,
+    /// Short git commit of the working tree, empty outside git repos
+    pub commit_sha: String,
+    /// Test-selecting arguments of the run (filters, paths, `-k`), empty for a full run
+    pub selection: String,
+    /// Project root the run was recorded for
+    pub working_dir: String,
+    /// Names of the tests that failed in this run
+    pub failed_tests: Vec<String>,
+}
+
+/// Coverage totals saved with `rtk coverage --save-baseline`.
+///
+/// One baseline per project; later `rtk coverage` runs print their delta against it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageBaseline {
+    /// Short git commit the baseline was saved at, empty outside git repos
+    pub commit_sha: String,
+    /// RFC 3339 time the baseline was saved
+    pub timestamp: String,
+    /// Covered and total lines (statements for Go / coverage.py)
+    pub lines: (usize, usize),
+    /// Covered and total branches, (0, 0) when the report has none
+    pub branches: (usize, usize),
+    /// Per-file covered and total lines
+    pub files: Vec<(String, usize, usize)>,
+}
+
 /// Aggregated statistics across all recorded commands.
 ///
 /// Provides overall metrics and breakdowns by command and by day.
@@ -305,6 +339,8 @@ impl Tracker {
             "CREATE INDEX IF NOT EXISTS idx_working_dir ON commands(working_dir)",
             [],
         )?;
+        create_test_history_tables(&conn)?;
+        create_coverage_baseline_table(&conn)?;
 
         Ok(Self { conn })
     }
@@ -351,6 +387,8 @@ impl Tracker {
             "CREATE INDEX IF NOT EXISTS idx_working_dir ON commands(working_dir)",
             [],
         )?;
+        create_test_history_tables(&conn)?;
+        create_coverage_baseline_table(&conn)?;
 
         Ok(Self { conn })
     }
@@ -422,6 +460,168 @@ impl Tracker {
         Ok(())
     }
 
+    /// Record one test-suite run and the names of the tests that failed in it.
+    ///
+    /// Passing tests are not stored: a test counts as passed in every run of the
+    /// same runner, project and `selection` where it is absent from `failed_tests`.
+    pub fn record_test_run(
+        &self,
+        runner: &str,
+        commit_sha: &str,
+        selection: &str,
+        failed_tests: &[String],
+        working_dir: &str,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO test_runs (timestamp, runner, commit_sha, selection, failed, working_dir)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                Utc::now().to_rfc3339(),
+                runner,
+                commit_sha,
+                selection,
+                failed_tests.len() as i64,
+                working_dir
+            ],
+        )?;
+        let run_id = self.conn.last_insert_rowid();
+        let mut stmt = self
+            .conn
+            .prepare("INSERT INTO test_failures (run_id, test_name) VALUES (?1, ?2)")?;
+        for name in failed_tests {
+            stmt.execute(params![run_id, name])?;
+        }
+
+        let cutoff = Utc::now() - chrono::Duration::days(HISTORY_DAYS);
+        self.conn.execute(
+            "DELETE FROM test_runs WHERE timestamp < ?1",
+            params![cutoff.to_rfc3339()],
+        )?;
+        self.conn.execute(
+            "DELETE FROM test_failures WHERE run_id NOT IN (SELECT id FROM test_runs)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    /// Get recorded test runs, newest first, optionally limited to one runner
+    /// and one test selection.
+    pub fn get_test_runs(
+        &self,
+        scope: &QueryScope,
+        runner: Option<&str>,
+        selection: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TestRunRecord>> {
+        let mut conditions = Vec::new();
+        let mut values: Vec<String> = Vec::new();
+        if let QueryScope::Project(dir) = scope {
+            values.push(dir.clone());
+            conditions.push(format!("r.working_dir = ?{}", values.len()));
+        }
+        if let Some(runner) = runner {
+            values.push(runner.to_string());
+            conditions.push(format!("r.runner = ?{}", values.len()));
+        }
+        if let Some(selection) = selection {
+            values.push(selection.to_string());
+            conditions.push(format!("r.selection = ?{}", values.len()));
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" WHERE {}", conditions.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT r.runner, r.commit_sha, r.selection, r.working_dir,
+                    GROUP_CONCAT(f.test_name, char(10))
+             FROM test_runs r LEFT JOIN test_failures f ON f.run_id = r.id{}
+             GROUP BY r.id
+             ORDER BY r.id DESC
+             LIMIT {}",
+            where_clause, limit
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(values.iter()), |row| {
+            let failed: Option<String> = row.get(4)?;
+            Ok(TestRunRecord {
+                runner: row.get(0)?,
+                commit_sha: row.get(1)?,
+                selection: row.get(2)?,
+                working_dir: row.get(3)?,
+                failed_tests: failed
+                    .map(|f| f.lines().map(String::from).collect())
+                    .unwrap_or_default(),
+            })
+        })?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Save (or replace) the coverage baseline of a project.
+    pub fn save_coverage_baseline(
+        &self,
+        working_dir: &str,
+        baseline: &CoverageBaseline,
+    ) -> Result<()> {
+        let files: Vec<String> = baseline
+            .files
+            .iter()
+            .map(|(path, covered, total)| format!("{}\t{}\t{}", path, covered, total))
+            .collect();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO coverage_baselines
+                (working_dir, timestamp, commit_sha, lines_covered, lines_total,
+                 branches_covered, branches_total, files)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                working_dir,
+                baseline.timestamp,
+                baseline.commit_sha,
+                baseline.lines.0 as i64,
+                baseline.lines.1 as i64,
+                baseline.branches.0 as i64,
+                baseline.branches.1 as i64,
+                files.join("\n")
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Get the saved coverage baseline of a project, if any.
+    pub fn get_coverage_baseline(&self, working_dir: &str) -> Result<Option<CoverageBaseline>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT timestamp, commit_sha, lines_covered, lines_total,
+                    branches_covered, branches_total, files
+             FROM coverage_baselines WHERE working_dir = ?1",
+        )?;
+        let mut rows = stmt.query_map(params![working_dir], |row| {
+            let files: String = row.get(6)?;
+            Ok(CoverageBaseline {
+                timestamp: row.get(0)?,
+                commit_sha: row.get(1)?,
+                lines: (
+                    row.get::<_, i64>(2)? as usize,
+                    row.get::<_, i64>(3)? as usize,
+                ),
+                branches: (
+                    row.get::<_, i64>(4)? as usize,
+                    row.get::<_, i64>(5)? as usize,
+                ),
+                files: files
+                    .lines()
+                    .filter_map(|line| {
+                        let mut parts = line.rsplitn(3, '\t');
+                        let total = parts.next()?.parse().ok()?;
+                        let covered = parts.next()?.parse().ok()?;
+                        Some((parts.next()?.to_string(), covered, total))
+                    })
+                    .collect(),
+            })
+        })?;
+        Ok(rows.next().transpose()?)
+    }
+
     /// Get overall summary statistics, optionally scoped to a project.
     pub fn get_summary(&self, scope: &QueryScope, top_n: usize) -> Result<GainSummary> {
         let mut total_commands = 0usize;
@@ -826,6 +1026,56 @@ impl Tracker {
     }
 }
 
+/// Create the per-test outcome tables used for flaky-test detection.
+fn create_test_history_tables(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS test_runs (
+            id INTEGER PRIMARY KEY,
+            timestamp TEXT NOT NULL,
+            runner TEXT NOT NULL,
+            commit_sha TEXT NOT NULL DEFAULT '',
+            selection TEXT NOT NULL DEFAULT '',
+            failed INTEGER NOT NULL,
+            working_dir TEXT NOT NULL DEFAULT ''
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_test_runs_project ON test_runs(working_dir, runner)",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS test_failures (
+            run_id INTEGER NOT NULL,
+            test_name TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_test_failures_run ON test_failures(run_id)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Create the per-project coverage baseline table used by `rtk coverage`.
+fn create_coverage_baseline_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS coverage_baselines (
+            working_dir TEXT PRIMARY KEY,
+            timestamp TEXT NOT NULL,
+            commit_sha TEXT NOT NULL DEFAULT '',
+            lines_covered INTEGER NOT NULL,
+            lines_total INTEGER NOT NULL,
+            branches_covered INTEGER NOT NULL,
+            branches_total INTEGER NOT NULL,
+            files TEXT NOT NULL DEFAULT ''
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
 /// Build a SQL WHERE clause and optional parameter for scope filtering.
 ///
 /// Returns `(" WHERE working_dir = ?1", Some(dir))` for Project scope,
@@ -1334,4 +1584,85 @@ mod tests {
         let summary_10 = tracker.get_summary(&scope, 10).unwrap();
         assert_eq!(summary_10.by_command.len(), 5);
     }
+
+    // 16. test runs are scoped by project, runner and selection, newest first
+    #[test]
+    fn test_record_and_get_test_runs() {
+        let (tracker, _dir) = test_tracker();
+        let failed = vec!["tests::a".to_string(), "tests::b".to_string()];
+        tracker
+            .record_test_run("cargo", "abc1234", "", &[], "/projects/foo")
+            .unwrap();
+        tracker
+            .record_test_run("cargo", "def5678", "", &failed, "/projects/foo")
+            .unwrap();
+        tracker
+            .record_test_run("cargo", "def5678", "tests::a", &[], "/projects/foo")
+            .unwrap();
+        tracker
+            .record_test_run("pytest", "def5678", "", &failed, "/projects/foo")
+            .unwrap();
+        tracker
+            .record_test_run("cargo", "0000000", "", &failed, "/projects/bar")
+            .unwrap();
+
+        let scope = QueryScope::Project("/projects/foo".to_string());
+        let runs = tracker
+            .get_test_runs(&scope, Some("cargo"), Some(""), 10)
+            .unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].commit_sha, "def5678");
+        assert_eq!(runs[0].failed_tests, failed);
+        assert!(runs[1].failed_tests.is_empty());
+
+        let subset = tracker
+            .get_test_runs(&scope, Some("cargo"), Some("tests::a"), 10)
+            .unwrap();
+        assert_eq!(subset.len(), 1);
+        assert_eq!(subset[0].selection, "tests::a");
+
+        let all = tracker
+            .get_test_runs(&QueryScope::Global, None, None, 10)
+            .unwrap();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[0].working_dir, "/projects/bar");
+    }
+
+    // 17. coverage baselines are stored per project and replaced on save
+    #[test]
+    fn test_save_and_get_coverage_baseline() {
+        let (tracker, _dir) = test_tracker();
+        assert!(tracker
+            .get_coverage_baseline("/projects/foo")
+            .unwrap()
+            .is_none());
+
+        let mut baseline = CoverageBaseline {
+            commit_sha: "abc1234".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            lines: (80, 100),
+            branches: (0, 0),
+            files: vec![
+                ("src/a b.rs".to_string(), 30, 40),
+                ("src/c.rs".to_string(), 50, 60),
+            ],
+        };
+        tracker
+            .save_coverage_baseline("/projects/foo", &baseline)
+            .unwrap();
+        baseline.lines = (90, 100);
+        tracker
+            .save_coverage_baseline("/projects/foo", &baseline)
+            .unwrap();
+
+        let saved = tracker
+            .get_coverage_baseline("/projects/foo")
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved, baseline);
+        assert!(tracker
+            .get_coverage_baseline("/projects/bar")
+            .unwrap()
+            .is_none());
+    }
 }
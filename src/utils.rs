@@ -7,7 +7,9 @@
 
 use anyhow::{Context, Result};
 use regex::Regex;
+use std::path::Path;
 use std::process::Command;
+use std::time::SystemTime;
 
 /// Tronque une chaîne à `max_len` caractères avec "..." si nécessaire.
 ///
@@ -173,25 +175,45 @@ pub fn ok_confirmation(action: &str, detail: &str) -> String {
 }
 
 /// Detect the package manager used in the current directory.
-/// Returns "pnpm", "yarn", or "npm" based on lockfile presence.
+/// Returns "pnpm", "bun", "yarn", or "npm" based on lockfile presence.
 ///
 /// # Examples
 /// ```no_run
 /// use rtk::utils::detect_package_manager;
 /// let pm = detect_package_manager();
-/// // Returns "pnpm" if pnpm-lock.yaml exists, "yarn" if yarn.lock, else "npm"
+/// // "pnpm" for pnpm-lock.yaml, "bun" for bun.lockb/bun.lock,
+/// // "yarn" for yarn.lock or .yarnrc.yml, else "npm"
 /// ```
 #[allow(dead_code)]
 pub fn detect_package_manager() -> &'static str {
-    if std::path::Path::new("pnpm-lock.yaml").exists() {
+    detect_package_manager_in(std::path::Path::new("."))
+}
+
+fn detect_package_manager_in(dir: &std::path::Path) -> &'static str {
+    if dir.join("pnpm-lock.yaml").exists() {
         "pnpm"
-    } else if std::path::Path::new("yarn.lock").exists() {
+    } else if dir.join("bun.lockb").exists() || dir.join("bun.lock").exists() {
+        "bun"
+    } else if dir.join("yarn.lock").exists() || dir.join(".yarnrc.yml").exists() {
         "yarn"
     } else {
         "npm"
     }
 }
 
+/// Whether the current project uses Yarn 2+ ("Berry") rather than Yarn classic.
+/// Berry projects carry a `.yarnrc.yml`, and their yarn.lock has a `__metadata` block.
+pub fn is_yarn_berry() -> bool {
+    is_yarn_berry_in(std::path::Path::new("."))
+}
+
+fn is_yarn_berry_in(dir: &std::path::Path) -> bool {
+    dir.join(".yarnrc.yml").exists()
+        || std::fs::read_to_string(dir.join("yarn.lock"))
+            .map(|lock| lock.contains("__metadata:"))
+            .unwrap_or(false)
+}
+
 /// Build a Command using the detected package manager's exec mechanism.
 /// Returns a Command ready to have tool-specific args appended.
 pub fn package_manager_exec(tool: &str) -> Command {
@@ -204,25 +226,78 @@ pub fn package_manager_exec(tool: &str) -> Command {
     if tool_exists {
         Command::new(tool)
     } else {
-        let pm = detect_package_manager();
-        match pm {
-            "pnpm" => {
-                let mut c = Command::new("pnpm");
-                c.arg("exec").arg("--").arg(tool);
-                c
-            }
-            "yarn" => {
-                let mut c = Command::new("yarn");
-                c.arg("exec").arg("--").arg(tool);
-                c
-            }
-            _ => {
-                let mut c = Command::new("npx");
-                c.arg("--no-install").arg("--").arg(tool);
-                c
-            }
+        package_manager_local_exec(tool)
+    }
+}
+
+/// Build a Command that runs a project-local binary through the detected
+/// package manager, without looking for a global install first.
+pub fn package_manager_local_exec(tool: &str) -> Command {
+    match detect_package_manager() {
+        "pnpm" => {
+            let mut c = Command::new("pnpm");
+            c.arg("exec").arg("--").arg(tool);
+            c
         }
+        "bun" => {
+            let mut c = Command::new("bun");
+            c.arg("x").arg(tool);
+            c
+        }
+        // Berry dropped `yarn exec` for binaries; `yarn run` resolves them instead
+        "yarn" if is_yarn_berry() => {
+            let mut c = Command::new("yarn");
+            c.arg("run").arg(tool);
+            c
+        }
+        "yarn" => {
+            let mut c = Command::new("yarn");
+            c.arg("exec").arg("--").arg(tool);
+            c
+        }
+        _ => {
+            let mut c = Command::new("npx");
+            c.arg("--no-install").arg("--").arg(tool);
+            c
+        }
+    }
+}
+
+/// Contents of a report file the command was asked to write, unless it was
+/// last modified before `since` (left over from an earlier run that the
+/// current one never reached, e.g. a collection error)
+pub fn read_if_modified_since(path: &Path, since: SystemTime) -> Option<String> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    if modified < since {
+        return None;
     }
+    std::fs::read_to_string(path).ok()
+}
+
+/// Join arguments into a command line that can be pasted back into a shell,
+/// single-quoting the ones that need it.
+///
+/// # Examples
+/// ```
+/// use rtk::utils::shell_join;
+/// let args = vec!["--cached".to_string(), "my file.rs".to_string()];
+/// assert_eq!(shell_join(&args), "--cached 'my file.rs'");
+/// ```
+pub fn shell_join(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| {
+            let plain = !arg.is_empty()
+                && arg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%^~".contains(c));
+            if plain {
+                arg.clone()
+            } else {
+                format!("'{}'", arg.replace('\'', "'\\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 /// Safety net: if command failed but filtered output looks like success,
@@ -288,6 +363,37 @@ fn test_ensure_failure_visibility_with_stderr() {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_read_if_modified_since_skips_stale_reports() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let report = dir.path().join("report.xml");
+        std::fs::write(&report, "<testsuite/>").unwrap();
+        let written = std::fs::metadata(&report).unwrap().modified().unwrap();
+
+        assert_eq!(
+            read_if_modified_since(&report, written).as_deref(),
+            Some("<testsuite/>")
+        );
+        let later = written + std::time::Duration::from_secs(60);
+        assert_eq!(read_if_modified_since(&report, later), None);
+        assert_eq!(
+            read_if_modified_since(&dir.path().join("missing.xml"), written),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shell_join_quotes_special_args() {
+        let args: Vec<String> = ["diff", "HEAD~1", "", "a b.rs", "it's", "*.rs"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            shell_join(&args),
+            r#"diff HEAD~1 '' 'a b.rs' 'it'\''s' '*.rs'"#
+        );
+    }
+
     #[test]
     fn test_truncate_short_string() {
         assert_eq!(truncate("hello", 10), "hello");
@@ -428,7 +534,40 @@ mod tests {
         // In the test environment (rtk repo), there's no JS lockfile
         // so it should default to "npm"
         let pm = detect_package_manager();
-        assert!(["pnpm", "yarn", "npm"].contains(&pm));
+        assert!(["pnpm", "bun", "yarn", "npm"].contains(&pm));
+    }
+
+    #[test]
+    fn test_detect_package_manager_lockfiles() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(detect_package_manager_in(dir.path()), "npm");
+
+        std::fs::write(dir.path().join(".yarnrc.yml"), "nodeLinker: pnp\n").unwrap();
+        assert_eq!(detect_package_manager_in(dir.path()), "yarn");
+
+        std::fs::write(dir.path().join("bun.lock"), "{}").unwrap();
+        assert_eq!(detect_package_manager_in(dir.path()), "bun");
+
+        std::fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(detect_package_manager_in(dir.path()), "pnpm");
+    }
+
+    #[test]
+    fn test_is_yarn_berry() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("yarn.lock"),
+            "# yarn lockfile v1\n\nreact@^18.2.0:\n  version \"18.2.0\"\n",
+        )
+        .unwrap();
+        assert!(!is_yarn_berry_in(dir.path()));
+
+        std::fs::write(
+            dir.path().join("yarn.lock"),
+            "__metadata:\n  version: 8\n  cacheKey: 10c0\n",
+        )
+        .unwrap();
+        assert!(is_yarn_berry_in(dir.path()));
     }
 
     #[test]
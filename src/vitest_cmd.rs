@@ -103,7 +103,8 @@ fn extract_failures_from_json(json: &VitestJsonOutput) -> Vec<TestFailure> {
     for file in &json.test_results {
         for test in &file.assertion_results {
             if test.status == "failed" {
-                let error_message = test.failure_messages.join("\n");
+                let error_message =
+                    crate::stack_trace::compress_stack_traces(&test.failure_messages.join("\n"));
                 failures.push(TestFailure {
                     test_name: test.full_name.clone(),
                     file_path: file.name.clone(),
@@ -240,6 +241,15 @@ fn run_vitest(args: &[String], verbose: u8) -> Result<()> {
     // Parse output using VitestParser
     let parse_result = VitestParser::parse(&stdout);
     let mode = FormatMode::from_verbosity(verbose);
+    let failed_tests = match &parse_result {
+        ParseResult::Full(data) | ParseResult::Degraded(data, _) => Some(
+            data.failures
+                .iter()
+                .map(|f| f.test_name.clone())
+                .collect::<Vec<_>>(),
+        ),
+        ParseResult::Passthrough(_) => None,
+    };
 
     let mut filtered = match parse_result {
         ParseResult::Full(data) => {
@@ -260,6 +270,8 @@ fn run_vitest(args: &[String], verbose: u8) -> Result<()> {
         }
     };
 
+    crate::test_history::record_and_annotate("vitest", args, failed_tests, &mut filtered);
+
     let exit_code = output.status.code().unwrap_or(1);
     crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
     if let Some(hint) = crate::tee::tee_and_hint(&combined, "vitest_run", exit_code) {
@@ -0,0 +1,421 @@
+//! Yarn install commands with compact output.
+//!
+//! Yarn Berry (2+) prints one `➤ YNxxxx:` line per event. `rtk yarn` folds
+//! the resolution / fetch / link steps into one timing line and keeps only
+//! warning and error codes. Yarn classic keeps its warnings, errors and
+//! `Done in` line. Subcommands other than install / add / up / remove /
+//! dedupe pass through.
+
+use crate::tracking;
+use crate::utils::strip_ansi;
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::process::Command;
+
+/// Subcommands that resolve, fetch and link dependencies
+const INSTALL_SUBCOMMANDS: &[&str] = &["install", "add", "up", "upgrade", "remove", "dedupe"];
+
+/// Progress and bookkeeping codes that never need attention
+const NOISE_CODES: &[&str] = &["YN0000", "YN0007", "YN0008", "YN0013", "YN0019", "YN0085"];
+
+/// Codes that fail the install
+const ERROR_CODES: &[&str] = &["YN0001", "YN0009", "YN0018", "YN0028", "YN0035", "YN0082"];
+
+/// Warning / error lines shown before collapsing into "+N more"
+const MAX_MESSAGES: usize = 10;
+
+lazy_static! {
+    static ref BERRY_LINE: Regex = Regex::new(r"^➤ (YN\d{4}): (.*)$").unwrap();
+    static ref STEP_START: Regex = Regex::new(r"^┌ (\w+) step").unwrap();
+    static ref STEP_END: Regex = Regex::new(r"^└ Completed(?: in (.+))?$").unwrap();
+    static ref BERRY_DONE: Regex =
+        Regex::new(r"^(?:· )?((?:Done|Failed)\b.*?(?:in .+)?)$").unwrap();
+    static ref PACKAGES_ADDED: Regex =
+        Regex::new(r"^(\d+) packages? (?:was|were) added to the project(?: \((.+)\))?").unwrap();
+    static ref CLASSIC_DONE: Regex = Regex::new(r"^Done in ([\d.]+s)\.?$").unwrap();
+}
+
+pub fn run(args: &[String], verbose: u8) -> Result<()> {
+    let is_install = match args.first() {
+        None => true,
+        Some(first) => first.starts_with('-') || INSTALL_SUBCOMMANDS.contains(&first.as_str()),
+    };
+    if !is_install || args.iter().any(|a| a == "-h" || a == "--help") {
+        return run_passthrough(args, verbose);
+    }
+
+    let timer = tracking::TimedExecution::start();
+
+    let mut cmd = Command::new("yarn");
+    cmd.args(args)
+        // Berry settings; Yarn classic ignores them
+        .env("YARN_ENABLE_COLORS", "0")
+        .env("YARN_ENABLE_HYPERLINKS", "0")
+        .env("YARN_ENABLE_PROGRESS_BARS", "0");
+
+    if verbose > 0 {
+        eprintln!("Running: yarn {}", args.join(" "));
+    }
+
+    let output = cmd
+        .output()
+        .context("Failed to run yarn. Is Yarn installed (corepack enable)?")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let raw = format!("{}\n{}", stdout, stderr);
+    let exit_code = output.status.code().unwrap_or(1);
+
+    let label = args
+        .first()
+        .filter(|a| !a.starts_with('-'))
+        .map(String::as_str)
+        .unwrap_or("install");
+    let mut filtered = filter_yarn_output(label, &raw, exit_code == 0);
+
+    crate::utils::ensure_failure_visibility(&mut filtered, exit_code, &stderr);
+    if let Some(hint) = crate::tee::tee_and_hint(&raw, "yarn", exit_code) {
+        println!("{}\n{}", filtered, hint);
+    } else {
+        println!("{}", filtered);
+    }
+
+    timer.track(
+        &format!("yarn {}", args.join(" ")),
+        &format!("rtk yarn {}", args.join(" ")),
+        &raw,
+        &filtered,
+    );
+
+    if !output.status.success() {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+fn run_passthrough(args: &[String], verbose: u8) -> Result<()> {
+    let timer = tracking::TimedExecution::start();
+
+    if verbose > 0 {
+        eprintln!("yarn passthrough: {:?}", args);
+    }
+    let status = Command::new("yarn")
+        .args(args)
+        .status()
+        .context("Failed to run yarn. Is Yarn installed (corepack enable)?")?;
+
+    timer.track_passthrough(
+        &format!("yarn {}", args.join(" ")),
+        &format!("rtk yarn {} (passthrough)", args.join(" ")),
+    );
+
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+    Ok(())
+}
+
+fn filter_yarn_output(label: &str, output: &str, success: bool) -> String {
+    let clean = strip_ansi(output);
+    if clean.lines().any(|l| BERRY_LINE.is_match(l)) {
+        filter_berry(label, &clean, success)
+    } else {
+        filter_classic(label, &clean, success)
+    }
+}
+
+#[derive(Default)]
+struct Step {
+    name: String,
+    duration: Option<String>,
+    detail: Option<String>,
+    fetched: usize,
+    built: usize,
+}
+
+/// Yarn 2+ install log
+fn filter_berry(label: &str, output: &str, success: bool) -> String {
+    let mut steps: Vec<Step> = Vec::new();
+    let mut done = None;
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut changes = None;
+
+    for line in output.lines() {
+        let Some(caps) = BERRY_LINE.captures(line) else {
+            continue;
+        };
+        let code = &caps[1];
+        let message = caps[2].trim_start_matches(['│', '·', ' ']).trim_end();
+
+        if let Some(step) = STEP_START.captures(message) {
+            steps.push(Step {
+                name: step[1].to_lowercase(),
+                ..Default::default()
+            });
+            continue;
+        }
+        if let Some(end) = STEP_END.captures(message) {
+            if let Some(step) = steps.last_mut() {
+                step.duration = end.get(1).map(|m| m.as_str().to_string());
+            }
+            continue;
+        }
+
+        if ERROR_CODES.contains(&code) {
+            push_unique(&mut errors, format!("{}: {}", code, message));
+            continue;
+        }
+        match code {
+            "YN0013" => {
+                if let Some(step) = steps.last_mut() {
+                    match PACKAGES_ADDED.captures(message) {
+                        Some(added) => {
+                            step.detail = Some(match added.get(2) {
+                                Some(size) => format!("{} added, {}", &added[1], size.as_str()),
+                                None => format!("{} added", &added[1]),
+                            })
+                        }
+                        None => step.fetched += 1,
+                    }
+                }
+            }
+            "YN0007" | "YN0008" => {
+                if let Some(step) = steps.last_mut() {
+                    step.built += 1;
+                }
+            }
+            "YN0085" => changes = Some(message.to_string()),
+            "YN0000" => {
+                if let Some(caps) = BERRY_DONE.captures(message) {
+                    done = Some(caps[1].to_string());
+                }
+            }
+            _ if NOISE_CODES.contains(&code) => {}
+            _ => push_unique(&mut warnings, format!("{}: {}", code, message)),
+        }
+    }
+
+    let mark = if success && errors.is_empty() {
+        "✓"
+    } else {
+        "✗"
+    };
+    let mut out = vec![format!(
+        "{} yarn {}: {}",
+        mark,
+        label,
+        done.unwrap_or_else(|| if success {
+            "done".into()
+        } else {
+            "failed".into()
+        })
+    )];
+
+    let timings: Vec<String> = steps.iter().map(format_step).collect();
+    if !timings.is_empty() {
+        out.push(format!("  {}", timings.join(" · ")));
+    }
+    if let Some(changes) = changes {
+        out.push(format!("  {}", changes));
+    }
+    push_section(&mut out, "Errors", &errors);
+    push_section(&mut out, "Warnings", &warnings);
+    out.join("\n")
+}
+
+fn format_step(step: &Step) -> String {
+    let mut text = step.name.clone();
+    if let Some(duration) = &step.duration {
+        text.push(' ');
+        text.push_str(duration);
+    }
+    let mut details = Vec::new();
+    if let Some(detail) = &step.detail {
+        details.push(detail.clone());
+    }
+    if step.fetched > 0 {
+        details.push(format!("{} fetched", step.fetched));
+    }
+    if step.built > 0 {
+        details.push(format!("{} built", step.built));
+    }
+    if !details.is_empty() {
+        text.push_str(&format!(" ({})", details.join(", ")));
+    }
+    text
+}
+
+/// Yarn 1 install log
+fn filter_classic(label: &str, output: &str, success: bool) -> String {
+    let mut done = None;
+    let mut errors: Vec<String> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    let mut saved: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(caps) = CLASSIC_DONE.captures(trimmed) {
+            done = Some(format!("done in {}", &caps[1]));
+        } else if let Some(msg) = trimmed.strip_prefix("error ") {
+            push_unique(&mut errors, msg.to_string());
+        } else if let Some(msg) = trimmed.strip_prefix("warning ") {
+            push_unique(&mut warnings, msg.to_string());
+        } else if trimmed.starts_with("success Saved") && !trimmed.contains("lockfile") {
+            push_unique(
+                &mut saved,
+                trimmed.trim_start_matches("success ").to_string(),
+            );
+        } else if trimmed.starts_with("├─") || trimmed.starts_with("└─") {
+            // Listed under both "Direct dependencies" and "All dependencies"
+            push_unique(&mut saved, trimmed.to_string());
+        }
+    }
+
+    let mark = if success && errors.is_empty() {
+        "✓"
+    } else {
+        "✗"
+    };
+    let mut out = vec![format!(
+        "{} yarn {}: {}",
+        mark,
+        label,
+        done.unwrap_or_else(|| if success {
+            "done".into()
+        } else {
+            "failed".into()
+        })
+    )];
+    out.extend(saved.iter().map(|s| format!("  {}", s)));
+    push_section(&mut out, "Errors", &errors);
+    push_section(&mut out, "Warnings", &warnings);
+    out.join("\n")
+}
+
+fn push_unique(list: &mut Vec<String>, item: String) {
+    if !list.contains(&item) {
+        list.push(item);
+    }
+}
+
+fn push_section(out: &mut Vec<String>, title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    out.push(format!("{} ({}):", title, items.len()));
+    for item in items.iter().take(MAX_MESSAGES) {
+        out.push(format!("  {}", item));
+    }
+    if items.len() > MAX_MESSAGES {
+        out.push(format!("  ... +{} more", items.len() - MAX_MESSAGES));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_tokens(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    const BERRY_INSTALL: &str = r#"➤ YN0000: · Yarn 4.1.0
+➤ YN0000: ┌ Resolution step
+➤ YN0085: │ + react-dom@npm:18.2.0, react@npm:18.2.0, and 120 more.
+➤ YN0002: │ my-app@workspace:. doesn't provide @types/react (p1a2b3), requested by @testing-library/react
+➤ YN0086: │ Some peer dependencies are incorrectly met by your project; run yarn explain peer-requirements <hash> for details.
+➤ YN0000: └ Completed in 0s 512ms
+➤ YN0000: ┌ Fetch step
+➤ YN0013: │ 122 packages were added to the project (+ 12.3 MiB).
+➤ YN0000: └ Completed in 1s 2ms
+➤ YN0000: ┌ Link step
+➤ YN0007: │ esbuild@npm:0.19.0 must be built because it never has been before or the last one failed
+➤ YN0007: │ core-js@npm:3.35.0 must be built because it never has been before or the last one failed
+➤ YN0000: └ Completed in 2s 103ms
+➤ YN0000: · Done with warnings in 3s 812ms
+"#;
+
+    #[test]
+    fn test_filter_berry_install() {
+        let filtered = filter_yarn_output("install", BERRY_INSTALL, true);
+        assert_eq!(
+            filtered,
+            "✓ yarn install: Done with warnings in 3s 812ms\n  \
+             resolution 0s 512ms · fetch 1s 2ms (122 added, + 12.3 MiB) · link 2s 103ms (2 built)\n  \
+             + react-dom@npm:18.2.0, react@npm:18.2.0, and 120 more.\n\
+             Warnings (2):\n  \
+             YN0002: my-app@workspace:. doesn't provide @types/react (p1a2b3), requested by @testing-library/react\n  \
+             YN0086: Some peer dependencies are incorrectly met by your project; run yarn explain peer-requirements <hash> for details."
+        );
+    }
+
+    #[test]
+    fn test_filter_berry_fetch_lines_and_errors() {
+        let mut output = String::from("➤ YN0000: ┌ Fetch step\n");
+        for i in 0..80 {
+            output.push_str(&format!(
+                "➤ YN0013: │ pkg-{}@npm:1.0.{} can't be found in the cache and will be fetched from the remote registry\n",
+                i, i
+            ));
+        }
+        output.push_str("➤ YN0000: └ Completed in 5s 12ms\n");
+        output.push_str("➤ YN0000: ┌ Link step\n");
+        output.push_str("➤ YN0009: │ esbuild@npm:0.19.0 couldn't be built successfully (exit code 1, logs can be found here: /tmp/xfs-1/build.log)\n");
+        output.push_str("➤ YN0000: └ Completed in 1s 40ms\n");
+        output.push_str("➤ YN0000: · Failed with errors in 6s 90ms\n");
+
+        let filtered = filter_yarn_output("install", &output, false);
+        assert!(filtered.starts_with("✗ yarn install: Failed with errors in 6s 90ms"));
+        assert!(filtered.contains("fetch 5s 12ms (80 fetched)"));
+        assert!(filtered.contains("Errors (1):\n  YN0009: esbuild@npm:0.19.0 couldn't be built"));
+        assert!(!filtered.contains("pkg-3@npm"));
+
+        let savings =
+            100.0 - (count_tokens(&filtered) as f64 / count_tokens(&output) as f64 * 100.0);
+        assert!(
+            savings >= 80.0,
+            "expected ≥80% savings, got {:.1}%",
+            savings
+        );
+    }
+
+    #[test]
+    fn test_filter_classic_install() {
+        let output = r#"yarn install v1.22.19
+[1/4] Resolving packages...
+[2/4] Fetching packages...
+[3/4] Linking dependencies...
+warning " > react-dom@18.2.0" has unmet peer dependency "react@^18.2.0".
+warning " > react-dom@18.2.0" has unmet peer dependency "react@^18.2.0".
+[4/4] Building fresh packages...
+success Saved lockfile.
+Done in 3.21s.
+"#;
+        assert_eq!(
+            filter_yarn_output("install", output, true),
+            "✓ yarn install: done in 3.21s\nWarnings (1):\n  \" > react-dom@18.2.0\" has unmet peer dependency \"react@^18.2.0\"."
+        );
+    }
+
+    #[test]
+    fn test_filter_classic_add() {
+        let output = r#"yarn add v1.22.19
+[1/4] Resolving packages...
+[2/4] Fetching packages...
+[3/4] Linking dependencies...
+[4/4] Building fresh packages...
+success Saved lockfile.
+success Saved 1 new dependency.
+info Direct dependencies
+└─ lodash@4.17.21
+info All dependencies
+└─ lodash@4.17.21
+Done in 1.02s.
+"#;
+        assert_eq!(
+            filter_yarn_output("add", output, true),
+            "✓ yarn add: done in 1.02s\n  Saved 1 new dependency.\n  └─ lodash@4.17.21"
+        );
+    }
+}